@@ -0,0 +1,289 @@
+use crate::data::format_thousands;
+use crate::models::{
+    AnnualCostBasis, Car, DepreciationMethod, FuelType, InsuranceMode, MaintenanceBasis,
+    MaintenanceCostDatabase, ResaleChannel, SharedSettings,
+};
+
+/// Describe a setting, noting whether it is the default or the user's own value
+fn describe<T: PartialEq>(what: &str, value: T, default: T, formatted: String) -> String {
+    if value == default {
+        format!("Used default {} {}", what, formatted)
+    } else {
+        format!("Used your {} {}", what, formatted)
+    }
+}
+
+/// List every assumption that feeds into a car's computed costs, in plain language
+pub fn collect_assumptions(
+    car: &Car,
+    settings: &SharedSettings,
+    maintenance_db: &MaintenanceCostDatabase,
+) -> Vec<String> {
+    let defaults = SharedSettings::default();
+    let mut assumptions = Vec::new();
+    if car.fuel_type.uses_gas() {
+        assumptions.push(describe(
+            "gas price",
+            settings.average_gas_price,
+            defaults.average_gas_price,
+            format!("${:.2}/gallon", settings.average_gas_price),
+        ));
+    }
+    if car.fuel_type.uses_electricity() {
+        assumptions.push(describe(
+            "electricity price",
+            settings.electricity_price,
+            defaults.electricity_price,
+            format!("${:.2}/kWh", settings.electricity_price),
+        ));
+    }
+    if car.fuel_type == FuelType::Hybrid {
+        assumptions.push(format!(
+            "{}% of miles are driven on electricity, the rest on gas",
+            car.electric_miles_percent.trim()
+        ));
+    }
+    assumptions.extend([
+        describe(
+            "annual mileage",
+            settings.annual_mileage,
+            defaults.annual_mileage,
+            format!("{:.0} miles/year", settings.annual_mileage),
+        ),
+        match car.lifetime_miles(settings.lifetime_miles) {
+            lifetime if !car.lifetime_miles_override.trim().is_empty() => format!(
+                "Used this car's own lifetime of {:.0} miles instead of the shared {:.0} miles",
+                lifetime, settings.lifetime_miles
+            ),
+            _ => describe(
+                "lifetime",
+                settings.lifetime_miles,
+                defaults.lifetime_miles,
+                format!("{:.0} miles", settings.lifetime_miles),
+            ),
+        },
+        describe(
+            "opportunity cost rate",
+            settings.opportunity_cost_rate,
+            defaults.opportunity_cost_rate,
+            format!(
+                "{}% ({} on the purchase price)",
+                settings.opportunity_cost_rate,
+                if settings.compound_opportunity_cost {
+                    "compounded yearly"
+                } else {
+                    "simple interest"
+                }
+            ),
+        ),
+        match car.insurance_mode {
+            InsuranceMode::Premium => format!(
+                "Insurance is the {} premium × {} per year, held flat for the whole ownership period",
+                car.insurance_period.label().to_lowercase(),
+                car.insurance_period.per_year()
+            ),
+            InsuranceMode::PerMile => "Insurance is your per-mile rate × the remaining miles".to_string(),
+        },
+        match car.age_from_model_year(settings.current_year) {
+            Some(age) => format!(
+                "Vehicle age ({:.1} years) comes from the {} model year",
+                age,
+                car.year.trim()
+            ),
+            None => "Vehicle age is estimated from current mileage ÷ annual mileage".to_string(),
+        },
+    ]);
+
+    if let Some(years) = settings.ownership_years.filter(|years| *years > 0.0) {
+        assumptions.push(format!(
+            "Car is replaced after {} more years, whatever its mileage",
+            years
+        ));
+    }
+    if car.keep_until_age.trim().is_empty() {
+        assumptions.push(
+            "No planned ownership age, so the car is kept until it reaches the lifetime mileage"
+                .to_string(),
+        );
+    } else {
+        assumptions.push(format!(
+            "Car is sold when it is {} years old",
+            car.keep_until_age.trim()
+        ));
+    }
+
+    if !car.first_year_miles.trim().is_empty() {
+        assumptions.push(format!(
+            "First year is prorated to the {} miles left this year",
+            car.first_year_miles.trim()
+        ));
+    }
+
+    let has_resale_curve = maintenance_db
+        .get_fuzzy(&car.make, &car.model, &car.trim)
+        .is_some_and(|d| !d.value_by_age.is_empty());
+    assumptions.push(match car.depreciation_method {
+        _ if !car.expected_resale_value.trim().is_empty() => format!(
+            "Resale value is your expected ${} rather than a projection",
+            car.expected_resale_value.trim()
+        ),
+        DepreciationMethod::StraightLine => {
+            "Resale value falls in a straight line to $0 at the lifetime mileage".to_string()
+        }
+        DepreciationMethod::CurveFromDatabase if has_resale_curve => format!(
+            "Resale value follows the {} {} resale curve",
+            car.make.trim(),
+            car.model.trim()
+        ),
+        DepreciationMethod::CurveFromDatabase => format!(
+            "No resale curve for \"{} {}\", so resale value loses {}% per year",
+            car.make.trim(),
+            car.model.trim(),
+            settings.depreciation_rate
+        ),
+        DepreciationMethod::ExponentialDecay => format!(
+            "Resale value loses {}% per year",
+            settings.depreciation_rate
+        ),
+    });
+
+    if let Some((city, highway)) = car.split_mpg().filter(|_| car.fuel_type.uses_gas()) {
+        assumptions.push(format!(
+            "MPG blends {} city and {} highway for {}% highway driving",
+            city, highway, settings.highway_percent
+        ));
+    }
+
+    if car.fuel_type.uses_gas() && settings.mpg_loss_percent > 0.0 {
+        assumptions.push(format!(
+            "Fuel economy falls {}% a year from the MPG entered",
+            settings.mpg_loss_percent
+        ));
+    }
+
+    if car.fuel_type.uses_gas()
+        && car
+            .current_mileage
+            .trim()
+            .parse::<f64>()
+            .is_ok_and(|miles| miles > 0.0)
+    {
+        assumptions.push(match settings.historical_gas_price {
+            Some(price) => format!(
+                "Fuel already spent assumes ${:.2}/gallon for the miles on the odometer",
+                price
+            ),
+            None => "Fuel already spent assumes today's gas price for the miles on the odometer"
+                .to_string(),
+        });
+    }
+
+    if let AnnualCostBasis::FixedYears(years) = settings.annual_cost_basis {
+        if years > 0 {
+            assumptions.push(format!(
+                "Annual cost averages running costs over the next {} years, with the purchase spread over the whole ownership",
+                years
+            ));
+        }
+    }
+
+    if settings.inflation_rate != 0.0 {
+        assumptions.push(describe(
+            "inflation rate",
+            settings.inflation_rate,
+            defaults.inflation_rate,
+            format!(
+                "{}%/yr for the total in future dollars (other figures are in today's dollars)",
+                settings.inflation_rate
+            ),
+        ));
+    }
+
+    if settings.discounted_total {
+        assumptions.push(format!(
+            "The discounted total discounts each year's costs and the resale to today at {}%/yr",
+            settings.opportunity_cost_rate
+        ));
+    }
+
+    if settings.sales_tax_on_purchase {
+        assumptions.push(format!(
+            "Pays {}% sales tax on the purchase price",
+            settings.sales_tax_rate
+        ));
+    }
+
+    if settings.annual_registration_fee > 0.0 {
+        assumptions.push(format!(
+            "Registration costs ${}/yr",
+            format_thousands(settings.annual_registration_fee)
+        ));
+    }
+
+    let (tire_cost, tire_life) = (car.tire_set_cost.trim(), car.tire_life_miles.trim());
+    if !tire_cost.is_empty() && !tire_life.is_empty() {
+        assumptions.push(format!(
+            "A ${} set of tires is replaced every {} miles",
+            tire_cost, tire_life
+        ));
+    }
+
+    if car.resale_channel == ResaleChannel::TradeIn {
+        let mut line = format!(
+            "Traded in at {}% below private-sale value",
+            settings.trade_in_discount_percent
+        );
+        if settings.trade_in_tax_credit {
+            line.push_str(&format!(
+                ", with a {}% sales-tax credit on the next purchase",
+                settings.sales_tax_rate
+            ));
+        }
+        assumptions.push(line);
+    }
+
+    let number = |value: &str| value.trim().parse::<f64>().ok();
+    let warranty_limits: Vec<String> = [
+        number(&car.extended_warranty_years).map(|years| format!("{} years", years)),
+        number(&car.extended_warranty_miles)
+            .map(|miles| format!("{} miles", format_thousands(miles))),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    if !warranty_limits.is_empty() {
+        let mut line = format!(
+            "The warranty covers all maintenance for {} from purchase",
+            warranty_limits.join(" or ")
+        );
+        if warranty_limits.len() > 1 {
+            line.push_str(", whichever comes first");
+        }
+        assumptions.push(line);
+    }
+
+    match maintenance_db.get_fuzzy(&car.make, &car.model, &car.trim) {
+        Some(data) if data.basis() != MaintenanceBasis::NoData => {
+            let how = match data.basis() {
+                MaintenanceBasis::MileageOnly => "using only its mileage table (no age table)",
+                MaintenanceBasis::TimeOnly => "using only its age table (no mileage table)",
+                _ => "averaging its mileage and age tables 50/50",
+            };
+            let mut line = format!("Maintenance uses the {} profile, {}", data.name(), how);
+            if settings.region_cost_multiplier != 1.0 {
+                line.push_str(&format!(
+                    ", scaled by a regional multiplier of {:.2}×",
+                    settings.region_cost_multiplier
+                ));
+            }
+            assumptions.push(line);
+        }
+        _ => assumptions.push(format!(
+            "No maintenance data for \"{} {}\" (assumed $0)",
+            car.make.trim(),
+            car.model.trim()
+        )),
+    }
+
+    assumptions
+}
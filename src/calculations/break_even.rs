@@ -0,0 +1,107 @@
+use crate::calculations::compute_car_data;
+use crate::models::{Car, MaintenanceCostDatabase, SharedSettings};
+
+/// Annual mileages swept for the break-even: 2k to 40k miles a year in 1k steps
+pub const BREAK_EVEN_MIN_MILES: f64 = 2000.0;
+pub const BREAK_EVEN_MAX_MILES: f64 = 40000.0;
+pub const BREAK_EVEN_STEP_MILES: f64 = 1000.0;
+
+/// Annual cost of both cars when driven `annual_mileage` miles a year
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BreakEvenPoint {
+    pub annual_mileage: f64,
+    pub ev_annual: f64,
+    pub gas_annual: f64,
+}
+
+impl BreakEvenPoint {
+    /// How much more the EV costs per year (negative when it's cheaper)
+    pub fn ev_premium(&self) -> f64 {
+        self.ev_annual - self.gas_annual
+    }
+}
+
+/// Where, if anywhere, the EV's higher price pays off within the swept range
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BreakEvenOutcome {
+    /// Annual costs are equal at this mileage; `ev_cheaper_above` says which side the
+    /// EV wins on
+    Crossover {
+        annual_mileage: f64,
+        ev_cheaper_above: bool,
+    },
+    /// The EV costs less per year at every mileage swept
+    EvAlwaysCheaper,
+    /// The EV costs more per year at every mileage swept
+    EvAlwaysMoreExpensive,
+}
+
+/// The swept annual costs of an EV and a gas car, and where they cross
+#[derive(Clone, Debug, PartialEq)]
+pub struct BreakEven {
+    pub points: Vec<BreakEvenPoint>,
+    pub outcome: BreakEvenOutcome,
+}
+
+impl BreakEven {
+    /// Annual mileage where the two cars cost the same, or `None` when one is cheaper
+    /// throughout (see `outcome` for which)
+    pub fn crossover_mileage(&self) -> Option<f64> {
+        match self.outcome {
+            BreakEvenOutcome::Crossover { annual_mileage, .. } => Some(annual_mileage),
+            _ => None,
+        }
+    }
+}
+
+/// Sweep annual mileage and find where the EV's annual cost (purchase spread over the
+/// ownership plus running costs) meets the gas car's. Every other setting is kept, so
+/// a longer yearly distance means a shorter ownership to the same lifetime mileage. The
+/// crossover is interpolated between sweep samples; if the lines cross more than once,
+/// the first crossing is reported. Returns `None` when either car can't be computed.
+pub fn break_even_mileage(
+    ev: &Car,
+    gas: &Car,
+    settings: &SharedSettings,
+    maintenance_db: &MaintenanceCostDatabase,
+) -> Option<BreakEven> {
+    let steps = ((BREAK_EVEN_MAX_MILES - BREAK_EVEN_MIN_MILES) / BREAK_EVEN_STEP_MILES) as usize;
+    let points = (0..=steps)
+        .map(|i| {
+            let annual_mileage = BREAK_EVEN_MIN_MILES + i as f64 * BREAK_EVEN_STEP_MILES;
+            let settings = SharedSettings {
+                annual_mileage,
+                ..settings.clone()
+            };
+            Some(BreakEvenPoint {
+                annual_mileage,
+                ev_annual: compute_car_data(ev, &settings, maintenance_db)?.annual_cost,
+                gas_annual: compute_car_data(gas, &settings, maintenance_db)?.annual_cost,
+            })
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    let crossover = points.windows(2).find_map(|pair| {
+        let (a, b) = (pair[0].ev_premium(), pair[1].ev_premium());
+        if a == 0.0 {
+            Some((pair[0].annual_mileage, b < 0.0))
+        } else if a.signum() != b.signum() || b == 0.0 {
+            let t = a / (a - b);
+            let miles =
+                pair[0].annual_mileage + t * (pair[1].annual_mileage - pair[0].annual_mileage);
+            Some((miles, b < a))
+        } else {
+            None
+        }
+    });
+
+    let outcome = match crossover {
+        Some((annual_mileage, ev_cheaper_above)) => BreakEvenOutcome::Crossover {
+            annual_mileage,
+            ev_cheaper_above,
+        },
+        None if points[0].ev_premium() < 0.0 => BreakEvenOutcome::EvAlwaysCheaper,
+        None => BreakEvenOutcome::EvAlwaysMoreExpensive,
+    };
+    Some(BreakEven { points, outcome })
+}
@@ -0,0 +1,108 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::models::{Car, ComputedCarData, MaintenanceCostDatabase, PrimaryMetric, SharedSettings};
+
+/// Bump when the computation changes so results cached by older builds are recomputed
+pub const CACHE_VERSION: u32 = 30;
+
+/// A cached result and the hash of the inputs it was computed from
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct CacheEntry {
+    pub input_hash: String,
+    pub data: ComputedCarData,
+}
+
+/// Computed results persisted across sessions, one entry per car id
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct ComputedCache {
+    pub entries: HashMap<usize, CacheEntry>,
+}
+
+/// 64-bit FNV-1a, stable across builds unlike the standard library's hasher
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Hash of every input that affects a car's computed costs. Fields that are only
+/// displayed (notes, photo, comparison inclusion, headline preference, household selection, ...) are cleared first so editing
+/// them doesn't invalidate the cache.
+pub fn cache_key(car: &Car, settings: &SharedSettings, db: &MaintenanceCostDatabase) -> String {
+    let mut car = car.clone();
+    car.id = 0;
+    car.vin.clear();
+    car.listing_url.clear();
+    car.notes.clear();
+    car.image_data_url = None;
+    car.color = None;
+    car.in_comparison = true;
+
+    let mut settings = settings.clone();
+    settings.primary_metric = PrimaryMetric::default();
+    settings.selling_car_id = None;
+
+    // Today's date isn't saved with the settings, but a car's age depends on it
+    let inputs = (
+        CACHE_VERSION,
+        &car,
+        &settings,
+        settings.current_year,
+        db.get_fuzzy(&car.make, &car.model, &car.trim),
+    );
+    let json = serde_json::to_string(&inputs).unwrap_or_default();
+    format!("{:016x}", fnv1a(json.as_bytes()))
+}
+
+/// Whether `data` reads back from JSON as itself. serde_json writes NaN and infinities as
+/// `null`, which then fails to load (or loads as `None`), and one such entry would make the
+/// whole stored cache unreadable.
+fn round_trips(data: &ComputedCarData) -> bool {
+    serde_json::to_string(data)
+        .ok()
+        .and_then(|json| serde_json::from_str::<ComputedCarData>(&json).ok())
+        .is_some_and(|restored| restored == *data)
+}
+
+impl ComputedCache {
+    /// The cached result for this car, if it was computed from the same inputs
+    pub fn get(
+        &self,
+        car: &Car,
+        settings: &SharedSettings,
+        db: &MaintenanceCostDatabase,
+    ) -> Option<&ComputedCarData> {
+        let entry = self.entries.get(&car.id)?;
+        (entry.input_hash == cache_key(car, settings, db)).then_some(&entry.data)
+    }
+
+    /// Store a result, replacing any stale entry for the car. A result with non-finite
+    /// values isn't stored (the stale entry is still dropped). Returns whether anything changed.
+    pub fn insert(
+        &mut self,
+        car: &Car,
+        settings: &SharedSettings,
+        db: &MaintenanceCostDatabase,
+        data: ComputedCarData,
+    ) -> bool {
+        if !round_trips(&data) {
+            return self.entries.remove(&car.id).is_some();
+        }
+        let entry = CacheEntry {
+            input_hash: cache_key(car, settings, db),
+            data,
+        };
+        if self.entries.get(&car.id) == Some(&entry) {
+            return false;
+        }
+        self.entries.insert(car.id, entry);
+        true
+    }
+
+    /// Drop entries for cars that no longer exist
+    pub fn retain_cars(&mut self, cars: &[Car]) {
+        self.entries
+            .retain(|id, _| cars.iter().any(|car| car.id == *id));
+    }
+}
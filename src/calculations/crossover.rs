@@ -0,0 +1,121 @@
+use crate::calculations::tco::opportunity_cost;
+use crate::calculations::{compute_car_data, compute_cash_flows};
+use crate::models::{Car, MaintenanceCostDatabase, SharedSettings};
+
+/// Which of two cars is cheaper to have bought as the miles add up
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CrossoverOutcome {
+    /// Spending is equal after `miles` more miles; past that, the car with id
+    /// `cheaper_after` has cost less
+    Crossover { miles: f64, cheaper_after: usize },
+    /// The car with this id costs less the whole way
+    AlwaysCheaper(usize),
+}
+
+/// Where two cars' cumulative spending crosses, looked at up to `horizon_miles` from
+/// today: as far as both cars are kept
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CostCrossover {
+    pub horizon_miles: f64,
+    pub outcome: CrossoverOutcome,
+}
+
+/// Cumulative spending at each cash-flow period boundary, as (miles driven from today,
+/// dollars): the upfront costs at 0 miles, then running costs and the opportunity cost
+/// as they accrue. Resale isn't counted, since it depends on when the car is sold.
+fn spending_curve(
+    car: &Car,
+    settings: &SharedSettings,
+    maintenance_db: &MaintenanceCostDatabase,
+) -> Option<Vec<(f64, f64)>> {
+    let computed = compute_car_data(car, settings, maintenance_db)?;
+    let price = computed.operands.purchase_price;
+    let upfront = price + computed.sales_tax + computed.extended_warranty_cost;
+
+    let mut curve = vec![(0.0, upfront)];
+    let (mut years, mut running) = (0.0, 0.0);
+    for flow in compute_cash_flows(car, settings, maintenance_db) {
+        years += flow.years;
+        running += flow.total();
+        curve.push((
+            years * settings.annual_mileage,
+            upfront + running + opportunity_cost(price, settings, years),
+        ));
+    }
+    Some(curve)
+}
+
+/// Spending at `miles`, linear between the curve's points
+fn spending_at(curve: &[(f64, f64)], miles: f64) -> f64 {
+    curve
+        .windows(2)
+        .find(|w| miles <= w[1].0)
+        .map(|w| {
+            let ((x0, y0), (x1, y1)) = (w[0], w[1]);
+            if x1 > x0 {
+                y0 + (miles - x0) / (x1 - x0) * (y1 - y0)
+            } else {
+                y1
+            }
+        })
+        .unwrap_or_else(|| curve.last().map_or(0.0, |p| p.1))
+}
+
+/// Find the mileage (driven from today) where car `b` catches up with car `a` on total
+/// spending, typically a pricier but cheaper-to-run car overtaking a cheap one. Both
+/// curves are linear between period boundaries, so the crossing is exact; if they cross
+/// more than once, the first crossing is reported. Returns `None` when either car can't
+/// be computed.
+pub fn cost_crossover(
+    a: &Car,
+    b: &Car,
+    settings: &SharedSettings,
+    maintenance_db: &MaintenanceCostDatabase,
+) -> Option<CostCrossover> {
+    let curve_a = spending_curve(a, settings, maintenance_db)?;
+    let curve_b = spending_curve(b, settings, maintenance_db)?;
+    let horizon_miles = curve_a.last()?.0.min(curve_b.last()?.0);
+
+    let mut marks: Vec<f64> = curve_a
+        .iter()
+        .chain(&curve_b)
+        .map(|p| p.0)
+        .filter(|miles| *miles <= horizon_miles)
+        .collect();
+    marks.sort_by(f64::total_cmp);
+    marks.dedup();
+
+    // How much more car `a` has cost than car `b` at each mark
+    let gaps: Vec<(f64, f64)> = marks
+        .iter()
+        .map(|&miles| {
+            (
+                miles,
+                spending_at(&curve_a, miles) - spending_at(&curve_b, miles),
+            )
+        })
+        .collect();
+    let cheaper = |gap: f64| if gap > 0.0 { b.id } else { a.id };
+
+    // Equal upfront costs don't count as a crossing; the first difference decides who leads
+    let Some(lead) = gaps.iter().position(|(_, gap)| *gap != 0.0) else {
+        return Some(CostCrossover {
+            horizon_miles,
+            outcome: CrossoverOutcome::AlwaysCheaper(a.id),
+        });
+    };
+    let crossing = gaps[lead..].windows(2).find_map(|pair| {
+        let ((x0, g0), (x1, g1)) = (pair[0], pair[1]);
+        (g1 == 0.0 || g0.signum() != g1.signum()).then(|| {
+            let miles = x0 + g0 / (g0 - g1) * (x1 - x0);
+            CrossoverOutcome::Crossover {
+                miles,
+                cheaper_after: cheaper(-g0),
+            }
+        })
+    });
+    Some(CostCrossover {
+        horizon_miles,
+        outcome: crossing.unwrap_or(CrossoverOutcome::AlwaysCheaper(cheaper(gaps[lead].1))),
+    })
+}
@@ -0,0 +1,60 @@
+use crate::models::{DepreciationMethod, MaintenanceCostData, ResaleChannel, SharedSettings};
+
+/// Default share of value lost each year under exponential decay
+pub const DEFAULT_DEPRECIATION_RATE: f64 = 0.15;
+
+/// Project the resale value at the end of ownership, returning it along with the method
+/// actually applied. A curve-based projection without a usable curve falls back to decay
+/// at `decay_rate` (a fraction lost per year).
+#[allow(clippy::too_many_arguments)]
+pub fn project_resale_value(
+    method: DepreciationMethod,
+    decay_rate: f64,
+    purchase_price: f64,
+    current_age: f64,
+    years_remaining: f64,
+    miles_remaining: f64,
+    miles_to_lifetime: f64,
+    maint_data: Option<&MaintenanceCostData>,
+) -> (f64, DepreciationMethod) {
+    let exponential = || purchase_price * (1.0 - decay_rate).powf(years_remaining);
+
+    match method {
+        DepreciationMethod::ExponentialDecay => (exponential(), method),
+        DepreciationMethod::StraightLine => {
+            let fraction_used = (miles_remaining / miles_to_lifetime).clamp(0.0, 1.0);
+            (purchase_price * (1.0 - fraction_used), method)
+        }
+        DepreciationMethod::CurveFromDatabase => {
+            match maint_data
+                .and_then(|d| d.retained_value_ratio(current_age, current_age + years_remaining))
+            {
+                Some(ratio) => (purchase_price * ratio, method),
+                None => (exponential(), DepreciationMethod::ExponentialDecay),
+            }
+        }
+    }
+}
+
+/// Adjust a projected private-sale value for how the car is sold, returning the sale
+/// proceeds and any sales-tax credit. A trade-in pays the discounted value, and where
+/// trade-ins are tax-advantaged, the next purchase isn't taxed on that value.
+pub fn apply_resale_channel(
+    channel: ResaleChannel,
+    private_sale_value: f64,
+    settings: &SharedSettings,
+) -> (f64, f64) {
+    match channel {
+        ResaleChannel::PrivateSale => (private_sale_value, 0.0),
+        ResaleChannel::TradeIn => {
+            let discount = (settings.trade_in_discount_percent / 100.0).clamp(0.0, 1.0);
+            let trade_in_value = private_sale_value * (1.0 - discount);
+            let tax_credit = if settings.trade_in_tax_credit {
+                trade_in_value * settings.sales_tax_rate.max(0.0) / 100.0
+            } else {
+                0.0
+            };
+            (trade_in_value, tax_credit)
+        }
+    }
+}
@@ -0,0 +1,550 @@
+use crate::data::format_thousands;
+use crate::models::{
+    ComputeOperands, ComputedCarData, DepreciationMethod, FuelType, InsuranceMode,
+    MaintenanceBasis, ResaleChannel,
+};
+
+/// A field shown in the cost summary that can be explained
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CostField {
+    TotalCostOfOwnership,
+    TotalCostNominal,
+    DiscountedTotal,
+    AnnualCost,
+    AnnualOperatingCost,
+    CostPerMile,
+    CostPerPassengerMile,
+    CostIncurredToDate,
+    CostProjectedRemaining,
+    YearsRemaining,
+    RemainingMiles,
+    FuelTotal,
+    FuelAnnual,
+    InsuranceAnnual,
+    OpportunityCost,
+    ResaleValue,
+    DepreciationCost,
+    TradeInTaxCredit,
+    ExtendedWarranty,
+    EffectiveCashPrice,
+    InterestPaid,
+    SalesTax,
+    RegistrationTotal,
+    TireTotal,
+    MaintenanceTotal,
+    MaintenanceAnnual,
+}
+
+fn money(value: f64) -> String {
+    if value < 0.0 {
+        format!("−${}", format_thousands(-value))
+    } else {
+        format!("${}", format_thousands(value))
+    }
+}
+
+fn miles(value: f64) -> String {
+    format!("{} mi", format_thousands(value))
+}
+
+/// " + $X tires" when the car has tire costs, otherwise nothing
+fn tires_part(tires: f64) -> String {
+    if tires != 0.0 {
+        format!(" + {} tires", money(tires))
+    } else {
+        String::new()
+    }
+}
+
+/// How miles turn into fuel cost for the car's fuel type, e.g. "÷ 30 MPG × $3.50/gal"
+fn fuel_rate(ops: &ComputeOperands, mpg: f64, gas_price: f64, gas_note: &str) -> String {
+    let blend = match ops.mpg_city_highway {
+        Some((city, highway)) => format!(
+            " ({:.0}% highway at {}, city at {})",
+            ops.highway_percent, highway, city
+        ),
+        None => String::new(),
+    };
+    let gas = format!(
+        "÷ {} MPG{} × ${:.2}/gal{}",
+        (mpg * 10.0).round() / 10.0,
+        blend,
+        gas_price,
+        gas_note
+    );
+    let electric = format!(
+        "÷ {} mi/kWh × ${:.2}/kWh",
+        ops.miles_per_kwh, ops.electricity_price
+    );
+    match ops.fuel_type {
+        FuelType::Gasoline => gas,
+        FuelType::Electric => electric,
+        FuelType::Hybrid => format!(
+            "× ({:.0}% {} + {:.0}% {})",
+            (1.0 - ops.electric_share) * 100.0,
+            gas,
+            ops.electric_share * 100.0,
+            electric
+        ),
+    }
+}
+
+/// The formula behind a computed field with the actual values substituted,
+/// e.g. "$25,000 × 8% × 9.2 years = $18,400"
+pub fn explain(computed: &ComputedCarData, field: CostField) -> String {
+    let ops = &computed.operands;
+    let years = format!("{:.1} years", computed.years_remaining);
+    let insurance_total = computed.insurance_cost_annual * computed.years_remaining;
+
+    match field {
+        CostField::RemainingMiles => {
+            let to_lifetime = format!(
+                "{} {}lifetime − {} current",
+                miles(ops.lifetime_miles),
+                if ops.lifetime_miles_overridden {
+                    "car's own "
+                } else {
+                    ""
+                },
+                miles(ops.current_mileage)
+            );
+            match ops.ownership_cap_miles {
+                Some(cap) => format!(
+                    "lesser of ({}) and {} until the planned sale = {}",
+                    to_lifetime,
+                    miles(cap),
+                    miles(computed.remaining_miles)
+                ),
+                None => format!("{} = {}", to_lifetime, miles(computed.remaining_miles)),
+            }
+        }
+        CostField::YearsRemaining => format!(
+            "{} ÷ {}/yr = {}",
+            miles(computed.remaining_miles),
+            miles(ops.annual_mileage),
+            years
+        ),
+        CostField::FuelTotal => format!(
+            "{} {} = {}",
+            miles(computed.remaining_miles),
+            if ops.mpg_loss_percent > 0.0 && computed.effective_mpg > 0.0 {
+                fuel_rate(
+                    ops,
+                    (computed.effective_mpg * 10.0).round() / 10.0,
+                    ops.gas_price,
+                    &format!(
+                        " (average as {} MPG falls {}%/yr)",
+                        (ops.mpg * 10.0).round() / 10.0,
+                        ops.mpg_loss_percent
+                    ),
+                )
+            } else {
+                fuel_rate(ops, ops.mpg, ops.gas_price, "")
+            },
+            money(computed.fuel_cost_total)
+        ),
+        CostField::FuelAnnual => format!(
+            "{} ÷ {} = {}",
+            money(computed.fuel_cost_total),
+            years,
+            money(computed.fuel_cost_annual)
+        ),
+        CostField::InsuranceAnnual => {
+            let base = match ops.insurance_mode {
+                InsuranceMode::Premium => format!(
+                    "${:.2} {} premium × {}",
+                    ops.insurance_input,
+                    ops.insurance_period.label().to_lowercase(),
+                    ops.insurance_period.per_year()
+                ),
+                InsuranceMode::PerMile => format!(
+                    "${:.3}/mi × {}/yr",
+                    ops.insurance_input,
+                    miles(ops.annual_mileage)
+                ),
+            };
+            let regional = if ops.insurance_multiplier != 1.0 {
+                format!(" × {:.2} regional", ops.insurance_multiplier)
+            } else {
+                String::new()
+            };
+            format!(
+                "{}{} = {}",
+                base,
+                regional,
+                money(computed.insurance_cost_annual)
+            )
+        }
+        CostField::OpportunityCost if ops.opportunity_compounded => format!(
+            "{} × ((1 + {}%)^{:.1} − 1) = {}",
+            money(ops.purchase_price),
+            ops.opportunity_cost_rate,
+            computed.years_remaining,
+            money(computed.opportunity_cost)
+        ),
+        CostField::OpportunityCost => format!(
+            "{} × {}% × {} = {}",
+            money(ops.purchase_price),
+            ops.opportunity_cost_rate,
+            years,
+            money(computed.opportunity_cost)
+        ),
+        CostField::ResaleValue => {
+            let ratio = if ops.purchase_price > 0.0 {
+                ops.private_sale_value / ops.purchase_price
+            } else {
+                0.0
+            };
+            let how = match computed.depreciation_method {
+                _ if ops.resale_overridden => "your expected resale value".to_string(),
+                DepreciationMethod::ExponentialDecay => format!(
+                    "(1 − {}%)^{:.1}",
+                    ops.depreciation_rate, computed.years_remaining
+                ),
+                DepreciationMethod::StraightLine => format!(
+                    "(1 − {} ÷ {})",
+                    miles(computed.remaining_miles),
+                    miles(ops.lifetime_miles - ops.current_mileage)
+                ),
+                DepreciationMethod::CurveFromDatabase => {
+                    "the resale curve's retained share".to_string()
+                }
+            };
+            let private = if ops.resale_overridden {
+                format!("{} ({:.1}% retained)", how, ratio * 100.0)
+            } else {
+                format!(
+                    "{} × {} ({:.1}% retained)",
+                    money(ops.purchase_price),
+                    how,
+                    ratio * 100.0
+                )
+            };
+            match computed.resale_channel {
+                ResaleChannel::PrivateSale => {
+                    format!("{} = {}", private, money(computed.resale_value))
+                }
+                ResaleChannel::TradeIn => format!(
+                    "{} = {} private sale × (1 − {}% trade-in discount) = {}",
+                    private,
+                    money(ops.private_sale_value),
+                    ops.trade_in_discount_percent,
+                    money(computed.resale_value)
+                ),
+            }
+        }
+        CostField::DepreciationCost => format!(
+            "{} purchase − {} resale = {}",
+            money(ops.purchase_price),
+            money(computed.resale_value),
+            money(computed.depreciation_cost)
+        ),
+        CostField::TradeInTaxCredit => {
+            if computed.trade_in_tax_credit == 0.0 {
+                return "No sales-tax credit (only applies to tax-advantaged trade-ins)"
+                    .to_string();
+            }
+            format!(
+                "{} trade-in × {}% sales tax = {}",
+                money(computed.resale_value),
+                ops.sales_tax_rate,
+                money(computed.trade_in_tax_credit)
+            )
+        }
+        CostField::ExtendedWarranty => {
+            if !computed.has_extended_warranty() {
+                return "No extended warranty".to_string();
+            }
+            let net = computed.warranty_net_savings();
+            let verdict = if net >= 0.0 {
+                format!("pays off by {}", money(net))
+            } else {
+                format!("costs {} more than it covers", money(-net))
+            };
+            format!(
+                "{} covered maintenance over the first {:.1} years − {} warranty = {}",
+                money(computed.warranty_covered_maintenance),
+                ops.warranty_years.min(computed.years_remaining),
+                money(computed.extended_warranty_cost),
+                verdict
+            )
+        }
+        CostField::EffectiveCashPrice => {
+            let Some(effective) = computed.effective_cash_price else {
+                return "Bought outright, so the cash price is the purchase price".to_string();
+            };
+            format!(
+                "{} down + {} × ${:.2}/mo at {}% APR, discounted at {}% = {} (vs {} cash)",
+                money(ops.down_payment),
+                ops.loan_term_months,
+                ops.monthly_payment,
+                ops.loan_apr,
+                ops.opportunity_cost_rate,
+                money(effective),
+                money(ops.purchase_price)
+            )
+        }
+        CostField::InterestPaid => {
+            if ops.loan_term_months == 0 {
+                return "Bought outright, so there's no loan interest".to_string();
+            }
+            let term = ops.loan_term_months as f64;
+            let owned_months = (computed.years_remaining * 12.0).min(term);
+            let paid_off = if owned_months < term {
+                ", with the rest of the balance paid off at the sale"
+            } else {
+                ""
+            };
+            format!(
+                "{:.0} payments × ${:.2}/mo at {}% APR, less the principal repaid = {}{}",
+                owned_months,
+                computed.monthly_payment,
+                ops.loan_apr,
+                money(computed.total_interest_paid),
+                paid_off
+            )
+        }
+        CostField::SalesTax => {
+            if ops.purchase_tax_rate == 0.0 {
+                return "Sales tax on the purchase is turned off in the settings".to_string();
+            }
+            format!(
+                "{} × {}% sales tax = {}",
+                money(ops.purchase_price),
+                ops.purchase_tax_rate,
+                money(computed.sales_tax)
+            )
+        }
+        CostField::RegistrationTotal => format!(
+            "{}/yr × {} = {}",
+            money(ops.annual_registration_fee),
+            years,
+            money(computed.registration_cost_total)
+        ),
+        CostField::TireTotal => {
+            if ops.tire_life_miles == 0.0 {
+                return "Enter a tire set price and tire life to include tires".to_string();
+            }
+            format!(
+                "{} ÷ {} per set × {} per set = {}",
+                miles(computed.remaining_miles),
+                miles(ops.tire_life_miles),
+                money(ops.tire_set_cost),
+                money(computed.tire_cost_total)
+            )
+        }
+        CostField::MaintenanceTotal => {
+            let blend = match computed.maintenance_basis {
+                MaintenanceBasis::NoData => {
+                    return "No maintenance data for this make/model, so maintenance is assumed $0"
+                        .to_string();
+                }
+                MaintenanceBasis::Blended => format!(
+                    "({} by mileage + {} by age) ÷ 2",
+                    money(ops.maintenance_by_mileage),
+                    money(ops.maintenance_by_time)
+                ),
+                MaintenanceBasis::MileageOnly => format!(
+                    "{} by mileage (no age table)",
+                    money(ops.maintenance_by_mileage)
+                ),
+                MaintenanceBasis::TimeOnly => format!(
+                    "{} by age (no mileage table)",
+                    money(ops.maintenance_by_time)
+                ),
+            };
+            let warranty = if computed.warranty_covered_maintenance != 0.0 {
+                format!(
+                    " − {} under warranty",
+                    money(computed.warranty_covered_maintenance)
+                )
+            } else {
+                String::new()
+            };
+            format!(
+                "{} × {:.2} regional{} = {}",
+                blend,
+                ops.region_multiplier,
+                warranty,
+                money(computed.maintenance_cost_total)
+            )
+        }
+        CostField::MaintenanceAnnual => format!(
+            "{} ÷ {} = {}",
+            money(computed.maintenance_cost_total),
+            years,
+            money(computed.maintenance_cost_annual)
+        ),
+        CostField::TotalCostOfOwnership => {
+            let tax_credit = if computed.trade_in_tax_credit != 0.0 {
+                format!(" − {} tax credit", money(computed.trade_in_tax_credit))
+            } else {
+                String::new()
+            };
+            let warranty = if computed.extended_warranty_cost != 0.0 {
+                format!(" + {} warranty", money(computed.extended_warranty_cost))
+            } else {
+                String::new()
+            };
+            let interest = if computed.total_interest_paid != 0.0 {
+                format!(" + {} interest", money(computed.total_interest_paid))
+            } else {
+                String::new()
+            };
+            let sales_tax = if computed.sales_tax != 0.0 {
+                format!(" + {} sales tax", money(computed.sales_tax))
+            } else {
+                String::new()
+            };
+            let registration = if computed.registration_cost_total != 0.0 {
+                format!(
+                    " + {} registration",
+                    money(computed.registration_cost_total)
+                )
+            } else {
+                String::new()
+            };
+            format!(
+                "{} price{}{}{} + {} fuel + {} maintenance{} + {} insurance{} + {} opportunity − {} resale{} = {}",
+                money(ops.purchase_price),
+                sales_tax,
+                warranty,
+                interest,
+                money(computed.fuel_cost_total),
+                money(computed.maintenance_cost_total),
+                tires_part(computed.tire_cost_total),
+                money(insurance_total),
+                registration,
+                money(computed.opportunity_cost),
+                money(computed.resale_value),
+                tax_credit,
+                money(computed.total_cost_of_ownership)
+            )
+        }
+        CostField::TotalCostNominal => format!(
+            "{} in today's dollars + {} inflation on running costs − {} inflation on resale and credits, at {}%/yr = {}",
+            money(computed.total_cost_of_ownership),
+            money(ops.inflation_on_running),
+            money(ops.inflation_on_resale),
+            ops.inflation_rate,
+            money(computed.total_cost_nominal)
+        ),
+        CostField::DiscountedTotal => {
+            let Some(discounted) = computed.discounted_total_cost else {
+                return "Turn on the discounted total in the settings to see it".to_string();
+            };
+            let purchase = if ops.loan_term_months > 0 {
+                "effective cash price"
+            } else {
+                "price"
+            };
+            format!(
+                "{} {} + {} running costs discounted at {}%/yr − {} resale discounted from the sale = {} (replaces the {} opportunity cost)",
+                money(ops.discounted_purchase),
+                purchase,
+                money(ops.discounted_running),
+                ops.opportunity_cost_rate,
+                money(ops.discounted_resale),
+                money(discounted),
+                money(computed.opportunity_cost)
+            )
+        }
+        CostField::CostPerMile => format!(
+            "{} ÷ {} = ${:.2}/mi",
+            money(computed.total_cost_of_ownership),
+            miles(computed.remaining_miles),
+            computed.cost_per_mile
+        ),
+        CostField::CostPerPassengerMile => match computed.cost_per_passenger_mile {
+            Some(per_seat) => format!(
+                "${:.2}/mi ÷ {} seats = ${:.3}/passenger-mi",
+                computed.cost_per_mile, ops.seats, per_seat
+            ),
+            None => "Enter the car's seats to see cost per passenger-mile".to_string(),
+        },
+        CostField::CostIncurredToDate => format!(
+            "{} {} + {} maintenance to date = {}",
+            miles(ops.current_mileage),
+            fuel_rate(ops, ops.mpg, ops.historical_gas_price, " historical"),
+            money(ops.incurred_maintenance),
+            money(computed.cost_incurred_to_date)
+        ),
+        CostField::CostProjectedRemaining => {
+            let registration = if computed.registration_cost_total != 0.0 {
+                format!(
+                    " + {} registration",
+                    money(computed.registration_cost_total)
+                )
+            } else {
+                String::new()
+            };
+            format!(
+                "{} fuel + {} maintenance{} + {} insurance{} = {}",
+                money(computed.fuel_cost_total),
+                money(computed.maintenance_cost_total),
+                tires_part(computed.tire_cost_total),
+                money(insurance_total),
+                registration,
+                money(computed.cost_projected_remaining)
+            )
+        }
+        CostField::AnnualOperatingCost => {
+            let opportunity = if ops.operating_includes_opportunity {
+                format!(
+                    " + {} opportunity",
+                    money(computed.opportunity_cost / computed.years_remaining)
+                )
+            } else {
+                String::new()
+            };
+            let warranty = if computed.extended_warranty_cost != 0.0 {
+                format!(
+                    " + {} warranty",
+                    money(computed.extended_warranty_cost / computed.years_remaining)
+                )
+            } else {
+                String::new()
+            };
+            let registration = if computed.registration_cost_total != 0.0 {
+                format!(" + {} registration", money(ops.annual_registration_fee))
+            } else {
+                String::new()
+            };
+            format!(
+                "{} fuel + {} maintenance{} + {} insurance{}{}{} = {}/yr (purchase price excluded)",
+                money(computed.fuel_cost_annual),
+                money(computed.maintenance_cost_annual),
+                tires_part(computed.tire_cost_total / computed.years_remaining),
+                money(insurance_total / computed.years_remaining),
+                registration,
+                warranty,
+                opportunity,
+                money(computed.annual_operating_cost)
+            )
+        }
+        CostField::AnnualCost if ops.annual_cost_window_years < computed.years_remaining => {
+            let running_total = computed.fuel_cost_total
+                + computed.maintenance_cost_total
+                + computed.tire_cost_total
+                + insurance_total
+                + computed.registration_cost_total
+                + computed.total_interest_paid;
+            format!(
+                "({} total − {} running costs) ÷ {} + {} running costs in the next {:.1} years ÷ {:.1} = {}",
+                money(computed.total_cost_of_ownership),
+                money(running_total),
+                years,
+                money(ops.annual_cost_window_running),
+                ops.annual_cost_window_years,
+                ops.annual_cost_window_years,
+                money(computed.annual_cost)
+            )
+        }
+        CostField::AnnualCost => format!(
+            "{} ÷ {} = {}",
+            money(computed.total_cost_of_ownership),
+            years,
+            money(computed.annual_cost)
+        ),
+    }
+}
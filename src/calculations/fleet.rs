@@ -0,0 +1,55 @@
+use crate::models::ComputedCarData;
+
+/// Combined total cost of ownership across every computable car
+#[derive(Clone, Debug, PartialEq)]
+pub struct FleetTotal {
+    /// Sum of the unrounded per-car totals
+    pub exact: f64,
+    /// Each car's total rounded to the display unit, as shown next to the fleet total
+    pub displayed_parts: Vec<f64>,
+    /// Sum of `displayed_parts`, so the shown total always ties out with the shown parts
+    pub displayed: f64,
+}
+
+impl FleetTotal {
+    /// How far the displayed total is from the exact one because of per-car rounding
+    pub fn rounding_difference(&self) -> f64 {
+        self.displayed - self.exact
+    }
+}
+
+/// Sum the fleet's total cost of ownership. The exact total uses compensated summation to
+/// avoid floating-point drift; the displayed total adds up whole display units (e.g. 1.0 for
+/// dollars, 0.01 for cents) so it matches the rounded per-car figures exactly.
+pub fn fleet_total<'a>(
+    computed: impl IntoIterator<Item = &'a ComputedCarData>,
+    display_unit: f64,
+) -> FleetTotal {
+    let totals: Vec<f64> = computed
+        .into_iter()
+        .map(|c| c.total_cost_of_ownership)
+        .collect();
+
+    // Neumaier summation
+    let mut sum = 0.0;
+    let mut compensation = 0.0;
+    for &value in &totals {
+        let next = sum + value;
+        compensation += if sum.abs() >= value.abs() {
+            (sum - next) + value
+        } else {
+            (value - next) + sum
+        };
+        sum = next;
+    }
+
+    let units: Vec<i64> = totals
+        .iter()
+        .map(|v| (v / display_unit).round() as i64)
+        .collect();
+    FleetTotal {
+        exact: sum + compensation,
+        displayed_parts: units.iter().map(|&u| u as f64 * display_unit).collect(),
+        displayed: units.iter().sum::<i64>() as f64 * display_unit,
+    }
+}
@@ -0,0 +1,121 @@
+use crate::models::{Car, ComputeError, FuelType};
+
+/// Tailpipe CO2 from burning a gallon of gasoline (EPA figure)
+pub const CO2_KG_PER_GALLON: f64 = 8.89;
+
+/// How much more gas a mile takes, on average over `years` starting `start` years from
+/// now, than it does today when fuel economy falls `loss_percent` a year. Efficiency
+/// `t` years out is today's × (1 − loss)^t, so gas per mile is averaged exactly rather
+/// than stepped year by year. 1 when there's no loss.
+pub fn gas_use_factor(loss_percent: f64, start: f64, years: f64) -> f64 {
+    let keep = 1.0 - loss_percent.clamp(0.0, 99.0) / 100.0;
+    if keep >= 1.0 || years <= 0.0 {
+        return 1.0;
+    }
+    let k = -keep.ln();
+    ((k * (start + years)).exp() - (k * start).exp()) / (k * years)
+}
+
+/// A car's efficiency, parsed for its fuel type. Inputs the fuel type doesn't use are
+/// ignored rather than validated, and are zero here.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FuelEconomy {
+    pub fuel_type: FuelType,
+    pub mpg: f64,
+    pub miles_per_kwh: f64,
+    /// Share of miles driven on electricity, from 0 to 1
+    pub electric_share: f64,
+}
+
+/// Combined MPG for a mix of city and highway driving. Gas per mile averages over the
+/// mix, so this is a weighted harmonic mean rather than a plain average of the ratings.
+pub fn blended_mpg(city: f64, highway: f64, highway_percent: f64) -> f64 {
+    let highway_share = highway_percent.clamp(0.0, 100.0) / 100.0;
+    1.0 / (highway_share / highway + (1.0 - highway_share) / city)
+}
+
+impl FuelEconomy {
+    /// Parse the efficiency inputs the car's fuel type needs. A missing input on an
+    /// electric car or hybrid is reported by name, rather than as a generic missing field
+    /// or by falling back to gasoline. City and highway ratings, when both are given, are
+    /// blended at `highway_percent` in place of the single MPG.
+    pub fn parse(car: &Car, highway_percent: f64) -> Result<Self, ComputeError> {
+        let fuel_type = car.fuel_type;
+        // Gas cars have a single required MPG the form already flags; other fuel types
+        // name the input that's holding them up
+        let invalid = |input: &'static str| match fuel_type {
+            FuelType::Gasoline => ComputeError::InvalidInput,
+            _ => ComputeError::InvalidFuelInput(fuel_type, input),
+        };
+        let required = |value: &str, input: &'static str| {
+            let value = value.trim();
+            if value.is_empty() {
+                return Err(match fuel_type {
+                    FuelType::Gasoline => ComputeError::MissingInput,
+                    _ => ComputeError::MissingFuelInput(fuel_type, input),
+                });
+            }
+            value.parse::<f64>().map_err(|_| invalid(input))
+        };
+
+        let mpg = if fuel_type.uses_gas() {
+            match car.split_mpg() {
+                Some((city, highway)) => blended_mpg(city, highway, highway_percent),
+                None => required(&car.mpg, "MPG")?,
+            }
+        } else {
+            0.0
+        };
+        let miles_per_kwh = if fuel_type.uses_electricity() {
+            required(&car.miles_per_kwh, "miles per kWh")?
+        } else {
+            0.0
+        };
+        let electric_share = match fuel_type {
+            FuelType::Gasoline => 0.0,
+            FuelType::Electric => 1.0,
+            FuelType::Hybrid => {
+                required(&car.electric_miles_percent, "electric miles share")? / 100.0
+            }
+        };
+
+        if fuel_type.uses_gas() && !(mpg > 0.0 && mpg.is_finite()) {
+            return Err(invalid("MPG"));
+        }
+        if fuel_type.uses_electricity() && !(miles_per_kwh > 0.0 && miles_per_kwh.is_finite()) {
+            return Err(invalid("miles per kWh"));
+        }
+        if !(0.0..=1.0).contains(&electric_share) {
+            return Err(invalid("electric miles share"));
+        }
+        Ok(Self {
+            fuel_type,
+            mpg,
+            miles_per_kwh,
+            electric_share,
+        })
+    }
+
+    /// Gallons of gas burned per mile, averaged over the miles on gas and on electricity
+    pub fn gallons_per_mile(&self) -> f64 {
+        if self.electric_share < 1.0 {
+            (1.0 - self.electric_share) / self.mpg
+        } else {
+            0.0
+        }
+    }
+
+    /// kWh drawn per mile, averaged over the miles on gas and on electricity
+    pub fn kwh_per_mile(&self) -> f64 {
+        if self.electric_share > 0.0 {
+            self.electric_share / self.miles_per_kwh
+        } else {
+            0.0
+        }
+    }
+
+    /// Cost of driving one mile: gas for the share of miles on gas, electricity for the rest
+    pub fn cost_per_mile(&self, gas_price: f64, electricity_price: f64) -> f64 {
+        self.gallons_per_mile() * gas_price + self.kwh_per_mile() * electricity_price
+    }
+}
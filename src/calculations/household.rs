@@ -0,0 +1,79 @@
+use crate::calculations::{apply_resale_channel, compute_car_data};
+use crate::models::{Car, MaintenanceCostDatabase, SharedSettings};
+
+/// Net cost of replacing the household's selling car with one candidate
+#[derive(Clone, Debug, PartialEq)]
+pub struct HouseholdOption {
+    pub name: String,
+    pub candidate_tco: f64,
+    /// Candidate's TCO less what selling the current car brings in
+    pub net_cost: f64,
+    /// `net_cost` spread over the candidate's ownership period
+    pub net_annual: f64,
+    /// `net_annual` minus the annual cost of keeping the current car, when that's computable
+    pub change_vs_keeping_annual: Option<f64>,
+}
+
+/// Household view of selling one owned car and buying each candidate in its place
+#[derive(Clone, Debug, PartialEq)]
+pub struct HouseholdReplacement {
+    pub selling_name: String,
+    /// Proceeds from selling today, after any trade-in discount
+    pub sale_value: f64,
+    /// Sales tax saved on the replacement by trading in
+    pub sale_tax_credit: f64,
+    /// Annual cost of keeping the selling car instead
+    pub keep_annual: Option<f64>,
+    pub options: Vec<HouseholdOption>,
+}
+
+impl HouseholdReplacement {
+    /// Total credit from selling the current car, applied to each candidate
+    pub fn sale_credit(&self) -> f64 {
+        self.sale_value + self.sale_tax_credit
+    }
+}
+
+/// Compare replacing the car marked as selling (`settings.selling_car_id`) with each
+/// other computable car included in the comparison. The selling car's purchase price is
+/// read as its value today, and its resale channel decides whether it's sold privately or
+/// traded in. The household's other, kept car costs the same whichever candidate is
+/// chosen, so it doesn't change the comparison.
+pub fn household_replacement(
+    cars: &[Car],
+    settings: &SharedSettings,
+    maintenance_db: &MaintenanceCostDatabase,
+) -> Option<HouseholdReplacement> {
+    let selling_id = settings.selling_car_id?;
+    let selling = cars.iter().find(|c| c.id == selling_id)?;
+    let value_today = selling.purchase_price.trim().parse::<f64>().ok()?;
+    let (sale_value, sale_tax_credit) =
+        apply_resale_channel(selling.resale_channel, value_today, settings);
+    let sale_credit = sale_value + sale_tax_credit;
+    let keep_annual = compute_car_data(selling, settings, maintenance_db).map(|c| c.annual_cost);
+
+    let options = cars
+        .iter()
+        .filter(|c| c.id != selling_id && c.in_comparison)
+        .filter_map(|car| {
+            let computed = compute_car_data(car, settings, maintenance_db)?;
+            let net_cost = computed.total_cost_of_ownership - sale_credit;
+            let net_annual = net_cost / computed.years_remaining;
+            Some(HouseholdOption {
+                name: car.display_name(),
+                candidate_tco: computed.total_cost_of_ownership,
+                net_cost,
+                net_annual,
+                change_vs_keeping_annual: keep_annual.map(|keep| net_annual - keep),
+            })
+        })
+        .collect();
+
+    Some(HouseholdReplacement {
+        selling_name: selling.display_name(),
+        sale_value,
+        sale_tax_credit,
+        keep_annual,
+        options,
+    })
+}
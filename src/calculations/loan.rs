@@ -0,0 +1,69 @@
+/// A car loan: the amount borrowed after the down payment, repaid in equal monthly payments
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Loan {
+    pub down_payment: f64,
+    pub principal: f64,
+    /// Annual percentage rate, in percent
+    pub apr: f64,
+    pub term_months: u32,
+}
+
+impl Loan {
+    /// Level monthly payment that repays the principal over the term
+    pub fn monthly_payment(&self) -> f64 {
+        amortized_payment(self.principal, self.apr, self.term_months)
+    }
+
+    /// Balance still owed after `months` payments
+    pub fn balance_after(&self, months: f64) -> f64 {
+        let r = self.apr / 100.0 / 12.0;
+        let payment = self.monthly_payment();
+        let balance = if r == 0.0 {
+            self.principal - payment * months
+        } else {
+            let growth = (1.0 + r).powf(months);
+            self.principal * growth - payment * (growth - 1.0) / r
+        };
+        balance.max(0.0)
+    }
+
+    /// Interest paid over the first `months` of the loan, capped at the term. A balance
+    /// left when the car is sold early is paid off without further interest.
+    pub fn interest_paid_through(&self, months: f64) -> f64 {
+        let months = months.clamp(0.0, self.term_months as f64);
+        months * self.monthly_payment() - (self.principal - self.balance_after(months))
+    }
+
+    /// Down payment plus every loan payment discounted at `discount_rate` (annual, in
+    /// percent): the cash price that would cost the same as taking this loan
+    pub fn effective_cash_price(&self, discount_rate: f64) -> f64 {
+        self.down_payment
+            + present_value_of_payments(self.monthly_payment(), discount_rate, self.term_months)
+    }
+}
+
+/// Monthly payment on a fully amortizing loan; a 0% loan is the principal split evenly
+pub fn amortized_payment(principal: f64, apr: f64, term_months: u32) -> f64 {
+    if term_months == 0 {
+        return principal;
+    }
+    let n = term_months as f64;
+    let r = apr / 100.0 / 12.0;
+    if r == 0.0 {
+        principal / n
+    } else {
+        principal * r / (1.0 - (1.0 + r).powf(-n))
+    }
+}
+
+/// Present value of `months` level monthly payments at an annual rate in percent,
+/// discounted monthly
+pub fn present_value_of_payments(payment: f64, annual_rate: f64, months: u32) -> f64 {
+    let n = months as f64;
+    let r = annual_rate / 100.0 / 12.0;
+    if r == 0.0 {
+        payment * n
+    } else {
+        payment * (1.0 - (1.0 + r).powf(-n)) / r
+    }
+}
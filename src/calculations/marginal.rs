@@ -0,0 +1,73 @@
+use crate::calculations::tco::maintenance_for_mileage_ranges;
+use crate::calculations::try_compute_car_data;
+use crate::models::{Car, MaintenanceCostDatabase, SharedSettings, MILES_PER_MILEAGE_X};
+
+/// Size of each marginal-cost bucket in miles, one unit of a mileage table's x
+pub const MARGINAL_BUCKET_MILES: f64 = MILES_PER_MILEAGE_X;
+
+/// Incremental cost of driving one bucket of miles
+#[derive(Clone, Debug, PartialEq)]
+pub struct MarginalCostBucket {
+    pub start_miles: f64,
+    pub end_miles: f64,
+    pub maintenance_cost: f64,
+    pub fuel_cost: f64,
+    /// True when the bucket lies beyond the last maintenance data point,
+    /// where the curve is extrapolated linearly
+    pub extrapolated: bool,
+}
+
+impl MarginalCostBucket {
+    pub fn total(&self) -> f64 {
+        self.maintenance_cost + self.fuel_cost
+    }
+}
+
+/// Split the remaining ownership period into 10k-mile buckets and compute the
+/// maintenance and fuel cost of each one. Maintenance is costed the same way as the
+/// car's totals (both tables, the extended warranty and regional rates), so the buckets
+/// add up to them.
+pub fn compute_marginal_costs(
+    car: &Car,
+    settings: &SharedSettings,
+    maintenance_db: &MaintenanceCostDatabase,
+) -> Vec<MarginalCostBucket> {
+    let Ok(computed) = try_compute_car_data(car, settings, maintenance_db) else {
+        return Vec::new();
+    };
+    let Ok(current_mileage) = car.current_mileage.trim().parse::<f64>() else {
+        return Vec::new();
+    };
+    // Fuel costs the same for every mile, whatever the car runs on
+    let fuel_per_mile = computed.fuel_cost_total / computed.remaining_miles;
+
+    let maint_data = maintenance_db.get_fuzzy(&car.make, &car.model, &car.trim);
+    let last_data_miles = maint_data
+        .and_then(|d| d.by_mileage.last())
+        .map(|p| p.x * MILES_PER_MILEAGE_X)
+        .unwrap_or(0.0);
+
+    // Align buckets to 10k boundaries after the first, partial one
+    let end_miles = current_mileage + computed.remaining_miles;
+    let mut ranges = Vec::new();
+    let mut start = current_mileage;
+    while start < end_miles {
+        let end = ((start / MARGINAL_BUCKET_MILES).floor() + 1.0) * MARGINAL_BUCKET_MILES;
+        let end = end.min(end_miles);
+        ranges.push((start, end));
+        start = end;
+    }
+
+    let maintenance = maintenance_for_mileage_ranges(car, settings, maintenance_db, &ranges);
+    ranges
+        .into_iter()
+        .zip(maintenance)
+        .map(|((start, end), maintenance_cost)| MarginalCostBucket {
+            start_miles: start,
+            end_miles: end,
+            maintenance_cost,
+            fuel_cost: (end - start) * fuel_per_mile,
+            extrapolated: maint_data.is_some() && end > last_data_miles,
+        })
+        .collect()
+}
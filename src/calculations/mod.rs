@@ -1,3 +1,32 @@
+mod assumptions;
+mod break_even;
+mod cache;
+mod crossover;
+mod depreciation;
+mod explain;
+mod fleet;
+mod fuel;
+mod household;
+mod loan;
+mod marginal;
+mod sort;
 mod tco;
+mod winners;
 
-pub use tco::compute_car_data;
+pub use assumptions::collect_assumptions;
+pub use break_even::{
+    break_even_mileage, BreakEven, BreakEvenOutcome, BreakEvenPoint, BREAK_EVEN_MAX_MILES,
+    BREAK_EVEN_MIN_MILES, BREAK_EVEN_STEP_MILES,
+};
+pub use cache::{cache_key, CacheEntry, ComputedCache, CACHE_VERSION};
+pub use crossover::{cost_crossover, CostCrossover, CrossoverOutcome};
+pub use depreciation::{apply_resale_channel, project_resale_value, DEFAULT_DEPRECIATION_RATE};
+pub use explain::{explain, CostField};
+pub use fleet::{fleet_total, FleetTotal};
+pub use fuel::{blended_mpg, gas_use_factor, FuelEconomy, CO2_KG_PER_GALLON};
+pub use household::{household_replacement, HouseholdOption, HouseholdReplacement};
+pub use loan::{amortized_payment, present_value_of_payments, Loan};
+pub use marginal::{compute_marginal_costs, MarginalCostBucket, MARGINAL_BUCKET_MILES};
+pub use sort::{sorted_car_ids, CarSort};
+pub use tco::{compute_car_data, compute_cash_flows, try_compute_car_data};
+pub use winners::{category_winners, CategoryWinner, WinnerCategory, WINNER_CATEGORIES};
@@ -0,0 +1,94 @@
+use std::cmp::Ordering;
+
+use crate::calculations::try_compute_car_data;
+use crate::models::{Car, ComputedCarData, MaintenanceCostDatabase, SharedSettings};
+
+/// Order the car list is displayed in. Sorting only changes the display; the stored order
+/// (set by moving cars up and down) is left alone.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CarSort {
+    /// The order the cars were added or moved into
+    #[default]
+    Stored,
+    AnnualCost,
+    TotalCost,
+    CostPerMile,
+    Name,
+}
+
+impl CarSort {
+    pub const ALL: [CarSort; 5] = [
+        CarSort::Stored,
+        CarSort::AnnualCost,
+        CarSort::TotalCost,
+        CarSort::CostPerMile,
+        CarSort::Name,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CarSort::Stored => "Your order",
+            CarSort::AnnualCost => "Annual cost",
+            CarSort::TotalCost => "Total cost",
+            CarSort::CostPerMile => "Cost per mile",
+            CarSort::Name => "Name",
+        }
+    }
+
+    /// Stable identifier for form values
+    pub fn id(&self) -> &'static str {
+        match self {
+            CarSort::Stored => "stored",
+            CarSort::AnnualCost => "annual",
+            CarSort::TotalCost => "total",
+            CarSort::CostPerMile => "per_mile",
+            CarSort::Name => "name",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|s| s.id() == id)
+    }
+
+    /// The cost a computed car is sorted by, cheapest first; `None` for non-cost sorts
+    fn cost(&self) -> Option<fn(&ComputedCarData) -> f64> {
+        match self {
+            CarSort::AnnualCost => Some(|c| c.annual_cost),
+            CarSort::TotalCost => Some(|c| c.total_cost_of_ownership),
+            CarSort::CostPerMile => Some(|c| c.cost_per_mile),
+            CarSort::Stored | CarSort::Name => None,
+        }
+    }
+}
+
+/// Car ids in display order. Cost sorts put the cheapest first and cars that can't be
+/// computed last; ties keep the stored order.
+pub fn sorted_car_ids(
+    cars: &[Car],
+    settings: &SharedSettings,
+    maintenance_db: &MaintenanceCostDatabase,
+    sort: CarSort,
+) -> Vec<usize> {
+    let mut order: Vec<(usize, Option<f64>, String)> = cars
+        .iter()
+        .map(|car| {
+            let cost = sort.cost().and_then(|cost| {
+                try_compute_car_data(car, settings, maintenance_db)
+                    .ok()
+                    .map(|computed| cost(&computed))
+            });
+            (car.id, cost, car.display_name().to_lowercase())
+        })
+        .collect();
+    match sort {
+        CarSort::Stored => {}
+        CarSort::Name => order.sort_by(|a, b| a.2.cmp(&b.2)),
+        _ => order.sort_by(|a, b| match (a.1, b.1) {
+            (Some(a), Some(b)) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        }),
+    }
+    order.into_iter().map(|(id, _, _)| id).collect()
+}
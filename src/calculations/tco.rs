@@ -1,4 +1,11 @@
-use crate::models::{Car, ComputedCarData, MaintenanceCostDatabase, SharedSettings};
+use crate::calculations::{
+    apply_resale_channel, gas_use_factor, project_resale_value, FuelEconomy, Loan,
+    CO2_KG_PER_GALLON,
+};
+use crate::models::{
+    Car, ComputeError, ComputeOperands, ComputedCarData, InsuranceMode, MaintenanceBasis,
+    MaintenanceCostData, MaintenanceCostDatabase, PeriodCashFlow, ResaleChannel, SharedSettings,
+};
 
 /// Compute all derived fields from user inputs and shared settings
 pub fn compute_car_data(
@@ -6,77 +13,708 @@ pub fn compute_car_data(
     settings: &SharedSettings,
     maintenance_db: &MaintenanceCostDatabase,
 ) -> Option<ComputedCarData> {
+    try_compute_car_data(car, settings, maintenance_db).ok()
+}
+
+/// Parse a required numeric field
+fn parse_required(value: &str) -> Result<f64, ComputeError> {
+    value
+        .trim()
+        .parse::<f64>()
+        .map_err(|_| ComputeError::MissingInput)
+}
+
+/// Parse an optional numeric field, treating an empty string as unset
+fn parse_optional(value: &str) -> Result<Option<f64>, ComputeError> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(None);
+    }
+    value
+        .parse::<f64>()
+        .map(Some)
+        .map_err(|_| ComputeError::InvalidInput)
+}
+
+/// Parsed and validated inputs describing the remaining ownership period
+struct OwnershipPeriod {
+    purchase_price: f64,
+    current_mileage: f64,
+    fuel: FuelEconomy,
+    insurance_cost_annual: f64,
+    /// The premium or per-mile rate as entered
+    insurance_input: f64,
+    insurance_multiplier: f64,
+    current_age: f64,
+    remaining_miles: f64,
+    years_remaining: f64,
+    /// The car's own lifetime mileage when it has one, otherwise the setting
+    lifetime_miles: f64,
+    lifetime_miles_overridden: bool,
+    /// Miles left before the lifetime mileage, ignoring any planned sale
+    miles_to_lifetime: f64,
+    /// Miles left before the planned sale (by age or years of ownership), when one is set
+    ownership_cap_miles: Option<f64>,
+    /// Length of the first cash-flow period in years
+    first_period_years: f64,
+    extended_warranty_cost: f64,
+    /// Years from today the extended warranty covers maintenance
+    warranty_years: f64,
+    /// Tire set price and life, when both are given
+    tires: Option<(f64, f64)>,
+    loan: Option<Loan>,
+}
+
+/// Parse the car's inputs and work out how long it will be owned
+fn resolve_period(car: &Car, settings: &SharedSettings) -> Result<OwnershipPeriod, ComputeError> {
+    // Settings problems apply to every car, so report them ahead of the car's own inputs
+    settings.validate().map_err(ComputeError::InvalidSettings)?;
+
     // Parse required user inputs
-    let purchase_price = car.purchase_price.parse::<f64>().ok()?;
-    let current_mileage = car.current_mileage.parse::<f64>().ok()?;
-    let mpg = car.mpg.parse::<f64>().ok()?;
-    let insurance_cost_6month = car.insurance_cost.parse::<f64>().ok()?;
+    let purchase_price = parse_required(&car.purchase_price)?;
+    let current_mileage = parse_required(&car.current_mileage)?;
+    let fuel = FuelEconomy::parse(car, settings.highway_percent)?;
+    let ownership_years = parse_optional(&car.keep_until_age)?;
+    let first_year_miles = parse_optional(&car.first_year_miles)?;
+    let extended_warranty_cost = parse_optional(&car.extended_warranty_cost)?.unwrap_or(0.0);
+    let warranty_years_limit = parse_optional(&car.extended_warranty_years)?;
+    let warranty_miles_limit = parse_optional(&car.extended_warranty_miles)?;
+    let loan = parse_loan(car, purchase_price)?;
+    let lifetime_miles_override = parse_optional(&car.lifetime_miles_override)?;
+    let tire_set_cost = parse_optional(&car.tire_set_cost)?;
+    let tire_life_miles = parse_optional(&car.tire_life_miles)?;
 
     // Validate inputs
-    if mpg <= 0.0 || settings.annual_mileage <= 0.0 {
-        return None;
+    let negative = |value: Option<f64>| value.is_some_and(|v| v < 0.0);
+    if purchase_price < 0.0
+        || current_mileage < 0.0
+        || lifetime_miles_override.is_some_and(|miles| miles <= 0.0)
+        || extended_warranty_cost < 0.0
+        || negative(warranty_years_limit)
+        || negative(warranty_miles_limit)
+        || negative(tire_set_cost)
+        || tire_life_miles.is_some_and(|miles| miles <= 0.0)
+    {
+        return Err(ComputeError::InvalidInput);
     }
 
-    // Step 1: Calculate remaining miles
-    let remaining_miles = settings.lifetime_miles - current_mileage;
+    // Coverage ends at the years or miles limit, whichever comes first
+    let warranty_years = match (
+        warranty_years_limit,
+        warranty_miles_limit.map(|miles| miles / settings.annual_mileage),
+    ) {
+        (Some(years), Some(miles_years)) => years.min(miles_years),
+        (Some(years), None) | (None, Some(years)) => years,
+        (None, None) => 0.0,
+    };
+
+    // Step 1: Calculate remaining miles, capped by the planned ownership period if set
+    let lifetime_miles = lifetime_miles_override.unwrap_or(settings.lifetime_miles);
+    let miles_to_lifetime = lifetime_miles - current_mileage;
+    let mut remaining_miles = miles_to_lifetime;
     if remaining_miles <= 0.0 {
-        return None;
+        return Err(ComputeError::PastLifetimeMiles);
+    }
+
+    // The vehicle's current age from its model year, or estimated from its mileage when
+    // the year (or today's date) isn't known
+    let current_age = car
+        .age_from_model_year(settings.current_year)
+        .unwrap_or(current_mileage / settings.annual_mileage);
+
+    let mut ownership_cap_miles = None;
+    if let Some(ownership_years) = ownership_years {
+        let years_left = ownership_years - current_age;
+        if years_left <= 0.0 {
+            return Err(ComputeError::PastOwnershipYears);
+        }
+        let cap = years_left * settings.annual_mileage;
+        ownership_cap_miles = Some(cap);
+        remaining_miles = remaining_miles.min(cap);
+    }
+    // A fixed replacement schedule caps the miles the same way, counted from today
+    if let Some(years) = settings.ownership_years.filter(|years| *years > 0.0) {
+        let cap = years * settings.annual_mileage;
+        ownership_cap_miles = Some(ownership_cap_miles.map_or(cap, |other: f64| other.min(cap)));
+        remaining_miles = remaining_miles.min(cap);
     }
 
     // Step 2: Calculate years remaining
     let years_remaining = remaining_miles / settings.annual_mileage;
 
-    // Step 3: Calculate fuel costs
-    let fuel_cost_total = (remaining_miles / mpg) * settings.average_gas_price;
-    let fuel_cost_annual = fuel_cost_total / years_remaining;
+    // The first period runs to the end of the current year, defaulting to a full year
+    let first_period_years = match first_year_miles {
+        Some(miles) if miles <= 0.0 => return Err(ComputeError::InvalidInput),
+        Some(miles) => (miles / settings.annual_mileage).min(1.0),
+        None => 1.0,
+    };
 
-    // Step 4: Calculate insurance costs
-    let insurance_cost_annual = insurance_cost_6month * 2.0;
+    // Insurance, annualized: either the premium times the premiums paid per year or the
+    // per-mile rate over a year of driving (which totals remaining_miles * per_mile over
+    // the period)
+    let (insurance_input, insurance_base_annual) = match car.insurance_mode {
+        InsuranceMode::Premium => {
+            let premium = parse_required(&car.insurance_cost)?;
+            (premium, premium * car.insurance_period.per_year())
+        }
+        InsuranceMode::PerMile => {
+            let rate = parse_required(&car.insurance_per_mile)?;
+            (rate, rate * settings.annual_mileage)
+        }
+    };
+    if insurance_input < 0.0 {
+        return Err(ComputeError::InvalidInput);
+    }
+    let insurance_multiplier = if settings.region_multiplier_applies_to_insurance {
+        settings.region_cost_multiplier
+    } else {
+        1.0
+    };
+    let insurance_cost_annual = insurance_base_annual * insurance_multiplier;
 
-    // Step 5: Calculate maintenance costs
-    // Split 50/50 between mileage-based and time-based costs
-    let maintenance_cost_total = if let Some(maint_data) = maintenance_db.get(&car.make, &car.model)
-    {
-        let end_miles = current_mileage + remaining_miles;
-        let mileage_cost = maint_data.cost_for_mileage_range(current_mileage, end_miles);
+    Ok(OwnershipPeriod {
+        purchase_price,
+        current_mileage,
+        fuel,
+        insurance_cost_annual,
+        insurance_input,
+        insurance_multiplier,
+        current_age,
+        remaining_miles,
+        years_remaining,
+        lifetime_miles,
+        lifetime_miles_overridden: lifetime_miles_override.is_some(),
+        miles_to_lifetime,
+        ownership_cap_miles,
+        first_period_years,
+        extended_warranty_cost,
+        warranty_years,
+        tires: tire_set_cost.zip(tire_life_miles),
+        loan,
+    })
+}
+
+/// Parse the car's loan terms. A car is financed when it has a loan term; the APR and
+/// down payment default to zero.
+fn parse_loan(car: &Car, purchase_price: f64) -> Result<Option<Loan>, ComputeError> {
+    let Some(term_months) = parse_optional(&car.loan_term_months)? else {
+        return Ok(None);
+    };
+    let down_payment = parse_optional(&car.down_payment)?.unwrap_or(0.0);
+    let apr = parse_optional(&car.loan_apr)?.unwrap_or(0.0);
+    if term_months < 1.0 || apr < 0.0 || down_payment < 0.0 || down_payment > purchase_price {
+        return Err(ComputeError::InvalidInput);
+    }
+    Ok(Some(Loan {
+        down_payment,
+        principal: purchase_price - down_payment,
+        apr,
+        term_months: term_months.round() as u32,
+    }))
+}
+
+/// One period's cash flow plus the raw maintenance from each table behind it
+struct CostedPeriod {
+    flow: PeriodCashFlow,
+    /// Gas burned in this period, after any loss of fuel economy with age
+    gallons: f64,
+    maintenance_by_mileage: f64,
+    maintenance_by_time: f64,
+    /// Maintenance in this period covered by the extended warranty
+    warranty_covered: f64,
+}
+
+/// Maintenance over a stretch of the ownership period
+struct PeriodMaintenance {
+    /// Raw cost from the mileage table
+    mileage_cost: f64,
+    /// Raw cost from the age table
+    time_cost: f64,
+    /// The part of the combined cost covered by the extended warranty
+    warranty_covered: f64,
+    /// Combined cost left to pay
+    maintenance: f64,
+}
+
+/// Maintenance from `start` to `start + years` years from today. Split 50/50 between
+/// mileage-based and time-based costs (or using whichever table has data at full weight),
+/// scaled for regional labor rates, less what the extended warranty covers.
+fn maintenance_between(
+    period: &OwnershipPeriod,
+    maint_data: Option<&MaintenanceCostData>,
+    basis: MaintenanceBasis,
+    settings: &SharedSettings,
+    start: f64,
+    years: f64,
+) -> PeriodMaintenance {
+    let start_miles = period.current_mileage + start * settings.annual_mileage;
+    let start_age = period.current_age + start;
+    let costs = |years: f64| {
+        maint_data.map_or((0.0, 0.0), |data| {
+            (
+                data.cost_for_mileage_range(
+                    start_miles,
+                    start_miles + years * settings.annual_mileage,
+                ),
+                data.cost_for_time_range(start_age, start_age + years),
+            )
+        })
+    };
+    let (mileage_cost, time_cost) = costs(years);
+    let full_maintenance = basis.combine(mileage_cost, time_cost) * settings.region_cost_multiplier;
+
+    // The part still under warranty costs nothing to maintain
+    let covered_years = (period.warranty_years - start).clamp(0.0, years);
+    let warranty_covered = if covered_years <= 0.0 {
+        0.0
+    } else if covered_years >= years {
+        full_maintenance
+    } else {
+        let (mileage_cost, time_cost) = costs(covered_years);
+        basis.combine(mileage_cost, time_cost) * settings.region_cost_multiplier
+    };
+
+    PeriodMaintenance {
+        mileage_cost,
+        time_cost,
+        warranty_covered,
+        maintenance: full_maintenance - warranty_covered,
+    }
+}
+
+/// Maintenance left to pay over each of the given odometer ranges within the ownership
+/// period, costed exactly as the yearly periods are, so ranges covering the whole period
+/// add up to the maintenance total. Empty when the car can't be computed.
+pub(crate) fn maintenance_for_mileage_ranges(
+    car: &Car,
+    settings: &SharedSettings,
+    maintenance_db: &MaintenanceCostDatabase,
+    ranges: &[(f64, f64)],
+) -> Vec<f64> {
+    let Ok(period) = resolve_period(car, settings) else {
+        return Vec::new();
+    };
+    let maint_data = maintenance_db.get_fuzzy(&car.make, &car.model, &car.trim);
+    let basis = maint_data.map_or(MaintenanceBasis::NoData, |d| d.basis());
+    ranges
+        .iter()
+        .map(|&(start_miles, end_miles)| {
+            let start = (start_miles - period.current_mileage) / settings.annual_mileage;
+            let years = (end_miles - start_miles) / settings.annual_mileage;
+            maintenance_between(&period, maint_data, basis, settings, start, years).maintenance
+        })
+        .collect()
+}
+
+/// Split the ownership period into years (the first prorated to the rest of the current
+/// year and the last possibly partial) and cost each one
+fn period_cash_flows(
+    period: &OwnershipPeriod,
+    car: &Car,
+    settings: &SharedSettings,
+    maintenance_db: &MaintenanceCostDatabase,
+) -> Vec<CostedPeriod> {
+    let maint_data = maintenance_db.get_fuzzy(&car.make, &car.model, &car.trim);
+    let basis = maint_data.map_or(MaintenanceBasis::NoData, |d| d.basis());
+
+    let mut flows = Vec::new();
+    let mut start = 0.0;
+    // Tolerance keeps rounding in `start` from producing a zero-length trailing period
+    while period.years_remaining - start > 1e-9 {
+        let full_length = if flows.is_empty() {
+            period.first_period_years
+        } else {
+            1.0
+        };
+        let years = (period.years_remaining - start).min(full_length);
+        let miles = years * settings.annual_mileage;
 
-        // Calculate current age and end age of vehicle
-        // We need to estimate the vehicle's current age based on mileage
-        let current_age = current_mileage / settings.annual_mileage;
-        let end_age = current_age + years_remaining;
-        let time_cost = maint_data.cost_for_time_range(current_age, end_age);
+        let gallons = miles
+            * period.fuel.gallons_per_mile()
+            * gas_use_factor(settings.mpg_loss_percent, start, years);
+        let fuel = gallons * settings.average_gas_price
+            + miles * period.fuel.kwh_per_mile() * settings.electricity_price;
 
-        // Average the two costs (50/50 split)
-        (mileage_cost + time_cost) / 2.0
+        let PeriodMaintenance {
+            mileage_cost,
+            time_cost,
+            warranty_covered,
+            maintenance,
+        } = maintenance_between(period, maint_data, basis, settings, start, years);
+
+        // Interest on the loan payments that fall in this period
+        let financing = period.loan.map_or(0.0, |loan| {
+            loan.interest_paid_through((start + years) * 12.0)
+                - loan.interest_paid_through(start * 12.0)
+        });
+
+        flows.push(CostedPeriod {
+            flow: PeriodCashFlow {
+                year: flows.len(),
+                years,
+                fuel,
+                maintenance,
+                insurance: period.insurance_cost_annual * years,
+                registration: settings.annual_registration_fee * years,
+                // A set of tires wears out every `life` miles, spread evenly over the miles
+                tires: period.tires.map_or(0.0, |(cost, life)| miles / life * cost),
+                financing,
+            },
+            gallons,
+            maintenance_by_mileage: mileage_cost,
+            maintenance_by_time: time_cost,
+            warranty_covered,
+        });
+        start += years;
+    }
+    flows
+}
+
+/// The return `purchase_price` would have earned over `years`, as simple interest or
+/// compounded yearly
+pub(crate) fn opportunity_cost(purchase_price: f64, settings: &SharedSettings, years: f64) -> f64 {
+    let rate = settings.opportunity_cost_rate / 100.0;
+    if settings.compound_opportunity_cost {
+        purchase_price * ((1.0 + rate).powf(years) - 1.0)
+    } else {
+        purchase_price * rate * years
+    }
+}
+
+/// A total cost of ownership discounted to today, and the discounted parts behind it
+struct DiscountedTotal {
+    purchase: f64,
+    running: f64,
+    resale: f64,
+    total: f64,
+}
+
+/// Net present value of owning the car: each period's running costs discounted from the
+/// middle of the period and the resale (with any tax credit) from the sale, at `rate`
+/// percent compounded yearly. A financed purchase counts as its effective cash price.
+/// Discounting is what the opportunity cost approximates, so it isn't added on top.
+fn discounted_total_cost(
+    period: &OwnershipPeriod,
+    costed: &[CostedPeriod],
+    rate: f64,
+    upfront_extras: f64,
+    sale_credit: f64,
+) -> DiscountedTotal {
+    let discount = |years: f64| (1.0 + rate / 100.0).powf(-years);
+    let purchase = period.loan.map_or(period.purchase_price, |loan| {
+        loan.effective_cash_price(rate)
+    }) + upfront_extras;
+    let mut start = 0.0;
+    let mut running = 0.0;
+    for p in costed {
+        let flow = &p.flow;
+        running += (flow.total() - flow.financing) * discount(start + flow.years / 2.0);
+        start += flow.years;
+    }
+    let resale = sale_credit * discount(period.years_remaining);
+    DiscountedTotal {
+        purchase,
+        running,
+        resale,
+        total: purchase + running - resale,
+    }
+}
+
+/// Running costs over the first `window` years, prorating the period the window ends in
+fn running_cost_within(costed: &[CostedPeriod], window: f64) -> f64 {
+    let mut start = 0.0;
+    let mut total = 0.0;
+    for period in costed {
+        let years = period.flow.years;
+        let overlap = (window - start).clamp(0.0, years);
+        if overlap > 0.0 {
+            total += period.flow.total() * overlap / years;
+        }
+        start += years;
+    }
+    total
+}
+
+/// Per-year fuel, maintenance, tire, insurance, registration and financing outlays over the
+/// remaining ownership period. Returns an empty series when the car can't be computed.
+pub fn compute_cash_flows(
+    car: &Car,
+    settings: &SharedSettings,
+    maintenance_db: &MaintenanceCostDatabase,
+) -> Vec<PeriodCashFlow> {
+    resolve_period(car, settings)
+        .map(|period| {
+            period_cash_flows(&period, car, settings, maintenance_db)
+                .into_iter()
+                .map(|p| p.flow)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Compute all derived fields, reporting why the computation failed if it can't be done
+pub fn try_compute_car_data(
+    car: &Car,
+    settings: &SharedSettings,
+    maintenance_db: &MaintenanceCostDatabase,
+) -> Result<ComputedCarData, ComputeError> {
+    let period = resolve_period(car, settings)?;
+    let remaining_miles = period.remaining_miles;
+    let years_remaining = period.years_remaining;
+
+    // Steps 3-5: Sum fuel, insurance, maintenance and tires over the yearly cash flows
+    // so aggregates always agree with the series
+    let costed = period_cash_flows(&period, car, settings, maintenance_db);
+    let fuel_cost_total: f64 = costed.iter().map(|p| p.flow.fuel).sum();
+    let fuel_cost_annual = fuel_cost_total / years_remaining;
+    let total_gallons: f64 = costed.iter().map(|p| p.gallons).sum();
+    // Gas miles per gallon burned, which the yearly loss pulls below the car's MPG
+    let effective_mpg = if total_gallons > 0.0 {
+        remaining_miles * (1.0 - period.fuel.electric_share) / total_gallons
     } else {
         0.0
     };
+    let insurance_cost_total: f64 = costed.iter().map(|p| p.flow.insurance).sum();
+    let insurance_cost_annual = period.insurance_cost_annual;
+    let maintenance_cost_total: f64 = costed.iter().map(|p| p.flow.maintenance).sum();
     let maintenance_cost_annual = maintenance_cost_total / years_remaining;
+    let warranty_covered_maintenance: f64 = costed.iter().map(|p| p.warranty_covered).sum();
+    let registration_cost_total: f64 = costed.iter().map(|p| p.flow.registration).sum();
+    let tire_cost_total: f64 = costed.iter().map(|p| p.flow.tires).sum();
+    let total_interest_paid: f64 = costed.iter().map(|p| p.flow.financing).sum();
+    let extended_warranty_cost = period.extended_warranty_cost;
 
     // Step 6: Calculate opportunity cost
-    let opportunity_cost =
-        purchase_price * (settings.opportunity_cost_rate / 100.0) * years_remaining;
+    let opportunity_cost = opportunity_cost(period.purchase_price, settings, years_remaining);
+
+    // Step 7: Project the resale value at the end of ownership, unless the car has an
+    // expected resale value of its own
+    let depreciation_rate = settings.depreciation_rate.clamp(0.0, 100.0);
+    let (projected_value, depreciation_method) = project_resale_value(
+        car.depreciation_method,
+        depreciation_rate / 100.0,
+        period.purchase_price,
+        period.current_age,
+        years_remaining,
+        remaining_miles,
+        period.miles_to_lifetime,
+        maintenance_db.get_fuzzy(&car.make, &car.model, &car.trim),
+    );
+    let expected_resale_value =
+        parse_optional(&car.expected_resale_value)?.filter(|value| *value >= 0.0);
+    let private_sale_value = expected_resale_value.unwrap_or(projected_value);
+    let (resale_value, trade_in_tax_credit) =
+        apply_resale_channel(car.resale_channel, private_sale_value, settings);
+    let is_trade_in = car.resale_channel == ResaleChannel::TradeIn;
 
-    // Step 7: Calculate total cost of ownership
-    let total_cost_of_ownership = purchase_price
+    // Step 8: Calculate total cost of ownership, net of the resale and tax credits. The
+    // warranty and any sales tax are paid upfront, and the maintenance the warranty covers
+    // is already left out above. A financed car also pays its loan interest.
+    let purchase_tax_rate = if settings.sales_tax_on_purchase {
+        settings.sales_tax_rate
+    } else {
+        0.0
+    };
+    let sales_tax = period.purchase_price * purchase_tax_rate / 100.0;
+    let total_cost_of_ownership = period.purchase_price
+        + sales_tax
+        + extended_warranty_cost
+        + total_interest_paid
         + fuel_cost_total
         + maintenance_cost_total
-        + (insurance_cost_annual * years_remaining)
-        + opportunity_cost;
+        + tire_cost_total
+        + insurance_cost_total
+        + registration_cost_total
+        + opportunity_cost
+        - resale_value
+        - trade_in_tax_credit;
+
+    // The same total in future dollars: running costs grow with inflation to the middle of
+    // the year they're paid in, and the resale and tax credit to the sale. Loan interest
+    // is already fixed in future dollars, and upfront costs are paid today.
+    let inflation_rate = settings.inflation_rate;
+    let growth = |years: f64| (1.0 + inflation_rate / 100.0).powf(years) - 1.0;
+    let mut period_start = 0.0;
+    let mut inflation_on_running = 0.0;
+    for period in &costed {
+        let flow = &period.flow;
+        inflation_on_running +=
+            (flow.total() - flow.financing) * growth(period_start + flow.years / 2.0);
+        period_start += flow.years;
+    }
+    let inflation_on_resale = (resale_value + trade_in_tax_credit) * growth(years_remaining);
+    let total_cost_nominal = total_cost_of_ownership + inflation_on_running - inflation_on_resale;
+
+    let discounted = settings.discounted_total.then(|| {
+        discounted_total_cost(
+            &period,
+            &costed,
+            settings.opportunity_cost_rate,
+            sales_tax + extended_warranty_cost,
+            resale_value + trade_in_tax_credit,
+        )
+    });
+
+    // Step 9: Calculate annual cost. A fixed window still spreads the purchase (net of
+    // resale) over the whole ownership, but averages only that window's running costs.
+    let running_total: f64 = costed.iter().map(|p| p.flow.total()).sum();
+    let window_years = settings.annual_cost_basis.window_years(years_remaining);
+    let window_running = running_cost_within(&costed, window_years);
+    let annual_cost = if window_years < years_remaining {
+        (total_cost_of_ownership - running_total) / years_remaining + window_running / window_years
+    } else {
+        total_cost_of_ownership / years_remaining
+    };
+
+    // Already spent: fuel at the historical gas price and maintenance from new to today
+    let historical_gas_price = settings.historical_gas_price();
+    let incurred_fuel = period.current_mileage
+        * period
+            .fuel
+            .cost_per_mile(historical_gas_price, settings.electricity_price);
+    let incurred_maintenance = maintenance_db
+        .get_fuzzy(&car.make, &car.model, &car.trim)
+        .map_or(0.0, |data| {
+            data.basis().combine(
+                data.cost_for_mileage_range(0.0, period.current_mileage),
+                data.cost_for_time_range(0.0, period.current_age),
+            ) * settings.region_cost_multiplier
+        });
+    let cost_incurred_to_date = incurred_fuel + incurred_maintenance;
+    let cost_projected_remaining = fuel_cost_total
+        + maintenance_cost_total
+        + tire_cost_total
+        + insurance_cost_total
+        + registration_cost_total;
+
+    // Per seat, for comparing carpool and family cars; no or zero seats hides it
+    let seats = parse_optional(&car.seats)?.filter(|seats| *seats > 0.0);
+    let cost_per_mile = total_cost_of_ownership / remaining_miles;
+    let cost_per_passenger_mile = seats.map(|seats| cost_per_mile / seats);
 
-    // Step 8: Calculate annual cost
-    let annual_cost = total_cost_of_ownership / years_remaining;
+    // Operating cost per year leaves out the purchase price (and the resale that offsets it),
+    // but keeps the warranty since it stands in for the maintenance it covers
+    let mut annual_operating_cost = fuel_cost_annual
+        + maintenance_cost_annual
+        + tire_cost_total / years_remaining
+        + insurance_cost_total / years_remaining
+        + registration_cost_total / years_remaining
+        + extended_warranty_cost / years_remaining;
+    if settings.operating_cost_includes_opportunity {
+        annual_operating_cost += opportunity_cost / years_remaining;
+    }
 
-    Some(ComputedCarData {
+    Ok(ComputedCarData {
         remaining_miles,
         years_remaining,
         fuel_cost_total,
         fuel_cost_annual,
+        total_gallons,
+        effective_mpg,
+        total_kwh: remaining_miles * period.fuel.kwh_per_mile(),
+        total_co2_kg: total_gallons * CO2_KG_PER_GALLON,
         insurance_cost_annual,
         maintenance_cost_total,
         maintenance_cost_annual,
         opportunity_cost,
+        maintenance_basis: maintenance_db
+            .get_fuzzy(&car.make, &car.model, &car.trim)
+            .map_or(MaintenanceBasis::NoData, |d| d.basis()),
+        maintenance_profile_match: maintenance_db
+            .get_fuzzy(&car.make, &car.model, &car.trim)
+            .filter(|d| {
+                let trim = if d.trim.is_empty() {
+                    ""
+                } else {
+                    car.trim.as_str()
+                };
+                d.key() != MaintenanceCostData::make_trim_key(&car.make, &car.model, trim)
+            })
+            .map(|d| d.name()),
+        resale_value,
+        depreciation_cost: period.purchase_price - resale_value,
+        depreciation_method,
+        resale_channel: car.resale_channel,
+        trade_in_tax_credit,
+        extended_warranty_cost,
+        warranty_covered_maintenance,
+        effective_cash_price: period
+            .loan
+            .map(|loan| loan.effective_cash_price(settings.opportunity_cost_rate)),
+        sales_tax,
+        registration_cost_total,
+        tire_cost_total,
+        total_interest_paid,
+        monthly_payment: period.loan.map_or(0.0, |loan| loan.monthly_payment()),
+        cost_per_passenger_mile,
+        cost_incurred_to_date,
+        cost_projected_remaining,
         total_cost_of_ownership,
+        total_cost_nominal,
+        discounted_total_cost: discounted.as_ref().map(|d| d.total),
         annual_cost,
+        annual_operating_cost,
+        cost_per_mile,
+        operands: ComputeOperands {
+            purchase_price: period.purchase_price,
+            current_mileage: period.current_mileage,
+            lifetime_miles: period.lifetime_miles,
+            lifetime_miles_overridden: period.lifetime_miles_overridden,
+            ownership_cap_miles: period.ownership_cap_miles,
+            annual_mileage: settings.annual_mileage,
+            fuel_type: period.fuel.fuel_type,
+            mpg: period.fuel.mpg,
+            mpg_loss_percent: settings.mpg_loss_percent,
+            mpg_city_highway: car.split_mpg().filter(|_| period.fuel.fuel_type.uses_gas()),
+            highway_percent: settings.highway_percent,
+            gas_price: settings.average_gas_price,
+            miles_per_kwh: period.fuel.miles_per_kwh,
+            electricity_price: settings.electricity_price,
+            electric_share: period.fuel.electric_share,
+            insurance_mode: car.insurance_mode,
+            insurance_period: car.insurance_period,
+            insurance_input: period.insurance_input,
+            insurance_multiplier: period.insurance_multiplier,
+            maintenance_by_mileage: costed.iter().map(|p| p.maintenance_by_mileage).sum(),
+            maintenance_by_time: costed.iter().map(|p| p.maintenance_by_time).sum(),
+            region_multiplier: settings.region_cost_multiplier,
+            opportunity_cost_rate: settings.opportunity_cost_rate,
+            opportunity_compounded: settings.compound_opportunity_cost,
+            operating_includes_opportunity: settings.operating_cost_includes_opportunity,
+            private_sale_value,
+            resale_overridden: expected_resale_value.is_some(),
+            depreciation_rate,
+            trade_in_discount_percent: if is_trade_in {
+                settings.trade_in_discount_percent
+            } else {
+                0.0
+            },
+            sales_tax_rate: if is_trade_in && settings.trade_in_tax_credit {
+                settings.sales_tax_rate
+            } else {
+                0.0
+            },
+            purchase_tax_rate,
+            annual_registration_fee: settings.annual_registration_fee,
+            tire_set_cost: period.tires.map_or(0.0, |(cost, _)| cost),
+            tire_life_miles: period.tires.map_or(0.0, |(_, life)| life),
+            warranty_years: period.warranty_years,
+            down_payment: period.loan.map_or(0.0, |loan| loan.down_payment),
+            loan_apr: period.loan.map_or(0.0, |loan| loan.apr),
+            loan_term_months: period.loan.map_or(0, |loan| loan.term_months),
+            monthly_payment: period.loan.map_or(0.0, |loan| loan.monthly_payment()),
+            seats: seats.unwrap_or(0.0),
+            historical_gas_price,
+            incurred_fuel,
+            incurred_maintenance,
+            inflation_rate,
+            inflation_on_running,
+            inflation_on_resale,
+            discounted_purchase: discounted.as_ref().map_or(0.0, |d| d.purchase),
+            discounted_running: discounted.as_ref().map_or(0.0, |d| d.running),
+            discounted_resale: discounted.as_ref().map_or(0.0, |d| d.resale),
+            annual_cost_window_years: window_years,
+            annual_cost_window_running: window_running,
+        },
     })
 }
@@ -0,0 +1,93 @@
+use crate::models::ComputedCarData;
+
+/// Values within half a cent are treated as a tie
+const TIE_TOLERANCE: f64 = 0.005;
+
+/// A per-category comparison: label, how to read it, and whether higher is better
+pub struct WinnerCategory {
+    pub label: &'static str,
+    pub value: fn(&ComputedCarData) -> f64,
+    pub higher_is_better: bool,
+    /// Suffix for the formatted value, e.g. "/yr"
+    pub unit: &'static str,
+}
+
+pub const WINNER_CATEGORIES: &[WinnerCategory] = &[
+    WinnerCategory {
+        label: "Cheapest fuel",
+        value: |c| c.fuel_cost_annual,
+        higher_is_better: false,
+        unit: "/yr",
+    },
+    WinnerCategory {
+        label: "Cheapest maintenance",
+        value: |c| c.maintenance_cost_annual,
+        higher_is_better: false,
+        unit: "/yr",
+    },
+    WinnerCategory {
+        label: "Cheapest insurance",
+        value: |c| c.insurance_cost_annual,
+        higher_is_better: false,
+        unit: "/yr",
+    },
+    WinnerCategory {
+        label: "Lowest opportunity cost",
+        value: |c| c.opportunity_cost / c.years_remaining,
+        higher_is_better: false,
+        unit: "/yr",
+    },
+    WinnerCategory {
+        label: "Best resale value",
+        value: |c| c.resale_value,
+        higher_is_better: true,
+        unit: "",
+    },
+];
+
+/// The car (or tied cars) that wins one category
+#[derive(Clone, Debug, PartialEq)]
+pub struct CategoryWinner {
+    pub category: &'static str,
+    pub winners: Vec<String>,
+    pub value: f64,
+    pub unit: &'static str,
+}
+
+/// Name the best car in each category, skipping cars whose costs can't be computed and
+/// cars whose net cost is a profit, since their resale figures can't be trusted.
+/// Categories with no eligible cars are omitted.
+pub fn category_winners(cars: &[(String, Option<ComputedCarData>)]) -> Vec<CategoryWinner> {
+    WINNER_CATEGORIES
+        .iter()
+        .filter_map(|category| {
+            let values: Vec<(&String, f64)> = cars
+                .iter()
+                .filter_map(|(name, computed)| {
+                    computed
+                        .as_ref()
+                        .filter(|c| !c.has_net_profit())
+                        .map(|c| (name, (category.value)(c)))
+                })
+                .collect();
+            let best = values.iter().map(|(_, v)| *v).reduce(|a, b| {
+                if category.higher_is_better {
+                    a.max(b)
+                } else {
+                    a.min(b)
+                }
+            })?;
+            let winners = values
+                .iter()
+                .filter(|(_, v)| (v - best).abs() < TIE_TOLERANCE)
+                .map(|(name, _)| (*name).clone())
+                .collect();
+            Some(CategoryWinner {
+                category: category.label,
+                winners,
+                value: best,
+                unit: category.unit,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,152 @@
+use leptos::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{FileReader, HtmlAnchorElement, HtmlInputElement};
+
+use crate::components::ui::ImportReportModal;
+use crate::data::{AppExport, BackupImport, ImportMode, APP_EXPORT_FILENAME};
+use crate::models::{Car, MaintenanceCostDatabase, SharedSettings};
+
+/// Download the cars, settings and maintenance tables (photos included) as a JSON file
+fn download_backup(export: &AppExport) {
+    let url = format!(
+        "data:application/json;charset=utf-8,{}",
+        js_sys::encode_uri_component(&export.to_json())
+    );
+    if let Some(anchor) = document()
+        .create_element("a")
+        .ok()
+        .and_then(|el| el.dyn_into::<HtmlAnchorElement>().ok())
+    {
+        anchor.set_href(&url);
+        anchor.set_download(APP_EXPORT_FILENAME);
+        anchor.click();
+    }
+}
+
+/// Backup of all app data to a file, independent of the browser's local storage, and
+/// restoring from one
+#[component]
+pub fn BackupPanel(
+    cars: Signal<Vec<Car>>,
+    set_cars: WriteSignal<Vec<Car>>,
+    settings: Signal<SharedSettings>,
+    set_settings: WriteSignal<SharedSettings>,
+    maintenance_db: Signal<MaintenanceCostDatabase>,
+    set_maintenance_db: WriteSignal<MaintenanceCostDatabase>,
+) -> impl IntoView {
+    let (mode, set_mode) = signal(ImportMode::Replace);
+    let (status, set_status) = signal::<Option<Result<String, String>>>(None);
+    let pending = RwSignal::new(None::<BackupImport>);
+
+    let current = move || AppExport {
+        cars: cars.get_untracked(),
+        settings: settings.get_untracked(),
+        maintenance_db: maintenance_db.get_untracked(),
+    };
+
+    let export = move |_| download_backup(&current());
+
+    // Nothing is touched until the whole file has parsed and the preview is confirmed
+    let import_text = move |text: String| match AppExport::from_json(&text) {
+        Ok(backup) => {
+            set_status.set(None);
+            pending.set(Some(
+                backup.preview_import(&current(), mode.get_untracked()),
+            ));
+        }
+        Err(err) => set_status.set(Some(Err(format!("{}. Nothing was changed.", err)))),
+    };
+
+    let cancel = Callback::new(move |_| {
+        pending.set(None);
+        set_status.set(Some(Ok(
+            "Import cancelled. Nothing was changed.".to_string()
+        )));
+    });
+    let confirm = Callback::new(move |_| {
+        let Some(import) = pending.get_untracked() else {
+            return;
+        };
+        pending.set(None);
+        set_settings.set(import.result.settings);
+        set_maintenance_db.set(import.result.maintenance_db);
+        set_cars.set(import.result.cars);
+        set_status.set(Some(Ok("Backup imported.".to_string())));
+    });
+
+    let on_file = move |ev: leptos::ev::Event| {
+        let input: HtmlInputElement = event_target(&ev);
+        let Some(file) = input.files().and_then(|files| files.get(0)) else {
+            return;
+        };
+        // Clear the input so choosing the same file again still fires a change
+        input.set_value("");
+        let Ok(reader) = FileReader::new() else {
+            set_status.set(Some(Err("Couldn't read that file.".to_string())));
+            return;
+        };
+        let loaded = reader.clone();
+        let on_load = wasm_bindgen::closure::Closure::once_into_js(move || {
+            match loaded.result().ok().and_then(|result| result.as_string()) {
+                Some(text) => import_text(text),
+                None => set_status.set(Some(Err("Couldn't read that file.".to_string()))),
+            }
+        });
+        reader.set_onload(Some(on_load.unchecked_ref()));
+        if reader.read_as_text(&file).is_err() {
+            set_status.set(Some(Err("Couldn't read that file.".to_string())));
+        }
+    };
+
+    view! {
+        <div class="space-y-2">
+            <div class="flex flex-wrap items-center justify-end gap-2 text-sm">
+                <span class="text-gray-600">"Back up your cars, settings and maintenance tables"</span>
+                <button
+                    class="px-3 py-1 rounded-md border border-gray-300 bg-white font-medium text-gray-700 hover:bg-gray-50"
+                    on:click=export
+                >
+                    "Export"
+                </button>
+                <label for="backup-import-mode" class="sr-only">"Import mode"</label>
+                <select
+                    id="backup-import-mode"
+                    class="rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                    title="Replace discards the current data; merge keeps your settings, adds the backup's cars and combines maintenance profiles"
+                    on:change=move |ev| {
+                        set_mode.set(if event_target_value(&ev) == "merge" {
+                            ImportMode::Merge
+                        } else {
+                            ImportMode::Replace
+                        })
+                    }
+                >
+                    <option value="replace" selected=true>"Replace current data"</option>
+                    <option value="merge">"Merge with current data"</option>
+                </select>
+                <label class="px-3 py-1 rounded-md border border-gray-300 bg-white font-medium text-gray-700 hover:bg-gray-50 cursor-pointer">
+                    "Import"
+                    <input type="file" accept="application/json,.json" class="sr-only" on:change=on_file />
+                </label>
+            </div>
+            {move || status.get().map(|status| match status {
+                Ok(message) => view! {
+                    <p class="text-sm text-right text-green-700" role="status">{message}</p>
+                }.into_any(),
+                Err(message) => view! {
+                    <p class="text-sm text-right text-red-700" role="alert">{message}</p>
+                }.into_any(),
+            })}
+            {move || pending.get().map(|import| view! {
+                <ImportReportModal
+                    title="Import backup"
+                    report=import.report
+                    description=import.summary
+                    on_close=cancel
+                    on_confirm=confirm
+                    confirm_label="Import"
+                />
+            })}
+        </div>
+    }
+}
@@ -0,0 +1,16 @@
+use leptos::prelude::*;
+
+/// Collapsible list of the assumptions behind a car's computed costs
+#[component]
+pub fn AssumptionsReport(assumptions: Vec<String>) -> impl IntoView {
+    view! {
+        <details class="mt-6 border border-gray-200 rounded-lg">
+            <summary class="px-4 py-3 text-sm font-medium text-gray-900 cursor-pointer select-none">
+                "Assumptions used"
+            </summary>
+            <ul class="px-4 pb-4 space-y-1 text-sm text-gray-700 list-disc list-inside">
+                {assumptions.into_iter().map(|a| view! { <li>{a}</li> }).collect_view()}
+            </ul>
+        </details>
+    }
+}
@@ -1,26 +1,61 @@
+use leptos::html;
 use leptos::prelude::*;
+use leptos_use::signal_debounced;
+use std::time::Duration;
+use wasm_bindgen::JsCast;
 
-use crate::calculations::compute_car_data;
-use crate::components::cars::{CarCostSummary, CarForm};
-use crate::models::{Car, MaintenanceCostDatabase, SharedSettings};
+use crate::calculations::{
+    collect_assumptions, compute_marginal_costs, try_compute_car_data, ComputedCache,
+};
+use crate::components::cars::{
+    AssumptionsReport, CarCostSummary, CarForm, MarginalCostChart, WaterfallChart,
+};
+use crate::components::session_link::share_url;
+use crate::components::ui::focus_input;
+use crate::data::{format_dollars, format_thousands, SessionState};
+use crate::models::{Car, ComputeError, MaintenanceCostDatabase, SettingsIssue, SharedSettings};
+
+/// How long the "changed by" indicator stays up after the last settings edit
+const SETTINGS_DELTA_MS: u64 = 4000;
+
+/// The car with `car_id` as currently stored in `cars`, so a card follows changes made to
+/// the list as a whole (undo, redo, importing a backup) rather than keeping its own copy.
+/// Keeps the last value once the car is gone, while the card is being torn down.
+pub fn car_in_list(cars: Signal<Vec<Car>>, car_id: usize, initial: Car) -> Memo<Car> {
+    Memo::new(move |previous: Option<&Car>| {
+        cars.with(|cars| cars.iter().find(|c| c.id == car_id).cloned())
+            .or_else(|| previous.cloned())
+            .unwrap_or_else(|| initial.clone())
+    })
+}
 
 #[component]
 pub fn CarCard(
-    car: Car,
+    /// The car as stored in the list, see `car_in_list`
+    car: Signal<Car>,
     update_car: impl Fn(Car) + 'static + Copy + Send + Sync,
     car_id: usize,
     expanded_cars: ReadSignal<Vec<usize>>,
     set_expanded_cars: WriteSignal<Vec<usize>>,
     settings: Signal<SharedSettings>,
     maintenance_db: Signal<MaintenanceCostDatabase>,
+    computed_cache: Signal<ComputedCache>,
+    set_computed_cache: WriteSignal<ComputedCache>,
     on_delete: Box<dyn Fn()>,
+    /// Adds a copy of this car right after it
+    on_duplicate: Box<dyn Fn()>,
+    /// Moves the car up (-1) or down (1) in the list
+    on_move: Callback<isize>,
+    can_move_up: Signal<bool>,
+    can_move_down: Signal<bool>,
 ) -> impl IntoView {
-    let (car_signal, set_car_signal) = signal(car);
+    let car_signal = car;
 
-    // Create a wrapper that updates both local signal and parent
+    // Edits go straight to the list, which the card reads back through `car`
     let set_car_wrapper = move |f: &dyn Fn(&mut Car)| {
-        set_car_signal.update(f);
-        update_car(car_signal.get());
+        let mut updated = car_signal.get_untracked();
+        f(&mut updated);
+        update_car(updated);
     };
 
     let is_expanded = move || expanded_cars.get().contains(&car_id);
@@ -35,38 +70,173 @@ pub fn CarCard(
         });
     };
 
-    let car_display = move || {
-        let c = car_signal.get();
-        let name = if !c.make.is_empty() || !c.model.is_empty() {
-            format!("{} {}", c.make, c.model).trim().to_string()
-        } else {
-            format!("Car #{}", c.id)
-        };
-        let year = if !c.year.is_empty() {
-            format!(" ({})", c.year)
-        } else {
-            String::new()
-        };
-        format!("{}{}", name, year)
+    // Escape collapses an expanded card, unless something inside already handled it or
+    // focus is in a field where Escape may mean something (clearing a search box, closing
+    // a select), and returns focus to the header so it isn't lost with the hidden form
+    let header_button = NodeRef::<html::Button>::new();
+    let on_keydown = move |ev: leptos::ev::KeyboardEvent| {
+        if ev.key() != "Escape" || ev.default_prevented() || !is_expanded() {
+            return;
+        }
+        let in_field = ev.target().is_some_and(|target| {
+            if let Some(input) = target.dyn_ref::<web_sys::HtmlInputElement>() {
+                !matches!(input.type_().as_str(), "checkbox" | "radio" | "button")
+            } else {
+                target.dyn_ref::<web_sys::HtmlTextAreaElement>().is_some()
+                    || target.dyn_ref::<web_sys::HtmlSelectElement>().is_some()
+            }
+        });
+        if in_field {
+            return;
+        }
+        ev.prevent_default();
+        set_expanded_cars.update(|expanded| expanded.retain(|&id| id != car_id));
+        if let Some(button) = header_button.get() {
+            let _ = button.focus();
+        }
     };
 
-    let computed_data =
-        move || compute_car_data(&car_signal.get(), &settings.get(), &maintenance_db.get());
+    let car_display = move || car_signal.get().display_name();
+
+    // Start from the cached result when the inputs are unchanged since it was stored, then
+    // recompute after mount (and on every input change) and refresh the cache
+    let cached = computed_cache.with_untracked(|cache| {
+        car_signal.with_untracked(|car| {
+            settings.with_untracked(|settings| {
+                maintenance_db.with_untracked(|db| cache.get(car, settings, db).cloned())
+            })
+        })
+    });
+    let computed_result = RwSignal::new(cached.map(Ok).unwrap_or_else(|| {
+        try_compute_car_data(
+            &car_signal.get_untracked(),
+            &settings.get_untracked(),
+            &maintenance_db.get_untracked(),
+        )
+    }));
+    // After a settings edit, show how much this car's annual cost moved, measured from before
+    // the first of a burst of edits and cleared a few seconds after the last
+    let settings_delta = RwSignal::new(None::<f64>);
+    let delta_baseline = StoredValue::new(None::<f64>);
+    let delta_generation = StoredValue::new(0_u64);
+    let last_settings = StoredValue::new(None::<SharedSettings>);
+
+    Effect::new(move |_| {
+        let (car, settings, db) = (car_signal.get(), settings.get(), maintenance_db.get());
+        let result = try_compute_car_data(&car, &settings, &db);
+        let settings_changed =
+            last_settings.with_value(|last| last.as_ref().is_some_and(|last| *last != settings));
+        last_settings.set_value(Some(settings.clone()));
+        if settings_changed {
+            let previous =
+                computed_result.with_untracked(|r| r.as_ref().ok().map(|c| c.annual_cost));
+            if delta_baseline.get_value().is_none() {
+                delta_baseline.set_value(previous);
+            }
+            let delta = match (delta_baseline.get_value(), &result) {
+                (Some(before), Ok(after)) => Some(after.annual_cost - before),
+                _ => None,
+            };
+            settings_delta.set(delta.filter(|d| d.abs() >= 0.5));
+            let generation = delta_generation.get_value() + 1;
+            delta_generation.set_value(generation);
+            set_timeout(
+                move || {
+                    // The card may have been removed since
+                    if delta_generation.try_get_value() == Some(generation) {
+                        settings_delta.try_set(None);
+                        delta_baseline.try_set_value(None);
+                    }
+                },
+                Duration::from_millis(SETTINGS_DELTA_MS),
+            );
+        }
+        if let Ok(data) = &result {
+            let changed = computed_cache
+                .with_untracked(|cache| cache.get(&car, &settings, &db) != Some(data));
+            if changed {
+                set_computed_cache.update(|cache| {
+                    cache.insert(&car, &settings, &db, data.clone());
+                });
+            }
+        }
+        if computed_result.get_untracked() != result {
+            computed_result.set(result);
+        }
+    });
+    let computed_data = move || computed_result.get().ok();
+
+    // Screen-reader announcement of the recomputed costs, settled so typing a value
+    // doesn't announce every intermediate keystroke
+    let announcement = Memo::new(move |_| match computed_result.get() {
+        Ok(computed) => format!(
+            "{}: annual cost {}, total cost of ownership {}",
+            car_display(),
+            format_dollars(computed.annual_cost, 0),
+            format_dollars(computed.total_cost_of_ownership, 0)
+        ),
+        Err(err) => format!("{}: {}", car_display(), err),
+    });
+    let settled_announcement: Signal<String> = signal_debounced(announcement, 1000.0);
+
+    // A link that opens just this car with the current settings, for sending to someone
+    let link_copied = RwSignal::new(false);
+    let copy_share_link = move |_| {
+        let state = settings.with_untracked(|settings| {
+            car_signal
+                .with_untracked(|car| SessionState::for_link(settings, std::slice::from_ref(car)))
+        });
+        // Clipboard writes resolve asynchronously; the promise result isn't needed
+        let _ = window()
+            .navigator()
+            .clipboard()
+            .write_text(&share_url(&state));
+        link_copied.set(true);
+        set_timeout(
+            move || {
+                link_copied.try_set(false);
+            },
+            Duration::from_secs(2),
+        );
+    };
 
     view! {
-        <div class="bg-white overflow-hidden shadow rounded-lg">
+        <div
+            class="bg-white overflow-hidden shadow rounded-lg transition-opacity border-t-4 border-transparent"
+            class:opacity-60=move || !car_signal.get().in_comparison
+            style=move || {
+                car_signal
+                    .with(|car| car.tag_color())
+                    .map(|color| format!("border-top-color: {}", color))
+                    .unwrap_or_default()
+            }
+            on:keydown=on_keydown
+        >
             <div class="px-4 py-5 sm:p-6">
                 <div class="flex items-center justify-between">
                     <button
+                        node_ref=header_button
                         class="flex-1 flex items-center text-left"
+                        aria-expanded=move || is_expanded().to_string()
                         on:click=toggle_expanded
                     >
+                        {move || {
+                            car_signal.with(|car| car.photo_src().map(str::to_string)).map(|src| {
+                                view! { <img src=src alt="" class="h-10 w-10 mr-3 rounded object-cover border border-gray-200" /> }
+                            })
+                        }}
                         <span class="text-lg font-medium text-gray-900">{car_display}</span>
+                        {move || (settings.get().selling_car_id == Some(car_id)).then(|| view! {
+                            <span class="ml-2 px-2 py-0.5 rounded text-xs font-medium bg-amber-100 text-amber-800">
+                                "Owned · selling"
+                            </span>
+                        })}
                         <svg
                             class=move || format!(
                                 "ml-2 h-5 w-5 transform transition-transform {}",
                                 if is_expanded() { "rotate-180" } else { "" }
                             )
+                            aria-hidden="true"
                             xmlns="http://www.w3.org/2000/svg"
                             viewBox="0 0 20 20"
                             fill="currentColor"
@@ -74,41 +244,159 @@ pub fn CarCard(
                             <path fill-rule="evenodd" d="M5.293 7.293a1 1 0 011.414 0L10 10.586l3.293-3.293a1 1 0 111.414 1.414l-4 4a1 1 0 01-1.414 0l-4-4a1 1 0 010-1.414z" clip-rule="evenodd"/>
                         </svg>
                     </button>
+                    <label
+                        class="ml-4 inline-flex items-center text-xs text-gray-600 whitespace-nowrap"
+                        title="Include this car in the comparison and totals"
+                    >
+                        <input
+                            type="checkbox"
+                            class="mr-1 rounded border-gray-300 text-blue-600 focus:ring-blue-500"
+                            prop:checked=move || car_signal.get().in_comparison
+                            on:change=move |ev| {
+                                let checked = event_target_checked(&ev);
+                                set_car_wrapper(&|c| c.in_comparison = checked);
+                            }
+                        />
+                        "Compare"
+                    </label>
                     {move || {
                         if let Some(computed) = computed_data() {
+                            let metric = settings.get().primary_metric;
                             view! {
                                 <div class="ml-4 text-right">
-                                    <div class="text-sm text-gray-500">"Annual Cost"</div>
+                                    <div class="text-sm text-gray-500">{metric.label()}</div>
                                     <div class="text-lg font-semibold text-blue-600">
-                                        {format!("${:.0}", computed.annual_cost)}
+                                        {metric.format(&computed)}
                                     </div>
+                                    {computed.has_net_profit().then(|| view! {
+                                        <div class="text-xs font-medium text-amber-700" title="Resale and credits outweigh every cost; check the resale assumptions">
+                                            "Net profit? Check resale"
+                                        </div>
+                                    })}
+                                    {move || settings_delta.get().map(|delta| view! {
+                                        <div
+                                            class={if delta > 0.0 { "text-xs font-medium text-red-700" } else { "text-xs font-medium text-green-700" }}
+                                            title="Change in annual cost from your settings edit"
+                                        >
+                                            {if delta > 0.0 {
+                                                format!("changed by +${:.0}/yr", delta)
+                                            } else {
+                                                format!("changed by −${:.0}/yr", -delta)
+                                            }}
+                                        </div>
+                                    })}
                                 </div>
                             }.into_any()
                         } else {
                             view! { <div></div> }.into_any()
                         }
                     }}
+                    <div class="ml-4 flex flex-col">
+                        <button
+                            class="text-gray-400 hover:text-gray-700 disabled:opacity-30 disabled:cursor-not-allowed"
+                            title="Move up"
+                            aria-label="Move up"
+                            prop:disabled=move || !can_move_up.get()
+                            on:click=move |_| on_move.run(-1)
+                        >
+                            <svg class="h-4 w-4" aria-hidden="true" xmlns="http://www.w3.org/2000/svg" viewBox="0 0 20 20" fill="currentColor">
+                                <path fill-rule="evenodd" d="M14.707 12.707a1 1 0 01-1.414 0L10 9.414l-3.293 3.293a1 1 0 01-1.414-1.414l4-4a1 1 0 011.414 0l4 4a1 1 0 010 1.414z" clip-rule="evenodd"/>
+                            </svg>
+                        </button>
+                        <button
+                            class="text-gray-400 hover:text-gray-700 disabled:opacity-30 disabled:cursor-not-allowed"
+                            title="Move down"
+                            aria-label="Move down"
+                            prop:disabled=move || !can_move_down.get()
+                            on:click=move |_| on_move.run(1)
+                        >
+                            <svg class="h-4 w-4" aria-hidden="true" xmlns="http://www.w3.org/2000/svg" viewBox="0 0 20 20" fill="currentColor">
+                                <path fill-rule="evenodd" d="M5.293 7.293a1 1 0 011.414 0L10 10.586l3.293-3.293a1 1 0 111.414 1.414l-4 4a1 1 0 01-1.414 0l-4-4a1 1 0 010-1.414z" clip-rule="evenodd"/>
+                            </svg>
+                        </button>
+                    </div>
+                    <button
+                        class="ml-4 text-gray-400 hover:text-gray-700"
+                        title="Duplicate"
+                        aria-label="Duplicate"
+                        on:click=move |_| on_duplicate()
+                    >
+                        <svg class="h-5 w-5" aria-hidden="true" xmlns="http://www.w3.org/2000/svg" viewBox="0 0 20 20" fill="currentColor">
+                            <path d="M7 9a2 2 0 012-2h6a2 2 0 012 2v6a2 2 0 01-2 2H9a2 2 0 01-2-2V9z"/>
+                            <path d="M5 3a2 2 0 00-2 2v6a2 2 0 002 2V5h8a2 2 0 00-2-2H5z"/>
+                        </svg>
+                    </button>
+                    <button
+                        class="ml-4 text-gray-400 hover:text-gray-700"
+                        title=move || if link_copied.get() { "Link copied" } else { "Copy a link to share this car" }
+                        aria-label="Copy share link"
+                        on:click=copy_share_link
+                    >
+                        {move || if link_copied.get() {
+                            view! {
+                                <svg class="h-5 w-5 text-green-600" aria-hidden="true" xmlns="http://www.w3.org/2000/svg" viewBox="0 0 20 20" fill="currentColor">
+                                    <path fill-rule="evenodd" d="M16.707 5.293a1 1 0 010 1.414l-8 8a1 1 0 01-1.414 0l-4-4a1 1 0 011.414-1.414L8 12.586l7.293-7.293a1 1 0 011.414 0z" clip-rule="evenodd"/>
+                                </svg>
+                            }
+                            .into_any()
+                        } else {
+                            view! {
+                                <svg class="h-5 w-5" aria-hidden="true" xmlns="http://www.w3.org/2000/svg" viewBox="0 0 20 20" fill="currentColor">
+                                    <path fill-rule="evenodd" d="M12.586 4.586a2 2 0 112.828 2.828l-3 3a2 2 0 01-2.828 0 1 1 0 00-1.414 1.414 4 4 0 005.656 0l3-3a4 4 0 00-5.656-5.656l-1.5 1.5a1 1 0 101.414 1.414l1.5-1.5zm-5 5a2 2 0 012.828 0 1 1 0 101.414-1.414 4 4 0 00-5.656 0l-3 3a4 4 0 105.656 5.656l1.5-1.5a1 1 0 10-1.414-1.414l-1.5 1.5a2 2 0 11-2.828-2.828l3-3z" clip-rule="evenodd"/>
+                                </svg>
+                            }
+                            .into_any()
+                        }}
+                    </button>
                     <button
                         class="ml-4 text-red-600 hover:text-red-800"
+                        title="Delete car"
+                        aria-label="Delete car"
                         on:click=move |_| on_delete()
                     >
-                        <svg class="h-5 w-5" xmlns="http://www.w3.org/2000/svg" viewBox="0 0 20 20" fill="currentColor">
+                        <svg class="h-5 w-5" aria-hidden="true" xmlns="http://www.w3.org/2000/svg" viewBox="0 0 20 20" fill="currentColor">
                             <path fill-rule="evenodd" d="M9 2a1 1 0 00-.894.553L7.382 4H4a1 1 0 000 2v10a2 2 0 002 2h8a2 2 0 002-2V6a1 1 0 100-2h-3.382l-.724-1.447A1 1 0 0011 2H9zM7 8a1 1 0 012 0v6a1 1 0 11-2 0V8zm5-1a1 1 0 00-1 1v6a1 1 0 102 0V8a1 1 0 00-1-1z" clip-rule="evenodd"/>
                         </svg>
                     </button>
                 </div>
 
+                <div class="sr-only" role="status" aria-live="polite">
+                    {move || settled_announcement.get()}
+                </div>
+
                 <Show when=is_expanded>
-                    <CarForm car=car_signal set_car_wrapper=set_car_wrapper />
+                    <CarForm car=car_signal set_car_wrapper=set_car_wrapper settings=settings />
                     {move || {
-                        if let Some(computed) = computed_data() {
-                            view! { <CarCostSummary computed=computed /> }.into_any()
-                        } else {
-                            view! {
+                        match computed_result.get() {
+                            Ok(computed) => {
+                                let (car, settings, db) =
+                                    (car_signal.get(), settings.get(), maintenance_db.get());
+                                let buckets = compute_marginal_costs(&car, &settings, &db);
+                                let assumptions = collect_assumptions(&car, &settings, &db);
+                                view! {
+                                    <CarCostSummary
+                                        car_name=car_display()
+                                        car=car.clone()
+                                        computed=computed.clone()
+                                        primary_metric=settings.primary_metric
+                                    />
+                                    <WaterfallChart computed=computed />
+                                    <MarginalCostChart buckets=buckets />
+                                    <AssumptionsReport assumptions=assumptions />
+                                }
+                                .into_any()
+                            }
+                            // Reported once for all cars, next to the settings
+                            Err(err) if err.is_settings_error() => view! {
+                                <p class="mt-6 border-t border-gray-200 pt-6 text-sm text-gray-500">
+                                    "Costs will appear once the highlighted shared setting is fixed."
+                                </p>
+                            }.into_any(),
+                            Err(ComputeError::MissingInput) => view! {
                                 <div class="mt-6 border-t border-gray-200 pt-6">
                                     <div class="bg-yellow-50 border border-yellow-200 rounded-lg p-4">
                                         <div class="flex">
-                                            <svg class="h-5 w-5 text-yellow-400 mr-3" xmlns="http://www.w3.org/2000/svg" viewBox="0 0 20 20" fill="currentColor">
+                                            <svg class="h-5 w-5 text-yellow-400 mr-3" aria-hidden="true" xmlns="http://www.w3.org/2000/svg" viewBox="0 0 20 20" fill="currentColor">
                                                 <path fill-rule="evenodd" d="M8.257 3.099c.765-1.36 2.722-1.36 3.486 0l5.58 9.92c.75 1.334-.213 2.98-1.742 2.98H4.42c-1.53 0-2.493-1.646-1.743-2.98l5.58-9.92zM11 13a1 1 0 11-2 0 1 1 0 012 0zm-1-8a1 1 0 00-1 1v3a1 1 0 002 0V6a1 1 0 00-1-1z" clip-rule="evenodd"/>
                                             </svg>
                                             <div>
@@ -122,7 +410,54 @@ pub fn CarCard(
                                         </div>
                                     </div>
                                 </div>
-                            }.into_any()
+                            }.into_any(),
+                            // Every field is filled in, so say which setting rules the car out
+                            Err(ComputeError::PastLifetimeMiles) => view! {
+                                <div class="mt-6 border-t border-gray-200 pt-6">
+                                    <div class="bg-yellow-50 border border-yellow-200 rounded-lg p-4">
+                                        <h4 class="text-sm font-medium text-yellow-800">"Already past the lifetime mileage"</h4>
+                                        <p class="mt-1 text-sm text-yellow-700">
+                                            {move || {
+                                                let car = car_signal.get();
+                                                let lifetime = car.lifetime_miles(settings.get().lifetime_miles);
+                                                if car.lifetime_miles_override.trim().is_empty() {
+                                                    format!(
+                                                        "This car has already exceeded your lifetime mileage setting ({} miles), so there are no remaining costs to project. Raise the setting to include it.",
+                                                        format_thousands(lifetime)
+                                                    )
+                                                } else {
+                                                    format!(
+                                                        "This car has already exceeded its own lifetime mileage ({} miles), so there are no remaining costs to project. Raise it under Advanced to include it.",
+                                                        format_thousands(lifetime)
+                                                    )
+                                                }
+                                            }}
+                                        </p>
+                                        <button
+                                            class="mt-2 px-3 py-1 rounded-md border border-yellow-300 bg-white text-sm font-medium text-yellow-800 hover:bg-yellow-100"
+                                            class:hidden=move || car_signal.with(|c| !c.lifetime_miles_override.trim().is_empty())
+                                            on:click=move |_| focus_input(SettingsIssue::LifetimeMiles.input_id())
+                                        >
+                                            {format!("Go to {}", SettingsIssue::LifetimeMiles.label())}
+                                        </button>
+                                    </div>
+                                </div>
+                            }.into_any(),
+                            Err(err) => view! {
+                                <div class="mt-6 border-t border-gray-200 pt-6">
+                                    <div class="bg-yellow-50 border border-yellow-200 rounded-lg p-4">
+                                        <div class="flex">
+                                            <svg class="h-5 w-5 text-yellow-400 mr-3" aria-hidden="true" xmlns="http://www.w3.org/2000/svg" viewBox="0 0 20 20" fill="currentColor">
+                                                <path fill-rule="evenodd" d="M8.257 3.099c.765-1.36 2.722-1.36 3.486 0l5.58 9.92c.75 1.334-.213 2.98-1.742 2.98H4.42c-1.53 0-2.493-1.646-1.743-2.98l5.58-9.92zM11 13a1 1 0 11-2 0 1 1 0 012 0zm-1-8a1 1 0 00-1 1v3a1 1 0 002 0V6a1 1 0 00-1-1z" clip-rule="evenodd"/>
+                                            </svg>
+                                            <div>
+                                                <h4 class="text-sm font-medium text-yellow-800">"Can't calculate costs"</h4>
+                                                <p class="mt-1 text-sm text-yellow-700">{err.to_string()}</p>
+                                            </div>
+                                        </div>
+                                    </div>
+                                </div>
+                            }.into_any(),
                         }
                     }}
                 </Show>
@@ -0,0 +1,143 @@
+use leptos::prelude::*;
+
+use crate::data::{format_dollars, format_thousands};
+use crate::models::ComputedCarData;
+
+const CHART_WIDTH: f64 = 600.0;
+const BAR_HEIGHT: f64 = 28.0;
+
+struct CostShare {
+    label: &'static str,
+    value: f64,
+    /// Tailwind classes for the bar segment and its legend swatch
+    fill: &'static str,
+    swatch: &'static str,
+}
+
+/// Everything spent on the car, by category. Resale isn't a cost, so it's left out here and
+/// noted under the chart instead. The smaller one-off costs share an "Other" segment.
+fn cost_shares(computed: &ComputedCarData) -> Vec<CostShare> {
+    let share = |label, value, fill, swatch| CostShare {
+        label,
+        value,
+        fill,
+        swatch,
+    };
+    let other = computed.sales_tax
+        + computed.extended_warranty_cost
+        + computed.total_interest_paid
+        + computed.registration_cost_total;
+    [
+        share(
+            "Purchase",
+            computed.operands.purchase_price,
+            "fill-gray-400",
+            "bg-gray-400",
+        ),
+        share(
+            "Fuel",
+            computed.fuel_cost_total,
+            "fill-amber-400",
+            "bg-amber-400",
+        ),
+        share(
+            "Maintenance",
+            computed.maintenance_cost_total,
+            "fill-blue-500",
+            "bg-blue-500",
+        ),
+        share(
+            "Tires",
+            computed.tire_cost_total,
+            "fill-slate-600",
+            "bg-slate-600",
+        ),
+        share(
+            "Insurance",
+            computed.insurance_cost_annual * computed.years_remaining,
+            "fill-purple-400",
+            "bg-purple-400",
+        ),
+        share(
+            "Opportunity",
+            computed.opportunity_cost,
+            "fill-teal-400",
+            "bg-teal-400",
+        ),
+        share("Other", other, "fill-gray-300", "bg-gray-300"),
+    ]
+    .into_iter()
+    .filter(|s| s.value > 0.0)
+    .collect()
+}
+
+/// Stacked bar of where the money goes: each category's share of everything spent on the car
+#[component]
+pub fn CostShareChart(computed: ComputedCarData) -> impl IntoView {
+    let shares = cost_shares(&computed);
+    let spent: f64 = shares.iter().map(|s| s.value).sum();
+    if spent <= 0.0 {
+        return ().into_any();
+    }
+    let percent = move |value: f64| value / spent * 100.0;
+
+    let mut x = 0.0;
+    let segments = shares
+        .iter()
+        .map(|s| {
+            let width = s.value / spent * CHART_WIDTH;
+            let start = x;
+            x += width;
+            view! {
+                <rect x=start y=0.0 width=width height=BAR_HEIGHT class=s.fill>
+                    <title>
+                        {format!("{}: ${} ({:.0}%)", s.label, format_thousands(s.value), percent(s.value))}
+                    </title>
+                </rect>
+            }
+        })
+        .collect_view();
+
+    let legend = shares
+        .iter()
+        .map(|s| {
+            view! {
+                <span class="inline-flex items-center">
+                    <span class=format!("inline-block w-3 h-3 mr-1 {}", s.swatch)></span>
+                    {format!("{} ${} ({:.0}%)", s.label, format_thousands(s.value), percent(s.value))}
+                </span>
+            }
+        })
+        .collect_view();
+
+    let credits = computed.resale_value + computed.trade_in_tax_credit;
+
+    view! {
+        <div class="mb-4">
+            <h4 class="text-sm font-medium text-gray-900">"Where the Money Goes"</h4>
+            <svg
+                viewBox=format!("0 0 {} {}", CHART_WIDTH, BAR_HEIGHT)
+                class="w-full h-auto mt-2 rounded overflow-hidden"
+                xmlns="http://www.w3.org/2000/svg"
+                role="img"
+                aria-label="Share of total spending by cost category"
+            >
+                {segments}
+            </svg>
+            <div class="mt-2 flex flex-wrap items-center gap-x-4 gap-y-1 text-xs text-gray-600">
+                {legend}
+            </div>
+            {(credits > 0.0).then(|| view! {
+                <p class="mt-1 text-xs text-gray-500">
+                    {format!(
+                        "Of ${} spent, ${} comes back at resale, for a total cost of {}.",
+                        format_thousands(spent),
+                        format_thousands(credits),
+                        format_dollars(computed.total_cost_of_ownership, 0)
+                    )}
+                </p>
+            })}
+        </div>
+    }
+    .into_any()
+}
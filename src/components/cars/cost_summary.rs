@@ -1,87 +1,520 @@
+use codee::string::FromToStringCodec;
 use leptos::prelude::*;
+use leptos_use::storage::use_local_storage;
+use wasm_bindgen::JsCast;
+use web_sys::HtmlAnchorElement;
 
-use crate::models::ComputedCarData;
+use crate::calculations::{explain, CostField};
+use crate::components::cars::{CostShareChart, SummarySnapshotButton};
+use crate::components::ui::InfoPopover;
+use crate::data::{
+    format_dollars, format_ownership_horizon, format_thousands, summary_csv, summary_csv_filename,
+    CostBenchmark, FUEL_ANNUAL, INSURANCE_ANNUAL, MAINTENANCE_ANNUAL, TOTAL_ANNUAL,
+};
+use crate::models::{Car, ComputedCarData, FuelType, PrimaryMetric};
 
 #[component]
-pub fn CarCostSummary(computed: ComputedCarData) -> impl IntoView {
+pub fn CarCostSummary(
+    car_name: String,
+    /// The car's inputs, written out with the results by the CSV download; its model
+    /// year sets the car's age at the end of ownership
+    car: Car,
+    computed: ComputedCarData,
+    primary_metric: PrimaryMetric,
+) -> impl IntoView {
+    let (hide_ranges, set_hide_ranges, _) =
+        use_local_storage::<bool, FromToStringCodec>("carcalc_hide_typical_ranges");
+
+    let hint = move |benchmark: CostBenchmark| {
+        move || {
+            (!hide_ranges.get())
+                .then(|| view! { <div class="text-xs text-gray-400 mt-1">{benchmark.hint()}</div> })
+        }
+    };
+
+    // Projected end of ownership from today's date in the browser
+    let today = js_sys::Date::new_0();
+    let horizon = format_ownership_horizon(
+        computed.years_remaining,
+        today.get_full_year() as i32,
+        today.get_month(),
+        &car.year,
+    );
+    let csv = summary_csv(&car, &computed);
+    let csv_filename = summary_csv_filename(&car);
+    let download_csv = move |_| {
+        let url = format!(
+            "data:text/csv;charset=utf-8,{}",
+            js_sys::encode_uri_component(&csv)
+        );
+        if let Some(anchor) = document()
+            .create_element("a")
+            .ok()
+            .and_then(|el| el.dyn_into::<HtmlAnchorElement>().ok())
+        {
+            anchor.set_href(&url);
+            anchor.set_download(&csv_filename);
+            anchor.click();
+        }
+    };
+
+    // Formula with the actual values substituted, shown on demand
+    let why = |field: CostField| {
+        view! { <InfoPopover label="How is this calculated?" text=explain(&computed, field) /> }
+    };
+
     view! {
         <div class="mt-6 border-t border-gray-200 pt-6">
-            <h3 class="text-lg font-semibold text-gray-900 mb-4">"Calculated Costs"</h3>
+            <div class="flex items-center justify-between mb-4">
+                <h3 class="text-lg font-semibold text-gray-900">"Calculated Costs"</h3>
+                <div class="flex items-center space-x-2">
+                    <SummarySnapshotButton car_name=car_name computed=computed.clone() />
+                    <button
+                        class="inline-flex items-center px-3 py-1 border border-gray-300 text-xs font-medium rounded-md text-gray-700 bg-white hover:bg-gray-50"
+                        title="Download this car's inputs and every calculated figure as a spreadsheet"
+                        on:click=download_csv
+                    >
+                        "Download CSV"
+                    </button>
+                </div>
+            </div>
+
+            {computed.has_net_profit().then(|| view! {
+                <div role="alert" class="bg-amber-50 border border-amber-200 rounded-lg p-4 mb-4">
+                    <h4 class="text-sm font-medium text-amber-800">"This car comes out as a net profit"</h4>
+                    <p class="mt-1 text-sm text-amber-700">
+                        {format!(
+                            "Resale value and credits ({}) outweigh everything spent on the car. That almost never happens in practice, so the resale value or trade-in tax credit is probably higher than realistic. It's left out of the \"best\" picks in the comparison until it's fixed.",
+                            format_dollars(computed.resale_value + computed.trade_in_tax_credit, 0)
+                        )}
+                    </p>
+                </div>
+            })}
 
             <div class="bg-blue-50 rounded-lg p-4 mb-4">
-                <div class="grid grid-cols-1 sm:grid-cols-2 gap-4">
-                    <div>
-                        <div class="text-sm font-medium text-gray-600">"Total Cost of Ownership"</div>
-                        <div class="text-2xl font-bold text-blue-600">
-                            {format!("${:.2}", computed.total_cost_of_ownership)}
+                <div class="flex items-center text-sm font-medium text-gray-600">
+                    {primary_metric.label()}
+                    {why(match primary_metric {
+                        PrimaryMetric::Total => CostField::TotalCostOfOwnership,
+                        PrimaryMetric::Annual => CostField::AnnualCost,
+                        PrimaryMetric::Operating => CostField::AnnualOperatingCost,
+                        PrimaryMetric::PerMile => CostField::CostPerMile,
+                    })}
+                </div>
+                <div class="text-2xl font-bold text-blue-600">{primary_metric.format(&computed)}</div>
+                {(primary_metric == PrimaryMetric::Annual).then(|| hint(TOTAL_ANNUAL))}
+                <div class="mt-2 flex flex-wrap gap-x-4 text-xs text-gray-500">
+                    {PrimaryMetric::ALL
+                        .into_iter()
+                        .filter(|metric| *metric != primary_metric)
+                        .map(|metric| {
+                            view! { <span>{format!("{}: {}", metric.label(), metric.format(&computed))}</span> }
+                        })
+                        .collect_view()}
+                </div>
+            </div>
+
+            <CostShareChart computed=computed.clone() />
+
+            // For a car that's already been driven, what's gone into it versus what's ahead
+            {(computed.operands.current_mileage > 0.0).then(|| {
+                let ops = &computed.operands;
+                view! {
+                    <div class="grid grid-cols-2 gap-4 mb-4">
+                        <div class="bg-gray-50 p-3 rounded border border-gray-200">
+                            <div class="flex items-center justify-between">
+                                <div class="text-xs text-gray-500 uppercase tracking-wide">"Spent so far"</div>
+                                {why(CostField::CostIncurredToDate)}
+                            </div>
+                            <div class="text-lg font-semibold text-gray-900 mt-1">
+                                {format!("${:.2}", computed.cost_incurred_to_date)}
+                            </div>
+                            <div class="text-xs text-gray-400 mt-1">
+                                {format!(
+                                    "${:.0} {} + ${:.0} maintenance, to {:.0} mi",
+                                    ops.incurred_fuel,
+                                    match ops.fuel_type {
+                                        FuelType::Gasoline => format!("fuel at ${:.2}/gal", ops.historical_gas_price),
+                                        FuelType::Electric => format!("charging at ${:.2}/kWh", ops.electricity_price),
+                                        FuelType::Hybrid => "gas and charging".to_string(),
+                                    },
+                                    ops.incurred_maintenance,
+                                    ops.current_mileage
+                                )}
+                            </div>
                         </div>
-                    </div>
-                    <div>
-                        <div class="text-sm font-medium text-gray-600">"Annual Cost"</div>
-                        <div class="text-2xl font-bold text-blue-600">
-                            {format!("${:.2}", computed.annual_cost)}
+                        <div class="bg-gray-50 p-3 rounded border border-gray-200">
+                            <div class="flex items-center justify-between">
+                                <div class="text-xs text-gray-500 uppercase tracking-wide">"Still to spend"</div>
+                                {why(CostField::CostProjectedRemaining)}
+                            </div>
+                            <div class="text-lg font-semibold text-gray-900 mt-1">
+                                {format!("${:.2}", computed.cost_projected_remaining)}
+                            </div>
+                            <div class="text-xs text-gray-400 mt-1">
+                                {format!(
+                                    "Fuel, maintenance and insurance over the next {:.0} mi",
+                                    computed.remaining_miles
+                                )}
+                            </div>
                         </div>
                     </div>
-                </div>
-            </div>
+                }
+            })}
 
             <div class="grid grid-cols-1 gap-4 sm:grid-cols-2 lg:grid-cols-3">
+                {computed.cost_per_passenger_mile.map(|per_seat| view! {
+                    <div class="bg-white p-3 rounded border border-gray-200">
+                        <div class="flex items-center justify-between">
+                            <div class="text-xs text-gray-500 uppercase tracking-wide">"Cost per Passenger-Mile"</div>
+                            {why(CostField::CostPerPassengerMile)}
+                        </div>
+                        <div class="text-lg font-semibold text-gray-900 mt-1">
+                            {format!("${:.3}", per_seat)}
+                        </div>
+                        <div class="text-xs text-gray-400 mt-1">
+                            {format!("Split across {} seats", computed.operands.seats)}
+                        </div>
+                    </div>
+                })}
+
                 <div class="bg-white p-3 rounded border border-gray-200">
-                    <div class="text-xs text-gray-500 uppercase tracking-wide">"Years Remaining"</div>
+                    <div class="flex items-center justify-between">
+                        <div class="text-xs text-gray-500 uppercase tracking-wide">"Years Remaining"</div>
+                        {why(CostField::YearsRemaining)}
+                    </div>
                     <div class="text-lg font-semibold text-gray-900 mt-1">
                         {format!("{:.1}", computed.years_remaining)}
                     </div>
+                    <div class="text-xs text-gray-400 mt-1">{horizon}</div>
                 </div>
 
                 <div class="bg-white p-3 rounded border border-gray-200">
-                    <div class="text-xs text-gray-500 uppercase tracking-wide">"Remaining Miles"</div>
+                    <div class="flex items-center justify-between">
+                        <div class="text-xs text-gray-500 uppercase tracking-wide">"Remaining Miles"</div>
+                        {why(CostField::RemainingMiles)}
+                    </div>
                     <div class="text-lg font-semibold text-gray-900 mt-1">
                         {format!("{:.0}", computed.remaining_miles)}
                     </div>
+                    <div class="text-xs text-gray-400 mt-1">
+                        {format!(
+                            "Lifetime {} mi ({})",
+                            format_thousands(computed.operands.lifetime_miles),
+                            if computed.operands.lifetime_miles_overridden { "this car's own" } else { "shared setting" }
+                        )}
+                    </div>
                 </div>
 
                 <div class="bg-white p-3 rounded border border-gray-200">
-                    <div class="text-xs text-gray-500 uppercase tracking-wide">"Fuel Cost (Total)"</div>
+                    <div class="flex items-center justify-between">
+                        <div class="text-xs text-gray-500 uppercase tracking-wide">"Fuel Cost (Total)"</div>
+                        {why(CostField::FuelTotal)}
+                    </div>
                     <div class="text-lg font-semibold text-gray-900 mt-1">
                         {format!("${:.2}", computed.fuel_cost_total)}
                     </div>
                 </div>
 
                 <div class="bg-white p-3 rounded border border-gray-200">
-                    <div class="text-xs text-gray-500 uppercase tracking-wide">"Fuel Cost (Annual)"</div>
+                    <div class="flex items-center justify-between">
+                        <div class="text-xs text-gray-500 uppercase tracking-wide">"Fuel Cost (Annual)"</div>
+                        {why(CostField::FuelAnnual)}
+                    </div>
                     <div class="text-lg font-semibold text-gray-900 mt-1">
                         {format!("${:.2}", computed.fuel_cost_annual)}
                     </div>
+                    {hint(FUEL_ANNUAL)}
                 </div>
 
                 <div class="bg-white p-3 rounded border border-gray-200">
-                    <div class="text-xs text-gray-500 uppercase tracking-wide">"Insurance (Annual)"</div>
+                    <div class="flex items-center justify-between">
+                        <div class="text-xs text-gray-500 uppercase tracking-wide">"Insurance (Annual)"</div>
+                        {why(CostField::InsuranceAnnual)}
+                    </div>
                     <div class="text-lg font-semibold text-gray-900 mt-1">
                         {format!("${:.2}", computed.insurance_cost_annual)}
                     </div>
+                    {hint(INSURANCE_ANNUAL)}
                 </div>
 
                 <div class="bg-white p-3 rounded border border-gray-200">
-                    <div class="text-xs text-gray-500 uppercase tracking-wide">"Opportunity Cost"</div>
+                    <div class="flex items-center justify-between">
+                        <div class="text-xs text-gray-500 uppercase tracking-wide">"Opportunity Cost"</div>
+                        {why(CostField::OpportunityCost)}
+                    </div>
                     <div class="text-lg font-semibold text-gray-900 mt-1">
                         {format!("${:.2}", computed.opportunity_cost)}
                     </div>
                 </div>
 
                 <div class="bg-white p-3 rounded border border-gray-200">
-                    <div class="text-xs text-gray-500 uppercase tracking-wide">"Maintenance (Total)"</div>
+                    <div class="flex items-center justify-between">
+                        <div class="text-xs text-gray-500 uppercase tracking-wide">"Resale Value"</div>
+                        {why(CostField::ResaleValue)}
+                    </div>
+                    <div class="text-lg font-semibold text-gray-900 mt-1">
+                        {format!("${:.2}", computed.resale_value)}
+                    </div>
+                    <div class="text-xs text-gray-400 mt-1">
+                        {format!("{} · {}", computed.resale_source_label(), computed.resale_channel.label())}
+                    </div>
+                </div>
+
+                <div class="bg-white p-3 rounded border border-gray-200">
+                    <div class="flex items-center justify-between">
+                        <div class="text-xs text-gray-500 uppercase tracking-wide">"Depreciation"</div>
+                        {why(CostField::DepreciationCost)}
+                    </div>
+                    <div class="text-lg font-semibold text-gray-900 mt-1">
+                        {format!("${:.2}", computed.depreciation_cost)}
+                    </div>
+                    <div class="text-xs text-gray-400 mt-1">
+                        {format!("${:.0}/yr of value lost", computed.depreciation_cost / computed.years_remaining)}
+                    </div>
+                </div>
+
+                {(computed.operands.inflation_rate != 0.0).then(|| view! {
+                    <div class="bg-white p-3 rounded border border-gray-200">
+                        <div class="flex items-center justify-between">
+                            <div class="text-xs text-gray-500 uppercase tracking-wide">"Total in Future Dollars"</div>
+                            {why(CostField::TotalCostNominal)}
+                        </div>
+                        <div class="text-lg font-semibold text-gray-900 mt-1">
+                            {format!("${:.2}", computed.total_cost_nominal)}
+                        </div>
+                        <div class="text-xs text-gray-400 mt-1">
+                            {format!(
+                                "At {}%/yr inflation; ${:.0} in today's dollars",
+                                computed.operands.inflation_rate,
+                                computed.total_cost_of_ownership
+                            )}
+                        </div>
+                    </div>
+                })}
+
+                {computed.discounted_total_cost.map(|discounted| view! {
+                    <div class="bg-white p-3 rounded border border-gray-200">
+                        <div class="flex items-center justify-between">
+                            <div class="text-xs text-gray-500 uppercase tracking-wide">"Discounted Total (NPV)"</div>
+                            {why(CostField::DiscountedTotal)}
+                        </div>
+                        <div class="text-lg font-semibold text-gray-900 mt-1">
+                            {format!("${:.2}", discounted)}
+                        </div>
+                        <div class="text-xs text-gray-400 mt-1">
+                            {format!("Costs discounted at {}%/yr", computed.operands.opportunity_cost_rate)}
+                        </div>
+                    </div>
+                })}
+
+                {(computed.sales_tax != 0.0).then(|| view! {
+                    <div class="bg-white p-3 rounded border border-gray-200">
+                        <div class="flex items-center justify-between">
+                            <div class="text-xs text-gray-500 uppercase tracking-wide">"Sales Tax"</div>
+                            {why(CostField::SalesTax)}
+                        </div>
+                        <div class="text-lg font-semibold text-gray-900 mt-1">
+                            {format!("${:.2}", computed.sales_tax)}
+                        </div>
+                        <div class="text-xs text-gray-400 mt-1">
+                            {format!("{}% of the purchase price", computed.operands.purchase_tax_rate)}
+                        </div>
+                    </div>
+                })}
+
+                {(computed.registration_cost_total != 0.0).then(|| view! {
+                    <div class="bg-white p-3 rounded border border-gray-200">
+                        <div class="flex items-center justify-between">
+                            <div class="text-xs text-gray-500 uppercase tracking-wide">"Registration"</div>
+                            {why(CostField::RegistrationTotal)}
+                        </div>
+                        <div class="text-lg font-semibold text-gray-900 mt-1">
+                            {format!("${:.2}", computed.registration_cost_total)}
+                        </div>
+                        <div class="text-xs text-gray-400 mt-1">
+                            {format!("${:.0}/yr", computed.operands.annual_registration_fee)}
+                        </div>
+                    </div>
+                })}
+
+                {(computed.tire_cost_total != 0.0).then(|| view! {
+                    <div class="bg-white p-3 rounded border border-gray-200">
+                        <div class="flex items-center justify-between">
+                            <div class="text-xs text-gray-500 uppercase tracking-wide">"Tires"</div>
+                            {why(CostField::TireTotal)}
+                        </div>
+                        <div class="text-lg font-semibold text-gray-900 mt-1">
+                            {format!("${:.2}", computed.tire_cost_total)}
+                        </div>
+                        <div class="text-xs text-gray-400 mt-1">
+                            {format!(
+                                "{:.1} sets at ${:.0}",
+                                computed.remaining_miles / computed.operands.tire_life_miles,
+                                computed.operands.tire_set_cost
+                            )}
+                        </div>
+                    </div>
+                })}
+
+                {(computed.trade_in_tax_credit != 0.0).then(|| view! {
+                    <div class="bg-white p-3 rounded border border-gray-200">
+                        <div class="flex items-center justify-between">
+                            <div class="text-xs text-gray-500 uppercase tracking-wide">"Trade-in Tax Credit"</div>
+                            {why(CostField::TradeInTaxCredit)}
+                        </div>
+                        <div class="text-lg font-semibold text-gray-900 mt-1">
+                            {format!("${:.2}", computed.trade_in_tax_credit)}
+                        </div>
+                    </div>
+                })}
+
+                {computed.effective_cash_price.map(|effective| {
+                    let difference = effective - computed.operands.purchase_price;
+                    view! {
+                        <div class="bg-white p-3 rounded border border-gray-200">
+                            <div class="flex items-center justify-between">
+                                <div class="text-xs text-gray-500 uppercase tracking-wide">"Effective Cash Price"</div>
+                                {why(CostField::EffectiveCashPrice)}
+                            </div>
+                            <div class="text-lg font-semibold text-gray-900 mt-1">
+                                {format!("${:.2}", effective)}
+                            </div>
+                            <div class="text-xs text-gray-400 mt-1">
+                                {if difference <= 0.0 {
+                                    format!("Financing beats paying cash by ${:.0}", -difference)
+                                } else {
+                                    format!("Paying cash beats financing by ${:.0}", difference)
+                                }}
+                            </div>
+                        </div>
+                    }
+                })}
+
+                {computed.effective_cash_price.is_some().then(|| view! {
+                    <div class="bg-white p-3 rounded border border-gray-200">
+                        <div class="flex items-center justify-between">
+                            <div class="text-xs text-gray-500 uppercase tracking-wide">"Loan Interest"</div>
+                            {why(CostField::InterestPaid)}
+                        </div>
+                        <div class="text-lg font-semibold text-gray-900 mt-1">
+                            {format!("${:.2}", computed.total_interest_paid)}
+                        </div>
+                        <div class="text-xs text-gray-400 mt-1">
+                            {format!("${:.2}/mo", computed.monthly_payment)}
+                        </div>
+                    </div>
+                })}
+
+                {computed.has_extended_warranty().then(|| {
+                    let net = computed.warranty_net_savings();
+                    let verdict_class = if net >= 0.0 {
+                        "text-xs text-green-700 mt-1"
+                    } else {
+                        "text-xs text-red-700 mt-1"
+                    };
+                    view! {
+                        <div class="bg-white p-3 rounded border border-gray-200">
+                            <div class="flex items-center justify-between">
+                                <div class="text-xs text-gray-500 uppercase tracking-wide">"Extended Warranty"</div>
+                                {why(CostField::ExtendedWarranty)}
+                            </div>
+                            <div class="text-lg font-semibold text-gray-900 mt-1">
+                                {format!("${:.2}", computed.extended_warranty_cost)}
+                            </div>
+                            <div class=verdict_class>
+                                {if net >= 0.0 {
+                                    format!("Pays off: covers ${:.0} of maintenance", computed.warranty_covered_maintenance)
+                                } else {
+                                    format!("Doesn't pay off: covers only ${:.0} of maintenance", computed.warranty_covered_maintenance)
+                                }}
+                            </div>
+                        </div>
+                    }
+                })}
+
+                <div class="bg-white p-3 rounded border border-gray-200">
+                    <div class="flex items-center justify-between">
+                        <div class="text-xs text-gray-500 uppercase tracking-wide">"Maintenance (Total)"</div>
+                        {why(CostField::MaintenanceTotal)}
+                    </div>
                     <div class="text-lg font-semibold text-gray-900 mt-1">
                         {format!("${:.2}", computed.maintenance_cost_total)}
                     </div>
+                    <div class="text-xs text-gray-400 mt-1">{computed.maintenance_basis.label()}</div>
+                    {computed.maintenance_profile_match.clone().map(|profile| view! {
+                        <div class="text-xs text-amber-700 mt-1">
+                            {format!("Closest profile: {}", profile)}
+                        </div>
+                    })}
                 </div>
 
                 <div class="bg-white p-3 rounded border border-gray-200">
-                    <div class="text-xs text-gray-500 uppercase tracking-wide">"Maintenance (Annual)"</div>
+                    <div class="flex items-center justify-between">
+                        <div class="text-xs text-gray-500 uppercase tracking-wide">"Maintenance (Annual)"</div>
+                        {why(CostField::MaintenanceAnnual)}
+                    </div>
                     <div class="text-lg font-semibold text-gray-900 mt-1">
                         {format!("${:.2}", computed.maintenance_cost_annual)}
                     </div>
+                    {hint(MAINTENANCE_ANNUAL)}
                 </div>
             </div>
+
+            {(computed.total_gallons > 0.0 || computed.total_kwh > 0.0).then(|| view! {
+                <h4 class="mt-6 mb-3 text-sm font-medium text-gray-900">"Environmental"</h4>
+                <div class="grid grid-cols-1 gap-4 sm:grid-cols-2 lg:grid-cols-3">
+                    {(computed.total_gallons > 0.0).then(|| view! {
+                        <div class="bg-white p-3 rounded border border-gray-200">
+                            <div class="text-xs text-gray-500 uppercase tracking-wide">"Gas Burned"</div>
+                            <div class="text-lg font-semibold text-gray-900 mt-1">
+                                {format!("{} gal", format_thousands(computed.total_gallons))}
+                            </div>
+                            <div class="text-xs text-gray-400 mt-1">
+                                {if computed.operands.mpg_loss_percent > 0.0 {
+                                    format!(
+                                        "Averaging {:.1} MPG as fuel economy falls {}%/yr",
+                                        computed.effective_mpg,
+                                        computed.operands.mpg_loss_percent
+                                    )
+                                } else {
+                                    format!("Over the remaining {} miles", format_thousands(computed.remaining_miles))
+                                }}
+                            </div>
+                        </div>
+                        <div class="bg-white p-3 rounded border border-gray-200">
+                            <div class="text-xs text-gray-500 uppercase tracking-wide">"Tailpipe CO2"</div>
+                            <div class="text-lg font-semibold text-gray-900 mt-1">
+                                {format!("{:.1} metric tons", computed.total_co2_kg / 1000.0)}
+                            </div>
+                            <div class="text-xs text-gray-400 mt-1">
+                                {format!("{} kg/yr", format_thousands(computed.total_co2_kg / computed.years_remaining))}
+                            </div>
+                        </div>
+                    })}
+                    {(computed.total_kwh > 0.0).then(|| view! {
+                        <div class="bg-white p-3 rounded border border-gray-200">
+                            <div class="text-xs text-gray-500 uppercase tracking-wide">"Electricity Used"</div>
+                            <div class="text-lg font-semibold text-gray-900 mt-1">
+                                {format!("{} kWh", format_thousands(computed.total_kwh))}
+                            </div>
+                            <div class="text-xs text-gray-400 mt-1">
+                                "Charging emissions depend on your grid and aren't counted"
+                            </div>
+                        </div>
+                    })}
+                </div>
+            })}
+
+            <label class="mt-3 inline-flex items-center text-xs text-gray-500">
+                <input
+                    type="checkbox"
+                    class="mr-2 rounded border-gray-300 text-blue-600 focus:ring-blue-500"
+                    prop:checked=move || !hide_ranges.get()
+                    on:change=move |ev| set_hide_ranges.set(!event_target_checked(&ev))
+                />
+                "Show typical ranges"
+            </label>
         </div>
     }
 }
@@ -1,153 +1,711 @@
 use leptos::prelude::*;
 
-use crate::models::Car;
+use crate::components::cars::CarPhotoInput;
+use crate::components::ui::Tooltip;
+use crate::data::decode_vin;
+use crate::models::{
+    Car, CarField, DepreciationMethod, FuelType, InsuranceMode, InsurancePeriod, ResaleChannel,
+    SharedSettings, CAR_COLORS,
+};
 
 #[component]
 pub fn CarForm(
-    car: ReadSignal<Car>,
+    car: Signal<Car>,
     set_car_wrapper: impl Fn(&dyn Fn(&mut Car)) + 'static + Copy,
+    settings: Signal<SharedSettings>,
 ) -> impl IntoView {
+    // Start expanded for cars that already have anything beyond the required fields
+    let details_open = RwSignal::new(car.with_untracked(|c| c.has_details()));
+
+    // Why a required input can't be used, shown in red under it
+    let field_error = move |field: CarField| {
+        move || {
+            settings
+                .with(|s| car.with(|c| c.field_error(field, c.lifetime_miles(s.lifetime_miles))))
+        }
+    };
+    let error_message = move |field: CarField| {
+        move || {
+            field_error(field)()
+                .map(|message| view! { <p class="mt-1 text-sm text-red-600">{message}</p> })
+        }
+    };
+
+    // Looks the VIN up with NHTSA and fills in the make, model and year it decodes to, once
+    // per lookup: the result stays around for the status line, and editing the filled-in
+    // fields afterwards mustn't bring the decoded values back
+    let decode = Action::new_local(|vin: &String| decode_vin(vin.clone()));
+    let decode_applied = StoredValue::new(false);
+    Effect::new(move || {
+        if let Some(Ok(decoded)) = decode.value().get() {
+            if decode_applied.get_value() {
+                return;
+            }
+            decode_applied.set_value(true);
+            untrack(|| {
+                set_car_wrapper(&|c| {
+                    c.make = decoded.make.clone();
+                    c.model = decoded.model.clone();
+                    if !decoded.year.is_empty() {
+                        c.year = decoded.year.clone();
+                    }
+                })
+            });
+        }
+    });
+
     view! {
         <div class="mt-4 space-y-6">
-            <div class="grid grid-cols-1 gap-6 sm:grid-cols-2 lg:grid-cols-3">
-                <div>
-                    <label class="block text-sm font-medium text-gray-700">"Make"</label>
-                    <input
-                        type="text"
-                        class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
-                        prop:value=move || car.get().make
-                        on:input=move |ev| {
-                            set_car_wrapper(&|c| c.make = event_target_value(&ev));
-                        }
-                    />
-                </div>
-                <div>
-                    <label class="block text-sm font-medium text-gray-700">"Model"</label>
-                    <input
-                        type="text"
-                        class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
-                        prop:value=move || car.get().model
-                        on:input=move |ev| {
-                            set_car_wrapper(&|c| c.model = event_target_value(&ev));
-                        }
-                    />
-                </div>
-                <div>
-                    <label class="block text-sm font-medium text-gray-700">"Trim/Features (optional)"</label>
-                    <input
-                        type="text"
-                        class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
-                        prop:value=move || car.get().trim
-                        on:input=move |ev| {
-                            set_car_wrapper(&|c| c.trim = event_target_value(&ev));
-                        }
-                    />
-                </div>
-                <div>
-                    <label class="block text-sm font-medium text-gray-700">"Model Year"</label>
-                    <input
-                        type="text"
-                        class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
-                        prop:value=move || car.get().year
-                        on:input=move |ev| {
-                            set_car_wrapper(&|c| c.year = event_target_value(&ev));
-                        }
-                    />
-                </div>
-                <div>
-                    <label class="block text-sm font-medium text-gray-700">
-                        "Purchase Price ($)"
-                        <span class="text-red-600">" *"</span>
-                    </label>
-                    <input
-                        type="text"
-                        class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
-                        prop:value=move || car.get().purchase_price
-                        on:input=move |ev| {
-                            set_car_wrapper(&|c| c.purchase_price = event_target_value(&ev));
-                        }
-                    />
-                </div>
-                <div>
-                    <label class="block text-sm font-medium text-gray-700">
-                        "Current Mileage"
-                        <span class="text-red-600">" *"</span>
-                    </label>
-                    <input
-                        type="text"
-                        class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
-                        prop:value=move || car.get().current_mileage
-                        on:input=move |ev| {
-                            set_car_wrapper(&|c| c.current_mileage = event_target_value(&ev));
-                        }
-                    />
-                </div>
-                <div>
-                    <label class="block text-sm font-medium text-gray-700">
-                        "MPG"
-                        <span class="text-red-600">" *"</span>
-                    </label>
-                    <input
-                        type="text"
-                        class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
-                        prop:value=move || car.get().mpg
-                        on:input=move |ev| {
-                            set_car_wrapper(&|c| c.mpg = event_target_value(&ev));
-                        }
-                    />
-                </div>
-                <div>
-                    <label class="block text-sm font-medium text-gray-700">
-                        "Insurance Cost (6-month premium $)"
-                        <span class="text-red-600">" *"</span>
-                    </label>
-                    <input
-                        type="text"
-                        class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
-                        prop:value=move || car.get().insurance_cost
-                        on:input=move |ev| {
-                            set_car_wrapper(&|c| c.insurance_cost = event_target_value(&ev));
-                        }
-                    />
-                </div>
-            </div>
-
-            <div class="border-t border-gray-200 pt-6">
-                <h4 class="text-sm font-medium text-gray-900 mb-4">"Additional Information"</h4>
+            <div>
+                <h4 class="text-sm font-medium text-gray-900 mb-4">"Required to Calculate"</h4>
                 <div class="grid grid-cols-1 gap-6 sm:grid-cols-2">
                     <div>
-                        <label class="block text-sm font-medium text-gray-700">"VIN (optional)"</label>
+                        <label class="block text-sm font-medium text-gray-700 inline-flex items-center">
+                            "Purchase Price ($)"
+                            <span class="text-red-600">" *"</span>
+                            <Tooltip text="The total price you will pay for the car, including any dealer fees. Required because it is the largest single cost and drives the opportunity cost calculation." />
+                        </label>
                         <input
                             type="text"
                             class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
-                            prop:value=move || car.get().vin
+                            class:border-red-500=move || field_error(CarField::PurchasePrice)().is_some()
+                            aria-invalid=move || field_error(CarField::PurchasePrice)().is_some().to_string()
+                            prop:value=move || car.get().purchase_price
                             on:input=move |ev| {
-                                set_car_wrapper(&|c| c.vin = event_target_value(&ev));
+                                set_car_wrapper(&|c| c.purchase_price = event_target_value(&ev));
                             }
                         />
+                        {error_message(CarField::PurchasePrice)}
                     </div>
                     <div>
-                        <label class="block text-sm font-medium text-gray-700">"Listing URL (optional)"</label>
+                        <label class="block text-sm font-medium text-gray-700 inline-flex items-center">
+                            "Current Mileage"
+                            <span class="text-red-600">" *"</span>
+                            <Tooltip text="The odometer reading today. Required because it determines how many miles remain until your lifetime mileage, which sets the ownership period for every other cost." />
+                        </label>
+                        <input
+                            type="text"
+                            class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                            class:border-red-500=move || field_error(CarField::CurrentMileage)().is_some()
+                            aria-invalid=move || field_error(CarField::CurrentMileage)().is_some().to_string()
+                            prop:value=move || car.get().current_mileage
+                            on:input=move |ev| {
+                                set_car_wrapper(&|c| c.current_mileage = event_target_value(&ev));
+                            }
+                        />
+                        {error_message(CarField::CurrentMileage)}
+                    </div>
+                    <div>
+                        <label class="block text-sm font-medium text-gray-700 inline-flex items-center">
+                            "Fuel Type"
+                            <Tooltip text="What the car runs on. Gas cars are costed from MPG and the gas price, electric cars from miles per kWh and the electricity price, and plug-in hybrids from both, split by the share of miles you drive on electricity." />
+                        </label>
+                        <select
+                            class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                            prop:value=move || car.get().fuel_type.id()
+                            on:change=move |ev| {
+                                if let Some(fuel_type) = FuelType::from_id(&event_target_value(&ev)) {
+                                    set_car_wrapper(&|c| c.fuel_type = fuel_type);
+                                }
+                            }
+                        >
+                            {FuelType::ALL
+                                .into_iter()
+                                .map(|fuel_type| view! { <option value=fuel_type.id()>{fuel_type.label()}</option> })
+                                .collect_view()}
+                        </select>
+                    </div>
+                    <div class:hidden=move || !car.get().fuel_type.uses_electricity()>
+                        <label class="block text-sm font-medium text-gray-700 inline-flex items-center">
+                            "Miles per kWh"
+                            <span class="text-red-600">" *"</span>
+                            <Tooltip text="Electric efficiency, as listed on fueleconomy.gov (divide 100 by the kWh/100 mi figure). Most EVs get 3-4 miles per kWh; large trucks and SUVs closer to 2. Required to estimate charging costs." />
+                        </label>
+                        <input
+                            type="text"
+                            class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                            class:border-red-500=move || field_error(CarField::MilesPerKwh)().is_some()
+                            aria-invalid=move || field_error(CarField::MilesPerKwh)().is_some().to_string()
+                            prop:value=move || car.get().miles_per_kwh
+                            on:input=move |ev| {
+                                set_car_wrapper(&|c| c.miles_per_kwh = event_target_value(&ev));
+                            }
+                        />
+                        {error_message(CarField::MilesPerKwh)}
+                    </div>
+                    <div class:hidden=move || car.get().fuel_type != FuelType::Hybrid>
+                        <label class="block text-sm font-medium text-gray-700 inline-flex items-center">
+                            "Electric Miles (%)"
+                            <span class="text-red-600">" *"</span>
+                            <Tooltip text="The share of your miles a plug-in hybrid covers on battery power. It depends on the electric range and your daily driving: a 40-mile range with a 30-mile commute and regular charging can cover 70-80% of miles." />
+                        </label>
                         <input
                             type="text"
                             class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
-                            prop:value=move || car.get().listing_url
+                            class:border-red-500=move || field_error(CarField::ElectricMilesPercent)().is_some()
+                            aria-invalid=move || field_error(CarField::ElectricMilesPercent)().is_some().to_string()
+                            prop:value=move || car.get().electric_miles_percent
                             on:input=move |ev| {
-                                set_car_wrapper(&|c| c.listing_url = event_target_value(&ev));
+                                set_car_wrapper(&|c| c.electric_miles_percent = event_target_value(&ev));
                             }
                         />
+                        {error_message(CarField::ElectricMilesPercent)}
+                    </div>
+                    <div class:hidden=move || !car.get().fuel_type.uses_gas()>
+                        <label class="block text-sm font-medium text-gray-700 inline-flex items-center">
+                            "MPG"
+                            <span class="text-red-600">" *"</span>
+                            <Tooltip text="Combined (city/highway) miles per gallon, as listed on the EPA window sticker or fueleconomy.gov. Required to estimate fuel costs over the remaining miles, unless you fill in both the city and highway ratings instead." />
+                        </label>
+                        <input
+                            type="text"
+                            class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                            class:border-red-500=move || field_error(CarField::Mpg)().is_some()
+                            aria-invalid=move || field_error(CarField::Mpg)().is_some().to_string()
+                            prop:value=move || car.get().mpg
+                            on:input=move |ev| {
+                                set_car_wrapper(&|c| c.mpg = event_target_value(&ev));
+                            }
+                        />
+                        {error_message(CarField::Mpg)}
+                        <div class="mt-2 grid grid-cols-2 gap-2">
+                            <div>
+                                <label class="block text-xs font-medium text-gray-600 inline-flex items-center">
+                                    "City MPG"
+                                    <Tooltip text="Optional. With the highway rating, fuel costs use a blend of the two at the Highway Driving share in settings instead of the combined MPG." />
+                                </label>
+                                <input
+                                    type="text"
+                                    class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                                    class:border-red-500=move || field_error(CarField::MpgCity)().is_some()
+                                    aria-invalid=move || field_error(CarField::MpgCity)().is_some().to_string()
+                                    prop:value=move || car.get().mpg_city
+                                    on:input=move |ev| {
+                                        set_car_wrapper(&|c| c.mpg_city = event_target_value(&ev));
+                                    }
+                                />
+                                {error_message(CarField::MpgCity)}
+                            </div>
+                            <div>
+                                <label class="block text-xs font-medium text-gray-600 inline-flex items-center">
+                                    "Highway MPG"
+                                </label>
+                                <input
+                                    type="text"
+                                    class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                                    class:border-red-500=move || field_error(CarField::MpgHighway)().is_some()
+                                    aria-invalid=move || field_error(CarField::MpgHighway)().is_some().to_string()
+                                    prop:value=move || car.get().mpg_highway
+                                    on:input=move |ev| {
+                                        set_car_wrapper(&|c| c.mpg_highway = event_target_value(&ev));
+                                    }
+                                />
+                                {error_message(CarField::MpgHighway)}
+                            </div>
+                        </div>
+                    </div>
+                    <div>
+                        <label class="block text-sm font-medium text-gray-700 inline-flex items-center">
+                            {move || match car.get().insurance_mode {
+                                InsuranceMode::Premium => "Insurance Cost (premium $)",
+                                InsuranceMode::PerMile => "Insurance Cost ($ per mile)",
+                            }}
+                            <span class="text-red-600">" *"</span>
+                            <Tooltip text="Your insurance quote for this car, either as a flat premium (monthly, 6-month or annual, scaled up to a year) or as a pay-per-mile rate (multiplied by the remaining miles). Required because insurance is a recurring cost over the whole ownership period." />
+                        </label>
+                        <div class="mt-1 flex gap-2">
+                            <select
+                                aria-label="Insurance pricing"
+                                class="block rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                                prop:value=move || match car.get().insurance_mode {
+                                    InsuranceMode::Premium => "premium",
+                                    InsuranceMode::PerMile => "per_mile",
+                                }
+                                on:change=move |ev| {
+                                    let mode = if event_target_value(&ev) == "per_mile" {
+                                        InsuranceMode::PerMile
+                                    } else {
+                                        InsuranceMode::Premium
+                                    };
+                                    set_car_wrapper(&|c| c.insurance_mode = mode);
+                                }
+                            >
+                                <option value="premium">"Flat premium"</option>
+                                <option value="per_mile">"Per mile"</option>
+                            </select>
+                            <select
+                                aria-label="Premium period"
+                                class="block rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                                class:hidden=move || car.get().insurance_mode != InsuranceMode::Premium
+                                prop:value=move || car.get().insurance_period.id()
+                                on:change=move |ev| {
+                                    if let Some(period) = InsurancePeriod::from_id(&event_target_value(&ev)) {
+                                        set_car_wrapper(&|c| c.insurance_period = period);
+                                    }
+                                }
+                            >
+                                {InsurancePeriod::ALL
+                                    .into_iter()
+                                    .map(|period| view! { <option value=period.id()>{period.label()}</option> })
+                                    .collect_view()}
+                            </select>
+                            <input
+                                type="text"
+                                class="block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                                class:border-red-500=move || field_error(CarField::Insurance)().is_some()
+                                aria-invalid=move || field_error(CarField::Insurance)().is_some().to_string()
+                                prop:value=move || {
+                                    let car = car.get();
+                                    match car.insurance_mode {
+                                        InsuranceMode::Premium => car.insurance_cost,
+                                        InsuranceMode::PerMile => car.insurance_per_mile,
+                                    }
+                                }
+                                on:input=move |ev| {
+                                    let value = event_target_value(&ev);
+                                    set_car_wrapper(&|c| match c.insurance_mode {
+                                        InsuranceMode::Premium => c.insurance_cost = value.clone(),
+                                        InsuranceMode::PerMile => c.insurance_per_mile = value.clone(),
+                                    });
+                                }
+                            />
+                        </div>
+                        {error_message(CarField::Insurance)}
+                    </div>
+                </div>
+                <p class="mt-3 text-sm text-gray-500" class:hidden=move || details_open.get() || car.get().is_computable()>
+                    "Fill in these four fields to see this car's costs."
+                </p>
+                <button
+                    type="button"
+                    class="mt-3 text-sm font-medium text-blue-600 hover:text-blue-800"
+                    class:hidden=move || details_open.get() || !car.get().is_computable()
+                    on:click=move |_| details_open.set(true)
+                >
+                    "Add more details"
+                </button>
+            </div>
+
+            // Optional details stay hidden until the required set is valid and the user asks
+            // for them, then stay open so every field remains editable
+            <div class="space-y-6" class:hidden=move || !details_open.get()>
+                <div class="border-t border-gray-200 pt-6">
+                    <h4 class="text-sm font-medium text-gray-900 mb-4">"Vehicle & Ownership"</h4>
+                    <div class="grid grid-cols-1 gap-6 sm:grid-cols-2 lg:grid-cols-3">
+                        <div>
+                            <label class="block text-sm font-medium text-gray-700 inline-flex items-center">
+                                "Make"
+                                <Tooltip text="The manufacturer, e.g. Toyota or Ford. Used together with the model to look up maintenance cost data, so spell it the same way as in the maintenance table." />
+                            </label>
+                            <input
+                                type="text"
+                                class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                                prop:value=move || car.get().make
+                                on:input=move |ev| {
+                                    set_car_wrapper(&|c| c.make = event_target_value(&ev));
+                                }
+                            />
+                        </div>
+                        <div>
+                            <label class="block text-sm font-medium text-gray-700 inline-flex items-center">
+                                "Model"
+                                <Tooltip text="The model name, e.g. Camry or F-150. Combined with the make to find a matching maintenance cost profile; without a match, maintenance is assumed to cost $0." />
+                            </label>
+                            <input
+                                type="text"
+                                class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                                prop:value=move || car.get().model
+                                on:input=move |ev| {
+                                    set_car_wrapper(&|c| c.model = event_target_value(&ev));
+                                }
+                            />
+                        </div>
+                        <div>
+                            <label class="block text-sm font-medium text-gray-700 inline-flex items-center">
+                                "Trim/Features (optional)"
+                                <Tooltip text="The trim level or notable options, e.g. XLE or Sport. For your reference only; it does not affect the calculation." />
+                            </label>
+                            <input
+                                type="text"
+                                class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                                prop:value=move || car.get().trim
+                                on:input=move |ev| {
+                                    set_car_wrapper(&|c| c.trim = event_target_value(&ev));
+                                }
+                            />
+                        </div>
+                        <div>
+                            <label class="block text-sm font-medium text-gray-700 inline-flex items-center">
+                                "Model Year"
+                                <Tooltip text="The model year of the vehicle, e.g. 2020. Helps you tell similar cars apart." />
+                            </label>
+                            <input
+                                type="text"
+                                class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                                prop:value=move || car.get().year
+                                on:input=move |ev| {
+                                    set_car_wrapper(&|c| c.year = event_target_value(&ev));
+                                }
+                            />
+                        </div>
+                        <div>
+                            <label class="block text-sm font-medium text-gray-700 inline-flex items-center">
+                                "Seats (optional)"
+                                <Tooltip text="How many seats the car has. Shows the cost per passenger-mile, which is useful for comparing a minivan against two smaller cars for carpooling or a family. Leave blank to hide it." />
+                            </label>
+                            <input
+                                type="text"
+                                class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                                prop:value=move || car.get().seats
+                                on:input=move |ev| {
+                                    set_car_wrapper(&|c| c.seats = event_target_value(&ev));
+                                }
+                            />
+                        </div>
+                        <div>
+                            <label class="block text-sm font-medium text-gray-700 inline-flex items-center">
+                                "Keep Until Age (years from new, optional)"
+                                <Tooltip text="The vehicle age at which you plan to sell it, measured from new just like lifetime miles is measured on the odometer, not from today like Years of Ownership in Shared Settings. Leave blank to keep the car until it reaches your lifetime mileage." />
+                            </label>
+                            <input
+                                type="text"
+                                class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                                prop:value=move || car.get().keep_until_age
+                                on:input=move |ev| {
+                                    set_car_wrapper(&|c| c.keep_until_age = event_target_value(&ev));
+                                }
+                            />
+                        </div>
+                        <div>
+                            <label class="block text-sm font-medium text-gray-700 inline-flex items-center">
+                                "Miles Left This Year (optional)"
+                                <Tooltip text="How many more miles you expect to drive in the current year, e.g. for a car bought mid-year. The first year of the cost breakdown is prorated to this many miles. Leave blank to start with a full year." />
+                            </label>
+                            <input
+                                type="text"
+                                class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                                prop:value=move || car.get().first_year_miles
+                                on:input=move |ev| {
+                                    set_car_wrapper(&|c| c.first_year_miles = event_target_value(&ev));
+                                }
+                            />
+                        </div>
+                        <div>
+                            <label class="block text-sm font-medium text-gray-700 inline-flex items-center">
+                                "Depreciation"
+                                <Tooltip text="How the resale value at the end of ownership is projected. Exponential decay loses a fixed share of value each year (the depreciation rate in Shared Settings); straight line falls to zero at your lifetime mileage; the make/model curve uses the resale table in the maintenance data and falls back to exponential decay when there isn't one." />
+                            </label>
+                            <select
+                                class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                                prop:value=move || car.get().depreciation_method.id()
+                                on:change=move |ev| {
+                                    if let Some(method) = DepreciationMethod::from_id(&event_target_value(&ev)) {
+                                        set_car_wrapper(&|c| c.depreciation_method = method);
+                                    }
+                                }
+                            >
+                                {DepreciationMethod::ALL
+                                    .into_iter()
+                                    .map(|method| view! { <option value=method.id()>{method.label()}</option> })
+                                    .collect_view()}
+                            </select>
+                        </div>
+                        <div>
+                            <label class="block text-sm font-medium text-gray-700 inline-flex items-center">
+                                "Expected Resale Value (optional)"
+                                <Tooltip text="What you expect to sell the car for at the end of ownership, e.g. from a guaranteed buyback or a dealer quote. Replaces the depreciation projection above. Leave blank to use the projection." />
+                            </label>
+                            <input
+                                type="text"
+                                class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                                prop:value=move || car.get().expected_resale_value
+                                on:input=move |ev| {
+                                    set_car_wrapper(&|c| c.expected_resale_value = event_target_value(&ev));
+                                }
+                            />
+                        </div>
+                        <div>
+                            <label class="block text-sm font-medium text-gray-700 inline-flex items-center">
+                                "Sell By"
+                                <Tooltip text="How you plan to sell the car. A private sale gets full resale value; a trade-in pays less (see the trade-in discount in Shared Settings) but in many states the trade-in value isn't taxed on your next purchase." />
+                            </label>
+                            <select
+                                class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                                prop:value=move || car.get().resale_channel.id()
+                                on:change=move |ev| {
+                                    if let Some(channel) = ResaleChannel::from_id(&event_target_value(&ev)) {
+                                        set_car_wrapper(&|c| c.resale_channel = channel);
+                                    }
+                                }
+                            >
+                                {ResaleChannel::ALL
+                                    .into_iter()
+                                    .map(|channel| view! { <option value=channel.id()>{channel.label()}</option> })
+                                    .collect_view()}
+                            </select>
+                        </div>
+                    </div>
+                </div>
+
+                <div class="border-t border-gray-200 pt-6">
+                    <h4 class="text-sm font-medium text-gray-900 mb-4">"Financing"</h4>
+                    <div class="grid grid-cols-1 gap-6 sm:grid-cols-2 lg:grid-cols-3">
+                        <div>
+                            <label class="block text-sm font-medium text-gray-700 inline-flex items-center">
+                                "Down Payment (optional)"
+                                <Tooltip text="Cash paid upfront when financing. The rest of the purchase price is borrowed." />
+                            </label>
+                            <input
+                                type="text"
+                                class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                                prop:value=move || car.get().down_payment
+                                on:input=move |ev| {
+                                    set_car_wrapper(&|c| c.down_payment = event_target_value(&ev));
+                                }
+                            />
+                        </div>
+                        <div>
+                            <label class="block text-sm font-medium text-gray-700 inline-flex items-center">
+                                "Loan APR (%, optional)"
+                                <Tooltip text="The loan's annual percentage rate. Leave blank or enter 0 for 0% financing." />
+                            </label>
+                            <input
+                                type="text"
+                                class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                                prop:value=move || car.get().loan_apr
+                                on:input=move |ev| {
+                                    set_car_wrapper(&|c| c.loan_apr = event_target_value(&ev));
+                                }
+                            />
+                        </div>
+                        <div>
+                            <label class="block text-sm font-medium text-gray-700 inline-flex items-center">
+                                "Loan Term (months, optional)"
+                                <Tooltip text="How many months the loan runs, e.g. 60. Leave blank if you're paying cash. With a loan, the effective cash price discounts the payments at your opportunity cost rate, so you can compare 0% financing against a cash discount." />
+                            </label>
+                            <input
+                                type="text"
+                                class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                                prop:value=move || car.get().loan_term_months
+                                on:input=move |ev| {
+                                    set_car_wrapper(&|c| c.loan_term_months = event_target_value(&ev));
+                                }
+                            />
+                        </div>
+                    </div>
+                </div>
+
+                <div class="border-t border-gray-200 pt-6">
+                    <h4 class="text-sm font-medium text-gray-900 mb-4">"Extended Warranty"</h4>
+                    <div class="grid grid-cols-1 gap-6 sm:grid-cols-2 lg:grid-cols-3">
+                        <div>
+                            <label class="block text-sm font-medium text-gray-700 inline-flex items-center">
+                                "Extended Warranty Price (optional)"
+                                <Tooltip text="What you'd pay upfront for an extended or certified pre-owned warranty. It's added to the total cost, and maintenance during its coverage is left out, so you can see whether it pays off. Leave blank if the warranty came with the car." />
+                            </label>
+                            <input
+                                type="text"
+                                class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                                prop:value=move || car.get().extended_warranty_cost
+                                on:input=move |ev| {
+                                    set_car_wrapper(&|c| c.extended_warranty_cost = event_target_value(&ev));
+                                }
+                            />
+                        </div>
+                        <div>
+                            <label class="block text-sm font-medium text-gray-700 inline-flex items-center">
+                                "Warranty Years (optional)"
+                                <Tooltip text="How many years from purchase the warranty covers. With a mileage limit too, coverage ends at whichever comes first. Leave both limits blank for no coverage." />
+                            </label>
+                            <input
+                                type="text"
+                                class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                                prop:value=move || car.get().extended_warranty_years
+                                on:input=move |ev| {
+                                    set_car_wrapper(&|c| c.extended_warranty_years = event_target_value(&ev));
+                                }
+                            />
+                        </div>
+                        <div>
+                            <label class="block text-sm font-medium text-gray-700 inline-flex items-center">
+                                "Warranty Miles (optional)"
+                                <Tooltip text="How many miles from purchase the warranty covers, e.g. 36000. With a years limit too, coverage ends at whichever comes first." />
+                            </label>
+                            <input
+                                type="text"
+                                class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                                prop:value=move || car.get().extended_warranty_miles
+                                on:input=move |ev| {
+                                    set_car_wrapper(&|c| c.extended_warranty_miles = event_target_value(&ev));
+                                }
+                            />
+                        </div>
                     </div>
                 </div>
-                <div class="mt-6">
-                    <label class="block text-sm font-medium text-gray-700">"Notes (optional)"</label>
-                    <textarea
-                        rows="3"
-                        class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
-                        prop:value=move || car.get().notes
-                        on:input=move |ev| {
-                            set_car_wrapper(&|c| c.notes = event_target_value(&ev));
-                        }
-                    ></textarea>
+
+                <div class="border-t border-gray-200 pt-6">
+                    <h4 class="text-sm font-medium text-gray-900 mb-4">"Advanced"</h4>
+                    <div class="grid grid-cols-1 gap-6 sm:grid-cols-2">
+                        <div>
+                            <label class="block text-sm font-medium text-gray-700 inline-flex items-center">
+                                "Lifetime Miles (optional)"
+                                <Tooltip text="The odometer reading you'll drive this car to, in place of the lifetime mileage in Shared Settings. Useful when comparing a car you'll retire early against one you expect to run much longer. Leave blank to use the shared setting." />
+                            </label>
+                            <input
+                                type="text"
+                                class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                                placeholder=move || format!("{:.0} (shared setting)", settings.get().lifetime_miles)
+                                prop:value=move || car.get().lifetime_miles_override
+                                on:input=move |ev| {
+                                    set_car_wrapper(&|c| c.lifetime_miles_override = event_target_value(&ev));
+                                }
+                            />
+                        </div>
+                        <div>
+                            <label class="block text-sm font-medium text-gray-700 inline-flex items-center">
+                                "Tire Set Price (optional)"
+                                <Tooltip text="What a full set of tires costs installed for this car. Tires are a big, predictable cost, so they're counted on their own rather than folded into the maintenance curves: one set every tire-life miles. Needs the tire life too." />
+                            </label>
+                            <input
+                                type="text"
+                                class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                                prop:value=move || car.get().tire_set_cost
+                                on:input=move |ev| {
+                                    set_car_wrapper(&|c| c.tire_set_cost = event_target_value(&ev));
+                                }
+                            />
+                        </div>
+                        <div>
+                            <label class="block text-sm font-medium text-gray-700 inline-flex items-center">
+                                "Tire Life (miles, optional)"
+                                <Tooltip text="How many miles a set of tires lasts, e.g. its treadwear warranty of 50000. Performance tires often last 20,000-30,000 miles." />
+                            </label>
+                            <input
+                                type="text"
+                                class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                                prop:value=move || car.get().tire_life_miles
+                                on:input=move |ev| {
+                                    set_car_wrapper(&|c| c.tire_life_miles = event_target_value(&ev));
+                                }
+                            />
+                        </div>
+                    </div>
+                </div>
+
+                <div class="border-t border-gray-200 pt-6">
+                    <h4 class="text-sm font-medium text-gray-900 mb-4">"Additional Information"</h4>
+                    <div class="grid grid-cols-1 gap-6 sm:grid-cols-2">
+                        <div>
+                            <label class="block text-sm font-medium text-gray-700 inline-flex items-center">
+                                "VIN (optional)"
+                                <Tooltip text="The 17-character Vehicle Identification Number from the listing or windshield. Decode VIN looks it up in NHTSA's free vPIC database and fills in the make, model and year, which also helps match the maintenance data." />
+                            </label>
+                            <div class="mt-1 flex gap-2">
+                                <input
+                                    type="text"
+                                    class="block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                                    prop:value=move || car.get().vin
+                                    on:input=move |ev| {
+                                        set_car_wrapper(&|c| c.vin = event_target_value(&ev));
+                                    }
+                                />
+                                <button
+                                    type="button"
+                                    class="shrink-0 inline-flex items-center px-3 py-2 border border-gray-300 shadow-sm text-sm font-medium rounded-md text-gray-700 bg-white hover:bg-gray-50 disabled:opacity-50 disabled:cursor-not-allowed"
+                                    prop:disabled=move || decode.pending().get() || car.with(|c| c.vin.trim().is_empty())
+                                    on:click=move |_| {
+                                        decode_applied.set_value(false);
+                                        decode.dispatch(car.get_untracked().vin);
+                                    }
+                                >
+                                    {move || if decode.pending().get() { "Decoding..." } else { "Decode VIN" }}
+                                </button>
+                            </div>
+                            {move || match decode.value().get() {
+                                Some(Ok(decoded)) => view! {
+                                    <p class="mt-1 text-sm text-green-700" role="status">
+                                        {format!("Filled in {}", decoded.name())}
+                                    </p>
+                                }
+                                .into_any(),
+                                Some(Err(message)) => view! {
+                                    <p class="mt-1 text-sm text-red-600" role="alert">{message}</p>
+                                }
+                                .into_any(),
+                                None => ().into_any(),
+                            }}
+                        </div>
+                        <div>
+                            <label class="block text-sm font-medium text-gray-700 inline-flex items-center">
+                                "Listing URL (optional)"
+                                <Tooltip text="A link to the dealer or marketplace listing so you can find the car again." />
+                            </label>
+                            <input
+                                type="text"
+                                class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                                prop:value=move || car.get().listing_url
+                                on:input=move |ev| {
+                                    set_car_wrapper(&|c| c.listing_url = event_target_value(&ev));
+                                }
+                            />
+                        </div>
+                    </div>
+                    <div class="mt-6">
+                        <span class="block text-sm font-medium text-gray-700 inline-flex items-center">
+                            "Color tag (optional)"
+                            <Tooltip text="Marks this car's card and comparison column so you can tell several cars apart at a glance." />
+                        </span>
+                        <div class="mt-2 flex items-center gap-2" role="radiogroup" aria-label="Color tag">
+                            <button
+                                type="button"
+                                role="radio"
+                                class="h-6 px-2 rounded-full border border-gray-300 text-xs text-gray-600 hover:bg-gray-50"
+                                class:ring-2=move || car.get().color.is_none()
+                                class:ring-blue-500=move || car.get().color.is_none()
+                                aria-checked=move || car.get().color.is_none().to_string()
+                                on:click=move |_| set_car_wrapper(&|c| c.color = None)
+                            >
+                                "None"
+                            </button>
+                            {CAR_COLORS
+                                .into_iter()
+                                .map(|(name, hex)| {
+                                    let selected = move || car.get().color.as_deref() == Some(hex);
+                                    view! {
+                                        <button
+                                            type="button"
+                                            role="radio"
+                                            class="h-6 w-6 rounded-full ring-offset-2"
+                                            class:ring-2=selected
+                                            class:ring-gray-700=selected
+                                            style=format!("background-color: {}", hex)
+                                            title=name
+                                            aria-label=name
+                                            aria-checked=move || selected().to_string()
+                                            on:click=move |_| set_car_wrapper(&|c| c.color = Some(hex.to_string()))
+                                        ></button>
+                                    }
+                                })
+                                .collect_view()}
+                        </div>
+                    </div>
+                    <div class="mt-6">
+                        <CarPhotoInput car=car set_car_wrapper=set_car_wrapper />
+                    </div>
+                    <div class="mt-6">
+                        <label class="block text-sm font-medium text-gray-700 inline-flex items-center">
+                            "Notes (optional)"
+                            <Tooltip text="Anything else worth remembering: condition, features, seller details, test drive impressions." />
+                        </label>
+                        <textarea
+                            rows="3"
+                            class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                            prop:value=move || car.get().notes
+                            on:input=move |ev| {
+                                set_car_wrapper(&|c| c.notes = event_target_value(&ev));
+                            }
+                        ></textarea>
+                    </div>
                 </div>
             </div>
         </div>
@@ -1,16 +1,32 @@
+use leptos::ev;
 use leptos::prelude::*;
+use leptos::wasm_bindgen::JsCast;
+use leptos_use::signal_debounced;
 
-use crate::components::cars::CarCard;
+use crate::calculations::{sorted_car_ids, CarSort, ComputedCache};
+use crate::components::cars::{car_in_list, CarCard};
+use crate::data::{parse_car_row, EditHistory, ROW_IMPORT_COLUMNS};
 use crate::models::{Car, MaintenanceCostDatabase, SharedSettings};
 
+/// Quiet time after which a burst of edits (typing a price, say) becomes one undo step
+const HISTORY_SETTLE_MS: f64 = 750.0;
+
+/// Undo steps kept; each is a copy of every car
+const HISTORY_DEPTH: usize = 50;
+
 #[component]
 pub fn CarList(
     cars: Signal<Vec<Car>>,
     set_cars: WriteSignal<Vec<Car>>,
     settings: Signal<SharedSettings>,
     maintenance_db: Signal<MaintenanceCostDatabase>,
+    /// Free-text filter; cards that don't match are hidden and matches are highlighted
+    search: Signal<String>,
+    computed_cache: Signal<ComputedCache>,
+    set_computed_cache: WriteSignal<ComputedCache>,
 ) -> impl IntoView {
     let (expanded_cars, set_expanded_cars) = signal(Vec::<usize>::new());
+    let (sort, set_sort) = signal(CarSort::Stored);
     let next_id = RwSignal::new(1_usize);
 
     // Initialize next_id from existing cars
@@ -18,51 +34,283 @@ pub fn CarList(
         next_id.set(max_id + 1);
     }
 
-    let add_car = move |_| {
-        let id = next_id.get();
-        next_id.update(|n| *n += 1);
+    // Assign the next id to a new car, add it (after the car with id `after`, or at the
+    // end) and expand it. The list can be replaced wholesale (e.g. by importing a backup),
+    // so never reuse an id that's already taken.
+    let insert_car = move |mut new_car: Car, after: Option<usize>| {
+        let taken = cars.with_untracked(|cars| cars.iter().map(|c| c.id + 1).max());
+        let id = next_id.get().max(taken.unwrap_or(1));
+        next_id.set(id + 1);
 
-        let new_car = Car::new(id);
+        new_car.id = id;
         set_cars.update(|cars| {
-            cars.push(new_car);
+            match after.and_then(|after| cars.iter().position(|c| c.id == after)) {
+                Some(index) => cars.insert(index + 1, new_car),
+                None => cars.push(new_car),
+            }
         });
         set_expanded_cars.update(|expanded| {
             expanded.push(id);
         });
     };
+    let push_car = move |new_car: Car| insert_car(new_car, None);
+
+    let add_car = move |_| push_car(Car::new(0));
+
+    // Snapshot the list once edits settle. Undo and redo first record anything still
+    // settling, so the last few keystrokes are undone rather than lost (and an edit made
+    // since an undo can't be silently overwritten by a redo).
+    let history = RwSignal::new(EditHistory::new(cars.get_untracked(), HISTORY_DEPTH));
+    let settled_cars = signal_debounced(cars, HISTORY_SETTLE_MS);
+    Effect::new(move |_| {
+        let settled = settled_cars.get();
+        history.update(|history| {
+            history.record(settled);
+        });
+    });
+    let undo = move || {
+        let restored = history.try_update(|history| {
+            history.record(cars.get_untracked());
+            history.undo()
+        });
+        if let Some(Some(restored)) = restored {
+            set_cars.set(restored);
+        }
+    };
+    let redo = move || {
+        let restored = history.try_update(|history| {
+            history.record(cars.get_untracked());
+            history.redo()
+        });
+        if let Some(Some(restored)) = restored {
+            set_cars.set(restored);
+        }
+    };
+
+    // Ctrl+Z / Ctrl+Y (or Cmd, and Shift+Z for redo). Text fields keep their own undo.
+    let keydown = window_event_listener(ev::keydown, move |ev| {
+        if !(ev.ctrl_key() || ev.meta_key()) || ev.alt_key() || ev.default_prevented() {
+            return;
+        }
+        let in_text_field = ev.target().is_some_and(|target| {
+            if let Some(input) = target.dyn_ref::<web_sys::HtmlInputElement>() {
+                !matches!(input.type_().as_str(), "checkbox" | "radio" | "button")
+            } else {
+                target.dyn_ref::<web_sys::HtmlTextAreaElement>().is_some()
+            }
+        });
+        if in_text_field {
+            return;
+        }
+        match (ev.key().to_lowercase().as_str(), ev.shift_key()) {
+            ("z", false) => undo(),
+            ("z", true) | ("y", false) => redo(),
+            _ => return,
+        }
+        ev.prevent_default();
+    });
+    on_cleanup(move || keydown.remove());
+
+    let (show_paste, set_show_paste) = signal(false);
+    let (pasted_row, set_pasted_row) = signal(String::new());
+    let row_import = move || {
+        let row = pasted_row.get();
+        (!row.trim().is_empty()).then(|| parse_car_row(&row, 0))
+    };
+
+    let import_row = move |_| {
+        if let Some(import) = row_import() {
+            push_car(import.car);
+            set_pasted_row.set(String::new());
+            set_show_paste.set(false);
+        }
+    };
 
     view! {
         <div class="space-y-4">
             <div class="flex items-center justify-between">
                 <h2 class="text-xl font-semibold text-gray-900">"Your Cars"</h2>
-                <button
-                    class="inline-flex items-center px-4 py-2 border border-transparent text-sm font-medium rounded-md text-white bg-blue-600 hover:bg-blue-700 focus:outline-none focus:ring-2 focus:ring-offset-2 focus:ring-blue-500"
-                    on:click=add_car
-                >
-                    <svg class="mr-2 h-5 w-5" xmlns="http://www.w3.org/2000/svg" viewBox="0 0 20 20" fill="currentColor">
-                        <path fill-rule="evenodd" d="M10 3a1 1 0 011 1v5h5a1 1 0 110 2h-5v5a1 1 0 11-2 0v-5H4a1 1 0 110-2h5V4a1 1 0 011-1z" clip-rule="evenodd"/>
-                    </svg>
-                    "Add Car"
-                </button>
+                <div class="flex items-center gap-2">
+                    <label class="inline-flex items-center gap-2 text-sm text-gray-600">
+                        "Sort by"
+                        <select
+                            class="rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                            prop:value=move || sort.get().id()
+                            on:change=move |ev| {
+                                if let Some(value) = CarSort::from_id(&event_target_value(&ev)) {
+                                    set_sort.set(value);
+                                }
+                            }
+                        >
+                            {CarSort::ALL
+                                .into_iter()
+                                .map(|option| view! { <option value=option.id()>{option.label()}</option> })
+                                .collect_view()}
+                        </select>
+                    </label>
+                    <button
+                        class="inline-flex items-center px-3 py-2 border border-gray-300 text-sm font-medium rounded-md text-gray-700 bg-white hover:bg-gray-50 disabled:opacity-40 disabled:cursor-not-allowed focus:outline-none focus:ring-2 focus:ring-offset-2 focus:ring-blue-500"
+                        title="Undo the last change to your cars (Ctrl+Z)"
+                        prop:disabled=move || {
+                            !history.with(|h| h.can_undo())
+                                && settled_cars.with(|settled| cars.with(|cars| settled == cars))
+                        }
+                        on:click=move |_| undo()
+                    >
+                        "Undo"
+                    </button>
+                    <button
+                        class="inline-flex items-center px-3 py-2 border border-gray-300 text-sm font-medium rounded-md text-gray-700 bg-white hover:bg-gray-50 disabled:opacity-40 disabled:cursor-not-allowed focus:outline-none focus:ring-2 focus:ring-offset-2 focus:ring-blue-500"
+                        title="Redo the change you undid (Ctrl+Y)"
+                        prop:disabled=move || !history.with(|h| h.can_redo())
+                        on:click=move |_| redo()
+                    >
+                        "Redo"
+                    </button>
+                    <button
+                        class="inline-flex items-center px-4 py-2 border border-gray-300 text-sm font-medium rounded-md text-gray-700 bg-white hover:bg-gray-50 focus:outline-none focus:ring-2 focus:ring-offset-2 focus:ring-blue-500"
+                        on:click=move |_| set_show_paste.update(|v| *v = !*v)
+                    >
+                        "Paste row"
+                    </button>
+                    <button
+                        class="inline-flex items-center px-4 py-2 border border-transparent text-sm font-medium rounded-md text-white bg-blue-600 hover:bg-blue-700 focus:outline-none focus:ring-2 focus:ring-offset-2 focus:ring-blue-500"
+                        on:click=add_car
+                    >
+                        <svg class="mr-2 h-5 w-5" xmlns="http://www.w3.org/2000/svg" viewBox="0 0 20 20" fill="currentColor">
+                            <path fill-rule="evenodd" d="M10 3a1 1 0 011 1v5h5a1 1 0 110 2h-5v5a1 1 0 11-2 0v-5H4a1 1 0 110-2h5V4a1 1 0 011-1z" clip-rule="evenodd"/>
+                        </svg>
+                        "Add Car"
+                    </button>
+                </div>
             </div>
 
+            <Show when=move || show_paste.get()>
+                <div class="bg-white shadow rounded-lg p-4 space-y-3">
+                    <label for="paste-row" class="block text-sm font-medium text-gray-700">
+                        "Paste a spreadsheet row (tab- or comma-separated)"
+                    </label>
+                    <p class="text-xs text-gray-500">
+                        "Columns, in order: " {ROW_IMPORT_COLUMNS.join(", ")}
+                        ". Trailing columns may be left off; \"$\" and thousands separators are fine."
+                    </p>
+                    <textarea
+                        id="paste-row"
+                        rows="2"
+                        class="block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm font-mono"
+                        prop:value=move || pasted_row.get()
+                        on:input=move |ev| set_pasted_row.set(event_target_value(&ev))
+                    ></textarea>
+                    {move || row_import().map(|import| {
+                        let has_errors = import.has_errors();
+                        let ignored = import.ignored.clone();
+                        view! {
+                            <table class="min-w-full text-sm">
+                                <thead>
+                                    <tr class="text-left text-xs text-gray-500 uppercase tracking-wide">
+                                        <th class="py-1 pr-4">"Column"</th>
+                                        <th class="py-1">"Value"</th>
+                                    </tr>
+                                </thead>
+                                <tbody>
+                                    {import.fields.into_iter().map(|field| {
+                                        let value_class = if field.error.is_some() { "py-1 text-red-600" } else { "py-1 text-gray-900" };
+                                        view! {
+                                            <tr class="border-t border-gray-100">
+                                                <td class="py-1 pr-4 text-gray-600">{field.column}</td>
+                                                <td class=value_class>
+                                                    {if field.value.is_empty() { "—".to_string() } else { field.value }}
+                                                    {field.error.map(|e| view! { <div class="text-xs">{e}" (left blank)"</div> })}
+                                                </td>
+                                            </tr>
+                                        }
+                                    }).collect_view()}
+                                </tbody>
+                            </table>
+                            {has_errors.then(|| view! {
+                                <p class="text-sm text-yellow-700">"Some columns couldn't be parsed and will be left blank. You can fill them in after adding the car."</p>
+                            })}
+                            {(!ignored.is_empty()).then(|| view! {
+                                <p class="text-sm text-gray-500">{format!("Ignored extra columns: {}", ignored.join(", "))}</p>
+                            })}
+                        }
+                    })}
+                    <div class="flex justify-end gap-2">
+                        <button
+                            class="px-3 py-1.5 text-sm rounded-md border border-gray-300 text-gray-700 bg-white hover:bg-gray-50"
+                            on:click=move |_| {
+                                set_pasted_row.set(String::new());
+                                set_show_paste.set(false);
+                            }
+                        >
+                            "Cancel"
+                        </button>
+                        <button
+                            class="px-3 py-1.5 text-sm rounded-md text-white bg-blue-600 hover:bg-blue-700 disabled:opacity-50"
+                            prop:disabled=move || row_import().is_none()
+                            on:click=import_row
+                        >
+                            "Add car from row"
+                        </button>
+                    </div>
+                </div>
+            </Show>
+
             <For
-                each=move || cars.get().into_iter().enumerate()
-                key=|(_, car)| car.id
-                children=move |(index, car)| {
+                each=move || {
+                    // Sorting reorders only what's shown, never the stored list
+                    let cars = cars.get();
+                    let order = settings.with(|settings| {
+                        maintenance_db.with(|db| sorted_car_ids(&cars, settings, db, sort.get()))
+                    });
+                    order
+                        .into_iter()
+                        .filter_map(|id| cars.iter().find(|c| c.id == id).cloned())
+                        .collect::<Vec<_>>()
+                }
+                key=|car| car.id
+                children=move |car| {
                     let car_id = car.id;
+                    let stored_car = car_in_list(cars, car_id, car);
 
+                    // Looked up by id rather than position, since cards keep their state
+                    // when the list is reordered
                     let update_car = {
                         let set_cars = set_cars;
                         move |updated_car: Car| {
                             set_cars.update(|cars| {
-                                if index < cars.len() {
-                                    cars[index] = updated_car;
+                                if let Some(slot) = cars.iter_mut().find(|c| c.id == car_id) {
+                                    *slot = updated_car;
                                 }
                             });
                         }
                     };
 
+                    // Swap with the neighbouring car above (-1) or below (1)
+                    let on_move = Callback::new(move |offset: isize| {
+                        set_cars.update(|cars| {
+                            let Some(index) = cars.iter().position(|c| c.id == car_id) else {
+                                return;
+                            };
+                            if let Some(target) = index.checked_add_signed(offset) {
+                                if target < cars.len() {
+                                    cars.swap(index, target);
+                                }
+                            }
+                        });
+                    });
+                    // Moving only makes sense in the stored order
+                    let position = move || cars.with(|cars| {
+                        (cars.iter().position(|c| c.id == car_id), cars.len())
+                    });
+                    let unsorted = move || sort.get() == CarSort::Stored;
+                    let can_move_up = Signal::derive(move || {
+                        unsorted() && matches!(position(), (Some(i), _) if i > 0)
+                    });
+                    let can_move_down = Signal::derive(move || {
+                        unsorted() && matches!(position(), (Some(i), len) if i + 1 < len)
+                    });
+
                     let on_delete = {
                         let set_cars = set_cars;
                         let set_expanded_cars = set_expanded_cars;
@@ -76,21 +324,62 @@ pub fn CarList(
                         }
                     };
 
+                    let on_duplicate = move || {
+                        if let Some(original) =
+                            cars.with_untracked(|cars| cars.iter().find(|c| c.id == car_id).cloned())
+                        {
+                            insert_car(original, Some(car_id));
+                        }
+                    };
+
+                    let is_match = move || {
+                        let query = search.get();
+                        cars.with(|cars| {
+                            cars.iter()
+                                .find(|c| c.id == car_id)
+                                .is_some_and(|c| c.matches_search(&query))
+                        })
+                    };
+                    let is_searching = move || !search.get().trim().is_empty();
+
                     view! {
-                        <CarCard
-                            car=car
-                            update_car=update_car
-                            car_id=car_id
-                            expanded_cars=expanded_cars
-                            set_expanded_cars=set_expanded_cars
-                            settings=settings
-                            maintenance_db=maintenance_db
-                            on_delete=Box::new(on_delete)
-                        />
+                        <div
+                            class="rounded-lg"
+                            class:hidden=move || !is_match()
+                            class=("ring-2", move || is_searching() && is_match())
+                            class=("ring-yellow-400", move || is_searching() && is_match())
+                        >
+                            <CarCard
+                                car=stored_car.into()
+                                update_car=update_car
+                                car_id=car_id
+                                expanded_cars=expanded_cars
+                                set_expanded_cars=set_expanded_cars
+                                settings=settings
+                                maintenance_db=maintenance_db
+                                computed_cache=computed_cache
+                                set_computed_cache=set_computed_cache
+                                on_delete=Box::new(on_delete)
+                                on_duplicate=Box::new(on_duplicate)
+                                on_move=on_move
+                                can_move_up=can_move_up
+                                can_move_down=can_move_down
+                            />
+                        </div>
                     }
                 }
             />
 
+            <Show when=move || {
+                let query = search.get();
+                let cars = cars.get();
+                !cars.is_empty() && !cars.iter().any(|c| c.matches_search(&query))
+            }>
+                <div class="text-center py-8 bg-white rounded-lg shadow text-sm text-gray-500">
+                    {move || format!("No cars match \"{}\".", search.get().trim())}
+                </div>
+            </Show>
+
             <Show when=move || cars.get().is_empty()>
                 <div class="text-center py-12 bg-white rounded-lg shadow">
                     <svg class="mx-auto h-12 w-12 text-gray-400" xmlns="http://www.w3.org/2000/svg" fill="none" viewBox="0 0 24 24" stroke="currentColor">
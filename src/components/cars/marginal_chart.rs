@@ -0,0 +1,133 @@
+use leptos::prelude::*;
+
+use crate::calculations::{MarginalCostBucket, MARGINAL_BUCKET_MILES};
+
+const CHART_WIDTH: f64 = 600.0;
+const CHART_HEIGHT: f64 = 220.0;
+const MARGIN_LEFT: f64 = 56.0;
+const MARGIN_BOTTOM: f64 = 28.0;
+const MARGIN_TOP: f64 = 12.0;
+
+/// Scale a bucket's cost to a per-10k-mile rate so partial buckets compare fairly
+fn per_10k(bucket: &MarginalCostBucket, cost: f64) -> f64 {
+    let miles = bucket.end_miles - bucket.start_miles;
+    if miles <= 0.0 {
+        0.0
+    } else {
+        cost * MARGINAL_BUCKET_MILES / miles
+    }
+}
+
+/// Stacked bar chart of the fuel and maintenance cost of each additional 10k miles
+#[component]
+pub fn MarginalCostChart(buckets: Vec<MarginalCostBucket>) -> impl IntoView {
+    if buckets.is_empty() {
+        return view! { <div></div> }.into_any();
+    }
+
+    let max_total = buckets
+        .iter()
+        .map(|b| per_10k(b, b.total()))
+        .fold(0.0_f64, f64::max)
+        .max(1.0);
+
+    let plot_width = CHART_WIDTH - MARGIN_LEFT;
+    let plot_height = CHART_HEIGHT - MARGIN_TOP - MARGIN_BOTTOM;
+    let bar_slot = plot_width / buckets.len() as f64;
+    let bar_width = (bar_slot * 0.8).max(1.0);
+    let scale = move |v: f64| v / max_total * plot_height;
+    let baseline = MARGIN_TOP + plot_height;
+    let label_every = (buckets.len() / 8).max(1);
+    let has_extrapolated = buckets.iter().any(|b| b.extrapolated);
+
+    let bars = buckets
+        .iter()
+        .enumerate()
+        .map(|(i, bucket)| {
+            let x = MARGIN_LEFT + i as f64 * bar_slot + (bar_slot - bar_width) / 2.0;
+            let fuel_h = scale(per_10k(bucket, bucket.fuel_cost));
+            let maint_h = scale(per_10k(bucket, bucket.maintenance_cost));
+            let maint_class = if bucket.extrapolated {
+                "fill-orange-200 stroke-orange-400"
+            } else {
+                "fill-orange-400"
+            };
+            let title = format!(
+                "{:.0}k–{:.0}k mi: fuel ${:.0}, maintenance ${:.0}{}",
+                bucket.start_miles / 1000.0,
+                bucket.end_miles / 1000.0,
+                bucket.fuel_cost,
+                bucket.maintenance_cost,
+                if bucket.extrapolated { " (extrapolated)" } else { "" }
+            );
+            let label = (i % label_every == 0).then(|| {
+                view! {
+                    <text
+                        x=x + bar_width / 2.0
+                        y=CHART_HEIGHT - 8.0
+                        text-anchor="middle"
+                        class="fill-gray-500 text-[10px]"
+                    >
+                        {format!("{:.0}k", bucket.start_miles / 1000.0)}
+                    </text>
+                }
+            });
+            view! {
+                <g>
+                    <title>{title}</title>
+                    <rect x=x y=baseline - fuel_h width=bar_width height=fuel_h class="fill-blue-400" />
+                    <rect
+                        x=x
+                        y=baseline - fuel_h - maint_h
+                        width=bar_width
+                        height=maint_h
+                        class=maint_class
+                        stroke-dasharray="3 2"
+                    />
+                    {label}
+                </g>
+            }
+        })
+        .collect_view();
+
+    view! {
+        <div class="mt-6">
+            <h4 class="text-sm font-medium text-gray-900">"Marginal Cost per 10k Miles"</h4>
+            <p class="text-xs text-gray-500 mb-2">
+                "What each additional 10,000 miles costs in fuel and maintenance. Rising bars show where the car gets expensive to keep."
+            </p>
+            <svg
+                viewBox=format!("0 0 {} {}", CHART_WIDTH, CHART_HEIGHT)
+                class="w-full h-auto"
+                xmlns="http://www.w3.org/2000/svg"
+            >
+                <line x1=MARGIN_LEFT y1=MARGIN_TOP x2=MARGIN_LEFT y2=baseline class="stroke-gray-300" />
+                <line x1=MARGIN_LEFT y1=baseline x2=CHART_WIDTH y2=baseline class="stroke-gray-300" />
+                <text x=MARGIN_LEFT - 6.0 y=MARGIN_TOP + 10.0 text-anchor="end" class="fill-gray-500 text-[10px]">
+                    {format!("${:.0}", max_total)}
+                </text>
+                <text x=MARGIN_LEFT - 6.0 y=baseline text-anchor="end" class="fill-gray-500 text-[10px]">
+                    "$0"
+                </text>
+                {bars}
+            </svg>
+            <div class="mt-2 flex flex-wrap items-center gap-4 text-xs text-gray-600">
+                <span class="inline-flex items-center">
+                    <span class="inline-block w-3 h-3 mr-1 bg-blue-400"></span>
+                    "Fuel"
+                </span>
+                <span class="inline-flex items-center">
+                    <span class="inline-block w-3 h-3 mr-1 bg-orange-400"></span>
+                    "Maintenance"
+                </span>
+                {has_extrapolated.then(|| view! {
+                    <span class="inline-flex items-center">
+                        <span class="inline-block w-3 h-3 mr-1 bg-orange-200 border border-dashed border-orange-400"></span>
+                        "Maintenance (extrapolated beyond the data)"
+                    </span>
+                })}
+            </div>
+        </div>
+    }
+    .into_any()
+}
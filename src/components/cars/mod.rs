@@ -1,9 +1,21 @@
+mod assumptions;
 mod card;
+mod cost_share;
 mod cost_summary;
 mod form;
 mod list;
+mod marginal_chart;
+mod photo;
+mod snapshot;
+mod waterfall;
 
-pub use card::CarCard;
+pub use assumptions::AssumptionsReport;
+pub use card::{car_in_list, CarCard};
+pub use cost_share::CostShareChart;
 pub use cost_summary::CarCostSummary;
 pub use form::CarForm;
 pub use list::CarList;
+pub use marginal_chart::MarginalCostChart;
+pub use photo::CarPhotoInput;
+pub use snapshot::SummarySnapshotButton;
+pub use waterfall::WaterfallChart;
@@ -0,0 +1,116 @@
+use leptos::prelude::*;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{
+    CanvasRenderingContext2d, HtmlCanvasElement, HtmlImageElement, HtmlInputElement, Url,
+};
+
+use crate::models::Car;
+
+/// Longest edge of the stored thumbnail, in pixels
+const THUMBNAIL_MAX_EDGE: f64 = 160.0;
+/// JPEG quality used when re-encoding the thumbnail
+const THUMBNAIL_QUALITY: f64 = 0.7;
+/// Reject thumbnails larger than this so a few photos can't exhaust local storage
+const MAX_DATA_URL_LEN: usize = 60_000;
+
+/// Draw a loaded image onto a small canvas and encode it as a JPEG data URL
+fn downscale(img: &HtmlImageElement) -> Result<String, JsValue> {
+    let (width, height) = (img.natural_width() as f64, img.natural_height() as f64);
+    if width <= 0.0 || height <= 0.0 {
+        return Err(JsValue::from_str("image has no size"));
+    }
+    let scale = (THUMBNAIL_MAX_EDGE / width.max(height)).min(1.0);
+    let (w, h) = ((width * scale).round(), (height * scale).round());
+
+    let canvas: HtmlCanvasElement = document().create_element("canvas")?.dyn_into()?;
+    canvas.set_width(w as u32);
+    canvas.set_height(h as u32);
+    let ctx: CanvasRenderingContext2d = canvas
+        .get_context("2d")?
+        .ok_or_else(|| JsValue::from_str("2d canvas context unavailable"))?
+        .dyn_into()?;
+    ctx.draw_image_with_html_image_element_and_dw_and_dh(img, 0.0, 0.0, w, h)?;
+
+    canvas
+        .to_data_url_with_type_and_encoder_options("image/jpeg", &JsValue::from(THUMBNAIL_QUALITY))
+}
+
+/// Upload control for a car photo, stored as a small thumbnail on the car
+#[component]
+pub fn CarPhotoInput(
+    car: Signal<Car>,
+    set_car_wrapper: impl Fn(&dyn Fn(&mut Car)) + 'static + Copy,
+) -> impl IntoView {
+    let (error, set_error) = signal::<Option<&'static str>>(None);
+
+    let on_change = move |ev: leptos::ev::Event| {
+        let input: HtmlInputElement = event_target(&ev);
+        let Some(file) = input.files().and_then(|files| files.get(0)) else {
+            return;
+        };
+        set_error.set(None);
+
+        let Ok(object_url) = Url::create_object_url_with_blob(&file) else {
+            set_error.set(Some("Couldn't read that file."));
+            return;
+        };
+        let Ok(img) = HtmlImageElement::new() else {
+            set_error.set(Some("Couldn't read that file."));
+            return;
+        };
+
+        let loaded = img.clone();
+        let url = object_url.clone();
+        let on_load = wasm_bindgen::closure::Closure::once_into_js(move || {
+            let _ = Url::revoke_object_url(&url);
+            match downscale(&loaded) {
+                Ok(data_url) if data_url.len() <= MAX_DATA_URL_LEN => {
+                    set_car_wrapper(&|c| c.image_data_url = Some(data_url.clone()));
+                }
+                Ok(_) => set_error.set(Some(
+                    "That image is too detailed to store. Try a simpler photo.",
+                )),
+                Err(_) => set_error.set(Some("Couldn't process that image.")),
+            }
+        });
+        let url = object_url.clone();
+        let on_error = wasm_bindgen::closure::Closure::once_into_js(move || {
+            let _ = Url::revoke_object_url(&url);
+            set_error.set(Some("That file isn't an image this browser can read."));
+        });
+        img.set_onload(Some(on_load.unchecked_ref()));
+        img.set_onerror(Some(on_error.unchecked_ref()));
+        img.set_src(&object_url);
+
+        // Allow re-selecting the same file after removing it
+        input.set_value("");
+    };
+
+    view! {
+        <div>
+            <label class="block text-sm font-medium text-gray-700">"Photo (optional)"</label>
+            <div class="mt-1 flex items-center space-x-3">
+                {move || {
+                    car.with(|car| car.photo_src().map(str::to_string)).map(|src| {
+                        view! { <img src=src alt="Car photo" class="h-16 w-16 rounded object-cover border border-gray-200" /> }
+                    })
+                }}
+                <input
+                    type="file"
+                    accept="image/*"
+                    class="block text-sm text-gray-700 file:mr-3 file:py-1 file:px-3 file:rounded-md file:border file:border-gray-300 file:bg-white file:text-sm file:font-medium hover:file:bg-gray-50"
+                    on:change=on_change
+                />
+                <button
+                    class="text-sm text-red-600 hover:text-red-800"
+                    class:hidden=move || car.get().image_data_url.is_none()
+                    on:click=move |_| set_car_wrapper(&|c| c.image_data_url = None)
+                >
+                    "Remove"
+                </button>
+            </div>
+            <p class="mt-1 text-xs text-gray-500">"Photos are shrunk to a small thumbnail to save browser storage."</p>
+            {move || error.get().map(|msg| view! { <p class="mt-1 text-sm text-red-600">{msg}</p> })}
+        </div>
+    }
+}
@@ -0,0 +1,255 @@
+use leptos::prelude::*;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{CanvasRenderingContext2d, HtmlAnchorElement, HtmlCanvasElement};
+
+use crate::data::format_dollars;
+use crate::models::ComputedCarData;
+
+const IMAGE_WIDTH: f64 = 640.0;
+const ROW_HEIGHT: f64 = 32.0;
+const PADDING: f64 = 24.0;
+
+/// Label/value pairs shown in the cost summary, in display order
+pub fn summary_rows(computed: &ComputedCarData) -> Vec<(&'static str, String)> {
+    let mut rows = vec![
+        (
+            "Total Cost of Ownership",
+            format_dollars(computed.total_cost_of_ownership, 2),
+        ),
+        (
+            "Total in Future Dollars",
+            format_dollars(computed.total_cost_nominal, 2),
+        ),
+        ("Annual Cost", format_dollars(computed.annual_cost, 2)),
+        (
+            "Annual Operating Cost",
+            format_dollars(computed.annual_operating_cost, 2),
+        ),
+        (
+            "Cost per Mile",
+            format!("{}/mi", format_dollars(computed.cost_per_mile, 2)),
+        ),
+        (
+            "Years Remaining",
+            format!("{:.1}", computed.years_remaining),
+        ),
+        (
+            "Remaining Miles",
+            format!("{:.0}", computed.remaining_miles),
+        ),
+        (
+            "Fuel Cost (Total)",
+            format_dollars(computed.fuel_cost_total, 2),
+        ),
+        (
+            "Fuel Cost (Annual)",
+            format_dollars(computed.fuel_cost_annual, 2),
+        ),
+        (
+            "Insurance (Annual)",
+            format_dollars(computed.insurance_cost_annual, 2),
+        ),
+        (
+            "Opportunity Cost",
+            format_dollars(computed.opportunity_cost, 2),
+        ),
+        (
+            "Resale Value",
+            format!(
+                "{} ({}, {})",
+                format_dollars(computed.resale_value, 2),
+                computed.resale_source_label(),
+                computed.resale_channel.label()
+            ),
+        ),
+        (
+            "Depreciation",
+            format_dollars(computed.depreciation_cost, 2),
+        ),
+        (
+            "Maintenance (Total)",
+            format_dollars(computed.maintenance_cost_total, 2),
+        ),
+        (
+            "Maintenance (Annual)",
+            format_dollars(computed.maintenance_cost_annual, 2),
+        ),
+    ];
+    if let Some(discounted) = computed.discounted_total_cost {
+        rows.push(("Discounted Total (NPV)", format_dollars(discounted, 2)));
+    }
+    if computed.sales_tax != 0.0 {
+        rows.push(("Sales Tax", format_dollars(computed.sales_tax, 2)));
+    }
+    if computed.registration_cost_total != 0.0 {
+        rows.push((
+            "Registration (Total)",
+            format_dollars(computed.registration_cost_total, 2),
+        ));
+    }
+    if computed.tire_cost_total != 0.0 {
+        rows.push(("Tires (Total)", format_dollars(computed.tire_cost_total, 2)));
+    }
+    if computed.trade_in_tax_credit != 0.0 {
+        rows.push((
+            "Trade-in Tax Credit",
+            format_dollars(computed.trade_in_tax_credit, 2),
+        ));
+    }
+    if computed.operands.current_mileage > 0.0 {
+        rows.push((
+            "Spent So Far",
+            format_dollars(computed.cost_incurred_to_date, 2),
+        ));
+        rows.push((
+            "Still to Spend",
+            format_dollars(computed.cost_projected_remaining, 2),
+        ));
+    }
+    if let Some(per_seat) = computed.cost_per_passenger_mile {
+        rows.push(("Cost per Passenger-Mile", format_dollars(per_seat, 3)));
+    }
+    if let Some(effective) = computed.effective_cash_price {
+        rows.push(("Effective Cash Price", format_dollars(effective, 2)));
+        rows.push((
+            "Loan Interest",
+            format!(
+                "{} ({}/mo)",
+                format_dollars(computed.total_interest_paid, 2),
+                format_dollars(computed.monthly_payment, 2)
+            ),
+        ));
+    }
+    if computed.has_extended_warranty() {
+        rows.push((
+            "Extended Warranty",
+            format!(
+                "{} (covers {} maintenance)",
+                format_dollars(computed.extended_warranty_cost, 2),
+                format_dollars(computed.warranty_covered_maintenance, 2)
+            ),
+        ));
+    }
+    rows
+}
+
+/// Plain-text version of the cost summary, suitable for pasting into a chat
+pub fn summary_text(car_name: &str, computed: &ComputedCarData) -> String {
+    let mut text = format!("{}\n", car_name);
+    for (label, value) in summary_rows(computed) {
+        text.push_str(&format!("{}: {}\n", label, value));
+    }
+    text
+}
+
+/// Draw the cost summary onto an offscreen canvas and return it as a PNG data URL. A
+/// browser that can't encode the canvas (e.g. it's too large) returns an empty `data:,`
+/// URL rather than failing, so that's treated as an error too.
+fn render_summary_png(car_name: &str, computed: &ComputedCarData) -> Result<String, JsValue> {
+    let rows = summary_rows(computed);
+    let height = PADDING * 2.0 + ROW_HEIGHT * (rows.len() as f64 + 1.5);
+
+    let canvas: HtmlCanvasElement = document().create_element("canvas")?.dyn_into()?;
+    canvas.set_width(IMAGE_WIDTH as u32);
+    canvas.set_height(height as u32);
+
+    let ctx: CanvasRenderingContext2d = canvas
+        .get_context("2d")?
+        .ok_or_else(|| JsValue::from_str("2d canvas context unavailable"))?
+        .dyn_into()?;
+
+    ctx.set_fill_style_str("#ffffff");
+    ctx.fill_rect(0.0, 0.0, IMAGE_WIDTH, height);
+
+    ctx.set_fill_style_str("#111827");
+    ctx.set_font("bold 22px sans-serif");
+    ctx.fill_text(car_name, PADDING, PADDING + 22.0)?;
+
+    let mut y = PADDING + ROW_HEIGHT * 1.5 + 16.0;
+    for (i, (label, value)) in rows.iter().enumerate() {
+        // The first two rows are the headline figures, matching the summary layout
+        let (font, colour) = if i < 2 {
+            ("bold 18px sans-serif", "#2563eb")
+        } else {
+            ("16px sans-serif", "#111827")
+        };
+        ctx.set_font(font);
+        ctx.set_fill_style_str("#4b5563");
+        ctx.set_text_align("left");
+        ctx.fill_text(label, PADDING, y)?;
+        ctx.set_fill_style_str(colour);
+        ctx.set_text_align("right");
+        ctx.fill_text(value, IMAGE_WIDTH - PADDING, y)?;
+        y += ROW_HEIGHT;
+    }
+
+    let url = canvas.to_data_url_with_type("image/png")?;
+    if url.is_empty() || url == "data:," {
+        return Err(JsValue::from_str("canvas produced no image data"));
+    }
+    Ok(url)
+}
+
+/// Trigger a browser download of the given URL
+fn download_url(url: &str, filename: &str) -> Result<(), JsValue> {
+    let anchor: HtmlAnchorElement = document().create_element("a")?.dyn_into()?;
+    anchor.set_href(url);
+    anchor.set_download(filename);
+    anchor.click();
+    Ok(())
+}
+
+/// Buttons that export the cost summary as a PNG image, falling back to copying it as text
+#[component]
+pub fn SummarySnapshotButton(car_name: String, computed: ComputedCarData) -> impl IntoView {
+    let (status, set_status) = signal::<Option<&'static str>>(None);
+    let text = summary_text(&car_name, &computed);
+
+    // Copies the text, reporting `done` only once the clipboard write has gone through
+    let copy_text = Action::new_local(move |done: &&'static str| {
+        let done = *done;
+        let write = window().navigator().clipboard().write_text(&text);
+        async move {
+            set_status.set(Some(match JsFuture::from(write).await {
+                Ok(_) => done,
+                Err(_) => "Couldn't copy the summary to the clipboard.",
+            }));
+        }
+    });
+
+    let export_image = move |_| {
+        let result = render_summary_png(&car_name, &computed)
+            .and_then(|url| download_url(&url, "carcalc-summary.png"));
+        match result {
+            Ok(()) => set_status.set(Some("Image downloaded.")),
+            Err(_) => {
+                copy_text.dispatch(
+                    "Image export isn't supported here, so the summary was copied as text instead.",
+                );
+            }
+        }
+    };
+
+    let copy_clicked = move |_| {
+        copy_text.dispatch("Summary copied to clipboard.");
+    };
+
+    view! {
+        <div class="flex items-center space-x-2">
+            <span class="text-xs text-gray-500">{move || status.get()}</span>
+            <button
+                class="inline-flex items-center px-3 py-1 border border-gray-300 text-xs font-medium rounded-md text-gray-700 bg-white hover:bg-gray-50"
+                on:click=copy_clicked
+            >
+                "Copy text"
+            </button>
+            <button
+                class="inline-flex items-center px-3 py-1 border border-gray-300 text-xs font-medium rounded-md text-gray-700 bg-white hover:bg-gray-50"
+                on:click=export_image
+            >
+                "Export image"
+            </button>
+        </div>
+    }
+}
@@ -0,0 +1,231 @@
+use leptos::prelude::*;
+
+use crate::data::format_thousands;
+use crate::models::ComputedCarData;
+
+const CHART_WIDTH: f64 = 600.0;
+const CHART_HEIGHT: f64 = 240.0;
+const MARGIN_LEFT: f64 = 56.0;
+const MARGIN_BOTTOM: f64 = 28.0;
+const MARGIN_TOP: f64 = 20.0;
+
+#[derive(Clone, Copy, PartialEq)]
+enum StepKind {
+    /// Bar from zero, e.g. the purchase price
+    Start,
+    /// Floating bar that adds to (or subtracts from) the running total
+    Change,
+    /// Bar from zero showing the running total so far
+    Total,
+}
+
+struct WaterfallStep {
+    label: &'static str,
+    value: f64,
+    kind: StepKind,
+}
+
+/// Steps from purchase price up to total cost of ownership. Resale (and any trade-in tax
+/// credit) is a credit, so it's a negative step.
+fn waterfall_steps(computed: &ComputedCarData) -> Vec<WaterfallStep> {
+    let insurance_total = computed.insurance_cost_annual * computed.years_remaining;
+    let purchase_price = computed.operands.purchase_price;
+
+    let step = |label, value, kind| WaterfallStep { label, value, kind };
+    let mut steps = vec![step("Purchase", purchase_price, StepKind::Start)];
+    if computed.sales_tax != 0.0 {
+        steps.push(step("Sales tax", computed.sales_tax, StepKind::Change));
+    }
+    if computed.extended_warranty_cost != 0.0 {
+        steps.push(step(
+            "Warranty",
+            computed.extended_warranty_cost,
+            StepKind::Change,
+        ));
+    }
+    if computed.total_interest_paid != 0.0 {
+        steps.push(step(
+            "Interest",
+            computed.total_interest_paid,
+            StepKind::Change,
+        ));
+    }
+    steps.extend([
+        step("Fuel", computed.fuel_cost_total, StepKind::Change),
+        step(
+            "Maintenance",
+            computed.maintenance_cost_total,
+            StepKind::Change,
+        ),
+    ]);
+    if computed.tire_cost_total != 0.0 {
+        steps.push(step("Tires", computed.tire_cost_total, StepKind::Change));
+    }
+    steps.push(step("Insurance", insurance_total, StepKind::Change));
+    if computed.registration_cost_total != 0.0 {
+        steps.push(step(
+            "Registration",
+            computed.registration_cost_total,
+            StepKind::Change,
+        ));
+    }
+    steps.extend([
+        step("Opportunity", computed.opportunity_cost, StepKind::Change),
+        step("Resale", -computed.resale_value, StepKind::Change),
+    ]);
+    if computed.trade_in_tax_credit != 0.0 {
+        steps.push(step(
+            "Tax credit",
+            -computed.trade_in_tax_credit,
+            StepKind::Change,
+        ));
+    }
+    steps.push(step(
+        "TCO",
+        computed.total_cost_of_ownership,
+        StepKind::Total,
+    ));
+    steps
+}
+
+/// Signed dollar label, e.g. "+$1,200" for changes and "$25,000" for totals (or "−$800"
+/// for a total that ends up a net profit)
+fn step_label(step: &WaterfallStep) -> String {
+    match step.kind {
+        _ if step.value < 0.0 => format!("−${}", format_thousands(-step.value)),
+        StepKind::Change => format!("+${}", format_thousands(step.value)),
+        _ => format!("${}", format_thousands(step.value)),
+    }
+}
+
+/// Waterfall chart building up from the purchase price to the total cost of ownership
+#[component]
+pub fn WaterfallChart(computed: ComputedCarData) -> impl IntoView {
+    let steps = waterfall_steps(&computed);
+
+    // Running total before and after each step; negative steps (e.g. a resale credit)
+    // can pull the total down, possibly below zero
+    let mut running = 0.0;
+    let spans: Vec<(f64, f64)> = steps
+        .iter()
+        .map(|step| {
+            let span = match step.kind {
+                StepKind::Start | StepKind::Total => (0.0, step.value),
+                StepKind::Change => (running, running + step.value),
+            };
+            running = span.1;
+            span
+        })
+        .collect();
+
+    let max_value = spans
+        .iter()
+        .map(|(a, b)| a.max(*b))
+        .fold(0.0_f64, f64::max)
+        .max(1.0);
+    let min_value = spans.iter().map(|(a, b)| a.min(*b)).fold(0.0_f64, f64::min);
+
+    let plot_width = CHART_WIDTH - MARGIN_LEFT;
+    let plot_height = CHART_HEIGHT - MARGIN_TOP - MARGIN_BOTTOM;
+    let bar_slot = plot_width / steps.len() as f64;
+    let bar_width = bar_slot * 0.6;
+    let y_of = move |v: f64| MARGIN_TOP + (max_value - v) / (max_value - min_value) * plot_height;
+    let zero_y = y_of(0.0);
+
+    let bars = steps
+        .iter()
+        .zip(spans.iter())
+        .enumerate()
+        .map(|(i, (step, &(from, to)))| {
+            let x = MARGIN_LEFT + i as f64 * bar_slot + (bar_slot - bar_width) / 2.0;
+            let top = y_of(from.max(to));
+            let height = (y_of(from.min(to)) - top).max(1.0);
+            let fill = match step.kind {
+                StepKind::Start => "fill-gray-400",
+                StepKind::Total => "fill-blue-600",
+                StepKind::Change if step.value < 0.0 => "fill-green-500",
+                StepKind::Change => "fill-red-400",
+            };
+            let label = step_label(step);
+            // Connector to the next bar at this step's ending total
+            let connector = (i + 1 < steps.len()).then(|| {
+                view! {
+                    <line
+                        x1=x + bar_width
+                        y1=y_of(to)
+                        x2=x + bar_slot
+                        y2=y_of(to)
+                        class="stroke-gray-400"
+                        stroke-dasharray="2 2"
+                    />
+                }
+            });
+            view! {
+                <g>
+                    <title>{format!("{}: {}", step.label, label)}</title>
+                    <rect x=x y=top width=bar_width height=height class=fill />
+                    <text
+                        x=x + bar_width / 2.0
+                        y=top - 4.0
+                        text-anchor="middle"
+                        class="fill-gray-700 text-[10px]"
+                    >
+                        {label}
+                    </text>
+                    <text
+                        x=x + bar_width / 2.0
+                        y=CHART_HEIGHT - 8.0
+                        text-anchor="middle"
+                        class="fill-gray-500 text-[10px]"
+                    >
+                        {step.label}
+                    </text>
+                    {connector}
+                </g>
+            }
+        })
+        .collect_view();
+
+    view! {
+        <div class="mt-6">
+            <h4 class="text-sm font-medium text-gray-900">"Cost Breakdown"</h4>
+            <p class="text-xs text-gray-500 mb-2">
+                "How the purchase price builds up to the total cost of ownership over the remaining miles, less what the car sells for at the end."
+            </p>
+            <svg
+                viewBox=format!("0 0 {} {}", CHART_WIDTH, CHART_HEIGHT)
+                class="w-full h-auto"
+                xmlns="http://www.w3.org/2000/svg"
+            >
+                <line x1=MARGIN_LEFT y1=MARGIN_TOP x2=MARGIN_LEFT y2=MARGIN_TOP + plot_height class="stroke-gray-300" />
+                <line x1=MARGIN_LEFT y1=zero_y x2=CHART_WIDTH y2=zero_y class="stroke-gray-300" />
+                <text x=MARGIN_LEFT - 6.0 y=MARGIN_TOP + 10.0 text-anchor="end" class="fill-gray-500 text-[10px]">
+                    {format!("${}", format_thousands(max_value))}
+                </text>
+                <text x=MARGIN_LEFT - 6.0 y=zero_y text-anchor="end" class="fill-gray-500 text-[10px]">
+                    "$0"
+                </text>
+                {(min_value < 0.0).then(|| view! {
+                    <text x=MARGIN_LEFT - 6.0 y=MARGIN_TOP + plot_height text-anchor="end" class="fill-gray-500 text-[10px]">
+                        {format!("−${}", format_thousands(-min_value))}
+                    </text>
+                })}
+                {bars}
+            </svg>
+            <div class="mt-2 flex flex-wrap items-center gap-4 text-xs text-gray-600">
+                <span class="inline-flex items-center">
+                    <span class="inline-block w-3 h-3 mr-1 bg-red-400"></span>
+                    "Adds cost"
+                </span>
+                <span class="inline-flex items-center">
+                    <span class="inline-block w-3 h-3 mr-1 bg-green-500"></span>
+                    "Reduces cost"
+                </span>
+                <span class="inline-flex items-center">
+                    <span class="inline-block w-3 h-3 mr-1 bg-blue-600"></span>
+                    "Total"
+                </span>
+            </div>
+        </div>
+    }
+}
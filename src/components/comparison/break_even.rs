@@ -0,0 +1,201 @@
+use leptos::prelude::*;
+
+use crate::calculations::{
+    break_even_mileage, BreakEvenOutcome, BreakEvenPoint, BREAK_EVEN_MAX_MILES,
+    BREAK_EVEN_MIN_MILES,
+};
+use crate::data::format_thousands;
+use crate::models::{Car, MaintenanceCostDatabase, SharedSettings};
+
+const CHART_WIDTH: f64 = 600.0;
+const CHART_HEIGHT: f64 = 220.0;
+const MARGIN_LEFT: f64 = 56.0;
+const MARGIN_BOTTOM: f64 = 28.0;
+const MARGIN_TOP: f64 = 12.0;
+
+/// Pick an EV and a gas car and find the annual mileage where the EV's higher price
+/// pays off
+#[component]
+pub fn BreakEvenComparison(
+    cars: Signal<Vec<Car>>,
+    settings: Signal<SharedSettings>,
+    maintenance_db: Signal<MaintenanceCostDatabase>,
+) -> impl IntoView {
+    let (ev_id, set_ev_id) = signal(None::<usize>);
+    let (gas_id, set_gas_id) = signal(None::<usize>);
+
+    let result = Memo::new(move |_| {
+        let (ev_id, gas_id) = (ev_id.get()?, gas_id.get()?);
+        cars.with(|cars| {
+            let ev = cars.iter().find(|c| c.id == ev_id)?;
+            let gas = cars.iter().find(|c| c.id == gas_id)?;
+            settings.with(|settings| {
+                maintenance_db.with(|db| break_even_mileage(ev, gas, settings, db))
+            })
+        })
+    });
+
+    let car_select = move |id: &'static str,
+                           label: &'static str,
+                           value: ReadSignal<Option<usize>>,
+                           set_value: WriteSignal<Option<usize>>| {
+        view! {
+            <div>
+                <label for=id class="block text-sm font-medium text-gray-700">{label}</label>
+                <select
+                    id=id
+                    class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                    prop:value=move || value.get().map(|id| id.to_string()).unwrap_or_default()
+                    on:change=move |ev| set_value.set(event_target_value(&ev).parse::<usize>().ok())
+                >
+                    <option value="">"Choose a car"</option>
+                    {move || {
+                        cars.get()
+                            .into_iter()
+                            .map(|car| view! { <option value=car.id.to_string()>{car.display_name()}</option> })
+                            .collect_view()
+                    }}
+                </select>
+            </div>
+        }
+    };
+
+    view! {
+        <div class="bg-white overflow-hidden shadow rounded-lg">
+            <div class="px-4 py-5 sm:p-6">
+                <h2 class="text-xl font-semibold text-gray-900">"EV vs Gas: Break-even Mileage"</h2>
+                <p class="mt-1 text-sm text-gray-600">
+                    "At what annual mileage does the EV's higher price pay off? Both cars' annual costs are recalculated from "
+                    {format!("{} to {} miles a year", format_thousands(BREAK_EVEN_MIN_MILES), format_thousands(BREAK_EVEN_MAX_MILES))}
+                    ", keeping every other setting."
+                </p>
+                <div class="mt-4 grid grid-cols-1 gap-4 sm:grid-cols-2">
+                    {car_select("break-even-ev", "Electric car", ev_id, set_ev_id)}
+                    {car_select("break-even-gas", "Gas car", gas_id, set_gas_id)}
+                </div>
+
+                {move || {
+                    let chosen = ev_id.get().is_some() && gas_id.get().is_some();
+                    match result.get() {
+                        None if chosen => Some(view! {
+                            <p class="mt-3 text-sm text-amber-700">
+                                "Both cars need complete details to compare them."
+                            </p>
+                        }.into_any()),
+                        None => None,
+                        Some(break_even) => {
+                            let verdict = match break_even.outcome {
+                                BreakEvenOutcome::Crossover { annual_mileage, ev_cheaper_above: true } => format!(
+                                    "The EV pays off above {} miles a year; below that the gas car is cheaper.",
+                                    format_thousands(annual_mileage)
+                                ),
+                                BreakEvenOutcome::Crossover { annual_mileage, ev_cheaper_above: false } => format!(
+                                    "The EV is cheaper below {} miles a year; above that the gas car is cheaper.",
+                                    format_thousands(annual_mileage)
+                                ),
+                                BreakEvenOutcome::EvAlwaysCheaper => {
+                                    "No break-even: the EV is cheaper at every mileage.".to_string()
+                                }
+                                BreakEvenOutcome::EvAlwaysMoreExpensive => {
+                                    "No break-even: the EV never pays off, costing more at every mileage.".to_string()
+                                }
+                            };
+                            Some(view! {
+                                <div class="mt-4">
+                                    <p class="text-sm font-medium text-gray-900" role="status">{verdict}</p>
+                                    <BreakEvenChart
+                                        points=break_even.points.clone()
+                                        crossover=break_even.crossover_mileage()
+                                    />
+                                </div>
+                            }.into_any())
+                        }
+                    }
+                }}
+            </div>
+        </div>
+    }
+}
+
+/// Annual cost of each car against annual mileage, with the crossover marked
+#[component]
+fn BreakEvenChart(points: Vec<BreakEvenPoint>, crossover: Option<f64>) -> impl IntoView {
+    let max_cost = points
+        .iter()
+        .map(|p| p.ev_annual.max(p.gas_annual))
+        .fold(0.0_f64, f64::max)
+        .max(1.0);
+    let min_cost = points
+        .iter()
+        .map(|p| p.ev_annual.min(p.gas_annual))
+        .fold(0.0_f64, f64::min);
+
+    let plot_width = CHART_WIDTH - MARGIN_LEFT;
+    let plot_height = CHART_HEIGHT - MARGIN_TOP - MARGIN_BOTTOM;
+    let x_of = move |miles: f64| {
+        MARGIN_LEFT
+            + (miles - BREAK_EVEN_MIN_MILES) / (BREAK_EVEN_MAX_MILES - BREAK_EVEN_MIN_MILES)
+                * plot_width
+    };
+    let y_of =
+        move |cost: f64| MARGIN_TOP + (max_cost - cost) / (max_cost - min_cost) * plot_height;
+    let line = |cost: fn(&BreakEvenPoint) -> f64| {
+        points
+            .iter()
+            .map(|p| format!("{:.1},{:.1}", x_of(p.annual_mileage), y_of(cost(p))))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    let ev_line = line(|p| p.ev_annual);
+    let gas_line = line(|p| p.gas_annual);
+
+    view! {
+        <svg
+            viewBox=format!("0 0 {} {}", CHART_WIDTH, CHART_HEIGHT)
+            class="mt-3 w-full h-auto"
+            xmlns="http://www.w3.org/2000/svg"
+            role="img"
+            aria-label="Annual cost of each car by annual mileage"
+        >
+            <line x1=MARGIN_LEFT y1=MARGIN_TOP x2=MARGIN_LEFT y2=MARGIN_TOP + plot_height class="stroke-gray-300" />
+            <line x1=MARGIN_LEFT y1=MARGIN_TOP + plot_height x2=CHART_WIDTH y2=MARGIN_TOP + plot_height class="stroke-gray-300" />
+            <text x=MARGIN_LEFT - 6.0 y=MARGIN_TOP + 10.0 text-anchor="end" class="fill-gray-500 text-[10px]">
+                {format!("${}/yr", format_thousands(max_cost))}
+            </text>
+            <text x=MARGIN_LEFT - 6.0 y=MARGIN_TOP + plot_height text-anchor="end" class="fill-gray-500 text-[10px]">
+                {format!("${}/yr", format_thousands(min_cost))}
+            </text>
+            <text x=MARGIN_LEFT y=CHART_HEIGHT - 8.0 class="fill-gray-500 text-[10px]">
+                {format!("{} mi/yr", format_thousands(BREAK_EVEN_MIN_MILES))}
+            </text>
+            <text x=CHART_WIDTH y=CHART_HEIGHT - 8.0 text-anchor="end" class="fill-gray-500 text-[10px]">
+                {format!("{} mi/yr", format_thousands(BREAK_EVEN_MAX_MILES))}
+            </text>
+            {crossover.map(|miles| view! {
+                <line
+                    x1=x_of(miles)
+                    y1=MARGIN_TOP
+                    x2=x_of(miles)
+                    y2=MARGIN_TOP + plot_height
+                    class="stroke-gray-400"
+                    stroke-dasharray="3 3"
+                />
+                <text x=x_of(miles) y=CHART_HEIGHT - 8.0 text-anchor="middle" class="fill-gray-700 text-[10px]">
+                    {format!("{} mi/yr", format_thousands(miles))}
+                </text>
+            })}
+            <polyline points=ev_line fill="none" class="stroke-green-600" stroke-width="2" />
+            <polyline points=gas_line fill="none" class="stroke-red-500" stroke-width="2" />
+        </svg>
+        <div class="mt-2 flex flex-wrap items-center gap-4 text-xs text-gray-600">
+            <span class="inline-flex items-center">
+                <span class="inline-block w-3 h-0.5 mr-1 bg-green-600"></span>
+                "Electric car"
+            </span>
+            <span class="inline-flex items-center">
+                <span class="inline-block w-3 h-0.5 mr-1 bg-red-500"></span>
+                "Gas car"
+            </span>
+        </div>
+    }
+}
@@ -0,0 +1,113 @@
+use leptos::prelude::*;
+
+use crate::calculations::{cost_crossover, CrossoverOutcome};
+use crate::data::format_thousands;
+use crate::models::{Car, MaintenanceCostDatabase, SharedSettings};
+
+/// Pick two cars and find how many miles it takes for the pricier one's lower running
+/// costs to catch up with the cheaper one
+#[component]
+pub fn CostCrossoverComparison(
+    cars: Signal<Vec<Car>>,
+    settings: Signal<SharedSettings>,
+    maintenance_db: Signal<MaintenanceCostDatabase>,
+) -> impl IntoView {
+    let (first_id, set_first_id) = signal(None::<usize>);
+    let (second_id, set_second_id) = signal(None::<usize>);
+
+    let result = Memo::new(move |_| {
+        let (first_id, second_id) = (first_id.get()?, second_id.get()?);
+        cars.with(|cars| {
+            let first = cars.iter().find(|c| c.id == first_id)?;
+            let second = cars.iter().find(|c| c.id == second_id)?;
+            settings.with(|settings| {
+                maintenance_db.with(|db| cost_crossover(first, second, settings, db))
+            })
+        })
+    });
+
+    let name_of = move |id: usize| {
+        cars.with(|cars| {
+            cars.iter()
+                .find(|c| c.id == id)
+                .map(|c| c.display_name())
+                .unwrap_or_default()
+        })
+    };
+
+    let car_select = move |id: &'static str,
+                           label: &'static str,
+                           value: ReadSignal<Option<usize>>,
+                           set_value: WriteSignal<Option<usize>>| {
+        view! {
+            <div>
+                <label for=id class="block text-sm font-medium text-gray-700">{label}</label>
+                <select
+                    id=id
+                    class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                    prop:value=move || value.get().map(|id| id.to_string()).unwrap_or_default()
+                    on:change=move |ev| set_value.set(event_target_value(&ev).parse::<usize>().ok())
+                >
+                    <option value="">"Choose a car"</option>
+                    {move || {
+                        cars.get()
+                            .into_iter()
+                            .map(|car| view! { <option value=car.id.to_string()>{car.display_name()}</option> })
+                            .collect_view()
+                    }}
+                </select>
+            </div>
+        }
+    };
+
+    view! {
+        <div class="bg-white overflow-hidden shadow rounded-lg">
+            <div class="px-4 py-5 sm:p-6">
+                <h2 class="text-xl font-semibold text-gray-900">"Break-even Miles Between Two Cars"</h2>
+                <p class="mt-1 text-sm text-gray-600">
+                    "How far you'd drive before the pricier car's lower running costs make up the difference. Spending counts the purchase (with any sales tax and warranty), then fuel, maintenance, insurance and the opportunity cost as the miles add up, before either car is sold."
+                </p>
+                <div class="mt-4 grid grid-cols-1 gap-4 sm:grid-cols-2">
+                    {car_select("crossover-first", "First car", first_id, set_first_id)}
+                    {car_select("crossover-second", "Second car", second_id, set_second_id)}
+                </div>
+
+                {move || {
+                    let (Some(first), Some(second)) = (first_id.get(), second_id.get()) else {
+                        return None;
+                    };
+                    if first == second {
+                        return Some(view! {
+                            <p class="mt-3 text-sm text-amber-700">"Choose two different cars."</p>
+                        }.into_any());
+                    }
+                    let Some(crossover) = result.get() else {
+                        return Some(view! {
+                            <p class="mt-3 text-sm text-amber-700">
+                                "Both cars need complete details to compare them."
+                            </p>
+                        }.into_any());
+                    };
+                    let annual_mileage = settings.with(|s| s.annual_mileage);
+                    let verdict = match crossover.outcome {
+                        CrossoverOutcome::Crossover { miles, cheaper_after } => format!(
+                            "{} becomes cheaper after {} more miles, about {:.1} years at {} miles a year.",
+                            name_of(cheaper_after),
+                            format_thousands(miles),
+                            miles / annual_mileage,
+                            format_thousands(annual_mileage)
+                        ),
+                        CrossoverOutcome::AlwaysCheaper(id) => format!(
+                            "No break-even: {} costs less the whole way, over the {} miles both cars are kept.",
+                            name_of(id),
+                            format_thousands(crossover.horizon_miles)
+                        ),
+                    };
+                    Some(view! {
+                        <p class="mt-4 text-sm font-medium text-gray-900" role="status">{verdict}</p>
+                    }.into_any())
+                }}
+            </div>
+        </div>
+    }
+}
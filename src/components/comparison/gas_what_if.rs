@@ -0,0 +1,165 @@
+use leptos::prelude::*;
+
+use crate::calculations::compute_car_data;
+use crate::data::format_thousands;
+use crate::models::{Car, MaintenanceCostDatabase, SharedSettings};
+
+/// Range offered by the slider, in dollars per gallon
+const MIN_GAS_PRICE: f64 = 1.0;
+const MAX_GAS_PRICE: f64 = 10.0;
+
+/// Slider that tries out another gas price on every card and comparison without touching
+/// the saved setting, with each car's total and annual cost at both prices
+#[component]
+pub fn GasPriceWhatIf(
+    cars: Signal<Vec<Car>>,
+    /// The saved settings, used as the baseline
+    settings: Signal<SharedSettings>,
+    maintenance_db: Signal<MaintenanceCostDatabase>,
+    /// Gas price the rest of the page is computed with instead of the setting, if any
+    gas_price_override: RwSignal<Option<f64>>,
+) -> impl IntoView {
+    let baseline_price = move || settings.with(|s| s.average_gas_price);
+    let shown_price = move || gas_price_override.get().unwrap_or_else(baseline_price);
+
+    // (name, baseline total, what-if total, baseline annual, what-if annual) per car
+    let rows = Memo::new(move |_| {
+        let price = gas_price_override.get()?;
+        let baseline = settings.get();
+        let what_if = SharedSettings {
+            average_gas_price: price,
+            ..baseline.clone()
+        };
+        let rows = cars.with(|cars| {
+            maintenance_db.with(|db| {
+                cars.iter()
+                    .filter(|car| car.in_comparison)
+                    .filter_map(|car| {
+                        let before = compute_car_data(car, &baseline, db)?;
+                        let after = compute_car_data(car, &what_if, db)?;
+                        Some((
+                            car.display_name(),
+                            before.total_cost_of_ownership,
+                            after.total_cost_of_ownership,
+                            before.annual_cost,
+                            after.annual_cost,
+                        ))
+                    })
+                    .collect::<Vec<_>>()
+            })
+        });
+        Some(rows)
+    });
+
+    // Cheapest car by total at each price, to show when the winner changes
+    let cheapest = move |rows: &[(String, f64, f64, f64, f64)], at_what_if: bool| {
+        rows.iter()
+            .min_by(|a, b| {
+                let (a, b) = if at_what_if { (a.2, b.2) } else { (a.1, b.1) };
+                a.total_cmp(&b)
+            })
+            .map(|row| row.0.clone())
+    };
+
+    view! {
+        <div class="bg-white overflow-hidden shadow rounded-lg">
+            <div class="px-4 py-5 sm:p-6">
+                <h2 class="text-xl font-semibold text-gray-900">"What If Gas Prices Change?"</h2>
+                <p class="mt-1 text-sm text-gray-600">
+                    "Try another gas price across every car and comparison below. Your saved setting isn't changed."
+                </p>
+                <div class="mt-4 flex flex-wrap items-center gap-4">
+                    <label for="gas-what-if" class="text-sm font-medium text-gray-700">
+                        {move || format!("Gas at ${:.2}/gal", shown_price())}
+                    </label>
+                    <input
+                        id="gas-what-if"
+                        type="range"
+                        min=MIN_GAS_PRICE
+                        max=MAX_GAS_PRICE
+                        step="0.05"
+                        class="flex-1 min-w-48"
+                        prop:value=move || shown_price().to_string()
+                        on:input=move |ev| {
+                            if let Ok(price) = event_target_value(&ev).parse::<f64>() {
+                                gas_price_override.set(Some(price));
+                            }
+                        }
+                    />
+                    <button
+                        class="px-3 py-1.5 text-sm rounded-md border border-gray-300 text-gray-700 bg-white hover:bg-gray-50 disabled:opacity-50"
+                        prop:disabled=move || gas_price_override.get().is_none()
+                        on:click=move |_| gas_price_override.set(None)
+                    >
+                        {move || format!("Back to ${:.2}", baseline_price())}
+                    </button>
+                </div>
+
+                {move || rows.get().map(|rows| {
+                    if rows.is_empty() {
+                        return view! {
+                            <p class="mt-3 text-sm text-amber-700">"No car in the comparison can be calculated yet."</p>
+                        }
+                        .into_any();
+                    }
+                    let price = shown_price();
+                    let winner_before = cheapest(&rows, false);
+                    let winner_after = cheapest(&rows, true);
+                    let verdict = match (&winner_before, &winner_after) {
+                        (Some(before), Some(after)) if rows.len() >= 2 && before != after => Some(format!(
+                            "At ${:.2}/gal, {} becomes the cheapest car instead of {}.",
+                            price, after, before
+                        )),
+                        (_, Some(after)) if rows.len() >= 2 => Some(format!(
+                            "{} stays the cheapest car at ${:.2}/gal.",
+                            after, price
+                        )),
+                        _ => None,
+                    };
+                    view! {
+                        <table class="mt-4 min-w-full text-sm">
+                            <thead>
+                                <tr class="text-left text-xs text-gray-500 uppercase tracking-wide">
+                                    <th class="py-1 pr-4">"Car"</th>
+                                    <th class="py-1 pr-4 text-right">{format!("Total at ${:.2}", baseline_price())}</th>
+                                    <th class="py-1 pr-4 text-right">{format!("Total at ${:.2}", price)}</th>
+                                    <th class="py-1 text-right">"Annual change"</th>
+                                </tr>
+                            </thead>
+                            <tbody>
+                                {rows.into_iter().map(|(name, total_before, total_after, annual_before, annual_after)| {
+                                    let change = annual_after - annual_before;
+                                    let change_class = if change > 0.0 {
+                                        "py-1 text-right text-red-700"
+                                    } else if change < 0.0 {
+                                        "py-1 text-right text-green-700"
+                                    } else {
+                                        "py-1 text-right text-gray-500"
+                                    };
+                                    view! {
+                                        <tr class="border-t border-gray-100">
+                                            <td class="py-1 pr-4 text-gray-900">{name}</td>
+                                            <td class="py-1 pr-4 text-right text-gray-600">{format!("${}", format_thousands(total_before))}</td>
+                                            <td class="py-1 pr-4 text-right text-gray-900">{format!("${}", format_thousands(total_after))}</td>
+                                            <td class=change_class>
+                                                {format!(
+                                                    "{}${}/yr",
+                                                    if change < -0.5 { "−" } else if change > 0.5 { "+" } else { "" },
+                                                    format_thousands(change)
+                                                )}
+                                            </td>
+                                        </tr>
+                                    }
+                                }).collect_view()}
+                            </tbody>
+                        </table>
+                        {verdict.map(|verdict| view! {
+                            <p class="mt-3 text-sm font-medium text-gray-900" role="status">{verdict}</p>
+                        })}
+                    }
+                    .into_any()
+                })}
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,137 @@
+use leptos::prelude::*;
+
+use crate::calculations::household_replacement;
+use crate::models::{Car, MaintenanceCostDatabase, SharedSettings};
+
+fn signed_money(value: f64) -> String {
+    if value < 0.0 {
+        format!("−${:.0}", -value)
+    } else {
+        format!("+${:.0}", value)
+    }
+}
+
+/// Household mode: pick the owned car being sold and see the net cost of replacing it
+/// with each other car
+#[component]
+pub fn HouseholdComparison(
+    cars: Signal<Vec<Car>>,
+    settings: Signal<SharedSettings>,
+    set_settings: WriteSignal<SharedSettings>,
+    maintenance_db: Signal<MaintenanceCostDatabase>,
+) -> impl IntoView {
+    let replacement = Memo::new(move |_| {
+        cars.with(|cars| {
+            settings.with(|settings| {
+                maintenance_db.with(|db| household_replacement(cars, settings, db))
+            })
+        })
+    });
+
+    view! {
+        <div class="bg-white overflow-hidden shadow rounded-lg">
+            <div class="px-4 py-5 sm:p-6">
+                <h2 class="text-xl font-semibold text-gray-900">"Household: Replace a Car"</h2>
+                <p class="mt-1 text-sm text-gray-600">
+                    "Mark a car you own and plan to sell. Its purchase price is read as what it's worth today, and selling it is credited against each candidate."
+                </p>
+                <label for="selling-car" class="mt-4 block text-sm font-medium text-gray-700">
+                    "Currently owned (selling)"
+                </label>
+                <select
+                    id="selling-car"
+                    class="mt-1 block w-full sm:w-80 rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                    prop:value=move || settings.get().selling_car_id.map(|id| id.to_string()).unwrap_or_default()
+                    on:change=move |ev| {
+                        let id = event_target_value(&ev).parse::<usize>().ok();
+                        set_settings.update(|s| s.selling_car_id = id);
+                    }
+                >
+                    <option value="">"None (household mode off)"</option>
+                    {move || {
+                        cars.get()
+                            .into_iter()
+                            .map(|car| view! { <option value=car.id.to_string()>{car.display_name()}</option> })
+                            .collect_view()
+                    }}
+                </select>
+
+                {move || {
+                    let selling_id = settings.with(|s| s.selling_car_id);
+                    let is_marked = cars.with(|cars| cars.iter().any(|c| Some(c.id) == selling_id));
+                    match replacement.get() {
+                        None if is_marked => Some(view! {
+                            <p class="mt-3 text-sm text-amber-700">
+                                "Enter the selling car's purchase price (its value today) to see household costs."
+                            </p>
+                        }.into_any()),
+                        None => None,
+                        Some(household) => {
+                            let credit_detail = if household.sale_tax_credit > 0.0 {
+                                format!(
+                                    "${:.0} sale + ${:.0} trade-in tax credit",
+                                    household.sale_value, household.sale_tax_credit
+                                )
+                            } else {
+                                format!("${:.0} sale", household.sale_value)
+                            };
+                            let has_keep = household.keep_annual.is_some();
+                            let rows = household
+                                .options
+                                .iter()
+                                .map(|option| {
+                                    let change = option.change_vs_keeping_annual;
+                                    view! {
+                                        <tr class="border-t border-gray-100">
+                                            <td class="py-2 pr-3 text-gray-900">{option.name.clone()}</td>
+                                            <td class="py-2 pr-3 text-right">{format!("${:.0}", option.candidate_tco)}</td>
+                                            <td class="py-2 pr-3 text-right font-semibold">{format!("${:.0}", option.net_cost)}</td>
+                                            <td class="py-2 pr-3 text-right">{format!("${:.0}/yr", option.net_annual)}</td>
+                                            {has_keep.then(|| view! {
+                                                <td class=move || format!(
+                                                    "py-2 text-right {}",
+                                                    if change.unwrap_or(0.0) > 0.0 { "text-red-700" } else { "text-green-700" }
+                                                )>
+                                                    {change.map(|c| format!("{}/yr", signed_money(c)))}
+                                                </td>
+                                            })}
+                                        </tr>
+                                    }
+                                })
+                                .collect_view();
+                            Some(view! {
+                                <div class="mt-4">
+                                    <p class="text-sm text-gray-700">
+                                        <span class="inline-block px-2 py-0.5 mr-2 rounded text-xs font-medium bg-amber-100 text-amber-800">
+                                            "Owned · selling"
+                                        </span>
+                                        <span class="font-medium">{household.selling_name.clone()}</span>
+                                        {format!(" credits ${:.0} ({})", household.sale_credit(), credit_detail)}
+                                        {household.keep_annual.map(|keep| format!("; keeping it costs ${:.0}/yr", keep))}
+                                    </p>
+                                    <div class="mt-3 overflow-x-auto">
+                                        <table class="min-w-full text-sm">
+                                            <thead>
+                                                <tr class="text-xs text-gray-500 uppercase">
+                                                    <th class="py-1 pr-3 text-left">"Candidate"</th>
+                                                    <th class="py-1 pr-3 text-right">"Candidate TCO"</th>
+                                                    <th class="py-1 pr-3 text-right">"Net household cost"</th>
+                                                    <th class="py-1 pr-3 text-right">"Net per year"</th>
+                                                    {has_keep.then(|| view! { <th class="py-1 text-right">"vs keeping"</th> })}
+                                                </tr>
+                                            </thead>
+                                            <tbody>{rows}</tbody>
+                                        </table>
+                                    </div>
+                                    {household.options.is_empty().then(|| view! {
+                                        <p class="mt-2 text-sm text-gray-500">"No other computable cars to compare as replacements."</p>
+                                    })}
+                                </div>
+                            }.into_any())
+                        }
+                    }
+                }}
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,11 @@
+mod break_even;
+mod crossover;
+mod gas_what_if;
+mod household;
+mod table;
+
+pub use break_even::BreakEvenComparison;
+pub use crossover::CostCrossoverComparison;
+pub use gas_what_if::GasPriceWhatIf;
+pub use household::HouseholdComparison;
+pub use table::ComparisonTable;
@@ -0,0 +1,279 @@
+use leptos::prelude::*;
+use wasm_bindgen::JsCast;
+
+use crate::calculations::{category_winners, compute_car_data, fleet_total};
+use crate::data::format_dollars;
+use crate::models::{Car, ComputedCarData, MaintenanceCostDatabase, SharedSettings};
+
+/// A compared cost category: label, how to read it, and how to format it
+type ComparisonRow = (&'static str, fn(&ComputedCarData) -> f64);
+
+const ROWS: &[ComparisonRow] = &[
+    ("Total Cost of Ownership", |c| c.total_cost_of_ownership),
+    ("Annual Cost", |c| c.annual_cost),
+    ("Annual Operating Cost", |c| c.annual_operating_cost),
+    ("Fuel (Total)", |c| c.fuel_cost_total),
+    ("Maintenance (Total)", |c| c.maintenance_cost_total),
+    ("Insurance (Annual)", |c| c.insurance_cost_annual),
+    ("Opportunity Cost", |c| c.opportunity_cost),
+    ("Depreciation", |c| c.depreciation_cost),
+    ("Loan Interest", |c| c.total_interest_paid),
+];
+
+fn cell_id(row: usize, col: usize) -> String {
+    format!("comparison-cell-{}-{}", row, col)
+}
+
+/// Move keyboard focus to a table cell
+fn focus_cell(row: usize, col: usize) {
+    if let Some(el) = document()
+        .get_element_by_id(&cell_id(row, col))
+        .and_then(|el| el.dyn_into::<web_sys::HtmlElement>().ok())
+    {
+        let _ = el.focus();
+    }
+}
+
+/// Side-by-side cost comparison of every car, one column per car
+#[component]
+pub fn ComparisonTable(
+    cars: Signal<Vec<Car>>,
+    settings: Signal<SharedSettings>,
+    maintenance_db: Signal<MaintenanceCostDatabase>,
+) -> impl IntoView {
+    let columns = Memo::new(move |_| {
+        let settings = settings.get();
+        let db = maintenance_db.get();
+        cars.get()
+            .iter()
+            .filter(|car| car.in_comparison)
+            .map(|car| {
+                let mut name = car.display_name();
+                if settings.selling_car_id == Some(car.id) {
+                    name.push_str(" (selling)");
+                }
+                (name, compute_car_data(car, &settings, &db))
+            })
+            .collect::<Vec<_>>()
+    });
+    // Each column's tag color, in the same order as `columns`
+    let colors = Memo::new(move |_| {
+        cars.with(|cars| {
+            cars.iter()
+                .filter(|car| car.in_comparison)
+                .map(|car| car.tag_color())
+                .collect::<Vec<_>>()
+        })
+    });
+
+    // Roving focus: only the active cell is in the tab order
+    let (active, set_active) = signal((0_usize, 0_usize));
+
+    let on_keydown = move |ev: leptos::ev::KeyboardEvent| {
+        let (row, col) = active.get_untracked();
+        let last_row = ROWS.len() - 1;
+        let last_col = columns.with_untracked(|c| c.len());
+        let next = match ev.key().as_str() {
+            "ArrowUp" => (row.saturating_sub(1), col),
+            "ArrowDown" => ((row + 1).min(last_row), col),
+            "ArrowLeft" => (row, col.saturating_sub(1)),
+            "ArrowRight" => (row, (col + 1).min(last_col)),
+            "Home" => (row, 0),
+            "End" => (row, last_col),
+            _ => return,
+        };
+        ev.prevent_default();
+        set_active.set(next);
+        focus_cell(next.0, next.1);
+    };
+
+    view! {
+        <div class="bg-white overflow-hidden shadow rounded-lg">
+            <div class="px-4 py-5 sm:p-6">
+                <h2 class="text-xl font-semibold text-gray-900">"Comparison"</h2>
+                <p class="mt-1 text-sm text-gray-600">
+                    "Use the arrow keys to move between cells. The cheapest value in each row is highlighted."
+                </p>
+                <div class="mt-4 overflow-x-auto">
+                    <table
+                        role="grid"
+                        aria-label="Cost comparison"
+                        class="min-w-full text-sm border-separate border-spacing-0"
+                        on:keydown=on_keydown
+                    >
+                        <thead>
+                            <tr>
+                                <th scope="col" class="sticky left-0 z-20 bg-white px-3 py-2 text-left font-medium text-gray-500 border-b border-r border-gray-200">
+                                    "Cost"
+                                </th>
+                                {move || {
+                                    columns
+                                        .get()
+                                        .into_iter()
+                                        .zip(colors.get())
+                                        .map(|((name, computed), color)| {
+                                            let profit = computed.as_ref().is_some_and(|c| c.has_net_profit());
+                                            view! {
+                                                <th scope="col" class="px-3 py-2 text-right font-medium text-gray-900 whitespace-nowrap border-b border-gray-200">
+                                                    {color.map(|color| view! {
+                                                        <span
+                                                            class="inline-block h-2.5 w-2.5 mr-1.5 rounded-full align-middle"
+                                                            style=format!("background-color: {}", color)
+                                                            aria-hidden="true"
+                                                        ></span>
+                                                    })}
+                                                    {name}
+                                                    {profit.then(|| view! {
+                                                        <span
+                                                            class="ml-1 text-amber-600"
+                                                            title="Net cost is a profit, so this car's resale assumptions look too high. It isn't picked as best."
+                                                        >
+                                                            "⚠"
+                                                        </span>
+                                                    })}
+                                                </th>
+                                            }
+                                        })
+                                        .collect_view()
+                                }}
+                            </tr>
+                        </thead>
+                        <tbody>
+                            {move || {
+                                let columns = columns.get();
+                                ROWS.iter()
+                                    .enumerate()
+                                    .map(|(r, (label, value))| {
+                                        let values: Vec<Option<f64>> = columns
+                                            .iter()
+                                            .map(|(_, c)| c.as_ref().map(value))
+                                            .collect();
+                                        // A car that comes out as a net profit has suspect inputs,
+                                        // so it can't be the best in any row
+                                        let eligible: Vec<Option<f64>> = columns
+                                            .iter()
+                                            .zip(&values)
+                                            .map(|((_, c), v)| v.filter(|_| !c.as_ref().is_some_and(|c| c.has_net_profit())))
+                                            .collect();
+                                        let best = eligible
+                                            .iter()
+                                            .flatten()
+                                            .copied()
+                                            .fold(None, |acc: Option<f64>, v| Some(acc.map_or(v, |a| a.min(v))));
+                                        let best_names = columns
+                                            .iter()
+                                            .zip(&eligible)
+                                            .filter(|(_, v)| v.is_some() && *v == &best)
+                                            .map(|((name, _), _)| name.clone())
+                                            .collect::<Vec<_>>()
+                                            .join(", ");
+                                        let tabindex = move |c: usize| if active.get() == (r, c) { 0 } else { -1 };
+
+                                        let cells = values
+                                            .into_iter()
+                                            .enumerate()
+                                            .map(|(i, v)| {
+                                                let col = i + 1;
+                                                let is_best = eligible[i].is_some() && eligible[i] == best;
+                                                view! {
+                                                    <td
+                                                        id=cell_id(r, col)
+                                                        role="gridcell"
+                                                        tabindex=move || tabindex(col)
+                                                        on:focus=move |_| set_active.set((r, col))
+                                                        class=if is_best {
+                                                            "px-3 py-2 text-right whitespace-nowrap border-b border-gray-100 bg-green-50 text-green-700 font-semibold focus:outline-none focus:ring-2 focus:ring-inset focus:ring-blue-500"
+                                                        } else {
+                                                            "px-3 py-2 text-right whitespace-nowrap border-b border-gray-100 text-gray-900 focus:outline-none focus:ring-2 focus:ring-inset focus:ring-blue-500"
+                                                        }
+                                                    >
+                                                        {match v {
+                                                            Some(v) => format_dollars(v, 0),
+                                                            None => "—".to_string(),
+                                                        }}
+                                                    </td>
+                                                }
+                                            })
+                                            .collect_view();
+
+                                        view! {
+                                            <tr>
+                                                <th
+                                                    scope="row"
+                                                    id=cell_id(r, 0)
+                                                    role="rowheader"
+                                                    tabindex=move || tabindex(0)
+                                                    on:focus=move |_| set_active.set((r, 0))
+                                                    class="sticky left-0 z-10 bg-white px-3 py-2 text-left font-medium text-gray-700 whitespace-nowrap border-b border-r border-gray-200 focus:outline-none focus:ring-2 focus:ring-inset focus:ring-blue-500"
+                                                >
+                                                    <div>{*label}</div>
+                                                    {(!best_names.is_empty()).then(|| view! {
+                                                        <div class="text-xs font-normal text-green-700">{format!("Best: {}", best_names)}</div>
+                                                    })}
+                                                </th>
+                                                {cells}
+                                            </tr>
+                                        }
+                                    })
+                                    .collect_view()
+                            }}
+                        </tbody>
+                    </table>
+                </div>
+                {move || {
+                    // Whole dollars, matching the cells above, so the total ties out with them
+                    let fleet = columns.with(|columns| {
+                        fleet_total(columns.iter().filter_map(|(_, c)| c.as_ref()), 1.0)
+                    });
+                    let profits = columns.with(|columns| {
+                        columns
+                            .iter()
+                            .filter(|(_, c)| c.as_ref().is_some_and(|c| c.has_net_profit()))
+                            .count()
+                    });
+                    (fleet.displayed_parts.len() > 1).then(|| view! {
+                        <p class="mt-3 text-sm text-gray-700">
+                            <span class="font-medium">
+                                {format!("Fleet total ({} cars): {}", fleet.displayed_parts.len(), format_dollars(fleet.displayed, 0))}
+                            </span>
+                            {(fleet.rounding_difference().abs() >= 0.005).then(|| view! {
+                                <span class="ml-2 text-xs text-gray-500">
+                                    {format!("sum of the rounded totals above; unrounded {}", format_dollars(fleet.exact, 2))}
+                                </span>
+                            })}
+                            {(profits > 0).then(|| view! {
+                                <span class="ml-2 text-xs text-amber-700">
+                                    {format!("includes {} car(s) with a net profit (⚠), which lowers the total", profits)}
+                                </span>
+                            })}
+                        </p>
+                    })
+                }}
+
+                <h3 class="mt-6 text-sm font-medium text-gray-900">"Winner by Category"</h3>
+                <ul class="mt-2 grid grid-cols-1 gap-2 sm:grid-cols-2 lg:grid-cols-3">
+                    {move || {
+                        columns.with(|columns| category_winners(columns))
+                            .into_iter()
+                            .map(|winner| {
+                                let tie = winner.winners.len() > 1;
+                                view! {
+                                    <li class="rounded border border-gray-200 p-3">
+                                        <div class="text-xs text-gray-500 uppercase tracking-wide">{winner.category}</div>
+                                        <div class="mt-1 text-sm font-semibold text-green-700">
+                                            {winner.winners.join(", ")}
+                                        </div>
+                                        <div class="text-xs text-gray-500">
+                                            {format!("{}{}", format_dollars(winner.value, 0), winner.unit)}
+                                            {tie.then_some(" (tie)")}
+                                        </div>
+                                    </li>
+                                }
+                            })
+                            .collect_view()
+                    }}
+                </ul>
+            </div>
+        </div>
+    }
+}
@@ -1,27 +1,289 @@
 use codee::string::JsonSerdeCodec;
+use leptos::ev;
 use leptos::prelude::*;
+use leptos::wasm_bindgen::JsValue;
+use leptos_use::signal_debounced;
 use leptos_use::storage::use_local_storage;
 
+use crate::calculations::ComputedCache;
+use crate::components::backup::BackupPanel;
 use crate::components::cars::CarList;
+use crate::components::comparison::{
+    BreakEvenComparison, ComparisonTable, CostCrossoverComparison, GasPriceWhatIf,
+    HouseholdComparison,
+};
 use crate::components::maintenance::MaintenanceDataEditor;
+use crate::components::session_link::SessionLinkBanner;
 use crate::components::settings::SharedSettingsForm;
-use crate::models::{Car, MaintenanceCostDatabase, SharedSettings};
+use crate::components::ui::focus_input;
+use crate::data::{
+    decode_session_fragment, encode_session_fragment, get_sample_maintenance_data, SessionState,
+    MAX_SESSION_FRAGMENT_LEN, SESSION_FRAGMENT_PREFIX,
+};
+use crate::models::{Car, ComputeError, MaintenanceCostDatabase, SharedSettings};
+
+const CARS_STORAGE_KEY: &str = "carcalc_cars";
+
+/// How long typing must pause before the car list is written to local storage
+const CARS_SAVE_DEBOUNCE_MS: f64 = 300.0;
+
+/// How long recomputing must pause before the computed-cost cache is written to local
+/// storage; it's only a head start for the next visit, so there's no hurry
+const COMPUTED_CACHE_SAVE_DEBOUNCE_MS: f64 = 2000.0;
+
+/// How long edits must pause before the `#state=` link in the address bar is refreshed
+const LINK_UPDATE_DEBOUNCE_MS: f64 = 1000.0;
+
+/// Replace the URL fragment without adding a history entry or scrolling
+fn replace_fragment(fragment: &str) {
+    let location = window().location();
+    let url = format!(
+        "{}{}{}",
+        location.pathname().unwrap_or_default(),
+        location.search().unwrap_or_default(),
+        fragment
+    );
+    if let Ok(history) = window().history() {
+        let _ = history.replace_state_with_url(&JsValue::NULL, "", Some(&url));
+    }
+}
 
 #[component]
 pub fn HomePage() -> impl IntoView {
-    let (settings, set_settings, _) =
+    // A `#state=` link overrides local storage for this visit: its settings and cars are
+    // shown, but nothing is written back until the user chooses to keep them
+    let from_link = decode_session_fragment(&window().location().hash().unwrap_or_default());
+    let (link_state, link_error) = match from_link {
+        Some(Ok(state)) => (Some(state), None),
+        Some(Err(err)) => (None, Some(err)),
+        None => (None, None),
+    };
+    let persist = RwSignal::new(link_state.is_none());
+
+    let (stored_settings, set_stored_settings, _) =
         use_local_storage::<SharedSettings, JsonSerdeCodec>("carcalc_settings");
+    let (settings, set_settings) = signal(
+        link_state
+            .as_ref()
+            .map(|state| state.settings.clone())
+            .unwrap_or_else(|| stored_settings.get_untracked()),
+    );
+    Effect::new(move |_| {
+        let current = settings.get();
+        if persist.get() && stored_settings.with_untracked(|stored| *stored != current) {
+            set_stored_settings.set(current);
+        }
+    });
+    // Today's date, to the month, so cars with a model year get their real age
+    let today = js_sys::Date::new_0();
+    let current_year = today.get_full_year() as f64 + today.get_month() as f64 / 12.0;
+    let settings: Signal<SharedSettings> = Memo::new(move |_| SharedSettings {
+        current_year: Some(current_year),
+        ..settings.get()
+    })
+    .into();
 
     let (maintenance_db, set_maintenance_db, _) =
         use_local_storage::<MaintenanceCostDatabase, JsonSerdeCodec>("carcalc_maintenance_db");
+    // First run (or nothing saved): start from the sample profiles so the editor and
+    // maintenance estimates have data. Tables saved by older versions may repeat an x
+    // value; merge those once on load.
+    let mut loaded_db = maintenance_db.get_untracked();
+    if loaded_db.data.is_empty() {
+        set_maintenance_db.set(get_sample_maintenance_data());
+    } else if loaded_db.coalesce_duplicate_points() {
+        set_maintenance_db.set(loaded_db);
+    }
+
+    // Edits update the in-memory car list immediately; persisting it (a JSON serialize of
+    // every car, photos included) is debounced so a burst of keystrokes is one write
+    let (stored_cars, set_stored_cars, _) =
+        use_local_storage::<Vec<Car>, JsonSerdeCodec>(CARS_STORAGE_KEY);
+    let (cars, set_cars) = signal(
+        link_state
+            .map(|state| state.cars)
+            .unwrap_or_else(|| stored_cars.get_untracked()),
+    );
+    let settled_cars: Signal<Vec<Car>> = signal_debounced(cars, CARS_SAVE_DEBOUNCE_MS);
+    Effect::new(move |_| {
+        let settled = settled_cars.get();
+        if persist.get() && stored_cars.with_untracked(|stored| *stored != settled) {
+            set_stored_cars.set(settled);
+        }
+    });
+
+    // Write any pending edits straight to storage when the page is hidden or closed, since
+    // the debounce timer (and the storage hook's own effect) may never get to run
+    let flush_cars = move || {
+        let pending = cars.get_untracked();
+        if !persist.get_untracked() || stored_cars.with_untracked(|stored| *stored == pending) {
+            return;
+        }
+        if let (Ok(json), Ok(Some(storage))) =
+            (serde_json::to_string(&pending), window().local_storage())
+        {
+            let _ = storage.set_item(CARS_STORAGE_KEY, &json);
+        }
+    };
+    let pagehide = window_event_listener(ev::pagehide, move |_| flush_cars());
+    // Pasting another link into the address bar only changes the fragment; reload so it's read
+    let hashchange = window_event_listener(ev::hashchange, move |_| {
+        let hash = window().location().hash().unwrap_or_default();
+        if hash.starts_with(SESSION_FRAGMENT_PREFIX) {
+            let _ = window().location().reload();
+        }
+    });
+    let visibility = window_event_listener(ev::visibilitychange, move |_| {
+        if document().hidden() {
+            flush_cars();
+        }
+    });
+    on_cleanup(move || {
+        pagehide.remove();
+        visibility.remove();
+        hashchange.remove();
+    });
+    let cars: Signal<Vec<Car>> = cars.into();
+
+    // Keep the address bar's `#state=` link in step with the settled state, dropping it
+    // when the session has grown too large to share that way
+    let settled_settings: Signal<SharedSettings> =
+        signal_debounced(settings, LINK_UPDATE_DEBOUNCE_MS);
+    let link_fragment = Memo::new(move |_| {
+        settled_settings.with(|settings| {
+            settled_cars
+                .with(|cars| encode_session_fragment(&SessionState::for_link(settings, cars)))
+        })
+    });
+    let oversized =
+        Signal::derive(move || link_fragment.with(|f| f.len() > MAX_SESSION_FRAGMENT_LEN));
+    Effect::new(move |_| {
+        let fragment = link_fragment.get();
+        if fragment.len() > MAX_SESSION_FRAGMENT_LEN {
+            replace_fragment("");
+        } else {
+            replace_fragment(&fragment);
+        }
+    });
+
+    // Cards update the cache in memory as they recompute; like the car list, writing it
+    // out (a serialize of every car's results) waits for the edits to settle
+    let (stored_cache, set_stored_cache, _) =
+        use_local_storage::<ComputedCache, JsonSerdeCodec>("carcalc_computed_cache");
+    let (computed_cache, set_computed_cache) = signal(stored_cache.get_untracked());
+    let settled_cache: Signal<ComputedCache> =
+        signal_debounced(computed_cache, COMPUTED_CACHE_SAVE_DEBOUNCE_MS);
+    Effect::new(move |_| {
+        let settled = settled_cache.get();
+        if stored_cache.with_untracked(|stored| *stored != settled) {
+            set_stored_cache.set(settled);
+        }
+    });
+    let computed_cache: Signal<ComputedCache> = computed_cache.into();
+
+    // Forget cached results for deleted cars
+    Effect::new(move |_| {
+        let cars = cars.get();
+        if computed_cache.with_untracked(|c| c.entries.len() > cars.len()) {
+            set_computed_cache.update(|cache| cache.retain_cars(&cars));
+        }
+    });
+
+    let (search, set_search) = signal(String::new());
+
+    // A gas price tried out on the slider, used for every card and comparison but never
+    // saved or put in the link
+    let gas_price_override = RwSignal::new(None::<f64>);
+    let what_if_settings: Signal<SharedSettings> =
+        Memo::new(move |_| match gas_price_override.get() {
+            Some(price) => SharedSettings {
+                average_gas_price: price,
+                ..settings.get()
+            },
+            None => settings.get(),
+        })
+        .into();
 
-    let (cars, set_cars, _) = use_local_storage::<Vec<Car>, JsonSerdeCodec>("carcalc_cars");
+    let saved_session = Signal::derive(move || SessionState {
+        settings: stored_settings.get(),
+        cars: stored_cars.get(),
+    });
 
     view! {
         <div class="px-4 py-6 sm:px-0 space-y-6">
+            <SessionLinkBanner
+                settings=settings
+                cars=cars
+                set_settings=set_settings
+                set_cars=set_cars
+                saved=saved_session
+                persist=persist
+                link_error=link_error
+                oversized=oversized
+            />
+            <BackupPanel
+                cars=cars
+                set_cars=set_cars
+                settings=settings
+                set_settings=set_settings
+                maintenance_db=maintenance_db
+                set_maintenance_db=set_maintenance_db
+            />
             <SharedSettingsForm settings=settings set_settings=set_settings />
-            <MaintenanceDataEditor maintenance_db=maintenance_db _set_maintenance_db=set_maintenance_db />
-            <CarList cars=cars set_cars=set_cars settings=settings maintenance_db=maintenance_db />
+            <MaintenanceDataEditor
+                maintenance_db=maintenance_db
+                set_maintenance_db=set_maintenance_db
+                cars=cars
+                set_cars=set_cars
+            />
+            <div>
+                <label for="car-search" class="sr-only">"Search cars"</label>
+                <input
+                    type="search"
+                    id="car-search"
+                    placeholder="Search make, model, trim, year, VIN or notes"
+                    class="block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                    prop:value=move || search.get()
+                    on:input=move |ev| set_search.set(event_target_value(&ev))
+                />
+            </div>
+            {move || settings.with(|s| s.validate().err()).map(|issue| view! {
+                <div class="rounded-md bg-red-50 border border-red-200 p-4 text-sm text-red-800 flex flex-wrap items-center justify-between gap-2" role="alert">
+                    <span>{format!("No car can be calculated. {}", ComputeError::InvalidSettings(issue))}</span>
+                    <button
+                        class="px-3 py-1 rounded-md border border-red-300 bg-white font-medium hover:bg-red-100"
+                        on:click=move |_| focus_input(issue.input_id())
+                    >
+                        {format!("Go to {}", issue.label())}
+                    </button>
+                </div>
+            })}
+            <GasPriceWhatIf
+                cars=cars
+                settings=settings
+                maintenance_db=maintenance_db
+                gas_price_override=gas_price_override
+            />
+            <CarList
+                cars=cars
+                set_cars=set_cars
+                settings=what_if_settings
+                maintenance_db=maintenance_db
+                search=search.into()
+                computed_cache=computed_cache
+                set_computed_cache=set_computed_cache
+            />
+            <Show when=move || { cars.with(|cars| cars.iter().filter(|c| c.in_comparison).count() >= 2) }>
+                <ComparisonTable cars=cars settings=what_if_settings maintenance_db=maintenance_db />
+                <HouseholdComparison
+                    cars=cars
+                    settings=what_if_settings
+                    set_settings=set_settings
+                    maintenance_db=maintenance_db
+                />
+                <CostCrossoverComparison cars=cars settings=what_if_settings maintenance_db=maintenance_db />
+                <BreakEvenComparison cars=cars settings=what_if_settings maintenance_db=maintenance_db />
+            </Show>
         </div>
     }
 }
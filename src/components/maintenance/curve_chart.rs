@@ -0,0 +1,114 @@
+use leptos::prelude::*;
+
+use crate::data::format_thousands;
+use crate::models::{scale_x, MaintenanceDataPoint};
+
+const CHART_WIDTH: f64 = 320.0;
+const CHART_HEIGHT: f64 = 160.0;
+const MARGIN_LEFT: f64 = 48.0;
+const MARGIN_RIGHT: f64 = 8.0;
+const MARGIN_BOTTOM: f64 = 28.0;
+const MARGIN_TOP: f64 = 8.0;
+
+/// Line chart of a cumulative cost curve, scaled to the data's range. Points where the
+/// cost drops below the one before are drawn in red, since a cumulative cost can't fall.
+#[component]
+pub fn CurveChart(
+    points: Vec<MaintenanceDataPoint>,
+    /// Axis label for the x values
+    x_label: &'static str,
+    /// Multiplier from stored x to displayed x, as in `PointsTable`
+    x_scale: f64,
+) -> impl IntoView {
+    if points.is_empty() {
+        return view! {
+            <p class="text-xs text-gray-400 mb-3">"No points yet, so there's no curve to draw."</p>
+        }
+        .into_any();
+    }
+
+    let bounds = |value: fn(&MaintenanceDataPoint) -> f64| {
+        let (min, max) = points
+            .iter()
+            .map(value)
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| {
+                (lo.min(v), hi.max(v))
+            });
+        // A single value still needs a range to scale against
+        if max > min {
+            (min, max)
+        } else {
+            (min - 1.0, max + 1.0)
+        }
+    };
+    let (min_x, max_x) = bounds(|p| p.x);
+    let (min_y, max_y) = bounds(|p| p.y);
+
+    let plot_width = CHART_WIDTH - MARGIN_LEFT - MARGIN_RIGHT;
+    let plot_height = CHART_HEIGHT - MARGIN_TOP - MARGIN_BOTTOM;
+    let x_of = move |x: f64| MARGIN_LEFT + (x - min_x) / (max_x - min_x) * plot_width;
+    let y_of = move |y: f64| MARGIN_TOP + (max_y - y) / (max_y - min_y) * plot_height;
+
+    let line = points
+        .iter()
+        .map(|p| format!("{:.1},{:.1}", x_of(p.x), y_of(p.y)))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let dips = points.windows(2).filter(|w| w[1].y < w[0].y).count();
+    let markers = points
+        .iter()
+        .enumerate()
+        .map(|(i, p)| {
+            let dip = i > 0 && p.y < points[i - 1].y;
+            view! {
+                <circle
+                    cx=x_of(p.x)
+                    cy=y_of(p.y)
+                    r={if dip { 3.5 } else { 2.5 }}
+                    class={if dip { "fill-red-600" } else { "fill-blue-600" }}
+                >
+                    <title>{format!("{} {}: ${:.2}", scale_x(p.x, x_scale), x_label.to_lowercase(), p.y)}</title>
+                </circle>
+            }
+        })
+        .collect_view();
+
+    view! {
+        <svg
+            viewBox=format!("0 0 {} {}", CHART_WIDTH, CHART_HEIGHT)
+            class="w-full h-auto mb-1"
+            xmlns="http://www.w3.org/2000/svg"
+            role="img"
+            aria-label=format!("Cumulative cost by {}", x_label.to_lowercase())
+        >
+            <line x1=MARGIN_LEFT y1=MARGIN_TOP x2=MARGIN_LEFT y2=MARGIN_TOP + plot_height class="stroke-gray-300" />
+            <line x1=MARGIN_LEFT y1=MARGIN_TOP + plot_height x2=CHART_WIDTH - MARGIN_RIGHT y2=MARGIN_TOP + plot_height class="stroke-gray-300" />
+            <text x=MARGIN_LEFT - 4.0 y=MARGIN_TOP + 8.0 text-anchor="end" class="fill-gray-500 text-[10px]">
+                {format!("${}", format_thousands(max_y))}
+            </text>
+            <text x=MARGIN_LEFT - 4.0 y=MARGIN_TOP + plot_height text-anchor="end" class="fill-gray-500 text-[10px]">
+                {format!("${}", format_thousands(min_y))}
+            </text>
+            <text x=MARGIN_LEFT y=CHART_HEIGHT - 14.0 class="fill-gray-500 text-[10px]">
+                {format!("{}", scale_x(min_x, x_scale))}
+            </text>
+            <text x=CHART_WIDTH - MARGIN_RIGHT y=CHART_HEIGHT - 14.0 text-anchor="end" class="fill-gray-500 text-[10px]">
+                {format!("{}", scale_x(max_x, x_scale))}
+            </text>
+            <text x=MARGIN_LEFT + plot_width / 2.0 y=CHART_HEIGHT - 2.0 text-anchor="middle" class="fill-gray-500 text-[10px]">
+                {x_label}
+            </text>
+            <polyline points=line fill="none" class="stroke-blue-600" stroke-width="1.5" />
+            {markers}
+        </svg>
+        {(dips > 0).then(|| view! {
+            <p class="text-xs text-red-600 mb-3">
+                {format!(
+                    "{} point(s) in red cost less than the point before. A cumulative cost shouldn't fall, so check them for typos.",
+                    dips
+                )}
+            </p>
+        })}
+    }
+    .into_any()
+}
@@ -1,27 +1,72 @@
 use leptos::prelude::*;
 
-use crate::models::MaintenanceCostDatabase;
+use crate::components::maintenance::{
+    CurveSmoother, MaintenanceImportPanel, MergeProfiles, NewProfileForm, PendingRename,
+    PointsTable, ServiceScheduleEditor,
+};
+use crate::data::get_sample_maintenance_data;
+use crate::models::{Car, MaintenanceCostDatabase, MILES_PER_MILEAGE_X};
 
 #[component]
 pub fn MaintenanceDataEditor(
     maintenance_db: Signal<MaintenanceCostDatabase>,
-    _set_maintenance_db: WriteSignal<MaintenanceCostDatabase>,
+    set_maintenance_db: WriteSignal<MaintenanceCostDatabase>,
+    cars: Signal<Vec<Car>>,
+    set_cars: WriteSignal<Vec<Car>>,
 ) -> impl IntoView {
     let (selected_key, set_selected_key) = signal::<Option<String>>(None);
     let (is_expanded, set_is_expanded) = signal(false);
+    let pending_rename = RwSignal::new(None::<PendingRename>);
 
     let all_makes_models = move || maintenance_db.get().get_all_keys();
 
     let selected_data = move || {
-        if let Some(key) = selected_key.get() {
-            let parts: Vec<&str> = key.split('_').collect();
-            if parts.len() >= 2 {
-                let make = parts[0];
-                let model = parts[1..].join("_");
-                return maintenance_db.get().get(make, &model).cloned();
+        let key = selected_key.get()?;
+        maintenance_db.with(|db| db.data.get(&key).cloned())
+    };
+
+    // Seeded sample curves for the selected vehicle, if it's one we ship data for
+    let selected_sample = move || {
+        let key = selected_key.get()?;
+        get_sample_maintenance_data().data.remove(&key)
+    };
+
+    let restore_sample = move |_| {
+        let (Some(key), Some(sample)) = (selected_key.get(), selected_sample()) else {
+            return;
+        };
+        let confirmed = window()
+            .confirm_with_message(&format!(
+                "Replace all {} {} maintenance points with the original sample data?",
+                sample.make, sample.model
+            ))
+            .unwrap_or(false);
+        if confirmed {
+            set_maintenance_db.update(|db| {
+                if let Some(data) = db.data.get_mut(&key) {
+                    data.by_mileage = sample.by_mileage;
+                    data.by_time = sample.by_time;
+                }
+            });
+        }
+    };
+
+    // Throw away every edit and added profile, back to the data a fresh install starts with
+    let reset_all = move |_| {
+        let confirmed = window()
+            .confirm_with_message(
+                "Replace all maintenance data, including profiles you added or edited, with the built-in sample data?",
+            )
+            .unwrap_or(false);
+        if confirmed {
+            let sample = get_sample_maintenance_data();
+            if selected_key
+                .with_untracked(|key| key.as_ref().is_some_and(|k| !sample.data.contains_key(k)))
+            {
+                set_selected_key.set(None);
             }
+            set_maintenance_db.set(sample);
         }
-        None
     };
 
     view! {
@@ -36,6 +81,12 @@ pub fn MaintenanceDataEditor(
                     </div>
                     <button
                         class="ml-4 text-gray-600 hover:text-gray-800"
+                        aria-label=move || if is_expanded.get() {
+                            "Collapse maintenance cost data"
+                        } else {
+                            "Expand maintenance cost data"
+                        }
+                        aria-expanded=move || is_expanded.get().to_string()
                         on:click=move |_| set_is_expanded.update(|v| *v = !*v)
                     >
                         <svg
@@ -43,6 +94,7 @@ pub fn MaintenanceDataEditor(
                                 "h-6 w-6 transform transition-transform {}",
                                 if is_expanded.get() { "rotate-180" } else { "" }
                             )
+                            aria-hidden="true"
                             xmlns="http://www.w3.org/2000/svg"
                             viewBox="0 0 20 20"
                             fill="currentColor"
@@ -60,6 +112,7 @@ pub fn MaintenanceDataEditor(
                             </label>
                             <select
                                 class="block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                                prop:value=move || selected_key.get().unwrap_or_default()
                                 on:change=move |ev| {
                                     let value = event_target_value(&ev);
                                     set_selected_key.set(if value.is_empty() { None } else { Some(value) });
@@ -68,12 +121,11 @@ pub fn MaintenanceDataEditor(
                                 <option value="">"-- Select a vehicle --"</option>
                                 <For
                                     each=all_makes_models
-                                    key=|(make, model)| format!("{}_{}", make, model)
-                                    children=move |(make, model)| {
-                                        let key = format!("{}_{}", make.to_lowercase(), model.to_lowercase());
+                                    key=|(key, _)| key.clone()
+                                    children=move |(key, name)| {
                                         view! {
                                             <option value=key>
-                                                {format!("{} {}", make, model)}
+                                                {name}
                                             </option>
                                         }
                                     }
@@ -81,85 +133,130 @@ pub fn MaintenanceDataEditor(
                             </select>
                         </div>
 
+                        <NewProfileForm
+                            maintenance_db=maintenance_db
+                            set_maintenance_db=set_maintenance_db
+                            on_created=Callback::new(move |key| set_selected_key.set(Some(key)))
+                        />
+
+                        <div class="flex items-center justify-between border border-gray-200 rounded-lg p-4">
+                            <p class="text-xs text-gray-500">
+                                "Start over from the built-in sample data. Profiles you added are removed."
+                            </p>
+                            <button
+                                class="ml-4 inline-flex items-center px-3 py-2 border border-red-300 text-sm font-medium rounded-md text-red-700 bg-white hover:bg-red-50"
+                                on:click=reset_all
+                            >
+                                "Reset to defaults"
+                            </button>
+                        </div>
+
                         <Show when=move || selected_data().is_some()>
+                            <div class="flex justify-end">
+                                <button
+                                    class="inline-flex items-center px-3 py-2 border border-gray-300 text-sm font-medium rounded-md text-gray-700 bg-white hover:bg-gray-50 disabled:opacity-50 disabled:cursor-not-allowed"
+                                    prop:disabled=move || selected_sample().is_none()
+                                    title=move || {
+                                        if selected_sample().is_some() {
+                                            "Overwrite this vehicle's points with the built-in sample data"
+                                        } else {
+                                            "No sample data exists for this vehicle"
+                                        }
+                                    }
+                                    on:click=restore_sample
+                                >
+                                    "Restore sample data"
+                                </button>
+                            </div>
+
                             {move || {
-                                if let Some(data) = selected_data() {
+                                let warnings = selected_data().map(|d| d.validate()).unwrap_or_default();
+                                (!warnings.is_empty()).then(|| view! {
+                                    <div class="rounded-md bg-amber-50 border border-amber-200 p-4 text-sm text-amber-800" role="alert">
+                                        <p class="font-medium">"These points will give odd maintenance estimates:"</p>
+                                        <ul class="mt-1 list-disc list-inside">
+                                            {warnings.into_iter().map(|w| view! { <li>{w}</li> }).collect_view()}
+                                        </ul>
+                                    </div>
+                                })
+                            }}
+
+                            // Rebuilt only when the selection changes so sort, filter and edit state survive edits
+                            {move || {
+                                selected_key.get().map(|key| {
+                                    let mileage_key = key.clone();
+                                    let time_key = key.clone();
                                     view! {
                                         <div class="grid grid-cols-1 md:grid-cols-2 gap-6 mt-4">
-                                            <div class="border border-gray-200 rounded-lg p-4">
-                                                <h3 class="text-lg font-semibold text-gray-900 mb-2">
-                                                    "By Mileage"
-                                                </h3>
-                                                <p class="text-xs text-gray-500 mb-3">
-                                                    "Cumulative cost per 10k miles"
-                                                </p>
-                                                <div class="space-y-2 max-h-96 overflow-y-auto">
-                                                    <For
-                                                        each=move || data.by_mileage.clone()
-                                                        key=|point| format!("{}", point.x)
-                                                        children=move |point| {
-                                                            view! {
-                                                                <div class="flex items-center space-x-2 text-sm">
-                                                                    <span class="w-20 text-gray-600">
-                                                                        {format!("{}k mi", point.x * 10.0)}
-                                                                    </span>
-                                                                    <span class="flex-1 text-gray-900">
-                                                                        {format!("${:.2}", point.y)}
-                                                                    </span>
-                                                                </div>
-                                                            }
+                                            <PointsTable
+                                                title="By Mileage"
+                                                description="Cumulative cost at each mileage"
+                                                x_label="Miles"
+                                                x_scale=MILES_PER_MILEAGE_X
+                                                points=Signal::derive(move || selected_data().map(|d| d.by_mileage).unwrap_or_default())
+                                                on_change=Callback::new(move |points| {
+                                                    set_maintenance_db.update(|db| {
+                                                        if let Some(mut data) = db.data.get(&mileage_key).cloned() {
+                                                            data.by_mileage = points;
+                                                            db.set(data);
                                                         }
-                                                    />
-                                                </div>
-                                            </div>
-
-                                            <div class="border border-gray-200 rounded-lg p-4">
-                                                <h3 class="text-lg font-semibold text-gray-900 mb-2">
-                                                    "By Time"
-                                                </h3>
-                                                <p class="text-xs text-gray-500 mb-3">
-                                                    "Cumulative cost per year"
-                                                </p>
-                                                <div class="space-y-2 max-h-96 overflow-y-auto">
-                                                    <For
-                                                        each=move || data.by_time.clone()
-                                                        key=|point| format!("{}", point.x)
-                                                        children=move |point| {
-                                                            view! {
-                                                                <div class="flex items-center space-x-2 text-sm">
-                                                                    <span class="w-20 text-gray-600">
-                                                                        {format!("{} yr", point.x)}
-                                                                    </span>
-                                                                    <span class="flex-1 text-gray-900">
-                                                                        {format!("${:.2}", point.y)}
-                                                                    </span>
-                                                                </div>
-                                                            }
+                                                    });
+                                                })
+                                            />
+                                            <PointsTable
+                                                title="By Time"
+                                                description="Cumulative cost at each vehicle age"
+                                                x_label="Years"
+                                                x_scale=1.0
+                                                points=Signal::derive(move || selected_data().map(|d| d.by_time).unwrap_or_default())
+                                                on_change=Callback::new(move |points| {
+                                                    set_maintenance_db.update(|db| {
+                                                        if let Some(mut data) = db.data.get(&time_key).cloned() {
+                                                            data.by_time = points;
+                                                            db.set(data);
                                                         }
-                                                    />
-                                                </div>
-                                            </div>
+                                                    });
+                                                })
+                                            />
                                         </div>
+                                        <ServiceScheduleEditor profile_key=key set_maintenance_db=set_maintenance_db />
+                                    }
+                                })
+                            }}
 
-                                        <div class="mt-4 bg-blue-50 border border-blue-200 rounded-lg p-4">
-                                            <div class="flex">
-                                                <svg class="h-5 w-5 text-blue-400 mr-3" xmlns="http://www.w3.org/2000/svg" viewBox="0 0 20 20" fill="currentColor">
-                                                    <path fill-rule="evenodd" d="M18 10a8 8 0 11-16 0 8 8 0 0116 0zm-7-4a1 1 0 11-2 0 1 1 0 012 0zM9 9a1 1 0 000 2v3a1 1 0 001 1h1a1 1 0 100-2v-3a1 1 0 00-1-1H9z" clip-rule="evenodd"/>
-                                                </svg>
-                                                <div class="flex-1">
-                                                    <h4 class="text-sm font-medium text-blue-800">"How to update this data"</h4>
-                                                    <p class="mt-1 text-sm text-blue-700">
-                                                        "This data comes from CarEdge.com. To update it, visit CarEdge, find your vehicle's maintenance costs, and manually enter the data here. Data is stored locally in your browser."
-                                                    </p>
-                                                </div>
-                                            </div>
-                                        </div>
-                                    }.into_any()
-                                } else {
-                                    view! { <div></div> }.into_any()
-                                }
+                            {move || {
+                                selected_data().map(|data| {
+                                    view! {
+                                        <CurveSmoother data=data.clone() set_maintenance_db=set_maintenance_db />
+
+                                        <MergeProfiles
+                                            data=data
+                                            maintenance_db=maintenance_db
+                                            set_maintenance_db=set_maintenance_db
+                                            cars=cars
+                                            set_cars=set_cars
+                                            pending_rename=pending_rename
+                                        />
+                                    }
+                                })
                             }}
+
+                            <div class="mt-4 bg-blue-50 border border-blue-200 rounded-lg p-4">
+                                <div class="flex">
+                                    <svg class="h-5 w-5 text-blue-400 mr-3" aria-hidden="true" xmlns="http://www.w3.org/2000/svg" viewBox="0 0 20 20" fill="currentColor">
+                                        <path fill-rule="evenodd" d="M18 10a8 8 0 11-16 0 8 8 0 0116 0zm-7-4a1 1 0 11-2 0 1 1 0 012 0zM9 9a1 1 0 000 2v3a1 1 0 001 1h1a1 1 0 100-2v-3a1 1 0 00-1-1H9z" clip-rule="evenodd"/>
+                                    </svg>
+                                    <div class="flex-1">
+                                        <h4 class="text-sm font-medium text-blue-800">"How to update this data"</h4>
+                                        <p class="mt-1 text-sm text-blue-700">
+                                            "This data comes from CarEdge.com. To update it, visit CarEdge, find your vehicle's maintenance costs, and manually enter the data here. Data is stored locally in your browser."
+                                        </p>
+                                    </div>
+                                </div>
+                            </div>
                         </Show>
+
+                        <MaintenanceImportPanel maintenance_db=maintenance_db set_maintenance_db=set_maintenance_db />
                     </div>
                 </Show>
             </div>
@@ -0,0 +1,67 @@
+use leptos::prelude::*;
+
+use crate::components::ui::ImportReportModal;
+use crate::data::{parse_maintenance_import, ImportReport};
+use crate::models::MaintenanceCostDatabase;
+
+/// Paste maintenance profiles as JSON or CSV and report what loaded
+#[component]
+pub fn MaintenanceImportPanel(
+    maintenance_db: Signal<MaintenanceCostDatabase>,
+    set_maintenance_db: WriteSignal<MaintenanceCostDatabase>,
+) -> impl IntoView {
+    let (is_open, set_is_open) = signal(false);
+    let (text, set_text) = signal(String::new());
+    let report = RwSignal::new(None::<ImportReport>);
+
+    let run_import = move |_| {
+        let import =
+            parse_maintenance_import(&text.get_untracked(), &maintenance_db.get_untracked());
+        report.set(Some(import.report.clone()));
+        if !import.profiles.is_empty() {
+            set_maintenance_db.update(|db| import.apply(db));
+            set_text.set(String::new());
+        }
+    };
+
+    view! {
+        <div class="border-t border-gray-200 pt-4">
+            <button
+                class="text-sm font-medium text-blue-600 hover:text-blue-800"
+                on:click=move |_| set_is_open.update(|v| *v = !*v)
+            >
+                {move || if is_open.get() { "Hide import" } else { "Import maintenance data…" }}
+            </button>
+            <div class:hidden=move || !is_open.get() class="mt-2 space-y-2">
+                <p class="text-xs text-gray-500">
+                    "Paste JSON (a profile, a list of profiles, or an exported database) or CSV rows of make, model, table (mileage, time or value), x, y, where mileage x is in miles and time or value x in years. Mileage in JSON may be plain miles or 10,000-mile units as in an exported database (5 is 50,000 miles). Repeated x values keep the highest cost. Profiles for the same make/model are replaced."
+                </p>
+                <textarea
+                    rows="6"
+                    class="block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm font-mono"
+                    placeholder="Toyota,Prius,mileage,50000,1200"
+                    prop:value=move || text.get()
+                    on:input=move |ev| set_text.set(event_target_value(&ev))
+                ></textarea>
+                <button
+                    class="inline-flex items-center px-3 py-2 border border-transparent text-sm font-medium rounded-md text-white bg-blue-600 hover:bg-blue-700 disabled:opacity-50"
+                    prop:disabled=move || text.get().trim().is_empty()
+                    on:click=run_import
+                >
+                    "Import"
+                </button>
+            </div>
+            {move || {
+                report.get().map(|report_data| {
+                    view! {
+                        <ImportReportModal
+                            title="Maintenance import"
+                            report=report_data
+                            on_close=Callback::new(move |_| report.set(None))
+                        />
+                    }
+                })
+            }}
+        </div>
+    }
+}
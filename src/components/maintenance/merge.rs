@@ -0,0 +1,168 @@
+use leptos::prelude::*;
+
+use crate::models::{Car, MaintenanceCostData, MaintenanceCostDatabase, MergeStrategy};
+
+/// A completed merge whose cars may still reference the removed make/model
+#[derive(Clone, Debug, PartialEq)]
+pub struct PendingRename {
+    pub from: (String, String),
+    pub to: (String, String),
+}
+
+/// Merges another maintenance profile into the selected one
+#[component]
+pub fn MergeProfiles(
+    data: MaintenanceCostData,
+    maintenance_db: Signal<MaintenanceCostDatabase>,
+    set_maintenance_db: WriteSignal<MaintenanceCostDatabase>,
+    cars: Signal<Vec<Car>>,
+    set_cars: WriteSignal<Vec<Car>>,
+    pending_rename: RwSignal<Option<PendingRename>>,
+) -> impl IntoView {
+    let (other_key, set_other_key) = signal::<Option<String>>(None);
+    let (strategy, set_strategy) = signal(MergeStrategy::Average);
+
+    let base_key = data.key();
+    let base = StoredValue::new((data.make.clone(), data.model.clone()));
+    let base_has_trim = !data.trim.is_empty();
+    let merge_key = base_key.clone();
+
+    let candidates = move || {
+        let mut others: Vec<MaintenanceCostData> = maintenance_db
+            .get()
+            .data
+            .into_values()
+            .filter(|d| d.key() != base_key)
+            .collect();
+        others.sort_by_key(|d| d.key());
+        others
+    };
+
+    let merge = move |_| {
+        let Some(key) = other_key.get_untracked() else {
+            return;
+        };
+        let Some(other) = maintenance_db.get_untracked().data.get(&key).cloned() else {
+            return;
+        };
+        let (make, model) = base.get_value();
+        set_maintenance_db.update(|db| {
+            db.merge_keys(&merge_key, &key, strategy.get_untracked());
+        });
+        set_other_key.set(None);
+
+        // Renaming cars only makes sense between profiles that cover every trim
+        let affected = !base_has_trim
+            && other.trim.is_empty()
+            && cars
+                .get_untracked()
+                .iter()
+                .any(|c| MaintenanceCostData::make_key(&c.make, &c.model) == key);
+        if affected {
+            pending_rename.set(Some(PendingRename {
+                from: (other.make, other.model),
+                to: (make, model),
+            }));
+        }
+    };
+
+    let rename_cars = move |_| {
+        if let Some(rename) = pending_rename.get_untracked() {
+            let from_key = MaintenanceCostData::make_key(&rename.from.0, &rename.from.1);
+            set_cars.update(|cars| {
+                for car in cars
+                    .iter_mut()
+                    .filter(|c| MaintenanceCostData::make_key(&c.make, &c.model) == from_key)
+                {
+                    car.make = rename.to.0.clone();
+                    car.model = rename.to.1.clone();
+                }
+            });
+        }
+        pending_rename.set(None);
+    };
+
+    view! {
+        <div class="mt-4 border border-gray-200 rounded-lg p-4">
+            <h4 class="text-sm font-medium text-gray-900">"Merge another profile into this one"</h4>
+            <p class="text-xs text-gray-500">
+                "Combines both tables into this profile and deletes the other one, e.g. to fold \"Chevy Malibu\" into \"Chevrolet Malibu\"."
+            </p>
+            <div class="mt-3 flex flex-wrap items-center gap-2">
+                <select
+                    class="rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                    on:change=move |ev| {
+                        let value = event_target_value(&ev);
+                        set_other_key.set(if value.is_empty() { None } else { Some(value) });
+                    }
+                    prop:value=move || other_key.get().unwrap_or_default()
+                >
+                    <option value="">"-- Profile to merge in --"</option>
+                    {move || {
+                        candidates()
+                            .into_iter()
+                            .map(|d| {
+                                view! { <option value=d.key()>{d.name()}</option> }
+                            })
+                            .collect_view()
+                    }}
+                </select>
+                <select
+                    class="rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                    on:change=move |ev| {
+                        set_strategy.set(if event_target_value(&ev) == "base" {
+                            MergeStrategy::PreferBase
+                        } else {
+                            MergeStrategy::Average
+                        });
+                    }
+                >
+                    <option value="average" selected=true>"Average overlapping points"</option>
+                    <option value="base">"Prefer this profile's points"</option>
+                </select>
+                <button
+                    class="inline-flex items-center px-3 py-2 border border-gray-300 text-sm font-medium rounded-md text-gray-700 bg-white hover:bg-gray-50 disabled:opacity-50"
+                    disabled=move || other_key.get().is_none()
+                    on:click=merge
+                >
+                    "Merge"
+                </button>
+            </div>
+
+            {move || {
+                pending_rename.get().map(|rename| {
+                    let from_key = MaintenanceCostData::make_key(&rename.from.0, &rename.from.1);
+                    let count = cars
+                        .get()
+                        .iter()
+                        .filter(|c| MaintenanceCostData::make_key(&c.make, &c.model) == from_key)
+                        .count();
+                    view! {
+                        <div class="mt-3 bg-blue-50 border border-blue-200 rounded-lg p-3 text-sm text-blue-800">
+                            <p>
+                                {format!(
+                                    "{} car(s) still use \"{} {}\". Rename them to \"{} {}\" so they pick up the merged data?",
+                                    count, rename.from.0, rename.from.1, rename.to.0, rename.to.1,
+                                )}
+                            </p>
+                            <div class="mt-2 space-x-2">
+                                <button
+                                    class="inline-flex items-center px-3 py-1 border border-transparent text-xs font-medium rounded-md text-white bg-blue-600 hover:bg-blue-700"
+                                    on:click=rename_cars
+                                >
+                                    "Rename cars"
+                                </button>
+                                <button
+                                    class="inline-flex items-center px-3 py-1 border border-gray-300 text-xs font-medium rounded-md text-gray-700 bg-white hover:bg-gray-50"
+                                    on:click=move |_| pending_rename.set(None)
+                                >
+                                    "Keep as is"
+                                </button>
+                            </div>
+                        </div>
+                    }
+                })
+            }}
+        </div>
+    }
+}
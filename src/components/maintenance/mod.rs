@@ -1,3 +1,17 @@
+mod curve_chart;
 mod editor;
+mod import;
+mod merge;
+mod new_profile;
+mod points_table;
+mod schedule;
+mod smoother;
 
+pub use curve_chart::CurveChart;
 pub use editor::MaintenanceDataEditor;
+pub use import::MaintenanceImportPanel;
+pub use merge::{MergeProfiles, PendingRename};
+pub use new_profile::NewProfileForm;
+pub use points_table::PointsTable;
+pub use schedule::ServiceScheduleEditor;
+pub use smoother::CurveSmoother;
@@ -0,0 +1,107 @@
+use leptos::prelude::*;
+
+use crate::models::{MaintenanceCostData, MaintenanceCostDatabase};
+
+/// Creates an empty maintenance profile for a make/model (optionally one trim of it) missing
+/// from the database, so its tables can be filled in by hand. Reports the new profile's key
+/// through `on_created`.
+#[component]
+pub fn NewProfileForm(
+    maintenance_db: Signal<MaintenanceCostDatabase>,
+    set_maintenance_db: WriteSignal<MaintenanceCostDatabase>,
+    on_created: Callback<String>,
+) -> impl IntoView {
+    let make = RwSignal::new(String::new());
+    let model = RwSignal::new(String::new());
+    let trim = RwSignal::new(String::new());
+
+    let trimmed = move || {
+        (
+            make.get().trim().to_string(),
+            model.get().trim().to_string(),
+            trim.get().trim().to_string(),
+        )
+    };
+
+    let existing = move || {
+        let (make, model, trim) = trimmed();
+        let key = MaintenanceCostData::make_trim_key(&make, &model, &trim);
+        maintenance_db.with(|db| db.data.get(&key).map(|data| data.name()))
+    };
+
+    let can_create = move || {
+        let (make, model, _) = trimmed();
+        !make.is_empty() && !model.is_empty() && existing().is_none()
+    };
+
+    let create = move |_| {
+        if !can_create() {
+            return;
+        }
+        let (make_name, model_name, trim_name) = trimmed();
+        let mut data = MaintenanceCostData::new(make_name, model_name);
+        data.trim = trim_name;
+        let key = data.key();
+        set_maintenance_db.update(|db| db.set(data));
+        make.set(String::new());
+        model.set(String::new());
+        trim.set(String::new());
+        on_created.run(key);
+    };
+
+    let input_class = "block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm";
+
+    view! {
+        <div class="border border-gray-200 rounded-lg p-4">
+            <h4 class="text-sm font-medium text-gray-900">"New profile"</h4>
+            <p class="text-xs text-gray-500">
+                "Start empty cost tables for a make/model that isn't listed, then add its points. Give a trim to override the make/model profile for cars of that trim only."
+            </p>
+            <div class="mt-3 flex flex-wrap items-end gap-2">
+                <div class="flex-1 min-w-[8rem]">
+                    <label for="new-profile-make" class="block text-xs font-medium text-gray-700">"Make"</label>
+                    <input
+                        id="new-profile-make"
+                        type="text"
+                        class=input_class
+                        prop:value=move || make.get()
+                        on:input=move |ev| make.set(event_target_value(&ev))
+                    />
+                </div>
+                <div class="flex-1 min-w-[8rem]">
+                    <label for="new-profile-model" class="block text-xs font-medium text-gray-700">"Model"</label>
+                    <input
+                        id="new-profile-model"
+                        type="text"
+                        class=input_class
+                        prop:value=move || model.get()
+                        on:input=move |ev| model.set(event_target_value(&ev))
+                    />
+                </div>
+                <div class="flex-1 min-w-[8rem]">
+                    <label for="new-profile-trim" class="block text-xs font-medium text-gray-700">"Trim (optional)"</label>
+                    <input
+                        id="new-profile-trim"
+                        type="text"
+                        class=input_class
+                        placeholder="All trims"
+                        prop:value=move || trim.get()
+                        on:input=move |ev| trim.set(event_target_value(&ev))
+                    />
+                </div>
+                <button
+                    class="inline-flex items-center px-3 py-2 border border-transparent text-sm font-medium rounded-md text-white bg-blue-600 hover:bg-blue-700 disabled:opacity-50 disabled:cursor-not-allowed"
+                    prop:disabled=move || !can_create()
+                    on:click=create
+                >
+                    "Create profile"
+                </button>
+            </div>
+            {move || existing().map(|name| view! {
+                <p class="mt-2 text-sm text-amber-700" role="alert">
+                    {format!("A profile for {} already exists. Select it above to edit its points.", name)}
+                </p>
+            })}
+        </div>
+    }
+}
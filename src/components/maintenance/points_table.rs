@@ -0,0 +1,404 @@
+use leptos::prelude::*;
+
+use crate::components::maintenance::CurveChart;
+use crate::data::{parse_money, parse_points_csv, ImportStatus};
+use crate::models::{scale_x, MaintenanceDataPoint};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SortColumn {
+    X,
+    Y,
+}
+
+/// Sortable, filterable table of one maintenance curve's points with inline add, edit and
+/// delete. The display order is independent of storage, which is always kept sorted by x.
+#[component]
+pub fn PointsTable(
+    title: &'static str,
+    description: &'static str,
+    /// Column heading for the x values
+    x_label: &'static str,
+    /// Multiplier from stored x to displayed x (e.g. `MILES_PER_MILEAGE_X` to show
+    /// 10k-mile units as plain miles)
+    x_scale: f64,
+    points: Signal<Vec<MaintenanceDataPoint>>,
+    on_change: Callback<Vec<MaintenanceDataPoint>>,
+) -> impl IntoView {
+    let (sort, set_sort) = signal((SortColumn::X, true));
+    let (filter_min, set_filter_min) = signal(String::new());
+    let (filter_max, set_filter_max) = signal(String::new());
+    let (editing, set_editing) = signal::<Option<usize>>(None);
+    let (adding, set_adding) = signal(false);
+    let draft_x = RwSignal::new(String::new());
+    let draft_y = RwSignal::new(String::new());
+    let (edit_error, set_edit_error) = signal::<Option<&'static str>>(None);
+    let (show_paste, set_show_paste) = signal(false);
+    let (pasted, set_pasted) = signal(String::new());
+    let paste = Memo::new(move |_| {
+        let text = pasted.get();
+        (!text.trim().is_empty()).then(|| parse_points_csv(&text, x_scale))
+    });
+
+    let commit = move |mut updated: Vec<MaintenanceDataPoint>| {
+        updated.sort_by(|a, b| a.x.total_cmp(&b.x));
+        on_change.run(updated);
+    };
+
+    let toggle_sort = move |column: SortColumn| {
+        set_sort.update(|(col, asc)| {
+            if *col == column {
+                *asc = !*asc;
+            } else {
+                *col = column;
+                *asc = true;
+            }
+        });
+    };
+
+    let aria_sort = move |column: SortColumn| {
+        let (col, asc) = sort.get();
+        if col != column {
+            "none"
+        } else if asc {
+            "ascending"
+        } else {
+            "descending"
+        }
+    };
+
+    // The point in the draft inputs, in stored units
+    let draft_point = move || {
+        let (Some(x), Some(y)) = (
+            parse_money(&draft_x.get_untracked()),
+            parse_money(&draft_y.get_untracked()),
+        ) else {
+            set_edit_error.set(Some("Both values must be numbers."));
+            return None;
+        };
+        Some(MaintenanceDataPoint { x: x / x_scale, y })
+    };
+
+    let save_edit = move |index: usize| {
+        let Some(edited) = draft_point() else {
+            return;
+        };
+        let mut updated = points.get_untracked();
+        if let Some(point) = updated.get_mut(index) {
+            *point = edited;
+        }
+        set_editing.set(None);
+        set_edit_error.set(None);
+        commit(updated);
+    };
+
+    // Start a new point one step past the last, at the last cost
+    let start_add = move |_| {
+        let current = points.get_untracked();
+        let (x, y) = match current.as_slice() {
+            [.., before, last] => (last.x + (last.x - before.x), last.y),
+            [last] => (last.x + 1.0, last.y),
+            [] => (1.0, 0.0),
+        };
+        draft_x.set(format!("{}", scale_x(x, x_scale)));
+        draft_y.set(format!("{}", y));
+        set_edit_error.set(None);
+        set_editing.set(None);
+        set_adding.set(true);
+    };
+
+    let save_add = move |_| {
+        let Some(point) = draft_point() else {
+            return;
+        };
+        let mut updated = points.get_untracked();
+        updated.push(point);
+        set_adding.set(false);
+        set_edit_error.set(None);
+        commit(updated);
+    };
+
+    let delete = move |index: usize| {
+        let mut updated = points.get_untracked();
+        if index < updated.len() {
+            updated.remove(index);
+        }
+        set_editing.set(None);
+        commit(updated);
+    };
+
+    // Indices into the stored points, filtered and ordered for display
+    let visible_rows = move || {
+        let min = parse_money(&filter_min.get());
+        let max = parse_money(&filter_max.get());
+        let (col, asc) = sort.get();
+        let points = points.get();
+
+        let mut rows: Vec<(usize, MaintenanceDataPoint)> = points
+            .into_iter()
+            .enumerate()
+            .filter(|(_, p)| {
+                let x = scale_x(p.x, x_scale);
+                min.is_none_or(|m| x >= m) && max.is_none_or(|m| x <= m)
+            })
+            .collect();
+        rows.sort_by(|(_, a), (_, b)| {
+            let ord = match col {
+                SortColumn::X => a.x.total_cmp(&b.x),
+                SortColumn::Y => a.y.total_cmp(&b.y),
+            };
+            if asc {
+                ord
+            } else {
+                ord.reverse()
+            }
+        });
+        rows
+    };
+
+    let input_class = "block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm";
+    let header_button_class =
+        "inline-flex items-center font-medium text-gray-500 uppercase hover:text-gray-700";
+
+    view! {
+        <div class="border border-gray-200 rounded-lg p-4">
+            <h3 class="text-lg font-semibold text-gray-900 mb-2">{title}</h3>
+            <p class="text-xs text-gray-500 mb-3">{description}</p>
+            {move || view! { <CurveChart points=points.get() x_label=x_label x_scale=x_scale /> }}
+
+            <div class="flex items-center space-x-2 mb-3 text-sm">
+                <span class="text-gray-600">"Show"</span>
+                <input
+                    type="number"
+                    placeholder="min"
+                    aria-label=format!("Minimum {}", x_label)
+                    class="w-24 rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                    prop:value=move || filter_min.get()
+                    on:input=move |ev| set_filter_min.set(event_target_value(&ev))
+                />
+                <span class="text-gray-600">"to"</span>
+                <input
+                    type="number"
+                    placeholder="max"
+                    aria-label=format!("Maximum {}", x_label)
+                    class="w-24 rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                    prop:value=move || filter_max.get()
+                    on:input=move |ev| set_filter_max.set(event_target_value(&ev))
+                />
+                <span class="text-gray-600">{x_label}</span>
+            </div>
+
+            <div class="max-h-96 overflow-y-auto">
+                <table class="min-w-full text-sm">
+                    <thead>
+                        <tr class="text-xs">
+                            <th class="py-1 text-left" aria-sort=move || aria_sort(SortColumn::X)>
+                                <button class=header_button_class on:click=move |_| toggle_sort(SortColumn::X)>
+                                    {x_label}
+                                    {move || match sort.get() {
+                                        (SortColumn::X, true) => " ▲",
+                                        (SortColumn::X, false) => " ▼",
+                                        _ => "",
+                                    }}
+                                </button>
+                            </th>
+                            <th class="py-1 text-left" aria-sort=move || aria_sort(SortColumn::Y)>
+                                <button class=header_button_class on:click=move |_| toggle_sort(SortColumn::Y)>
+                                    "Cumulative cost"
+                                    {move || match sort.get() {
+                                        (SortColumn::Y, true) => " ▲",
+                                        (SortColumn::Y, false) => " ▼",
+                                        _ => "",
+                                    }}
+                                </button>
+                            </th>
+                            <th class="py-1"><span class="sr-only">"Actions"</span></th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        {move || {
+                            let editing_index = editing.get();
+                            visible_rows()
+                                .into_iter()
+                                .map(|(index, point)| {
+                                    if editing_index == Some(index) {
+                                        view! {
+                                            <tr class="border-t border-gray-100">
+                                                <td class="py-1 pr-2">
+                                                    <input
+                                                        type="number"
+                                                        aria-label=x_label
+                                                        class=input_class
+                                                        prop:value=move || draft_x.get()
+                                                        on:input=move |ev| draft_x.set(event_target_value(&ev))
+                                                    />
+                                                </td>
+                                                <td class="py-1 pr-2">
+                                                    <input
+                                                        type="number"
+                                                        aria-label="Cumulative cost"
+                                                        class=input_class
+                                                        prop:value=move || draft_y.get()
+                                                        on:input=move |ev| draft_y.set(event_target_value(&ev))
+                                                    />
+                                                </td>
+                                                <td class="py-1 text-right whitespace-nowrap space-x-2">
+                                                    <button class="text-blue-600 hover:text-blue-800" on:click=move |_| save_edit(index)>
+                                                        "Save"
+                                                    </button>
+                                                    <button
+                                                        class="text-gray-600 hover:text-gray-800"
+                                                        on:click=move |_| {
+                                                            set_editing.set(None);
+                                                            set_edit_error.set(None);
+                                                        }
+                                                    >
+                                                        "Cancel"
+                                                    </button>
+                                                </td>
+                                            </tr>
+                                        }
+                                        .into_any()
+                                    } else {
+                                        view! {
+                                            <tr class="border-t border-gray-100">
+                                                <td class="py-1 text-gray-600">{format!("{}", scale_x(point.x, x_scale))}</td>
+                                                <td class="py-1 text-gray-900">{format!("${:.2}", point.y)}</td>
+                                                <td class="py-1 text-right whitespace-nowrap space-x-2">
+                                                    <button
+                                                        class="text-blue-600 hover:text-blue-800"
+                                                        on:click=move |_| {
+                                                            draft_x.set(format!("{}", scale_x(point.x, x_scale)));
+                                                            draft_y.set(format!("{}", point.y));
+                                                            set_edit_error.set(None);
+                                                            set_adding.set(false);
+                                                            set_editing.set(Some(index));
+                                                        }
+                                                    >
+                                                        "Edit"
+                                                    </button>
+                                                    <button class="text-red-600 hover:text-red-800" on:click=move |_| delete(index)>
+                                                        "Delete"
+                                                    </button>
+                                                </td>
+                                            </tr>
+                                        }
+                                        .into_any()
+                                    }
+                                })
+                                .collect_view()
+                        }}
+                        <tr class="border-t border-gray-100" class:hidden=move || !adding.get()>
+                            <td class="py-1 pr-2">
+                                <input
+                                    type="number"
+                                    aria-label=format!("New point {}", x_label)
+                                    class=input_class
+                                    prop:value=move || draft_x.get()
+                                    on:input=move |ev| draft_x.set(event_target_value(&ev))
+                                />
+                            </td>
+                            <td class="py-1 pr-2">
+                                <input
+                                    type="number"
+                                    aria-label="New point cumulative cost"
+                                    class=input_class
+                                    prop:value=move || draft_y.get()
+                                    on:input=move |ev| draft_y.set(event_target_value(&ev))
+                                />
+                            </td>
+                            <td class="py-1 text-right whitespace-nowrap space-x-2">
+                                <button class="text-blue-600 hover:text-blue-800" on:click=save_add>
+                                    "Add"
+                                </button>
+                                <button
+                                    class="text-gray-600 hover:text-gray-800"
+                                    on:click=move |_| {
+                                        set_adding.set(false);
+                                        set_edit_error.set(None);
+                                    }
+                                >
+                                    "Cancel"
+                                </button>
+                            </td>
+                        </tr>
+                    </tbody>
+                </table>
+            </div>
+            <button
+                class="mt-2 text-sm text-blue-600 hover:text-blue-800"
+                class:hidden=move || adding.get()
+                on:click=start_add
+            >
+                "+ Add point"
+            </button>
+            <button
+                class="mt-2 ml-4 text-sm text-blue-600 hover:text-blue-800"
+                aria-expanded=move || show_paste.get().to_string()
+                on:click=move |_| set_show_paste.update(|v| *v = !*v)
+            >
+                "Paste CSV…"
+            </button>
+            <Show when=move || show_paste.get()>
+                <div class="mt-2 space-y-2">
+                    <p class="text-xs text-gray-500">
+                        {format!(
+                            "One point per line: {}, cumulative cost (e.g. copied from a spreadsheet). A header row is skipped.",
+                            x_label.to_lowercase()
+                        )}
+                    </p>
+                    <textarea
+                        rows="4"
+                        aria-label=format!("{} points as CSV", title)
+                        class="block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm font-mono"
+                        prop:value=move || pasted.get()
+                        on:input=move |ev| set_pasted.set(event_target_value(&ev))
+                    ></textarea>
+                    {move || paste.get().map(|paste| view! {
+                        <p class="text-sm text-gray-600">
+                            {format!("{} point(s) ready", paste.points.len())}
+                        </p>
+                        <ul class="text-sm space-y-0.5">
+                            {paste.report.records.into_iter().map(|record| {
+                                let class = if record.status == ImportStatus::Skipped {
+                                    "text-red-600"
+                                } else {
+                                    "text-gray-500"
+                                };
+                                view! { <li class=class>{format!("{}: {}", record.label, record.reason)}</li> }
+                            }).collect_view()}
+                        </ul>
+                    })}
+                    <div class="flex justify-end gap-2">
+                        <button
+                            class="px-3 py-1.5 text-sm rounded-md border border-gray-300 text-gray-700 bg-white hover:bg-gray-50 disabled:opacity-50"
+                            prop:disabled=move || paste.with(|p| p.as_ref().is_none_or(|p| p.points.is_empty()))
+                            on:click=move |_| {
+                                if let Some(paste) = paste.get_untracked() {
+                                    commit(paste.apply_to(&points.get_untracked(), true));
+                                    set_pasted.set(String::new());
+                                    set_show_paste.set(false);
+                                }
+                            }
+                        >
+                            "Add to table"
+                        </button>
+                        <button
+                            class="px-3 py-1.5 text-sm rounded-md text-white bg-blue-600 hover:bg-blue-700 disabled:opacity-50"
+                            prop:disabled=move || paste.with(|p| p.as_ref().is_none_or(|p| p.points.is_empty()))
+                            on:click=move |_| {
+                                if let Some(paste) = paste.get_untracked() {
+                                    commit(paste.apply_to(&points.get_untracked(), false));
+                                    set_pasted.set(String::new());
+                                    set_show_paste.set(false);
+                                }
+                            }
+                        >
+                            "Replace table"
+                        </button>
+                    </div>
+                </div>
+            </Show>
+            {move || edit_error.get().map(|msg| view! { <p class="mt-2 text-sm text-red-600">{msg}</p> })}
+        </div>
+    }
+}
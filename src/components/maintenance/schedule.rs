@@ -0,0 +1,163 @@
+use leptos::prelude::*;
+
+use crate::data::{format_thousands, schedule_to_cumulative, ServiceEntry};
+use crate::models::{MaintenanceCostDatabase, MILES_PER_MILEAGE_X};
+
+/// One editable row of the schedule
+#[derive(Clone, Copy)]
+struct ScheduleRow {
+    id: usize,
+    miles: RwSignal<String>,
+    cost: RwSignal<String>,
+}
+
+/// Builds the By Mileage table from a manufacturer's service schedule, entered as the
+/// cost of each service rather than a running total
+#[component]
+pub fn ServiceScheduleEditor(
+    /// Key of the profile whose By Mileage table is replaced
+    profile_key: String,
+    set_maintenance_db: WriteSignal<MaintenanceCostDatabase>,
+) -> impl IntoView {
+    let next_id = StoredValue::new(0_usize);
+    let new_row = move || {
+        let id = next_id.get_value();
+        next_id.set_value(id + 1);
+        ScheduleRow {
+            id,
+            miles: RwSignal::new(String::new()),
+            cost: RwSignal::new(String::new()),
+        }
+    };
+    let rows = RwSignal::new(vec![new_row()]);
+    let profile_key = StoredValue::new(profile_key);
+
+    let entries = move || {
+        rows.get()
+            .iter()
+            .filter_map(|row| {
+                let miles = row
+                    .miles
+                    .get()
+                    .trim()
+                    .replace(',', "")
+                    .parse::<f64>()
+                    .ok()?;
+                let cost = row
+                    .cost
+                    .get()
+                    .trim()
+                    .trim_start_matches('$')
+                    .replace(',', "")
+                    .parse::<f64>()
+                    .ok()?;
+                Some(ServiceEntry { miles, cost })
+            })
+            .collect::<Vec<_>>()
+    };
+    let cumulative = move || schedule_to_cumulative(&entries());
+
+    let apply = move |_| {
+        let points = cumulative();
+        if points.is_empty() {
+            return;
+        }
+        let confirmed = window()
+            .confirm_with_message(
+                "Replace this vehicle's By Mileage points with the running total of this schedule?",
+            )
+            .unwrap_or(false);
+        if confirmed {
+            let key = profile_key.get_value();
+            set_maintenance_db.update(|db| {
+                if let Some(data) = db.data.get_mut(&key) {
+                    data.by_mileage = points;
+                }
+            });
+        }
+    };
+
+    view! {
+        <div class="mt-4 border border-gray-200 rounded-lg p-4">
+            <h4 class="text-sm font-medium text-gray-900">"Build from service schedule"</h4>
+            <p class="text-xs text-gray-500">
+                "Enter each service from the maintenance schedule with its own cost, not a running total. They're added up into the By Mileage table."
+            </p>
+            <div class="mt-3 grid grid-cols-1 md:grid-cols-2 gap-6">
+                <div class="space-y-2">
+                    <div class="flex items-center space-x-2 text-xs font-medium text-gray-500 uppercase">
+                        <span class="flex-1">"Service at (miles)"</span>
+                        <span class="flex-1">"Cost of that service"</span>
+                        <span class="w-6"></span>
+                    </div>
+                    <For
+                        each=move || rows.get()
+                        key=|row| row.id
+                        children=move |row| {
+                            view! {
+                                <div class="flex items-center space-x-2">
+                                    <input
+                                        type="text"
+                                        inputmode="numeric"
+                                        aria-label="Service mileage"
+                                        placeholder="30000"
+                                        class="flex-1 min-w-0 rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                                        prop:value=move || row.miles.get()
+                                        on:input=move |ev| row.miles.set(event_target_value(&ev))
+                                    />
+                                    <input
+                                        type="text"
+                                        inputmode="decimal"
+                                        aria-label="Service cost"
+                                        placeholder="450"
+                                        class="flex-1 min-w-0 rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                                        prop:value=move || row.cost.get()
+                                        on:input=move |ev| row.cost.set(event_target_value(&ev))
+                                    />
+                                    <button
+                                        class="w-6 text-gray-400 hover:text-red-600"
+                                        aria-label="Remove service"
+                                        on:click=move |_| rows.update(|rows| rows.retain(|r| r.id != row.id))
+                                    >
+                                        "×"
+                                    </button>
+                                </div>
+                            }
+                        }
+                    />
+                    <button
+                        class="text-sm font-medium text-blue-600 hover:text-blue-800"
+                        on:click=move |_| rows.update(|rows| rows.push(new_row()))
+                    >
+                        "+ Add service"
+                    </button>
+                </div>
+                <div>
+                    <h5 class="text-xs font-medium text-gray-500 uppercase mb-2">"Cumulative cost"</h5>
+                    <div class="space-y-1 max-h-64 overflow-y-auto text-sm">
+                        {move || {
+                            cumulative()
+                                .into_iter()
+                                .map(|p| view! {
+                                    <div class="flex items-center space-x-2">
+                                        <span class="w-20 text-gray-600">{format!("{} mi", format_thousands(p.x * MILES_PER_MILEAGE_X))}</span>
+                                        <span class="text-gray-900">{format!("${:.2}", p.y)}</span>
+                                    </div>
+                                })
+                                .collect_view()
+                        }}
+                    </div>
+                    <div class="mt-4 flex justify-end">
+                        <button
+                            class="inline-flex items-center px-3 py-2 border border-transparent text-sm font-medium rounded-md text-white bg-blue-600 hover:bg-blue-700 disabled:opacity-50 disabled:cursor-not-allowed"
+                            prop:disabled=move || cumulative().is_empty()
+                            on:click=apply
+                        >
+                            "Replace By Mileage table"
+                        </button>
+                    </div>
+                </div>
+            </div>
+        </div>
+    }
+}
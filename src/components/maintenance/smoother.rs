@@ -0,0 +1,121 @@
+use leptos::prelude::*;
+
+use crate::models::{
+    scale_x, MaintenanceCostData, MaintenanceCostDatabase, MaintenanceDataPoint,
+    MILES_PER_MILEAGE_X,
+};
+
+/// Smooths a jagged maintenance curve, previewing the result before it is saved
+#[component]
+pub fn CurveSmoother(
+    data: MaintenanceCostData,
+    set_maintenance_db: WriteSignal<MaintenanceCostDatabase>,
+) -> impl IntoView {
+    let (window, set_window) = signal(3_usize);
+    let (preview, set_preview) = signal::<Option<MaintenanceCostData>>(None);
+
+    let original = StoredValue::new(data);
+
+    let preview_rows = |before: Vec<MaintenanceDataPoint>,
+                        after: Vec<MaintenanceDataPoint>,
+                        scale: f64,
+                        unit: &'static str| {
+        before
+            .into_iter()
+            .zip(after)
+            .map(|(b, a)| {
+                let changed = (a.y - b.y).abs() >= 0.005;
+                view! {
+                    <div class="flex items-center space-x-2 text-sm">
+                        <span class="w-20 text-gray-600">{format!("{} {}", scale_x(b.x, scale), unit)}</span>
+                        <span class="w-24 text-gray-500">{format!("${:.2}", b.y)}</span>
+                        <span class=if changed { "flex-1 font-medium text-blue-700" } else { "flex-1 text-gray-900" }>
+                            {format!("${:.2}", a.y)}
+                        </span>
+                    </div>
+                }
+            })
+            .collect_view()
+    };
+
+    view! {
+        <div class="mt-4 border border-gray-200 rounded-lg p-4">
+            <div class="flex items-center justify-between">
+                <div>
+                    <h4 class="text-sm font-medium text-gray-900">"Smooth curve"</h4>
+                    <p class="text-xs text-gray-500">
+                        "Averages neighbouring points to even out hand-entered data. First and last values are kept."
+                    </p>
+                </div>
+                <div class="flex items-center space-x-2">
+                    <select
+                        class="rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                        on:change=move |ev| {
+                            let value = event_target_value(&ev).parse::<usize>().unwrap_or(3);
+                            set_window.set(value);
+                            set_preview.set(None);
+                        }
+                    >
+                        <option value="3" selected=true>"3-point window"</option>
+                        <option value="5">"5-point window"</option>
+                        <option value="7">"7-point window"</option>
+                    </select>
+                    <button
+                        class="inline-flex items-center px-3 py-2 border border-gray-300 text-sm font-medium rounded-md text-gray-700 bg-white hover:bg-gray-50"
+                        on:click=move |_| {
+                            set_preview.set(Some(original.with_value(|d| d.smooth(window.get()))));
+                        }
+                    >
+                        "Preview"
+                    </button>
+                </div>
+            </div>
+
+            {move || {
+                preview.get().map(|smoothed| {
+                    let current = original.get_value();
+                    view! {
+                        <div class="mt-4 grid grid-cols-1 md:grid-cols-2 gap-6">
+                            <div>
+                                <h5 class="text-xs font-medium text-gray-500 uppercase mb-2">"By Mileage (before → after)"</h5>
+                                <div class="space-y-1 max-h-64 overflow-y-auto">
+                                    {preview_rows(
+                                        current.by_mileage.clone(),
+                                        smoothed.by_mileage.clone(),
+                                        MILES_PER_MILEAGE_X,
+                                        "mi",
+                                    )}
+                                </div>
+                            </div>
+                            <div>
+                                <h5 class="text-xs font-medium text-gray-500 uppercase mb-2">"By Time (before → after)"</h5>
+                                <div class="space-y-1 max-h-64 overflow-y-auto">
+                                    {preview_rows(current.by_time.clone(), smoothed.by_time.clone(), 1.0, "yr")}
+                                </div>
+                            </div>
+                        </div>
+                        <div class="mt-4 flex justify-end space-x-2">
+                            <button
+                                class="inline-flex items-center px-3 py-2 border border-gray-300 text-sm font-medium rounded-md text-gray-700 bg-white hover:bg-gray-50"
+                                on:click=move |_| set_preview.set(None)
+                            >
+                                "Discard"
+                            </button>
+                            <button
+                                class="inline-flex items-center px-3 py-2 border border-transparent text-sm font-medium rounded-md text-white bg-blue-600 hover:bg-blue-700"
+                                on:click=move |_| {
+                                    if let Some(smoothed) = preview.get_untracked() {
+                                        set_maintenance_db.update(|db| db.set(smoothed));
+                                    }
+                                    set_preview.set(None);
+                                }
+                            >
+                                "Apply smoothing"
+                            </button>
+                        </div>
+                    }
+                })
+            }}
+        </div>
+    }
+}
@@ -1,10 +1,14 @@
 mod app;
+mod backup;
 mod cars;
+mod comparison;
 mod home;
 mod maintenance;
+mod session_link;
 mod settings;
 pub mod ui;
 
 pub use app::App;
+pub use cars::car_in_list;
 pub use home::HomePage;
 // Internal modules are re-exported where needed through their parent modules
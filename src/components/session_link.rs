@@ -0,0 +1,123 @@
+use leptos::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::HtmlAnchorElement;
+
+use crate::data::{encode_session_fragment, SessionState};
+use crate::models::{Car, SharedSettings};
+
+/// Full link to this page opening `state`, e.g. for sharing a single car
+pub fn share_url(state: &SessionState) -> String {
+    let location = window().location();
+    format!(
+        "{}{}{}",
+        location.origin().unwrap_or_default(),
+        location.pathname().unwrap_or_default(),
+        encode_session_fragment(state)
+    )
+}
+
+/// Download the full session (photos included) as a JSON file
+fn download_session(settings: &SharedSettings, cars: &[Car]) {
+    let state = SessionState {
+        settings: settings.clone(),
+        cars: cars.to_vec(),
+    };
+    let Ok(json) = serde_json::to_string_pretty(&state) else {
+        return;
+    };
+    let url = format!(
+        "data:application/json;charset=utf-8,{}",
+        js_sys::encode_uri_component(&json)
+    );
+    if let Some(anchor) = document()
+        .create_element("a")
+        .ok()
+        .and_then(|el| el.dyn_into::<HtmlAnchorElement>().ok())
+    {
+        anchor.set_href(&url);
+        anchor.set_download("carcalc-session.json");
+        anchor.click();
+    }
+}
+
+/// Notices about the `#state=` link: a session opened from a link (not yet saved to this
+/// browser), a link that couldn't be read, or a session too large to keep in the URL
+#[component]
+pub fn SessionLinkBanner(
+    settings: Signal<SharedSettings>,
+    cars: Signal<Vec<Car>>,
+    set_settings: WriteSignal<SharedSettings>,
+    set_cars: WriteSignal<Vec<Car>>,
+    /// What's in local storage, which saving a shared link adds to or replaces
+    saved: Signal<SessionState>,
+    /// Whether edits are being saved to local storage; false while viewing a shared link
+    persist: RwSignal<bool>,
+    link_error: Option<String>,
+    oversized: Signal<bool>,
+) -> impl IntoView {
+    let download = move |_| {
+        settings
+            .with_untracked(|settings| cars.with_untracked(|cars| download_session(settings, cars)))
+    };
+
+    // Keep what's already saved: add the link's cars alongside it under fresh ids
+    let save_merged = move |_| {
+        let shared = SessionState {
+            settings: settings.get_untracked(),
+            cars: cars.get_untracked(),
+        };
+        let merged = shared.merged_into(&saved.get_untracked());
+        set_settings.set(merged.settings);
+        set_cars.set(merged.cars);
+        persist.set(true);
+    };
+    let replace_saved = move |_| {
+        let confirmed = window()
+            .confirm_with_message(
+                "Replace all the cars and settings saved in this browser with this shared link's?",
+            )
+            .unwrap_or(false);
+        if confirmed {
+            persist.set(true);
+        }
+    };
+
+    view! {
+        {link_error.map(|error| view! {
+            <div class="rounded-md bg-red-50 border border-red-200 p-4 text-sm text-red-800" role="alert">
+                {format!("{}. Showing the data saved in this browser instead.", error)}
+            </div>
+        })}
+        <Show when=move || !persist.get()>
+            <div class="rounded-md bg-blue-50 border border-blue-200 p-4 text-sm text-blue-800 flex flex-wrap items-center justify-between gap-2">
+                <span>"Viewing a shared link. Changes aren't saved to this browser until you choose to keep them."</span>
+                <div class="flex flex-wrap gap-2">
+                    <button
+                        class="px-3 py-1 rounded-md bg-blue-600 text-white font-medium hover:bg-blue-700"
+                        title="Adds the link's cars to the ones saved here and keeps your settings"
+                        on:click=save_merged
+                    >
+                        "Save to this browser"
+                    </button>
+                    <button
+                        class="px-3 py-1 rounded-md border border-blue-300 bg-white font-medium hover:bg-blue-100"
+                        on:click=replace_saved
+                    >
+                        "Replace everything"
+                    </button>
+                </div>
+            </div>
+        </Show>
+        <Show when=move || oversized.get()>
+            <div class="rounded-md bg-amber-50 border border-amber-200 p-4 text-sm text-amber-800 flex flex-wrap items-center justify-between gap-2">
+                <span>"This session is too large to share in the page link. Download it as a file instead."</span>
+                <button
+                    class="px-3 py-1 rounded-md border border-amber-300 bg-white font-medium hover:bg-amber-100"
+                    on:click=download
+                >
+                    "Download session"
+                </button>
+            </div>
+        </Show>
+    }
+}
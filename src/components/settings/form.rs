@@ -1,87 +1,602 @@
+use codee::string::FromToStringCodec;
 use leptos::prelude::*;
+use leptos_use::storage::use_local_storage;
 
 use crate::components::ui::Tooltip;
-use crate::models::SharedSettings;
+use crate::data::format_thousands;
+use crate::models::{
+    AnnualCostBasis, PrimaryMetric, SettingsIssue, SettingsPreset, SharedSettings, REGION_PRESETS,
+    SETTINGS_PRESETS,
+};
 
 #[component]
 pub fn SharedSettingsForm(
     settings: Signal<SharedSettings>,
     set_settings: WriteSignal<SharedSettings>,
 ) -> impl IntoView {
+    // A setting that stops every car from being computed is flagged on its own input
+    let has_issue = move |issue: SettingsIssue| settings.with(|s| s.validate() == Err(issue));
+    // Text typed into a must-be-positive input that wasn't saved, kept so the input shows
+    // what was typed instead of snapping back. Cleared when the setting changes elsewhere,
+    // e.g. from a preset.
+    let rejected_input = move |value: Memo<f64>| {
+        let rejected = RwSignal::new(None::<String>);
+        Effect::new(move |_| {
+            value.track();
+            rejected.set(None);
+        });
+        rejected
+    };
+    let rejected_mileage = rejected_input(Memo::new(move |_| settings.with(|s| s.annual_mileage)));
+    let rejected_lifetime = rejected_input(Memo::new(move |_| settings.with(|s| s.lifetime_miles)));
+    let rejected_gas_price =
+        rejected_input(Memo::new(move |_| settings.with(|s| s.average_gas_price)));
+    let rejected_for = move |issue: SettingsIssue| match issue {
+        SettingsIssue::AnnualMileage => rejected_mileage,
+        SettingsIssue::LifetimeMiles => rejected_lifetime,
+    };
+    let is_invalid =
+        move |issue: SettingsIssue| has_issue(issue) || rejected_for(issue).with(Option::is_some);
+    let issue_hint = move |issue: SettingsIssue| {
+        view! {
+            <Show when=move || is_invalid(issue)>
+                <p id=format!("{}-error", issue.input_id()) class="mt-1 text-sm text-red-600">
+                    "Must be greater than zero"
+                </p>
+            </Show>
+        }
+    };
+
+    // Collapsing is remembered across visits; a setting that needs fixing keeps it open
+    let (collapsed_pref, set_collapsed_pref, _) =
+        use_local_storage::<bool, FromToStringCodec>("carcalc_settings_collapsed");
+    let collapsed = move || collapsed_pref.get() && settings.with(|s| s.validate().is_ok());
+    let summary = move || {
+        settings.with(|s| {
+            format!(
+                "{} mi/yr · ${:.2}/gal · {}% opportunity",
+                format_thousands(s.annual_mileage),
+                s.average_gas_price,
+                s.opportunity_cost_rate
+            )
+        })
+    };
+
     view! {
         <div class="bg-white overflow-hidden shadow rounded-lg">
             <div class="px-4 py-5 sm:p-6">
-                <h2 class="text-xl font-semibold text-gray-900 mb-4">
-                    "Shared Settings"
-                </h2>
-                <div class="grid grid-cols-1 gap-6 sm:grid-cols-2">
-                    <div>
-                        <label for="opportunity-rate" class="block text-sm font-medium text-gray-700 inline-flex items-center">
-                            "Opportunity Cost Rate (%)"
-                            <Tooltip text="The annual return rate you could earn by investing the money instead of tying it up in a car. This represents the financial opportunity you're giving up. Common values: 8-10% for stock market average, 5% for conservative investments." />
-                        </label>
-                        <input
-                            type="number"
-                            step="0.1"
-                            id="opportunity-rate"
-                            class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
-                            prop:value=move || settings.get().opportunity_cost_rate
-                            on:input=move |ev| {
-                                let value = event_target_value(&ev).parse::<f64>().unwrap_or(8.0);
-                                set_settings.update(|s| s.opportunity_cost_rate = value);
-                            }
-                        />
+                <div class="flex flex-wrap items-center justify-between gap-2">
+                    <div class="flex-1 min-w-0">
+                        <h2 class="text-xl font-semibold text-gray-900">
+                            "Shared Settings"
+                        </h2>
+                        <p class="mt-1 text-sm text-gray-600 truncate" class:hidden=move || !collapsed()>
+                            {summary}
+                        </p>
                     </div>
-                    <div>
-                        <label for="annual-mileage" class="block text-sm font-medium text-gray-700 inline-flex items-center">
-                            "Annual Mileage"
-                            <Tooltip text="How many miles you expect to drive per year. This affects fuel costs and maintenance schedules. Average values: 12,000-15,000 miles for typical commuters, 20,000+ for high-mileage drivers, 5,000-8,000 for occasional drivers." />
-                        </label>
-                        <input
-                            type="number"
-                            step="1000"
-                            id="annual-mileage"
-                            class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
-                            prop:value=move || settings.get().annual_mileage
-                            on:input=move |ev| {
-                                let value = event_target_value(&ev).parse::<f64>().unwrap_or(12000.0);
-                                set_settings.update(|s| s.annual_mileage = value);
-                            }
-                        />
+                    <div class="inline-flex items-center text-sm text-gray-600">
+                        <span class="mr-2">"Headline"</span>
+                        <div role="radiogroup" aria-label="Headline metric" class="inline-flex rounded-md shadow-sm">
+                            {PrimaryMetric::ALL
+                                .into_iter()
+                                .map(|metric| {
+                                    let is_selected = move || settings.get().primary_metric == metric;
+                                    view! {
+                                        <button
+                                            type="button"
+                                            role="radio"
+                                            aria-checked=move || is_selected().to_string()
+                                            class=move || format!(
+                                                "px-3 py-1 text-sm border border-gray-300 first:rounded-l-md last:rounded-r-md -ml-px first:ml-0 {}",
+                                                if is_selected() { "bg-blue-600 text-white border-blue-600" } else { "bg-white text-gray-700 hover:bg-gray-50" }
+                                            )
+                                            on:click=move |_| set_settings.update(|s| s.primary_metric = metric)
+                                        >
+                                            {metric.short_label()}
+                                        </button>
+                                    }
+                                })
+                                .collect_view()}
+                        </div>
                     </div>
-                    <div>
-                        <label for="lifetime-miles" class="block text-sm font-medium text-gray-700 inline-flex items-center">
-                            "Default Lifetime Miles"
-                            <Tooltip text="The total miles you plan to drive a car over its lifetime with you. This determines how long you'll own the vehicle and affects total cost calculations. Common values: 150,000-200,000 miles for most vehicles, 250,000+ for highly reliable cars." />
-                        </label>
-                        <input
-                            type="number"
-                            step="10000"
-                            id="lifetime-miles"
-                            class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
-                            prop:value=move || settings.get().lifetime_miles
-                            on:input=move |ev| {
-                                let value = event_target_value(&ev).parse::<f64>().unwrap_or(200000.0);
-                                set_settings.update(|s| s.lifetime_miles = value);
-                            }
-                        />
+                    <button
+                        class="text-gray-600 hover:text-gray-800"
+                        aria-controls="shared-settings-body"
+                        aria-expanded=move || (!collapsed()).to_string()
+                        aria-label=move || if collapsed() { "Show settings" } else { "Hide settings" }
+                        on:click=move |_| set_collapsed_pref.set(!collapsed())
+                    >
+                        <svg
+                            class=move || format!(
+                                "h-6 w-6 transform transition-transform {}",
+                                if collapsed() { "" } else { "rotate-180" }
+                            )
+                            xmlns="http://www.w3.org/2000/svg"
+                            viewBox="0 0 20 20"
+                            fill="currentColor"
+                        >
+                            <path fill-rule="evenodd" d="M5.293 7.293a1 1 0 011.414 0L10 10.586l3.293-3.293a1 1 0 111.414 1.414l-4 4a1 1 0 01-1.414 0l-4-4a1 1 0 010-1.414z" clip-rule="evenodd"/>
+                        </svg>
+                    </button>
+                </div>
+                <div id="shared-settings-body" class="mt-4" class:hidden=collapsed>
+                    <div class="mb-6 flex flex-wrap items-center gap-2 text-sm">
+                        <span class="text-gray-700">"Start from a preset:"</span>
+                        {SETTINGS_PRESETS
+                            .iter()
+                            .map(|preset| {
+                                let is_active = move || settings.with(|s| preset.matches(s));
+                                view! {
+                                    <button
+                                        type="button"
+                                        title=preset.description
+                                        aria-pressed=move || is_active().to_string()
+                                        class=move || format!(
+                                            "px-3 py-1 rounded-full border {}",
+                                            if is_active() { "bg-blue-600 text-white border-blue-600" } else { "bg-white text-gray-700 border-gray-300 hover:bg-gray-50" }
+                                        )
+                                        on:click=move |_| set_settings.update(|s| *s = preset.apply(s))
+                                    >
+                                        {preset.name}
+                                    </button>
+                                }
+                            })
+                            .collect_view()}
+                        <span class="text-gray-500">
+                            {move || match settings.with(SettingsPreset::matching) {
+                                Some(preset) => format!("Matches “{}”", preset.name),
+                                None => "Custom values".to_string(),
+                            }}
+                        </span>
                     </div>
-                    <div>
-                        <label for="gas-price" class="block text-sm font-medium text-gray-700 inline-flex items-center">
-                            "Average Gas Price ($/gallon)"
-                            <Tooltip text="The average price per gallon of gas in your area. This affects the fuel cost calculation. Check your local gas station prices or use national averages. Consider using a long-term average rather than current prices for more stable comparisons." />
-                        </label>
-                        <input
-                            type="number"
-                            step="0.01"
-                            id="gas-price"
-                            class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
-                            prop:value=move || settings.get().average_gas_price
-                            on:input=move |ev| {
-                                let value = event_target_value(&ev).parse::<f64>().unwrap_or(3.50);
-                                set_settings.update(|s| s.average_gas_price = value);
-                            }
-                        />
+                    <div class="grid grid-cols-1 gap-6 sm:grid-cols-2">
+                        <div>
+                            <label for="opportunity-rate" class="block text-sm font-medium text-gray-700 inline-flex items-center">
+                                "Opportunity Cost Rate (%)"
+                                <Tooltip text="The annual return rate you could earn by investing the money instead of tying it up in a car. This represents the financial opportunity you're giving up. Common values: 8-10% for stock market average, 5% for conservative investments." />
+                            </label>
+                            <input
+                                type="number"
+                                step="0.1"
+                                id="opportunity-rate"
+                                class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                                prop:value=move || settings.get().opportunity_cost_rate
+                                on:input=move |ev| {
+                                    let value = event_target_value(&ev).parse::<f64>().unwrap_or(8.0);
+                                    set_settings.update(|s| s.opportunity_cost_rate = value);
+                                }
+                            />
+                            <label class="mt-2 flex items-center text-sm text-gray-700">
+                                <input
+                                    type="checkbox"
+                                    class="mr-2 rounded border-gray-300 text-blue-600 focus:ring-blue-500"
+                                    prop:checked=move || settings.get().operating_cost_includes_opportunity
+                                    on:change=move |ev| {
+                                        let checked = event_target_checked(&ev);
+                                        set_settings.update(|s| s.operating_cost_includes_opportunity = checked);
+                                    }
+                                />
+                                "Count toward annual operating cost"
+                            </label>
+                            <label class="mt-1 flex items-center text-sm text-gray-700">
+                                <input
+                                    type="checkbox"
+                                    class="mr-2 rounded border-gray-300 text-blue-600 focus:ring-blue-500"
+                                    prop:checked=move || settings.get().compound_opportunity_cost
+                                    on:change=move |ev| {
+                                        let checked = event_target_checked(&ev);
+                                        set_settings.update(|s| s.compound_opportunity_cost = checked);
+                                    }
+                                />
+                                "Compound yearly (instead of simple interest)"
+                            </label>
+                            <label class="mt-1 flex items-center text-sm text-gray-700">
+                                <input
+                                    type="checkbox"
+                                    class="mr-2 rounded border-gray-300 text-blue-600 focus:ring-blue-500"
+                                    prop:checked=move || settings.get().discounted_total
+                                    on:change=move |ev| {
+                                        let checked = event_target_checked(&ev);
+                                        set_settings.update(|s| s.discounted_total = checked);
+                                    }
+                                />
+                                "Also show a discounted total (net present value)"
+                            </label>
+                        </div>
+                        <div>
+                            <label for="annual-mileage" class="block text-sm font-medium text-gray-700 inline-flex items-center">
+                                "Annual Mileage"
+                                <Tooltip text="How many miles you expect to drive per year. This affects fuel costs and maintenance schedules. Average values: 12,000-15,000 miles for typical commuters, 20,000+ for high-mileage drivers, 5,000-8,000 for occasional drivers." />
+                            </label>
+                            <input
+                                type="number"
+                                step="1000"
+                                id="annual-mileage"
+                                class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                                class:border-red-500=move || is_invalid(SettingsIssue::AnnualMileage)
+                                aria-invalid=move || is_invalid(SettingsIssue::AnnualMileage).to_string()
+                                aria-describedby="annual-mileage-error"
+                                prop:value=move || rejected_mileage.get().unwrap_or_else(|| settings.get().annual_mileage.to_string())
+                                on:input=move |ev| {
+                                    let text = event_target_value(&ev);
+                                    match SharedSettings::parse_positive(&text) {
+                                        Some(value) => {
+                                            rejected_mileage.set(None);
+                                            set_settings.update(|s| s.annual_mileage = value);
+                                        }
+                                        None => rejected_mileage.set(Some(text)),
+                                    }
+                                }
+                            />
+                            {issue_hint(SettingsIssue::AnnualMileage)}
+                        </div>
+                        <div>
+                            <label for="lifetime-miles" class="block text-sm font-medium text-gray-700 inline-flex items-center">
+                                "Default Lifetime Miles"
+                                <Tooltip text="The total miles you plan to drive a car over its lifetime with you. This determines how long you'll own the vehicle and affects total cost calculations. Common values: 150,000-200,000 miles for most vehicles, 250,000+ for highly reliable cars." />
+                            </label>
+                            <input
+                                type="number"
+                                step="10000"
+                                id="lifetime-miles"
+                                class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                                class:border-red-500=move || is_invalid(SettingsIssue::LifetimeMiles)
+                                aria-invalid=move || is_invalid(SettingsIssue::LifetimeMiles).to_string()
+                                aria-describedby="lifetime-miles-error"
+                                prop:value=move || rejected_lifetime.get().unwrap_or_else(|| settings.get().lifetime_miles.to_string())
+                                on:input=move |ev| {
+                                    let text = event_target_value(&ev);
+                                    match SharedSettings::parse_positive(&text) {
+                                        Some(value) => {
+                                            rejected_lifetime.set(None);
+                                            set_settings.update(|s| s.lifetime_miles = value);
+                                        }
+                                        None => rejected_lifetime.set(Some(text)),
+                                    }
+                                }
+                            />
+                            {issue_hint(SettingsIssue::LifetimeMiles)}
+                        </div>
+                        <div>
+                            <label for="ownership-years" class="block text-sm font-medium text-gray-700 inline-flex items-center">
+                                "Years of Ownership From Today (optional)"
+                                <Tooltip text="Replace every car after this many years from today, whatever its mileage, for people who trade in on a fixed schedule. Ownership ends at this point, the lifetime mileage, or a car's own Keep Until Age (its age from new), whichever comes first. Leave blank to keep each car to its lifetime mileage." />
+                            </label>
+                            <input
+                                type="number"
+                                step="1"
+                                min="0"
+                                id="ownership-years"
+                                class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                                placeholder="Until lifetime miles"
+                                prop:value=move || settings.get().ownership_years.map(|y| y.to_string()).unwrap_or_default()
+                                on:input=move |ev| {
+                                    let value = event_target_value(&ev).parse::<f64>().ok().filter(|y| *y > 0.0);
+                                    set_settings.update(|s| s.ownership_years = value);
+                                }
+                            />
+                        </div>
+                        <div>
+                            <label for="gas-price" class="block text-sm font-medium text-gray-700 inline-flex items-center">
+                                "Average Gas Price ($/gallon)"
+                                <Tooltip text="The average price per gallon of gas in your area. This affects the fuel cost calculation. Check your local gas station prices or use national averages. Consider using a long-term average rather than current prices for more stable comparisons." />
+                            </label>
+                            <input
+                                type="number"
+                                step="0.01"
+                                id="gas-price"
+                                class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                                class:border-red-500=move || rejected_gas_price.with(Option::is_some)
+                                aria-invalid=move || rejected_gas_price.with(Option::is_some).to_string()
+                                aria-describedby="gas-price-error"
+                                prop:value=move || rejected_gas_price.get().unwrap_or_else(|| settings.get().average_gas_price.to_string())
+                                on:input=move |ev| {
+                                    let text = event_target_value(&ev);
+                                    match SharedSettings::parse_positive(&text) {
+                                        Some(value) => {
+                                            rejected_gas_price.set(None);
+                                            set_settings.update(|s| s.average_gas_price = value);
+                                        }
+                                        None => rejected_gas_price.set(Some(text)),
+                                    }
+                                }
+                            />
+                            <Show when=move || rejected_gas_price.with(Option::is_some)>
+                                <p id="gas-price-error" class="mt-1 text-sm text-red-600">
+                                    "Must be greater than zero"
+                                </p>
+                            </Show>
+                        </div>
+                        <div>
+                            <label for="electricity-price" class="block text-sm font-medium text-gray-700 inline-flex items-center">
+                                "Electricity Price ($/kWh)"
+                                <Tooltip text="What charging costs per kWh, used for electric cars and plug-in hybrids. Use your home electricity rate if you mostly charge at home (the US average is around $0.16); public fast charging often costs $0.40 or more." />
+                            </label>
+                            <input
+                                type="number"
+                                step="0.01"
+                                id="electricity-price"
+                                class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                                prop:value=move || settings.get().electricity_price
+                                on:input=move |ev| {
+                                    let value = event_target_value(&ev).parse::<f64>().unwrap_or(0.16);
+                                    set_settings.update(|s| s.electricity_price = value);
+                                }
+                            />
+                        </div>
+                        <div>
+                            <label for="historical-gas-price" class="block text-sm font-medium text-gray-700 inline-flex items-center">
+                                "Historical Gas Price ($/gallon)"
+                                <Tooltip text="The average gas price assumed for the miles already on a car, used to estimate what's been spent so far. Leave blank to use the current average gas price." />
+                            </label>
+                            <input
+                                type="number"
+                                step="0.01"
+                                id="historical-gas-price"
+                                class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                                placeholder=move || format!("Same as current (${:.2})", settings.get().average_gas_price)
+                                prop:value=move || settings.get().historical_gas_price.map(|p| p.to_string()).unwrap_or_default()
+                                on:input=move |ev| {
+                                    let value = event_target_value(&ev).parse::<f64>().ok();
+                                    set_settings.update(|s| s.historical_gas_price = value);
+                                }
+                            />
+                        </div>
+                        <div>
+                            <label for="inflation-rate" class="block text-sm font-medium text-gray-700 inline-flex items-center">
+                                "Inflation Rate (%/year)"
+                                <Tooltip text="How fast prices rise each year. Costs are shown in today's dollars; this rate is used for an extra total in future dollars, with fuel, maintenance, insurance and the resale value priced for the year they happen. Around 3% is typical; use 0 to leave it out." />
+                            </label>
+                            <input
+                                type="number"
+                                step="0.1"
+                                id="inflation-rate"
+                                class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                                prop:value=move || settings.get().inflation_rate
+                                on:input=move |ev| {
+                                    let value = event_target_value(&ev).parse::<f64>().unwrap_or(0.0);
+                                    set_settings.update(|s| s.inflation_rate = value);
+                                }
+                            />
+                        </div>
+                        <div>
+                            <label for="mpg-loss" class="block text-sm font-medium text-gray-700 inline-flex items-center">
+                                "MPG Loss (%/year)"
+                                <Tooltip text="How much fuel economy falls each year as a car ages. Each car's MPG is taken as what it gets today, and fuel cost is worked out as it declines from there. Around 0.5–1% is typical; use 0 to keep MPG flat." />
+                            </label>
+                            <input
+                                type="number"
+                                step="0.1"
+                                min="0"
+                                max="99"
+                                id="mpg-loss"
+                                class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                                prop:value=move || settings.get().mpg_loss_percent
+                                on:input=move |ev| {
+                                    let value = event_target_value(&ev).parse::<f64>().unwrap_or(0.0);
+                                    set_settings.update(|s| s.mpg_loss_percent = value);
+                                }
+                            />
+                        </div>
+                        <div>
+                            <label for="highway-percent" class="block text-sm font-medium text-gray-700 inline-flex items-center">
+                                "Highway Driving (%)"
+                                <Tooltip text="The share of your miles driven on the highway. Cars with both city and highway MPG filled in use a blend of the two at this share; the EPA's combined rating assumes 45%." />
+                            </label>
+                            <input
+                                type="number"
+                                step="5"
+                                min="0"
+                                max="100"
+                                id="highway-percent"
+                                class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                                prop:value=move || settings.get().highway_percent
+                                on:input=move |ev| {
+                                    let value = event_target_value(&ev).parse::<f64>().unwrap_or(45.0);
+                                    set_settings.update(|s| s.highway_percent = value.clamp(0.0, 100.0));
+                                }
+                            />
+                        </div>
+                        <div>
+                            <label for="depreciation-rate" class="block text-sm font-medium text-gray-700 inline-flex items-center">
+                                "Depreciation Rate (%/year)"
+                                <Tooltip text="The share of value a car loses each year under exponential decay, used to project resale value. 15% is typical; luxury cars and EVs often lose 20% or more, while trucks and popular compacts hold value better at around 10%." />
+                            </label>
+                            <input
+                                type="number"
+                                step="1"
+                                min="0"
+                                max="100"
+                                id="depreciation-rate"
+                                class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                                prop:value=move || settings.get().depreciation_rate
+                                on:input=move |ev| {
+                                    let value = event_target_value(&ev).parse::<f64>().unwrap_or(15.0);
+                                    set_settings.update(|s| s.depreciation_rate = value);
+                                }
+                            />
+                        </div>
+                        <div>
+                            <label for="annual-cost-basis" class="block text-sm font-medium text-gray-700 inline-flex items-center">
+                                "Annual Cost Averages Over"
+                                <Tooltip text="Annual cost normally spreads the total cost of ownership over every remaining year. Choose a fixed window to average only the next few years of fuel, maintenance and insurance instead, with the purchase still spread over the whole ownership. The window is capped at the years the car is kept." />
+                            </label>
+                            <div class="mt-1 flex space-x-2">
+                                <select
+                                    id="annual-cost-basis"
+                                    class="block flex-1 rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                                    prop:value=move || match settings.get().annual_cost_basis {
+                                        AnnualCostBasis::FullRemainingLife => "full",
+                                        AnnualCostBasis::FixedYears(_) => "fixed",
+                                    }
+                                    on:change=move |ev| {
+                                        let basis = if event_target_value(&ev) == "fixed" {
+                                            AnnualCostBasis::FixedYears(5)
+                                        } else {
+                                            AnnualCostBasis::FullRemainingLife
+                                        };
+                                        set_settings.update(|s| s.annual_cost_basis = basis);
+                                    }
+                                >
+                                    <option value="full">"Full remaining life"</option>
+                                    <option value="fixed">"Next few years"</option>
+                                </select>
+                                <input
+                                    type="number"
+                                    step="1"
+                                    min="1"
+                                    aria-label="Years to average over"
+                                    class="block w-20 rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                                    class:hidden=move || settings.get().annual_cost_basis == AnnualCostBasis::FullRemainingLife
+                                    prop:value=move || match settings.get().annual_cost_basis {
+                                        AnnualCostBasis::FixedYears(n) => n.to_string(),
+                                        AnnualCostBasis::FullRemainingLife => String::new(),
+                                    }
+                                    on:input=move |ev| {
+                                        if let Ok(years) = event_target_value(&ev).parse::<u32>() {
+                                            set_settings.update(|s| s.annual_cost_basis = AnnualCostBasis::FixedYears(years));
+                                        }
+                                    }
+                                />
+                            </div>
+                        </div>
+                        <div>
+                            <label for="region-multiplier" class="block text-sm font-medium text-gray-700 inline-flex items-center">
+                                "Regional Cost Multiplier"
+                                <Tooltip text="Scales maintenance costs to match local labor rates, since the sample maintenance tables reflect national averages. Examples: 0.85 for rural areas, 1.0 for the national average, 1.2 for Boston, 1.35 for the San Francisco Bay Area. Pick a preset or enter your own." />
+                            </label>
+                            <div class="mt-1 flex space-x-2">
+                                <input
+                                    type="number"
+                                    step="0.05"
+                                    min="0"
+                                    id="region-multiplier"
+                                    class="block w-24 rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                                    prop:value=move || settings.get().region_cost_multiplier
+                                    on:input=move |ev| {
+                                        let value = event_target_value(&ev).parse::<f64>().unwrap_or(1.0);
+                                        set_settings.update(|s| s.region_cost_multiplier = value);
+                                    }
+                                />
+                                <select
+                                    aria-label="Regional preset"
+                                    class="block flex-1 rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                                    prop:value=move || {
+                                        let multiplier = settings.get().region_cost_multiplier;
+                                        REGION_PRESETS
+                                            .iter()
+                                            .find(|(_, m)| (*m - multiplier).abs() < 1e-9)
+                                            .map(|(name, _)| name.to_string())
+                                            .unwrap_or_default()
+                                    }
+                                    on:change=move |ev| {
+                                        let name = event_target_value(&ev);
+                                        if let Some((_, m)) = REGION_PRESETS.iter().find(|(n, _)| *n == name) {
+                                            set_settings.update(|s| s.region_cost_multiplier = *m);
+                                        }
+                                    }
+                                >
+                                    <option value="">"Custom"</option>
+                                    {REGION_PRESETS
+                                        .iter()
+                                        .map(|(name, m)| {
+                                            view! { <option value=*name>{format!("{} ({:.2}×)", name, m)}</option> }
+                                        })
+                                        .collect_view()}
+                                </select>
+                            </div>
+                            <label class="mt-2 inline-flex items-center text-sm text-gray-700">
+                                <input
+                                    type="checkbox"
+                                    class="mr-2 rounded border-gray-300 text-blue-600 focus:ring-blue-500"
+                                    prop:checked=move || settings.get().region_multiplier_applies_to_insurance
+                                    on:change=move |ev| {
+                                        let checked = event_target_checked(&ev);
+                                        set_settings.update(|s| s.region_multiplier_applies_to_insurance = checked);
+                                    }
+                                />
+                                "Also apply to insurance"
+                            </label>
+                        </div>
+                        <div>
+                            <label for="trade-in-discount" class="block text-sm font-medium text-gray-700 inline-flex items-center">
+                                "Trade-in Discount (%)"
+                                <Tooltip text="How much less a dealer pays on trade-in than you'd get selling privately. Only affects cars set to sell by trade-in. Typical values: 10-20%." />
+                            </label>
+                            <input
+                                type="number"
+                                step="1"
+                                min="0"
+                                max="100"
+                                id="trade-in-discount"
+                                class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                                prop:value=move || settings.get().trade_in_discount_percent
+                                on:input=move |ev| {
+                                    let value = event_target_value(&ev).parse::<f64>().unwrap_or(15.0);
+                                    set_settings.update(|s| s.trade_in_discount_percent = value);
+                                }
+                            />
+                        </div>
+                        <div>
+                            <label for="sales-tax-rate" class="block text-sm font-medium text-gray-700 inline-flex items-center">
+                                "Sales Tax Rate (%)"
+                                <Tooltip text="Your local sales tax rate. In states that tax only the difference between the new purchase and your trade-in, trading in saves this percentage of the trade-in value." />
+                            </label>
+                            <input
+                                type="number"
+                                step="0.1"
+                                min="0"
+                                id="sales-tax-rate"
+                                class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                                prop:value=move || settings.get().sales_tax_rate
+                                on:input=move |ev| {
+                                    let value = event_target_value(&ev).parse::<f64>().unwrap_or(0.0);
+                                    set_settings.update(|s| s.sales_tax_rate = value);
+                                }
+                            />
+                            <label class="mt-2 inline-flex items-center text-sm text-gray-700">
+                                <input
+                                    type="checkbox"
+                                    class="mr-2 rounded border-gray-300 text-blue-600 focus:ring-blue-500"
+                                    prop:checked=move || settings.get().trade_in_tax_credit
+                                    on:change=move |ev| {
+                                        let checked = event_target_checked(&ev);
+                                        set_settings.update(|s| s.trade_in_tax_credit = checked);
+                                    }
+                                />
+                                "Credit sales tax on trade-ins"
+                            </label>
+                            <label class="mt-2 inline-flex items-center text-sm text-gray-700">
+                                <input
+                                    type="checkbox"
+                                    class="mr-2 rounded border-gray-300 text-blue-600 focus:ring-blue-500"
+                                    prop:checked=move || settings.get().sales_tax_on_purchase
+                                    on:change=move |ev| {
+                                        let checked = event_target_checked(&ev);
+                                        set_settings.update(|s| s.sales_tax_on_purchase = checked);
+                                    }
+                                />
+                                "Charge sales tax on the purchase price"
+                            </label>
+                        </div>
+                        <div>
+                            <label for="annual-registration-fee" class="block text-sm font-medium text-gray-700 inline-flex items-center">
+                                "Annual Registration ($/yr)"
+                                <Tooltip text="Registration, inspection and other yearly fees to keep a car on the road. Added to every car's running costs for each year it's owned." />
+                            </label>
+                            <input
+                                type="number"
+                                step="1"
+                                min="0"
+                                id="annual-registration-fee"
+                                class="mt-1 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm"
+                                prop:value=move || settings.get().annual_registration_fee
+                                on:input=move |ev| {
+                                    let value = event_target_value(&ev).parse::<f64>().unwrap_or(0.0).max(0.0);
+                                    set_settings.update(|s| s.annual_registration_fee = value);
+                                }
+                            />
+                        </div>
                     </div>
                 </div>
             </div>
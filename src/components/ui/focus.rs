@@ -0,0 +1,12 @@
+use leptos::prelude::*;
+use wasm_bindgen::JsCast;
+
+/// Focus an input by id, scrolling it into view
+pub fn focus_input(id: &str) {
+    if let Some(input) = document()
+        .get_element_by_id(id)
+        .and_then(|el| el.dyn_into::<web_sys::HtmlElement>().ok())
+    {
+        let _ = input.focus();
+    }
+}
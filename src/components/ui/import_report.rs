@@ -0,0 +1,127 @@
+use leptos::prelude::*;
+
+use crate::data::{ImportReport, ImportStatus};
+
+fn status_badge(status: ImportStatus) -> &'static str {
+    match status {
+        ImportStatus::Imported => "bg-green-100 text-green-800",
+        ImportStatus::Migrated => "bg-yellow-100 text-yellow-800",
+        ImportStatus::Skipped => "bg-red-100 text-red-800",
+    }
+}
+
+/// Modal listing what happened to each record of an import, with counts at the top.
+/// Closes on Escape, the close button, or a click on the backdrop. Given `on_confirm` it
+/// previews an import instead, with Cancel and confirm buttons at the bottom.
+#[component]
+pub fn ImportReportModal(
+    /// Heading, e.g. "Maintenance import"
+    title: &'static str,
+    report: ImportReport,
+    on_close: Callback<()>,
+    /// Sentence shown above the counts
+    #[prop(optional, into)]
+    description: Option<String>,
+    /// Applies the previewed import
+    #[prop(optional)]
+    on_confirm: Option<Callback<()>>,
+    /// Confirm button text
+    #[prop(default = "Apply")]
+    confirm_label: &'static str,
+) -> impl IntoView {
+    let summary = ImportStatus::ALL
+        .into_iter()
+        .map(|status| {
+            view! {
+                <span class=format!("px-2 py-1 rounded text-xs font-medium {}", status_badge(status))>
+                    {format!("{} {}", report.count(status), status.label().to_lowercase())}
+                </span>
+            }
+        })
+        .collect_view();
+
+    let rows = report
+        .records
+        .into_iter()
+        .map(|record| {
+            view! {
+                <tr class="border-t border-gray-100 align-top">
+                    <td class="py-2 pr-3 text-gray-900">{record.label}</td>
+                    <td class="py-2 pr-3">
+                        <span class=format!("px-2 py-0.5 rounded text-xs font-medium {}", status_badge(record.status))>
+                            {record.status.label()}
+                        </span>
+                    </td>
+                    <td class="py-2 text-gray-600">{record.reason}</td>
+                </tr>
+            }
+        })
+        .collect_view();
+
+    view! {
+        <div
+            class="fixed inset-0 z-50 flex items-center justify-center bg-black/40 p-4"
+            on:click=move |_| on_close.run(())
+            on:keydown=move |ev: leptos::ev::KeyboardEvent| {
+                if ev.key() == "Escape" {
+                    on_close.run(());
+                }
+            }
+        >
+            <div
+                role="dialog"
+                aria-modal="true"
+                aria-labelledby="import-report-title"
+                class="bg-white rounded-lg shadow-xl max-w-2xl w-full max-h-[80vh] flex flex-col"
+                on:click=|ev| ev.stop_propagation()
+            >
+                <div class="px-4 py-3 border-b border-gray-200 flex items-center justify-between">
+                    <h3 id="import-report-title" class="text-lg font-semibold text-gray-900">{title}</h3>
+                    <button
+                        type="button"
+                        class="text-gray-500 hover:text-gray-700"
+                        aria-label="Close"
+                        autofocus
+                        on:click=move |_| on_close.run(())
+                    >
+                        "✕"
+                    </button>
+                </div>
+                {description.map(|text| view! {
+                    <p class="px-4 pt-3 text-sm text-gray-700">{text}</p>
+                })}
+                <div class="px-4 py-3 flex flex-wrap gap-2">{summary}</div>
+                <div class="px-4 pb-4 overflow-y-auto">
+                    <table class="w-full text-sm text-left">
+                        <thead>
+                            <tr class="text-xs text-gray-500 uppercase">
+                                <th class="py-1 pr-3">"Record"</th>
+                                <th class="py-1 pr-3">"Result"</th>
+                                <th class="py-1">"Reason"</th>
+                            </tr>
+                        </thead>
+                        <tbody>{rows}</tbody>
+                    </table>
+                </div>
+                {on_confirm.map(|on_confirm| view! {
+                    <div class="px-4 py-3 border-t border-gray-200 flex justify-end gap-2">
+                        <button
+                            type="button"
+                            class="px-3 py-1 rounded-md border border-gray-300 bg-white text-sm font-medium text-gray-700 hover:bg-gray-50"
+                            on:click=move |_| on_close.run(())
+                        >
+                            "Cancel"
+                        </button>
+                        <button
+                            type="button"
+                            class="px-3 py-1 rounded-md bg-blue-600 text-sm font-medium text-white hover:bg-blue-700"
+                            on:click=move |_| on_confirm.run(())
+                        >
+                            {confirm_label}
+                        </button>
+                    </div>
+                })}
+            </div>
+        </div>
+    }
+}
@@ -1,3 +1,9 @@
+mod focus;
+mod import_report;
+mod popover;
 mod tooltip;
 
+pub use focus::focus_input;
+pub use import_report::ImportReportModal;
+pub use popover::InfoPopover;
 pub use tooltip::Tooltip;
@@ -0,0 +1,68 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use leptos::prelude::*;
+
+/// Source of unique ids so each popover can be referenced by `aria-controls`
+static NEXT_POPOVER_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// A click-to-open info popover for longer, dynamic explanations. Closes on Escape,
+/// a second click, or when focus leaves it.
+#[component]
+pub fn InfoPopover(
+    /// Accessible name for the trigger, e.g. "How is this calculated?"
+    label: &'static str,
+    /// The text to display in the popover
+    text: String,
+) -> impl IntoView {
+    let id = format!(
+        "popover-{}",
+        NEXT_POPOVER_ID.fetch_add(1, Ordering::Relaxed)
+    );
+    let (open, set_open) = signal(false);
+
+    view! {
+        <span
+            class="relative inline-flex items-center ml-1"
+            on:focusout=move |_| set_open.set(false)
+            on:keydown=move |ev: leptos::ev::KeyboardEvent| {
+                // Handled here so an enclosing card doesn't also collapse
+                if ev.key() == "Escape" && open.get_untracked() {
+                    ev.prevent_default();
+                    set_open.set(false);
+                }
+            }
+        >
+            <button
+                type="button"
+                aria-label=label
+                aria-expanded=move || open.get().to_string()
+                aria-controls=id.clone()
+                class="inline-flex rounded-full text-gray-400 hover:text-gray-600 focus:outline-none focus:ring-2 focus:ring-blue-500"
+                on:click=move |_| set_open.update(|o| *o = !*o)
+            >
+                <svg
+                    class="h-4 w-4"
+                    fill="currentColor"
+                    viewBox="0 0 20 20"
+                    xmlns="http://www.w3.org/2000/svg"
+                    aria-hidden="true"
+                    focusable="false"
+                >
+                    <path
+                        fill-rule="evenodd"
+                        d="M18 10a8 8 0 11-16 0 8 8 0 0116 0zm-7-4a1 1 0 11-2 0 1 1 0 012 0zM9 9a1 1 0 000 2v3a1 1 0 001 1h1a1 1 0 100-2v-3a1 1 0 00-1-1H9z"
+                        clip-rule="evenodd"
+                    />
+                </svg>
+            </button>
+            <span
+                id=id
+                role="status"
+                class:hidden=move || !open.get()
+                class="absolute right-0 top-6 z-10 w-72 px-3 py-2 text-xs normal-case tracking-normal font-normal text-white bg-gray-900 rounded-lg shadow-lg"
+            >
+                {text}
+            </span>
+        </span>
+    }
+}
@@ -1,26 +1,50 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
 use leptos::prelude::*;
 
-/// A tooltip component that displays helpful information when hovering over an info icon
+/// Source of unique ids so each tooltip can be referenced by `aria-describedby`
+static NEXT_TOOLTIP_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// A tooltip component that displays helpful information when hovering over or focusing an info icon
 #[component]
 pub fn Tooltip(
     /// The text to display in the tooltip
     text: &'static str,
 ) -> impl IntoView {
+    let id = format!(
+        "tooltip-{}",
+        NEXT_TOOLTIP_ID.fetch_add(1, Ordering::Relaxed)
+    );
+
     view! {
         <span class="group relative inline-flex items-center ml-1">
-            <svg
-                class="h-4 w-4 text-gray-400 hover:text-gray-600 cursor-help"
-                fill="currentColor"
-                viewBox="0 0 20 20"
-                xmlns="http://www.w3.org/2000/svg"
+            <span
+                tabindex="0"
+                role="button"
+                aria-label="More information"
+                aria-describedby=id.clone()
+                class="inline-flex rounded-full focus:outline-none focus:ring-2 focus:ring-blue-500"
+            >
+                <svg
+                    class="h-4 w-4 text-gray-400 hover:text-gray-600 group-focus-within:text-gray-600 cursor-help"
+                    fill="currentColor"
+                    viewBox="0 0 20 20"
+                    xmlns="http://www.w3.org/2000/svg"
+                    aria-hidden="true"
+                    focusable="false"
+                >
+                    <path
+                        fill-rule="evenodd"
+                        d="M18 10a8 8 0 11-16 0 8 8 0 0116 0zm-7-4a1 1 0 11-2 0 1 1 0 012 0zM9 9a1 1 0 000 2v3a1 1 0 001 1h1a1 1 0 100-2v-3a1 1 0 00-1-1H9z"
+                        clip-rule="evenodd"
+                    />
+                </svg>
+            </span>
+            <span
+                id=id
+                role="tooltip"
+                class="invisible group-hover:visible group-focus-within:visible opacity-0 group-hover:opacity-100 group-focus-within:opacity-100 transition-opacity absolute left-0 top-6 z-10 w-64 px-3 py-2 text-sm text-white bg-gray-900 rounded-lg shadow-lg"
             >
-                <path
-                    fill-rule="evenodd"
-                    d="M18 10a8 8 0 11-16 0 8 8 0 0116 0zm-7-4a1 1 0 11-2 0 1 1 0 012 0zM9 9a1 1 0 000 2v3a1 1 0 001 1h1a1 1 0 100-2v-3a1 1 0 00-1-1H9z"
-                    clip-rule="evenodd"
-                />
-            </svg>
-            <span class="invisible group-hover:visible opacity-0 group-hover:opacity-100 transition-opacity absolute left-0 top-6 z-10 w-64 px-3 py-2 text-sm text-white bg-gray-900 rounded-lg shadow-lg">
                 {text}
                 <svg
                     class="absolute text-gray-900 h-2 left-3 top-[-6px]"
@@ -28,6 +52,7 @@ pub fn Tooltip(
                     y="0px"
                     viewBox="0 0 255 255"
                     xml:space="preserve"
+                    aria-hidden="true"
                 >
                     <polygon class="fill-current" points="0,255 127.5,0 255,255" />
                 </svg>
@@ -0,0 +1,212 @@
+use serde::{Deserialize, Serialize};
+
+use crate::data::{ImportReport, ImportStatus};
+use crate::models::{Car, MaintenanceCostDatabase, MergeStrategy, SharedSettings};
+
+/// File name suggested for an exported backup
+pub const APP_EXPORT_FILENAME: &str = "carcalc-backup.json";
+
+/// Everything the app keeps in local storage, as written to a backup file. All three
+/// parts are required, so an empty object or some other JSON file (such as a downloaded
+/// session, which has no maintenance tables) isn't mistaken for a backup of nothing.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct AppExport {
+    pub cars: Vec<Car>,
+    pub settings: SharedSettings,
+    pub maintenance_db: MaintenanceCostDatabase,
+}
+
+impl AppExport {
+    /// Pretty-printed JSON, so a backup can be read and diffed by hand
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+}
+
+/// Whether two cars are the same apart from their id
+fn same_car(a: &Car, b: &Car) -> bool {
+    Car {
+        id: a.id,
+        ..b.clone()
+    } == *a
+}
+
+/// A backup import worked out but not yet applied
+#[derive(Clone, Debug, PartialEq)]
+pub struct BackupImport {
+    /// The data as it will be after the import
+    pub result: AppExport,
+    /// One sentence on what will be replaced or merged
+    pub summary: String,
+    /// What happens to each of the backup's cars and profiles, and the settings
+    pub report: ImportReport,
+}
+
+/// How an imported backup is combined with the data already in the browser
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ImportMode {
+    /// Discard the current data and use the backup's
+    #[default]
+    Replace,
+    /// Keep the current settings, add the backup's cars and combine maintenance profiles
+    Merge,
+}
+
+impl AppExport {
+    /// Parse a backup file, rejecting anything that isn't an `AppExport` object
+    pub fn from_json(text: &str) -> Result<Self, String> {
+        serde_json::from_str(text).map_err(|err| format!("That file isn't a valid backup: {}", err))
+    }
+
+    /// Work out what importing this backup into `current` would do, without changing
+    /// anything, so it can be shown for confirmation. Fixes up what a hand-edited backup
+    /// can get wrong along the way, and reports it: cars sharing an id (the car list is
+    /// keyed by id) get fresh ones, and maintenance points repeating an x are coalesced.
+    pub fn preview_import(mut self, current: &AppExport, mode: ImportMode) -> BackupImport {
+        let mut report = ImportReport::default();
+
+        let mut renumbered = std::collections::HashMap::new();
+        let mut next_id = self.cars.iter().map(|c| c.id + 1).max().unwrap_or(1);
+        let mut seen = std::collections::HashSet::new();
+        for (index, car) in self.cars.iter_mut().enumerate() {
+            if !seen.insert(car.id) {
+                renumbered.insert(index, car.id);
+                car.id = next_id;
+                next_id += 1;
+            }
+        }
+
+        let mut coalesced = std::collections::HashMap::new();
+        for (key, profile) in self.maintenance_db.data.iter_mut() {
+            let before =
+                profile.by_mileage.len() + profile.by_time.len() + profile.value_by_age.len();
+            profile.coalesce_duplicate_points();
+            let after =
+                profile.by_mileage.len() + profile.by_time.len() + profile.value_by_age.len();
+            if after < before {
+                coalesced.insert(key.clone(), before - after);
+            }
+        }
+
+        let result = self.clone().apply_to(current, mode);
+        let replace = mode == ImportMode::Replace;
+
+        let mut kept = current.cars.clone();
+        for (index, car) in self.cars.iter().enumerate() {
+            let name = car.display_name();
+            if replace {
+                match renumbered.get(&index) {
+                    Some(old_id) => report.push(
+                        name,
+                        ImportStatus::Migrated,
+                        format!(
+                            "Shared id {} with another car in the backup, so renumbered to {}",
+                            old_id, car.id
+                        ),
+                    ),
+                    None => report.push(name, ImportStatus::Imported, "Replaces your cars"),
+                }
+            } else if kept.iter().any(|c| same_car(c, car)) {
+                report.push(name, ImportStatus::Skipped, "Already in your list");
+            } else {
+                kept.push(car.clone());
+                report.push(name, ImportStatus::Imported, "Added as a new car");
+            }
+        }
+
+        for (key, profile) in &self.maintenance_db.data {
+            let existing = current.maintenance_db.data.contains_key(key);
+            let mut reason = match (replace, existing) {
+                (true, _) => "Replaces your tables".to_string(),
+                (false, true) => "Combined with your profile, keeping your points".to_string(),
+                (false, false) => "Added as a new profile".to_string(),
+            };
+            let status = match coalesced.get(key) {
+                Some(dropped) => {
+                    reason.push_str(&format!(
+                        "; merged {} point{} repeating an x value",
+                        dropped,
+                        if *dropped == 1 { "" } else { "s" }
+                    ));
+                    ImportStatus::Migrated
+                }
+                None if !replace && existing => ImportStatus::Migrated,
+                None => ImportStatus::Imported,
+            };
+            report.push(profile.name(), status, reason);
+        }
+
+        if replace {
+            report.push(
+                "Shared settings",
+                ImportStatus::Imported,
+                "Replaces your settings",
+            );
+        } else {
+            report.push(
+                "Shared settings",
+                ImportStatus::Skipped,
+                "Your settings are kept",
+            );
+        }
+
+        let summary = if replace {
+            format!(
+                "Replace your {} cars, {} maintenance profiles and settings with the backup's {} cars, {} profiles and settings.",
+                current.cars.len(),
+                current.maintenance_db.data.len(),
+                self.cars.len(),
+                self.maintenance_db.data.len()
+            )
+        } else {
+            format!(
+                "Add {} of the backup's {} cars and combine its {} maintenance profiles with yours. Your settings are kept.",
+                result.cars.len() - current.cars.len(),
+                self.cars.len(),
+                self.maintenance_db.data.len()
+            )
+        };
+
+        BackupImport {
+            result,
+            summary,
+            report,
+        }
+    }
+
+    /// Combine this backup with the `current` data according to `mode`
+    pub fn apply_to(self, current: &AppExport, mode: ImportMode) -> AppExport {
+        match mode {
+            ImportMode::Replace => self,
+            ImportMode::Merge => self.merged_into(current),
+        }
+    }
+
+    /// Current data plus this backup: cars not already present are added under fresh ids,
+    /// and profiles for the same make/model are merged, preferring the current points
+    fn merged_into(self, current: &AppExport) -> AppExport {
+        let mut cars = current.cars.clone();
+        let mut next_id = cars.iter().map(|c| c.id + 1).max().unwrap_or(1);
+        for car in self.cars {
+            if !cars.iter().any(|c| same_car(c, &car)) {
+                cars.push(Car { id: next_id, ..car });
+                next_id += 1;
+            }
+        }
+
+        let mut maintenance_db = current.maintenance_db.clone();
+        for imported in self.maintenance_db.data.into_values() {
+            let merged = match maintenance_db.data.get(&imported.key()) {
+                Some(existing) => existing.merged_with(&imported, MergeStrategy::PreferBase),
+                None => imported,
+            };
+            maintenance_db.set(merged);
+        }
+
+        AppExport {
+            cars,
+            settings: current.settings.clone(),
+            maintenance_db,
+        }
+    }
+}
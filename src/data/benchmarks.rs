@@ -0,0 +1,68 @@
+/// Typical annual spend for a cost category across common US passenger vehicles
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CostBenchmark {
+    pub low: f64,
+    pub high: f64,
+}
+
+impl CostBenchmark {
+    /// Short hint text, e.g. "typical: $500–$1,500/yr"
+    pub fn hint(&self) -> String {
+        format!(
+            "typical: ${}–${}/yr",
+            format_thousands(self.low),
+            format_thousands(self.high)
+        )
+    }
+}
+
+/// Fuel for 10-15k miles/year at 20-40 MPG and $3-4/gallon
+pub const FUEL_ANNUAL: CostBenchmark = CostBenchmark {
+    low: 1000.0,
+    high: 2500.0,
+};
+
+/// Full-coverage premiums for a typical driver
+pub const INSURANCE_ANNUAL: CostBenchmark = CostBenchmark {
+    low: 1200.0,
+    high: 2400.0,
+};
+
+/// Routine and repair maintenance, rising with age
+pub const MAINTENANCE_ANNUAL: CostBenchmark = CostBenchmark {
+    low: 500.0,
+    high: 1500.0,
+};
+
+/// All-in annual cost of owning a new or lightly used car
+pub const TOTAL_ANNUAL: CostBenchmark = CostBenchmark {
+    low: 6000.0,
+    high: 12000.0,
+};
+
+/// Format a dollar amount with the sign ahead of the dollar sign, e.g. "$1200.50" or
+/// "−$300.00", so a net credit never reads as "$-300"
+pub fn format_dollars(value: f64, decimals: usize) -> String {
+    let digits = format!("{:.*}", decimals, value.abs());
+    if value < 0.0 && digits.chars().any(|ch| ch.is_ascii_digit() && ch != '0') {
+        format!("−${}", digits)
+    } else {
+        format!("${}", digits)
+    }
+}
+
+/// Format a dollar amount with thousands separators and no cents
+pub fn format_thousands(value: f64) -> String {
+    let digits = format!("{:.0}", value.abs());
+    let mut out = String::new();
+    for (i, ch) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            out.push(',');
+        }
+        out.push(ch);
+    }
+    if value < 0.0 && digits != "0" {
+        out.insert(0, '-');
+    }
+    out
+}
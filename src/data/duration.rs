@@ -0,0 +1,57 @@
+const MONTH_NAMES: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+fn plural(count: i64, unit: &str) -> String {
+    if count == 1 {
+        format!("1 {}", unit)
+    } else {
+        format!("{} {}s", count, unit)
+    }
+}
+
+/// A fractional number of years in words, rounded to the nearest month,
+/// e.g. 9.2 → "about 9 years 2 months"
+pub fn format_duration(years: f64) -> String {
+    let months = (years.max(0.0) * 12.0).round() as i64;
+    match (months / 12, months % 12) {
+        (0, 0) => "less than a month".to_string(),
+        (0, m) => format!("about {}", plural(m, "month")),
+        (y, 0) => format!("about {}", plural(y, "year")),
+        (y, m) => format!("about {} {}", plural(y, "year"), plural(m, "month")),
+    }
+}
+
+/// The month (year and 0-based month) reached `years` after the given month
+pub fn projected_end(now_year: i32, now_month: u32, years: f64) -> (i32, u32) {
+    let total = now_year as i64 * 12 + now_month as i64 + (years.max(0.0) * 12.0).round() as i64;
+    ((total / 12) as i32, (total % 12) as u32)
+}
+
+/// The ownership horizon as a date and duration, e.g. "until ~Mar 2034, about 9 years
+/// 2 months, when it's ~15 years old". The car's age is left out when the model year
+/// isn't a plausible year.
+pub fn format_ownership_horizon(
+    years: f64,
+    now_year: i32,
+    now_month: u32,
+    model_year: &str,
+) -> String {
+    let (end_year, end_month) = projected_end(now_year, now_month, years);
+    let mut text = format!(
+        "until ~{} {}, {}",
+        MONTH_NAMES[end_month as usize],
+        end_year,
+        format_duration(years)
+    );
+    let model_year = model_year
+        .trim()
+        .parse::<i32>()
+        .ok()
+        .filter(|y| (1886..=now_year + 2).contains(y));
+    if let Some(model_year) = model_year {
+        let age = (end_year - model_year).max(0);
+        text.push_str(&format!(", when it's ~{} old", plural(age as i64, "year")));
+    }
+    text
+}
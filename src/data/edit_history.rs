@@ -0,0 +1,61 @@
+/// Undo/redo history over snapshots of some state. Each recorded value becomes the present
+/// and the one it replaced goes onto the undo stack; recording after an undo drops the redo
+/// stack, as in any editor.
+#[derive(Clone, Debug)]
+pub struct EditHistory<T> {
+    undo: Vec<T>,
+    present: T,
+    redo: Vec<T>,
+    /// Most snapshots kept to undo back through; the oldest are dropped past this
+    depth: usize,
+}
+
+impl<T: Clone + PartialEq> EditHistory<T> {
+    pub fn new(present: T, depth: usize) -> Self {
+        Self {
+            undo: Vec::new(),
+            present,
+            redo: Vec::new(),
+            depth,
+        }
+    }
+
+    /// Make `value` the present state. Returns false (and changes nothing) when it's the
+    /// same as the present, so recording the value an undo just restored is harmless.
+    pub fn record(&mut self, value: T) -> bool {
+        if value == self.present {
+            return false;
+        }
+        let previous = std::mem::replace(&mut self.present, value);
+        self.undo.push(previous);
+        if self.undo.len() > self.depth {
+            self.undo.remove(0);
+        }
+        self.redo.clear();
+        true
+    }
+
+    /// Step back one snapshot, returning the state to restore
+    pub fn undo(&mut self) -> Option<T> {
+        let previous = self.undo.pop()?;
+        let current = std::mem::replace(&mut self.present, previous);
+        self.redo.push(current);
+        Some(self.present.clone())
+    }
+
+    /// Step forward again after an undo, returning the state to restore
+    pub fn redo(&mut self) -> Option<T> {
+        let next = self.redo.pop()?;
+        let current = std::mem::replace(&mut self.present, next);
+        self.undo.push(current);
+        Some(self.present.clone())
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+}
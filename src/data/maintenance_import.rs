@@ -0,0 +1,410 @@
+use serde_json::Value;
+
+use crate::data::parse_money;
+use crate::data::row_import::split_row;
+use crate::models::{
+    coalesce_points, MaintenanceCostData, MaintenanceCostDatabase, MaintenanceDataPoint,
+    MILES_PER_MILEAGE_X,
+};
+
+/// Largest stored mileage x (in `MILES_PER_MILEAGE_X` units, so a million miles) a JSON
+/// profile can have; a mileage table going past it was written in plain miles
+const MAX_STORED_MILEAGE_X: f64 = 100.0;
+
+/// What happened to one record during an import
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImportStatus {
+    /// Loaded as-is
+    Imported,
+    /// Loaded after being repaired or upgraded
+    Migrated,
+    /// Not loaded
+    Skipped,
+}
+
+impl ImportStatus {
+    pub const ALL: [ImportStatus; 3] = [
+        ImportStatus::Imported,
+        ImportStatus::Migrated,
+        ImportStatus::Skipped,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ImportStatus::Imported => "Imported",
+            ImportStatus::Migrated => "Migrated",
+            ImportStatus::Skipped => "Skipped",
+        }
+    }
+}
+
+/// One record in an import report, e.g. a maintenance profile or a CSV row
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImportRecord {
+    pub label: String,
+    pub status: ImportStatus,
+    pub reason: String,
+}
+
+/// Per-record outcome of an import, shown to the user afterwards
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ImportReport {
+    pub records: Vec<ImportRecord>,
+}
+
+impl ImportReport {
+    pub fn push(
+        &mut self,
+        label: impl Into<String>,
+        status: ImportStatus,
+        reason: impl Into<String>,
+    ) {
+        self.records.push(ImportRecord {
+            label: label.into(),
+            status,
+            reason: reason.into(),
+        });
+    }
+
+    pub fn count(&self, status: ImportStatus) -> usize {
+        self.records.iter().filter(|r| r.status == status).count()
+    }
+}
+
+/// Maintenance profiles parsed from pasted JSON or CSV, ready to apply
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MaintenanceImport {
+    pub profiles: Vec<MaintenanceCostData>,
+    pub report: ImportReport,
+}
+
+impl MaintenanceImport {
+    /// Add the loaded profiles to the database, replacing any with the same make/model
+    pub fn apply(self, db: &mut MaintenanceCostDatabase) {
+        for profile in self.profiles {
+            db.set(profile);
+        }
+    }
+}
+
+/// Parse maintenance profiles from JSON (one profile, a list of them, or an exported
+/// database) or CSV with `make,model,table,x,y` rows where `table` is `mileage`, `time`
+/// or `value`. CSV mileage x is in plain miles; JSON mileage x is in stored units unless
+/// it runs past a million miles' worth, in which case it's read as plain miles. Profiles
+/// already in `existing` are reported as replacements.
+pub fn parse_maintenance_import(
+    text: &str,
+    existing: &MaintenanceCostDatabase,
+) -> MaintenanceImport {
+    let text = text.trim();
+    if text.starts_with('{') || text.starts_with('[') {
+        parse_json(text, existing)
+    } else {
+        parse_csv(text, existing)
+    }
+}
+
+/// Points for one table parsed from pasted `x,cost` rows, with a report of rows that
+/// couldn't be used
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PointsPaste {
+    /// In stored units, sorted by x
+    pub points: Vec<MaintenanceDataPoint>,
+    pub report: ImportReport,
+}
+
+impl PointsPaste {
+    /// The table after the paste: the pasted points alone, or added to `existing` with a
+    /// pasted point replacing an existing one at the same x
+    pub fn apply_to(
+        &self,
+        existing: &[MaintenanceDataPoint],
+        append: bool,
+    ) -> Vec<MaintenanceDataPoint> {
+        if !append {
+            return self.points.clone();
+        }
+        let mut points: Vec<MaintenanceDataPoint> = existing
+            .iter()
+            .filter(|p| !self.points.iter().any(|pasted| pasted.x == p.x))
+            .cloned()
+            .chain(self.points.iter().cloned())
+            .collect();
+        points.sort_by(|a, b| a.x.total_cmp(&b.x));
+        points
+    }
+}
+
+/// Parse pasted CSV or spreadsheet rows of `x,cumulative_cost` for one table, e.g.
+/// `50000,1200` for the mileage table. x is in display units (plain miles, or years) and
+/// divided by `x_scale` to the stored units. A header row is skipped; every other row
+/// that isn't two non-negative numbers is reported by line number rather than dropped
+/// silently. Tab-separated rows may use thousands separators.
+pub fn parse_points_csv(text: &str, x_scale: f64) -> PointsPaste {
+    let mut paste = PointsPaste::default();
+    let mut seen_row = false;
+    for (i, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let cells = split_row(line);
+        let row = format!("Line {}", i + 1);
+        let parsed = match &cells[..] {
+            [x, y] => (parse_money(x), parse_money(y)),
+            _ => {
+                paste.report.push(
+                    row,
+                    ImportStatus::Skipped,
+                    format!("Expected 2 columns (x, cost), found {}", cells.len()),
+                );
+                seen_row = true;
+                continue;
+            }
+        };
+        let first_row = !seen_row;
+        seen_row = true;
+        match parsed {
+            (Some(x), Some(y)) if x >= 0.0 && y >= 0.0 => paste
+                .points
+                .push(MaintenanceDataPoint { x: x / x_scale, y }),
+            (Some(_), Some(_)) => {
+                paste
+                    .report
+                    .push(row, ImportStatus::Skipped, "Values can't be negative")
+            }
+            // A header like "miles,cumulative_cost"
+            (None, None) if first_row => {}
+            _ => paste.report.push(
+                row,
+                ImportStatus::Skipped,
+                format!("\"{}\" / \"{}\" aren't both numbers", cells[0], cells[1]),
+            ),
+        }
+    }
+    if let Some(fix) = sanitize_points(&mut paste.points) {
+        paste.report.push("Table", ImportStatus::Migrated, fix);
+    }
+    paste
+}
+
+fn display_name(profile: &MaintenanceCostData) -> String {
+    format!("{} {}", profile.make, profile.model)
+}
+
+fn parse_json(text: &str, existing: &MaintenanceCostDatabase) -> MaintenanceImport {
+    let mut import = MaintenanceImport::default();
+    let value: Value = match serde_json::from_str(text) {
+        Ok(value) => value,
+        Err(e) => {
+            import.report.push(
+                "Input",
+                ImportStatus::Skipped,
+                format!("Invalid JSON: {}", e),
+            );
+            return import;
+        }
+    };
+
+    // An exported database wraps profiles in a "data" map keyed by make/model
+    let records: Vec<(String, Value)> = match value {
+        Value::Array(items) => items
+            .into_iter()
+            .enumerate()
+            .map(|(i, v)| (format!("Record {}", i + 1), v))
+            .collect(),
+        Value::Object(mut map) => match map.remove("data") {
+            Some(Value::Object(data)) => data.into_iter().collect(),
+            Some(other) => vec![("data".to_string(), other)],
+            None => vec![("Record 1".to_string(), Value::Object(map))],
+        },
+        other => vec![("Input".to_string(), other)],
+    };
+
+    for (fallback_label, record) in records {
+        match serde_json::from_value::<MaintenanceCostData>(record) {
+            Ok(mut profile) => {
+                let mut fixes = Vec::new();
+                if profile
+                    .by_mileage
+                    .iter()
+                    .any(|p| p.x > MAX_STORED_MILEAGE_X)
+                {
+                    for point in &mut profile.by_mileage {
+                        point.x /= MILES_PER_MILEAGE_X;
+                    }
+                    fixes.push("mileage table read as plain miles".to_string());
+                }
+                validate_profile(profile, fixes, existing, &mut import)
+            }
+            Err(e) => import.report.push(
+                fallback_label,
+                ImportStatus::Skipped,
+                format!("Not a maintenance profile: {}", e),
+            ),
+        }
+    }
+    import
+}
+
+fn parse_csv(text: &str, existing: &MaintenanceCostDatabase) -> MaintenanceImport {
+    let mut import = MaintenanceImport::default();
+    let mut profiles: Vec<MaintenanceCostData> = Vec::new();
+
+    for (i, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let cells = split_row(line);
+        if i == 0
+            && cells
+                .first()
+                .is_some_and(|c| c.eq_ignore_ascii_case("make"))
+        {
+            continue;
+        }
+        let row = format!("Row {}", i + 1);
+        let [make, model, table, x, y] = &cells[..] else {
+            import.report.push(
+                row,
+                ImportStatus::Skipped,
+                format!(
+                    "Expected 5 columns (make, model, table, x, y), found {}",
+                    cells.len()
+                ),
+            );
+            continue;
+        };
+        let (Some(x), Some(y)) = (parse_money(x), parse_money(y)) else {
+            import.report.push(
+                row,
+                ImportStatus::Skipped,
+                format!("\"{}\" / \"{}\" aren't numbers", x, y),
+            );
+            continue;
+        };
+
+        let key = MaintenanceCostData::make_key(make, model);
+        let profile = match profiles.iter_mut().position(|p| p.key() == key) {
+            Some(index) => &mut profiles[index],
+            None => {
+                profiles.push(MaintenanceCostData::new(
+                    make.to_string(),
+                    model.to_string(),
+                ));
+                profiles.last_mut().unwrap()
+            }
+        };
+        let point = MaintenanceDataPoint { x, y };
+        match table.to_lowercase().as_str() {
+            "mileage" => profile.by_mileage.push(MaintenanceDataPoint {
+                x: x / MILES_PER_MILEAGE_X,
+                y,
+            }),
+            "time" | "age" => profile.by_time.push(point),
+            "value" | "resale" => profile.value_by_age.push(point),
+            other => import.report.push(
+                row,
+                ImportStatus::Skipped,
+                format!(
+                    "Unknown table \"{}\" (expected mileage, time or value)",
+                    other
+                ),
+            ),
+        }
+    }
+
+    for profile in profiles {
+        validate_profile(profile, Vec::new(), existing, &mut import);
+    }
+    import
+}
+
+/// Check a parsed profile, repairing what can be repaired and reporting the result along
+/// with `fixes` already made while parsing it
+fn validate_profile(
+    mut profile: MaintenanceCostData,
+    mut fixes: Vec<String>,
+    existing: &MaintenanceCostDatabase,
+    import: &mut MaintenanceImport,
+) {
+    profile.make = profile.make.trim().to_string();
+    profile.model = profile.model.trim().to_string();
+    let label = display_name(&profile);
+    if profile.make.is_empty() || profile.model.is_empty() {
+        import.report.push(
+            if label.trim().is_empty() {
+                "(unnamed)"
+            } else {
+                label.trim()
+            },
+            ImportStatus::Skipped,
+            "Missing make or model",
+        );
+        return;
+    }
+
+    for (name, points) in [
+        ("mileage", &mut profile.by_mileage),
+        ("time", &mut profile.by_time),
+        ("resale", &mut profile.value_by_age),
+    ] {
+        if let Some(fix) = sanitize_points(points) {
+            fixes.push(format!("{} table: {}", name, fix));
+        }
+    }
+
+    if profile.by_mileage.is_empty() && profile.by_time.is_empty() {
+        import.report.push(
+            label,
+            ImportStatus::Skipped,
+            "No valid maintenance data points",
+        );
+        return;
+    }
+
+    // A make/model repeated within the import replaces the earlier record
+    if let Some(index) = import
+        .profiles
+        .iter()
+        .position(|p| p.key() == profile.key())
+    {
+        import.profiles.remove(index);
+        fixes.push("replaced an earlier record for the same vehicle".to_string());
+    }
+
+    let (status, mut reason) = if fixes.is_empty() {
+        (ImportStatus::Imported, "All points valid".to_string())
+    } else {
+        (ImportStatus::Migrated, fixes.join("; "))
+    };
+    if existing.get(&profile.make, &profile.model).is_some() {
+        reason.push_str("; replaces the existing profile");
+    }
+    import.report.push(label, status, reason);
+    import.profiles.push(profile);
+}
+
+/// Drop negative or non-finite points, sort by x and coalesce points sharing an x (keeping
+/// the highest cost, as elsewhere). Returns a description of what was changed, if anything.
+fn sanitize_points(points: &mut Vec<MaintenanceDataPoint>) -> Option<String> {
+    let original = points.len();
+    points.retain(|p| p.x.is_finite() && p.y.is_finite() && p.x >= 0.0 && p.y >= 0.0);
+    let dropped = original - points.len();
+
+    let was_sorted = points.windows(2).all(|w| w[0].x < w[1].x);
+    let before_dedup = points.len();
+    coalesce_points(points);
+    let duplicates = before_dedup - points.len();
+
+    let mut fixes = Vec::new();
+    if dropped > 0 {
+        fixes.push(format!("dropped {} invalid point(s)", dropped));
+    }
+    if duplicates > 0 {
+        fixes.push(format!("merged {} duplicate point(s)", duplicates));
+    }
+    if !was_sorted && duplicates == 0 {
+        fixes.push("sorted points".to_string());
+    }
+    (!fixes.is_empty()).then(|| fixes.join(", "))
+}
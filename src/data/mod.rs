@@ -1,3 +1,32 @@
+mod app_export;
+mod benchmarks;
+mod duration;
+mod edit_history;
+mod maintenance_import;
+mod row_import;
 mod sample_maintenance;
+mod service_schedule;
+mod session_link;
+mod summary_csv;
+mod vin;
 
+pub use app_export::{AppExport, BackupImport, ImportMode, APP_EXPORT_FILENAME};
+pub use benchmarks::{
+    format_dollars, format_thousands, CostBenchmark, FUEL_ANNUAL, INSURANCE_ANNUAL,
+    MAINTENANCE_ANNUAL, TOTAL_ANNUAL,
+};
+pub use duration::{format_duration, format_ownership_horizon, projected_end};
+pub use edit_history::EditHistory;
+pub use maintenance_import::{
+    parse_maintenance_import, parse_points_csv, ImportRecord, ImportReport, ImportStatus,
+    MaintenanceImport, PointsPaste,
+};
+pub use row_import::{parse_car_row, parse_money, ImportedField, RowImport, ROW_IMPORT_COLUMNS};
 pub use sample_maintenance::get_sample_maintenance_data;
+pub use service_schedule::{schedule_to_cumulative, ServiceEntry};
+pub use session_link::{
+    decode_session_fragment, encode_session_fragment, SessionState, MAX_SESSION_FRAGMENT_LEN,
+    SESSION_FRAGMENT_PREFIX,
+};
+pub use summary_csv::{summary_csv, summary_csv_filename};
+pub use vin::{decode_vin, normalize_vin, parse_vpic_response, DecodedVin};
@@ -0,0 +1,137 @@
+use crate::models::Car;
+
+/// Column order expected in a pasted spreadsheet row
+pub const ROW_IMPORT_COLUMNS: &[&str] = &[
+    "Make",
+    "Model",
+    "Trim",
+    "Year",
+    "Purchase Price",
+    "Current Mileage",
+    "MPG",
+    "Insurance (6-month)",
+    "VIN",
+    "Listing URL",
+    "Notes",
+];
+
+/// One pasted cell and the column it maps to
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImportedField {
+    pub column: &'static str,
+    pub value: String,
+    /// Set when the cell couldn't be parsed; the field is left blank on the car
+    pub error: Option<String>,
+}
+
+/// Result of parsing a pasted row: the car to add plus a per-column mapping for preview
+#[derive(Clone, Debug, PartialEq)]
+pub struct RowImport {
+    pub car: Car,
+    pub fields: Vec<ImportedField>,
+    /// Cells past the last known column
+    pub ignored: Vec<String>,
+}
+
+impl RowImport {
+    pub fn has_errors(&self) -> bool {
+        self.fields.iter().any(|f| f.error.is_some())
+    }
+}
+
+/// Parse a dollar or number cell as formatted by a spreadsheet, e.g. "$25,000" or "1,234.5"
+pub fn parse_money(value: &str) -> Option<f64> {
+    let cleaned: String = value
+        .trim()
+        .chars()
+        .filter(|c| !matches!(c, '$' | ',' | ' '))
+        .collect();
+    cleaned.parse::<f64>().ok().filter(|v| v.is_finite())
+}
+
+/// Split a row on tabs (as copied from a spreadsheet) or, failing that, on commas,
+/// honoring double-quoted cells with `""` escapes
+pub(crate) fn split_row(row: &str) -> Vec<String> {
+    let row = row.trim_end_matches(['\r', '\n']);
+    if row.contains('\t') {
+        return row.split('\t').map(|c| c.trim().to_string()).collect();
+    }
+
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut in_quotes = false;
+    let mut chars = row.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                cell.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => cells.push(std::mem::take(&mut cell).trim().to_string()),
+            _ => cell.push(ch),
+        }
+    }
+    cells.push(cell.trim().to_string());
+    cells
+}
+
+/// Parse a pasted spreadsheet row into a new car with the given id.
+/// Numeric cells are normalized (currency symbols and separators stripped) so the
+/// car computes; cells that can't be parsed are reported and left blank.
+pub fn parse_car_row(row: &str, id: usize) -> RowImport {
+    let cells = split_row(row);
+    let mut car = Car::new(id);
+    let mut fields = Vec::new();
+
+    for (i, column) in ROW_IMPORT_COLUMNS.iter().enumerate() {
+        let value = cells.get(i).cloned().unwrap_or_default();
+        let mut error = None;
+
+        let numeric = |value: &str, what: &str| -> Result<String, String> {
+            if value.is_empty() {
+                return Ok(String::new());
+            }
+            parse_money(value)
+                .filter(|v| *v >= 0.0)
+                .map(|v| v.to_string())
+                .ok_or_else(|| format!("\"{}\" isn't a valid {}", value, what))
+        };
+        let mut numeric_field = |target: &mut String, what: &str| match numeric(&value, what) {
+            Ok(parsed) => *target = parsed,
+            Err(message) => error = Some(message),
+        };
+
+        match i {
+            0 => car.make = value.clone(),
+            1 => car.model = value.clone(),
+            2 => car.trim = value.clone(),
+            3 => numeric_field(&mut car.year, "year"),
+            4 => numeric_field(&mut car.purchase_price, "price"),
+            5 => numeric_field(&mut car.current_mileage, "mileage"),
+            6 => numeric_field(&mut car.mpg, "MPG"),
+            7 => numeric_field(&mut car.insurance_cost, "premium"),
+            8 => car.vin = value.clone(),
+            9 => car.listing_url = value.clone(),
+            _ => car.notes = value.clone(),
+        }
+
+        fields.push(ImportedField {
+            column,
+            value,
+            error,
+        });
+    }
+
+    let ignored = cells
+        .into_iter()
+        .skip(ROW_IMPORT_COLUMNS.len())
+        .filter(|c| !c.is_empty())
+        .collect();
+
+    RowImport {
+        car,
+        fields,
+        ignored,
+    }
+}
@@ -1,98 +1,321 @@
 use crate::models::{MaintenanceCostData, MaintenanceCostDatabase, MaintenanceDataPoint};
 
+/// A built-in maintenance profile as (x, y) pairs:
+/// - `by_mileage`: x in 10k-mile steps, y the cumulative cost in dollars. Costs include oil
+///   changes, filters, tire rotations, brake pads, fluids and major services.
+/// - `by_time`: x in years, y the cumulative cost in dollars, sized for 12k miles a year
+///   since the two tables are blended 50/50
+/// - `value_by_age`: x in years, y the percent of the original price retained
+struct SampleProfile {
+    make: &'static str,
+    model: &'static str,
+    by_mileage: &'static [(f64, f64)],
+    by_time: &'static [(f64, f64)],
+    value_by_age: &'static [(f64, f64)],
+}
+
+#[rustfmt::skip]
+const SAMPLE_PROFILES: &[SampleProfile] = &[
+    SampleProfile {
+        make: "Toyota",
+        model: "Prius",
+        // Hybrid known for reliability and low maintenance costs
+        by_mileage: &[
+            (1.0, 350.0), (2.0, 700.0), (3.0, 1100.0), (4.0, 1450.0),
+            (5.0, 1800.0), (6.0, 2300.0), (7.0, 2700.0), (8.0, 3100.0),
+            (9.0, 3650.0), (10.0, 4100.0), (12.0, 5200.0), (15.0, 6800.0),
+            (20.0, 9500.0),
+        ],
+        by_time: &[
+            (1.0, 420.0), (2.0, 840.0), (3.0, 1320.0), (4.0, 1740.0),
+            (5.0, 2160.0), (6.0, 2760.0), (7.0, 3240.0), (8.0, 3720.0),
+            (9.0, 4380.0), (10.0, 4920.0), (12.0, 6240.0), (15.0, 8160.0),
+        ],
+        value_by_age: &[
+            (0.0, 100.0), (1.0, 85.0), (3.0, 68.0), (5.0, 55.0),
+            (8.0, 40.0), (12.0, 25.0), (17.0, 12.0),
+        ],
+    },
+    SampleProfile {
+        make: "Ford",
+        model: "F-150",
+        // Popular truck: larger engine, more fluids, heavier wear on components
+        by_mileage: &[
+            (1.0, 500.0), (2.0, 1000.0), (3.0, 1600.0), (4.0, 2150.0),
+            (5.0, 2700.0), (6.0, 3400.0), (7.0, 4000.0), (8.0, 4600.0),
+            (9.0, 5350.0), (10.0, 6000.0), (12.0, 7600.0), (15.0, 10000.0),
+            (20.0, 14000.0),
+        ],
+        by_time: &[
+            (1.0, 600.0), (2.0, 1200.0), (3.0, 1920.0), (4.0, 2580.0),
+            (5.0, 3240.0), (6.0, 4080.0), (7.0, 4800.0), (8.0, 5520.0),
+            (9.0, 6420.0), (10.0, 7200.0), (12.0, 9120.0), (15.0, 12000.0),
+        ],
+        value_by_age: &[
+            (0.0, 100.0), (1.0, 88.0), (3.0, 75.0), (5.0, 63.0),
+            (8.0, 48.0), (12.0, 32.0), (17.0, 18.0),
+        ],
+    },
+    SampleProfile {
+        make: "Honda",
+        model: "Civic",
+        // Compact sedan; simple, cheap parts
+        by_mileage: &[
+            (1.0, 320.0), (2.0, 640.0), (3.0, 1010.0), (4.0, 1330.0),
+            (5.0, 1660.0), (6.0, 2120.0), (7.0, 2480.0), (8.0, 2850.0),
+            (9.0, 3360.0), (10.0, 3770.0), (12.0, 4780.0), (15.0, 6260.0),
+            (20.0, 8740.0),
+        ],
+        by_time: &[
+            (1.0, 390.0), (2.0, 770.0), (3.0, 1210.0), (4.0, 1600.0),
+            (5.0, 1990.0), (6.0, 2540.0), (7.0, 2980.0), (8.0, 3420.0),
+            (9.0, 4030.0), (10.0, 4530.0), (12.0, 5740.0), (15.0, 7510.0),
+        ],
+        value_by_age: &[
+            (0.0, 100.0), (1.0, 84.0), (3.0, 70.0), (5.0, 58.0),
+            (8.0, 44.0), (12.0, 29.0), (17.0, 15.0),
+        ],
+    },
+    SampleProfile {
+        make: "Toyota",
+        model: "Camry",
+        // Midsize sedan with a long service life
+        by_mileage: &[
+            (1.0, 370.0), (2.0, 750.0), (3.0, 1180.0), (4.0, 1550.0),
+            (5.0, 1930.0), (6.0, 2460.0), (7.0, 2890.0), (8.0, 3320.0),
+            (9.0, 3910.0), (10.0, 4390.0), (12.0, 5560.0), (15.0, 7280.0),
+            (20.0, 10160.0),
+        ],
+        by_time: &[
+            (1.0, 450.0), (2.0, 900.0), (3.0, 1410.0), (4.0, 1860.0),
+            (5.0, 2310.0), (6.0, 2950.0), (7.0, 3470.0), (8.0, 3980.0),
+            (9.0, 4690.0), (10.0, 5260.0), (12.0, 6680.0), (15.0, 8730.0),
+        ],
+        value_by_age: &[
+            (0.0, 100.0), (1.0, 85.0), (3.0, 70.0), (5.0, 58.0),
+            (8.0, 43.0), (12.0, 28.0), (17.0, 14.0),
+        ],
+    },
+    SampleProfile {
+        make: "Honda",
+        model: "Accord",
+        // Midsize sedan, similar upkeep to the Camry
+        by_mileage: &[
+            (1.0, 370.0), (2.0, 740.0), (3.0, 1160.0), (4.0, 1520.0),
+            (5.0, 1890.0), (6.0, 2420.0), (7.0, 2840.0), (8.0, 3260.0),
+            (9.0, 3830.0), (10.0, 4300.0), (12.0, 5460.0), (15.0, 7140.0),
+            (20.0, 9980.0),
+        ],
+        by_time: &[
+            (1.0, 440.0), (2.0, 880.0), (3.0, 1390.0), (4.0, 1830.0),
+            (5.0, 2270.0), (6.0, 2900.0), (7.0, 3400.0), (8.0, 3910.0),
+            (9.0, 4600.0), (10.0, 5170.0), (12.0, 6550.0), (15.0, 8570.0),
+        ],
+        value_by_age: &[
+            (0.0, 100.0), (1.0, 83.0), (3.0, 68.0), (5.0, 56.0),
+            (8.0, 41.0), (12.0, 27.0), (17.0, 13.0),
+        ],
+    },
+    SampleProfile {
+        make: "Hyundai",
+        model: "Elantra",
+        // Compact sedan; inexpensive routine service
+        by_mileage: &[
+            (1.0, 330.0), (2.0, 660.0), (3.0, 1040.0), (4.0, 1380.0),
+            (5.0, 1710.0), (6.0, 2180.0), (7.0, 2560.0), (8.0, 2940.0),
+            (9.0, 3470.0), (10.0, 3900.0), (12.0, 4940.0), (15.0, 6460.0),
+            (20.0, 9020.0),
+        ],
+        by_time: &[
+            (1.0, 400.0), (2.0, 800.0), (3.0, 1250.0), (4.0, 1650.0),
+            (5.0, 2050.0), (6.0, 2620.0), (7.0, 3080.0), (8.0, 3530.0),
+            (9.0, 4160.0), (10.0, 4670.0), (12.0, 5930.0), (15.0, 7750.0),
+        ],
+        value_by_age: &[
+            (0.0, 100.0), (1.0, 80.0), (3.0, 63.0), (5.0, 50.0),
+            (8.0, 36.0), (12.0, 22.0), (17.0, 10.0),
+        ],
+    },
+    SampleProfile {
+        make: "Honda",
+        model: "CR-V",
+        // Compact SUV; slightly more than a sedan for tires and brakes
+        by_mileage: &[
+            (1.0, 390.0), (2.0, 770.0), (3.0, 1210.0), (4.0, 1600.0),
+            (5.0, 1980.0), (6.0, 2530.0), (7.0, 2970.0), (8.0, 3410.0),
+            (9.0, 4020.0), (10.0, 4510.0), (12.0, 5720.0), (15.0, 7480.0),
+            (20.0, 10450.0),
+        ],
+        by_time: &[
+            (1.0, 460.0), (2.0, 920.0), (3.0, 1450.0), (4.0, 1910.0),
+            (5.0, 2380.0), (6.0, 3040.0), (7.0, 3560.0), (8.0, 4090.0),
+            (9.0, 4820.0), (10.0, 5410.0), (12.0, 6860.0), (15.0, 8980.0),
+        ],
+        value_by_age: &[
+            (0.0, 100.0), (1.0, 86.0), (3.0, 72.0), (5.0, 60.0),
+            (8.0, 45.0), (12.0, 30.0), (17.0, 16.0),
+        ],
+    },
+    SampleProfile {
+        make: "Toyota",
+        model: "RAV4",
+        // Compact SUV with low repair rates
+        by_mileage: &[
+            (1.0, 380.0), (2.0, 760.0), (3.0, 1190.0), (4.0, 1570.0),
+            (5.0, 1950.0), (6.0, 2490.0), (7.0, 2930.0), (8.0, 3360.0),
+            (9.0, 3940.0), (10.0, 4430.0), (12.0, 5620.0), (15.0, 7350.0),
+            (20.0, 10280.0),
+        ],
+        by_time: &[
+            (1.0, 450.0), (2.0, 910.0), (3.0, 1430.0), (4.0, 1880.0),
+            (5.0, 2340.0), (6.0, 2990.0), (7.0, 3500.0), (8.0, 4030.0),
+            (9.0, 4740.0), (10.0, 5330.0), (12.0, 6750.0), (15.0, 8830.0),
+        ],
+        value_by_age: &[
+            (0.0, 100.0), (1.0, 87.0), (3.0, 74.0), (5.0, 62.0),
+            (8.0, 47.0), (12.0, 31.0), (17.0, 17.0),
+        ],
+    },
+    SampleProfile {
+        make: "Subaru",
+        model: "Outback",
+        // All-wheel drive wagon; AWD fluids and CVT service add up
+        by_mileage: &[
+            (1.0, 460.0), (2.0, 910.0), (3.0, 1430.0), (4.0, 1880.0),
+            (5.0, 2340.0), (6.0, 2990.0), (7.0, 3510.0), (8.0, 4030.0),
+            (9.0, 4740.0), (10.0, 5330.0), (12.0, 6760.0), (15.0, 8840.0),
+            (20.0, 12350.0),
+        ],
+        by_time: &[
+            (1.0, 550.0), (2.0, 1090.0), (3.0, 1720.0), (4.0, 2260.0),
+            (5.0, 2810.0), (6.0, 3590.0), (7.0, 4210.0), (8.0, 4840.0),
+            (9.0, 5690.0), (10.0, 6400.0), (12.0, 8110.0), (15.0, 10610.0),
+        ],
+        value_by_age: &[
+            (0.0, 100.0), (1.0, 84.0), (3.0, 68.0), (5.0, 55.0),
+            (8.0, 40.0), (12.0, 25.0), (17.0, 12.0),
+        ],
+    },
+    SampleProfile {
+        make: "Ford",
+        model: "Explorer",
+        // Midsize SUV; heavier, with pricier brakes and tires
+        by_mileage: &[
+            (1.0, 510.0), (2.0, 1020.0), (3.0, 1600.0), (4.0, 2100.0),
+            (5.0, 2610.0), (6.0, 3340.0), (7.0, 3920.0), (8.0, 4500.0),
+            (9.0, 5290.0), (10.0, 5940.0), (12.0, 7540.0), (15.0, 9860.0),
+            (20.0, 13780.0),
+        ],
+        by_time: &[
+            (1.0, 610.0), (2.0, 1220.0), (3.0, 1910.0), (4.0, 2520.0),
+            (5.0, 3130.0), (6.0, 4000.0), (7.0, 4700.0), (8.0, 5390.0),
+            (9.0, 6350.0), (10.0, 7130.0), (12.0, 9050.0), (15.0, 11830.0),
+        ],
+        value_by_age: &[
+            (0.0, 100.0), (1.0, 80.0), (3.0, 62.0), (5.0, 48.0),
+            (8.0, 33.0), (12.0, 20.0), (17.0, 9.0),
+        ],
+    },
+    SampleProfile {
+        make: "Jeep",
+        model: "Wrangler",
+        // Off-roader with costly suspension and drivetrain work
+        by_mileage: &[
+            (1.0, 540.0), (2.0, 1080.0), (3.0, 1700.0), (4.0, 2250.0),
+            (5.0, 2790.0), (6.0, 3560.0), (7.0, 4180.0), (8.0, 4800.0),
+            (9.0, 5660.0), (10.0, 6360.0), (12.0, 8060.0), (15.0, 10540.0),
+            (20.0, 14720.0),
+        ],
+        by_time: &[
+            (1.0, 650.0), (2.0, 1300.0), (3.0, 2050.0), (4.0, 2700.0),
+            (5.0, 3350.0), (6.0, 4280.0), (7.0, 5020.0), (8.0, 5770.0),
+            (9.0, 6790.0), (10.0, 7630.0), (12.0, 9670.0), (15.0, 12650.0),
+        ],
+        value_by_age: &[
+            (0.0, 100.0), (1.0, 88.0), (3.0, 77.0), (5.0, 66.0),
+            (8.0, 52.0), (12.0, 36.0), (17.0, 20.0),
+        ],
+    },
+    SampleProfile {
+        make: "Chevrolet",
+        model: "Silverado 1500",
+        // Full-size truck, comparable to the F-150
+        by_mileage: &[
+            (1.0, 520.0), (2.0, 1040.0), (3.0, 1660.0), (4.0, 2240.0),
+            (5.0, 2810.0), (6.0, 3540.0), (7.0, 4160.0), (8.0, 4780.0),
+            (9.0, 5560.0), (10.0, 6240.0), (12.0, 7900.0), (15.0, 10400.0),
+            (20.0, 14560.0),
+        ],
+        by_time: &[
+            (1.0, 620.0), (2.0, 1250.0), (3.0, 2000.0), (4.0, 2680.0),
+            (5.0, 3370.0), (6.0, 4240.0), (7.0, 4990.0), (8.0, 5740.0),
+            (9.0, 6680.0), (10.0, 7490.0), (12.0, 9480.0), (15.0, 12480.0),
+        ],
+        value_by_age: &[
+            (0.0, 100.0), (1.0, 87.0), (3.0, 75.0), (5.0, 64.0),
+            (8.0, 49.0), (12.0, 33.0), (17.0, 19.0),
+        ],
+    },
+    SampleProfile {
+        make: "Tesla",
+        model: "Model 3",
+        // Electric; no oil changes, mostly tires, brake fluid and cabin filters
+        by_mileage: &[
+            (1.0, 190.0), (2.0, 390.0), (3.0, 600.0), (4.0, 800.0),
+            (5.0, 990.0), (6.0, 1260.0), (7.0, 1490.0), (8.0, 1710.0),
+            (9.0, 2010.0), (10.0, 2260.0), (12.0, 2860.0), (15.0, 3740.0),
+            (20.0, 5220.0),
+        ],
+        by_time: &[
+            (1.0, 230.0), (2.0, 460.0), (3.0, 730.0), (4.0, 960.0),
+            (5.0, 1190.0), (6.0, 1520.0), (7.0, 1780.0), (8.0, 2050.0),
+            (9.0, 2410.0), (10.0, 2710.0), (12.0, 3430.0), (15.0, 4490.0),
+        ],
+        value_by_age: &[
+            (0.0, 100.0), (1.0, 78.0), (3.0, 60.0), (5.0, 47.0),
+            (8.0, 33.0), (12.0, 20.0), (17.0, 9.0),
+        ],
+    },
+    SampleProfile {
+        make: "BMW",
+        model: "3 Series",
+        // Luxury sport sedan; dealer labor and premium parts
+        by_mileage: &[
+            (1.0, 740.0), (2.0, 1470.0), (3.0, 2310.0), (4.0, 3040.0),
+            (5.0, 3780.0), (6.0, 4830.0), (7.0, 5670.0), (8.0, 6510.0),
+            (9.0, 7660.0), (10.0, 8610.0), (12.0, 10920.0), (15.0, 14280.0),
+            (20.0, 19950.0),
+        ],
+        by_time: &[
+            (1.0, 880.0), (2.0, 1760.0), (3.0, 2770.0), (4.0, 3650.0),
+            (5.0, 4540.0), (6.0, 5800.0), (7.0, 6800.0), (8.0, 7810.0),
+            (9.0, 9200.0), (10.0, 10330.0), (12.0, 13100.0), (15.0, 17140.0),
+        ],
+        value_by_age: &[
+            (0.0, 100.0), (1.0, 78.0), (3.0, 58.0), (5.0, 44.0),
+            (8.0, 30.0), (12.0, 17.0), (17.0, 7.0),
+        ],
+    },
+];
+
+fn points(pairs: &[(f64, f64)]) -> Vec<MaintenanceDataPoint> {
+    pairs
+        .iter()
+        .map(|&(x, y)| MaintenanceDataPoint { x, y })
+        .collect()
+}
+
 /// Sample maintenance cost data based on typical costs for popular vehicles
 /// This data represents cumulative maintenance costs over time and mileage
 pub fn get_sample_maintenance_data() -> MaintenanceCostDatabase {
     let mut db = MaintenanceCostDatabase::default();
-
-    // Toyota Prius - known for reliability and lower maintenance costs
-    // Based on typical maintenance schedules: oil changes, tire rotations, brake service, etc.
-    let mut prius = MaintenanceCostData::new("Toyota".to_string(), "Prius".to_string());
-
-    // By mileage (x = 10k miles increments)
-    // Cumulative costs include: oil changes, filters, tire rotations, brake pads, fluids, etc.
-    prius.by_mileage = vec![
-        MaintenanceDataPoint { x: 1.0, y: 350.0 },   // 10k miles
-        MaintenanceDataPoint { x: 2.0, y: 700.0 },   // 20k miles
-        MaintenanceDataPoint { x: 3.0, y: 1100.0 },  // 30k miles (major service)
-        MaintenanceDataPoint { x: 4.0, y: 1450.0 },  // 40k miles
-        MaintenanceDataPoint { x: 5.0, y: 1800.0 },  // 50k miles
-        MaintenanceDataPoint { x: 6.0, y: 2300.0 },  // 60k miles (major service)
-        MaintenanceDataPoint { x: 7.0, y: 2700.0 },  // 70k miles
-        MaintenanceDataPoint { x: 8.0, y: 3100.0 },  // 80k miles
-        MaintenanceDataPoint { x: 9.0, y: 3650.0 },  // 90k miles (major service)
-        MaintenanceDataPoint { x: 10.0, y: 4100.0 }, // 100k miles
-        MaintenanceDataPoint { x: 12.0, y: 5200.0 }, // 120k miles (major service)
-        MaintenanceDataPoint { x: 15.0, y: 6800.0 }, // 150k miles
-        MaintenanceDataPoint { x: 20.0, y: 9500.0 }, // 200k miles
-    ];
-
-    // By time (x = years)
-    // Split 50/50 with mileage-based costs, assuming 12k miles/year
-    prius.by_time = vec![
-        MaintenanceDataPoint { x: 1.0, y: 420.0 },   // 1 year
-        MaintenanceDataPoint { x: 2.0, y: 840.0 },   // 2 years
-        MaintenanceDataPoint { x: 3.0, y: 1320.0 },  // 3 years
-        MaintenanceDataPoint { x: 4.0, y: 1740.0 },  // 4 years
-        MaintenanceDataPoint { x: 5.0, y: 2160.0 },  // 5 years
-        MaintenanceDataPoint { x: 6.0, y: 2760.0 },  // 6 years
-        MaintenanceDataPoint { x: 7.0, y: 3240.0 },  // 7 years
-        MaintenanceDataPoint { x: 8.0, y: 3720.0 },  // 8 years
-        MaintenanceDataPoint { x: 9.0, y: 4380.0 },  // 9 years
-        MaintenanceDataPoint { x: 10.0, y: 4920.0 }, // 10 years
-        MaintenanceDataPoint { x: 12.0, y: 6240.0 }, // 12 years
-        MaintenanceDataPoint { x: 15.0, y: 8160.0 }, // 15 years
-    ];
-
-    db.set(prius);
-
-    // Ford F-150 - popular truck with higher maintenance costs
-    // Larger engine, more fluids, heavier wear on components
-    let mut f150 = MaintenanceCostData::new("Ford".to_string(), "F-150".to_string());
-
-    // By mileage (x = 10k miles increments)
-    f150.by_mileage = vec![
-        MaintenanceDataPoint { x: 1.0, y: 500.0 },   // 10k miles
-        MaintenanceDataPoint { x: 2.0, y: 1000.0 },  // 20k miles
-        MaintenanceDataPoint { x: 3.0, y: 1600.0 },  // 30k miles (major service)
-        MaintenanceDataPoint { x: 4.0, y: 2150.0 },  // 40k miles
-        MaintenanceDataPoint { x: 5.0, y: 2700.0 },  // 50k miles
-        MaintenanceDataPoint { x: 6.0, y: 3400.0 },  // 60k miles (major service)
-        MaintenanceDataPoint { x: 7.0, y: 4000.0 },  // 70k miles
-        MaintenanceDataPoint { x: 8.0, y: 4600.0 },  // 80k miles
-        MaintenanceDataPoint { x: 9.0, y: 5350.0 },  // 90k miles (major service)
-        MaintenanceDataPoint { x: 10.0, y: 6000.0 }, // 100k miles
-        MaintenanceDataPoint { x: 12.0, y: 7600.0 }, // 120k miles (major service)
-        MaintenanceDataPoint {
-            x: 15.0,
-            y: 10000.0,
-        }, // 150k miles
-        MaintenanceDataPoint {
-            x: 20.0,
-            y: 14000.0,
-        }, // 200k miles
-    ];
-
-    // By time (x = years)
-    f150.by_time = vec![
-        MaintenanceDataPoint { x: 1.0, y: 600.0 },   // 1 year
-        MaintenanceDataPoint { x: 2.0, y: 1200.0 },  // 2 years
-        MaintenanceDataPoint { x: 3.0, y: 1920.0 },  // 3 years
-        MaintenanceDataPoint { x: 4.0, y: 2580.0 },  // 4 years
-        MaintenanceDataPoint { x: 5.0, y: 3240.0 },  // 5 years
-        MaintenanceDataPoint { x: 6.0, y: 4080.0 },  // 6 years
-        MaintenanceDataPoint { x: 7.0, y: 4800.0 },  // 7 years
-        MaintenanceDataPoint { x: 8.0, y: 5520.0 },  // 8 years
-        MaintenanceDataPoint { x: 9.0, y: 6420.0 },  // 9 years
-        MaintenanceDataPoint { x: 10.0, y: 7200.0 }, // 10 years
-        MaintenanceDataPoint { x: 12.0, y: 9120.0 }, // 12 years
-        MaintenanceDataPoint {
-            x: 15.0,
-            y: 12000.0,
-        }, // 15 years
-    ];
-
-    db.set(f150);
-
+    for profile in SAMPLE_PROFILES {
+        let mut data =
+            MaintenanceCostData::new(profile.make.to_string(), profile.model.to_string());
+        data.by_mileage = points(profile.by_mileage);
+        data.by_time = points(profile.by_time);
+        data.value_by_age = points(profile.value_by_age);
+        db.set(data);
+    }
     db
 }
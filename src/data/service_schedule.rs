@@ -0,0 +1,33 @@
+use crate::models::{MaintenanceDataPoint, MILES_PER_MILEAGE_X};
+
+/// One visit from a manufacturer's service schedule: what the service due at a mileage costs
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ServiceEntry {
+    pub miles: f64,
+    pub cost: f64,
+}
+
+/// Convert a service schedule (cost at each visit) into the cumulative `by_mileage` points
+/// the model expects, with x in units of 10k miles. Entries are sorted by mileage, services
+/// due at the same mileage are added together, and negative or non-numeric entries are
+/// skipped, so the result always rises with mileage.
+pub fn schedule_to_cumulative(entries: &[ServiceEntry]) -> Vec<MaintenanceDataPoint> {
+    let mut entries: Vec<ServiceEntry> = entries
+        .iter()
+        .copied()
+        .filter(|e| e.miles.is_finite() && e.cost.is_finite() && e.miles >= 0.0 && e.cost >= 0.0)
+        .collect();
+    entries.sort_by(|a, b| a.miles.total_cmp(&b.miles));
+
+    let mut points: Vec<MaintenanceDataPoint> = Vec::with_capacity(entries.len());
+    let mut total = 0.0;
+    for entry in entries {
+        total += entry.cost;
+        let x = entry.miles / MILES_PER_MILEAGE_X;
+        match points.last_mut() {
+            Some(last) if last.x == x => last.y = total,
+            _ => points.push(MaintenanceDataPoint { x, y: total }),
+        }
+    }
+    points
+}
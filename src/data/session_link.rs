@@ -0,0 +1,123 @@
+use serde::{Deserialize, Serialize};
+
+use crate::data::{AppExport, ImportMode};
+use crate::models::{Car, SharedSettings};
+
+/// URL fragment prefix for a shared session, as in `#state=...`
+pub const SESSION_FRAGMENT_PREFIX: &str = "#state=";
+
+/// Longest fragment kept in the URL; past this, links get unwieldy or truncated
+/// when pasted, so the session is offered as a download instead
+pub const MAX_SESSION_FRAGMENT_LEN: usize = 8000;
+
+/// Everything a shared link carries
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct SessionState {
+    #[serde(default)]
+    pub settings: SharedSettings,
+    #[serde(default)]
+    pub cars: Vec<Car>,
+}
+
+impl SessionState {
+    /// The state as shared in a link: photos are dropped since they'd dwarf everything else
+    pub fn for_link(settings: &SharedSettings, cars: &[Car]) -> Self {
+        Self {
+            settings: settings.clone(),
+            cars: cars
+                .iter()
+                .map(|car| Car {
+                    image_data_url: None,
+                    ..car.clone()
+                })
+                .collect(),
+        }
+    }
+
+    /// The data saved in this browser plus this shared session's cars, as when merging a
+    /// backup: cars not already saved are added under fresh ids and the saved settings are
+    /// kept
+    pub fn merged_into(self, saved: &SessionState) -> SessionState {
+        let merged = AppExport {
+            cars: self.cars,
+            settings: self.settings,
+            maintenance_db: Default::default(),
+        }
+        .apply_to(
+            &AppExport {
+                cars: saved.cars.clone(),
+                settings: saved.settings.clone(),
+                maintenance_db: Default::default(),
+            },
+            ImportMode::Merge,
+        );
+        SessionState {
+            settings: merged.settings,
+            cars: merged.cars,
+        }
+    }
+}
+
+const BASE64URL: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Unpadded base64url, safe to use in a URL fragment without escaping
+fn base64url_encode(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, b)| n | (u32::from(*b) << (16 - 8 * i)));
+        for i in 0..=chunk.len() {
+            out.push(BASE64URL[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+        }
+    }
+    out
+}
+
+fn base64url_decode(text: &str) -> Option<Vec<u8>> {
+    let values: Vec<u32> = text
+        .bytes()
+        .map(|c| BASE64URL.iter().position(|&b| b == c).map(|v| v as u32))
+        .collect::<Option<_>>()?;
+    if values.len() % 4 == 1 {
+        return None;
+    }
+    let mut out = Vec::with_capacity(values.len() * 3 / 4);
+    for chunk in values.chunks(4) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, v)| n | (v << (18 - 6 * i)));
+        for i in 0..chunk.len() - 1 {
+            out.push((n >> (16 - 8 * i)) as u8);
+        }
+    }
+    Some(out)
+}
+
+/// Encode a session as a URL fragment, e.g. `#state=eyJzZXR0aW5ncyI6...`
+pub fn encode_session_fragment(state: &SessionState) -> String {
+    let json = serde_json::to_string(state).unwrap_or_default();
+    format!(
+        "{}{}",
+        SESSION_FRAGMENT_PREFIX,
+        base64url_encode(json.as_bytes())
+    )
+}
+
+/// Decode a session from a URL fragment. Returns `None` when the fragment isn't a
+/// session link; an error when it is one but can't be read. Links never carry photos, so
+/// any a crafted link includes are dropped rather than loaded from wherever they point.
+pub fn decode_session_fragment(fragment: &str) -> Option<Result<SessionState, String>> {
+    let encoded = fragment.strip_prefix(SESSION_FRAGMENT_PREFIX)?;
+    Some(
+        base64url_decode(encoded.trim())
+            .ok_or_else(|| "The link's session data is corrupted".to_string())
+            .and_then(|bytes| {
+                serde_json::from_slice::<SessionState>(&bytes)
+                    .map(|state| SessionState::for_link(&state.settings, &state.cars))
+                    .map_err(|e| format!("The link's session data couldn't be read: {}", e))
+            }),
+    )
+}
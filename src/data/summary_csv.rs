@@ -0,0 +1,76 @@
+use serde_json::Value;
+
+use crate::models::{Car, ComputedCarData};
+
+/// Quote a CSV cell when it holds a comma, quote or line break
+fn csv_cell(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Append one `key,value` row per leaf of `value`, nested keys joined with dots
+fn flatten(prefix: &str, value: &Value, rows: &mut Vec<(String, String)>) {
+    let key = |name: &str| {
+        if prefix.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}.{}", prefix, name)
+        }
+    };
+    match value {
+        Value::Object(fields) => {
+            for (name, field) in fields {
+                flatten(&key(name), field, rows);
+            }
+        }
+        Value::Array(items) => {
+            for (i, item) in items.iter().enumerate() {
+                flatten(&key(&i.to_string()), item, rows);
+            }
+        }
+        Value::Null => rows.push((prefix.to_string(), String::new())),
+        Value::String(s) => rows.push((prefix.to_string(), s.clone())),
+        other => rows.push((prefix.to_string(), other.to_string())),
+    }
+}
+
+/// Two-column `field,value` CSV of a car's inputs (prefixed `car.`) followed by every
+/// computed field, each group in alphabetical order, for pulling the numbers into a
+/// spreadsheet. The photo is left out.
+pub fn summary_csv(car: &Car, computed: &ComputedCarData) -> String {
+    let mut car = car.clone();
+    car.image_data_url = None;
+
+    let mut rows = Vec::new();
+    if let Ok(inputs) = serde_json::to_value(&car) {
+        flatten("car", &inputs, &mut rows);
+    }
+    if let Ok(outputs) = serde_json::to_value(computed) {
+        flatten("", &outputs, &mut rows);
+    }
+
+    let mut csv = String::from("field,value\n");
+    for (key, value) in rows {
+        csv.push_str(&format!("{},{}\n", csv_cell(&key), csv_cell(&value)));
+    }
+    csv
+}
+
+/// File name for a car's CSV, e.g. "carcalc-toyota-prius-2010.csv"
+pub fn summary_csv_filename(car: &Car) -> String {
+    let slug: String = car
+        .display_name()
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    let slug = slug
+        .split('-')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    format!("carcalc-{}.csv", slug)
+}
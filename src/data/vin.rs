@@ -0,0 +1,146 @@
+use serde::Deserialize;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+
+/// NHTSA vPIC endpoint returning one flat record per VIN
+const VPIC_DECODE_URL: &str = "https://vpic.nhtsa.dot.gov/api/vehicles/DecodeVinValues";
+
+/// Make, model and model year decoded from a VIN
+#[derive(Clone, Debug, PartialEq)]
+pub struct DecodedVin {
+    pub make: String,
+    pub model: String,
+    /// Model year, empty when vPIC couldn't tell
+    pub year: String,
+}
+
+impl DecodedVin {
+    /// e.g. "2010 Toyota Prius", or "Toyota Prius" without a year
+    pub fn name(&self) -> String {
+        format!("{} {} {}", self.year, self.make, self.model)
+            .trim()
+            .to_string()
+    }
+}
+
+#[derive(Deserialize)]
+struct VpicResponse {
+    #[serde(rename = "Results", default)]
+    results: Vec<VpicResult>,
+}
+
+#[derive(Deserialize)]
+struct VpicResult {
+    #[serde(rename = "Make", default)]
+    make: Option<String>,
+    #[serde(rename = "Model", default)]
+    model: Option<String>,
+    #[serde(rename = "ModelYear", default)]
+    model_year: Option<String>,
+    #[serde(rename = "ErrorText", default)]
+    error_text: Option<String>,
+}
+
+/// Normalize a VIN as typed (trimmed, uppercase) and check it could be a real one: 17
+/// letters and digits, never I, O or Q. Returns the normalized VIN or why it can't be.
+pub fn normalize_vin(vin: &str) -> Result<String, String> {
+    let vin: String = vin
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .to_uppercase();
+    if vin.is_empty() {
+        return Err("Enter a VIN to decode".to_string());
+    }
+    if let Some(bad) = vin.chars().find(|c| !c.is_ascii_alphanumeric()) {
+        return Err(format!("A VIN can't contain '{}'", bad));
+    }
+    if let Some(bad) = vin.chars().find(|c| matches!(c, 'I' | 'O' | 'Q')) {
+        return Err(format!(
+            "A VIN never contains the letter {} (it's easily mistaken for a digit)",
+            bad
+        ));
+    }
+    if vin.len() != 17 {
+        return Err(format!(
+            "A VIN is 17 characters, but this one has {}",
+            vin.len()
+        ));
+    }
+    Ok(vin)
+}
+
+/// vPIC reports makes in capitals ("TOYOTA"); longer names read better title-cased, while
+/// short ones are usually initialisms ("BMW", "GMC") and stay as they are
+fn tidy_make(make: &str) -> String {
+    if make.len() <= 3 || make.chars().any(|c| c.is_lowercase()) {
+        return make.to_string();
+    }
+    let mut out = String::with_capacity(make.len());
+    let mut word_start = true;
+    for c in make.chars() {
+        if word_start {
+            out.extend(c.to_uppercase());
+        } else {
+            out.extend(c.to_lowercase());
+        }
+        word_start = !c.is_alphanumeric();
+    }
+    out
+}
+
+/// Pull the make, model and year out of a vPIC `DecodeVinValues` JSON response. A VIN
+/// vPIC can't place at all comes back with no make or model, so that's an error carrying
+/// vPIC's own explanation.
+pub fn parse_vpic_response(json: &str) -> Result<DecodedVin, String> {
+    let response: VpicResponse = serde_json::from_str(json)
+        .map_err(|e| format!("Unexpected response from the VIN decoder: {}", e))?;
+    let result = response
+        .results
+        .into_iter()
+        .next()
+        .ok_or_else(|| "The VIN decoder returned no results".to_string())?;
+
+    let field = |value: Option<String>| value.unwrap_or_default().trim().to_string();
+    let make = field(result.make);
+    let model = field(result.model);
+    if make.is_empty() || model.is_empty() {
+        let reason = field(result.error_text);
+        return Err(if reason.is_empty() {
+            "The VIN couldn't be decoded".to_string()
+        } else {
+            format!("The VIN couldn't be decoded: {}", reason)
+        });
+    }
+    Ok(DecodedVin {
+        make: tidy_make(&make),
+        model,
+        year: field(result.model_year),
+    })
+}
+
+/// Look up a VIN with the NHTSA vPIC service
+pub async fn decode_vin(vin: String) -> Result<DecodedVin, String> {
+    let vin = normalize_vin(&vin)?;
+    let url = format!("{}/{}?format=json", VPIC_DECODE_URL, vin);
+    let unreachable = |_| "Couldn't reach the VIN decoder. Check your connection.".to_string();
+
+    let window = web_sys::window().ok_or("No browser window to fetch from")?;
+    let response: web_sys::Response = JsFuture::from(window.fetch_with_str(&url))
+        .await
+        .map_err(unreachable)?
+        .dyn_into()
+        .map_err(|_| "Unexpected response from the VIN decoder".to_string())?;
+    if !response.ok() {
+        return Err(format!(
+            "The VIN decoder answered with an error ({})",
+            response.status()
+        ));
+    }
+    let text = JsFuture::from(response.text().map_err(unreachable)?)
+        .await
+        .map_err(unreachable)?
+        .as_string()
+        .unwrap_or_default();
+    parse_vpic_response(&text)
+}
@@ -1,4 +1,196 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// How a car's insurance cost is entered
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum InsuranceMode {
+    /// Flat premium, annualized by its `InsurancePeriod`
+    #[default]
+    Premium,
+    /// Pay-per-mile rate applied to the remaining miles
+    PerMile,
+}
+
+/// How long a flat insurance premium covers
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum InsurancePeriod {
+    Monthly,
+    #[default]
+    SixMonth,
+    Annual,
+}
+
+impl InsurancePeriod {
+    pub const ALL: [InsurancePeriod; 3] = [
+        InsurancePeriod::Monthly,
+        InsurancePeriod::SixMonth,
+        InsurancePeriod::Annual,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            InsurancePeriod::Monthly => "Monthly",
+            InsurancePeriod::SixMonth => "6-month",
+            InsurancePeriod::Annual => "Annual",
+        }
+    }
+
+    /// Stable identifier for form values
+    pub fn id(&self) -> &'static str {
+        match self {
+            InsurancePeriod::Monthly => "monthly",
+            InsurancePeriod::SixMonth => "six_month",
+            InsurancePeriod::Annual => "annual",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|p| p.id() == id)
+    }
+
+    /// Premiums paid per year
+    pub fn per_year(&self) -> f64 {
+        match self {
+            InsurancePeriod::Monthly => 12.0,
+            InsurancePeriod::SixMonth => 2.0,
+            InsurancePeriod::Annual => 1.0,
+        }
+    }
+}
+
+/// How the car's resale value at the end of ownership is projected
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum DepreciationMethod {
+    /// Value falls linearly to zero at the lifetime mileage
+    StraightLine,
+    /// Value loses a fixed percentage each year
+    #[default]
+    ExponentialDecay,
+    /// Value follows the make/model's resale curve in the maintenance database
+    CurveFromDatabase,
+}
+
+impl DepreciationMethod {
+    pub const ALL: [DepreciationMethod; 3] = [
+        DepreciationMethod::ExponentialDecay,
+        DepreciationMethod::StraightLine,
+        DepreciationMethod::CurveFromDatabase,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DepreciationMethod::StraightLine => "Straight line",
+            DepreciationMethod::ExponentialDecay => "Exponential decay",
+            DepreciationMethod::CurveFromDatabase => "Make/model resale curve",
+        }
+    }
+
+    /// Stable identifier for form values
+    pub fn id(&self) -> &'static str {
+        match self {
+            DepreciationMethod::StraightLine => "straight_line",
+            DepreciationMethod::ExponentialDecay => "exponential",
+            DepreciationMethod::CurveFromDatabase => "curve",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|m| m.id() == id)
+    }
+}
+
+/// How the car is sold at the end of ownership
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ResaleChannel {
+    /// Sold to a private buyer at full resale value
+    #[default]
+    PrivateSale,
+    /// Traded in to a dealer at a discount, optionally earning a sales-tax credit
+    TradeIn,
+}
+
+impl ResaleChannel {
+    pub const ALL: [ResaleChannel; 2] = [ResaleChannel::PrivateSale, ResaleChannel::TradeIn];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ResaleChannel::PrivateSale => "Private sale",
+            ResaleChannel::TradeIn => "Trade-in",
+        }
+    }
+
+    /// Stable identifier for form values
+    pub fn id(&self) -> &'static str {
+        match self {
+            ResaleChannel::PrivateSale => "private",
+            ResaleChannel::TradeIn => "trade_in",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|c| c.id() == id)
+    }
+}
+
+/// What the car runs on, which decides the efficiency inputs it needs
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum FuelType {
+    /// Gas only, rated in MPG
+    #[default]
+    Gasoline,
+    /// Electricity only, rated in miles per kWh
+    Electric,
+    /// Plug-in hybrid: a share of the miles on electricity, the rest on gas
+    Hybrid,
+}
+
+impl FuelType {
+    pub const ALL: [FuelType; 3] = [FuelType::Gasoline, FuelType::Electric, FuelType::Hybrid];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            FuelType::Gasoline => "Gasoline",
+            FuelType::Electric => "Electric",
+            FuelType::Hybrid => "Plug-in hybrid",
+        }
+    }
+
+    /// Stable identifier for form values
+    pub fn id(&self) -> &'static str {
+        match self {
+            FuelType::Gasoline => "gasoline",
+            FuelType::Electric => "electric",
+            FuelType::Hybrid => "hybrid",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|t| t.id() == id)
+    }
+
+    /// Whether the car burns gas for at least some of its miles
+    pub fn uses_gas(&self) -> bool {
+        *self != FuelType::Electric
+    }
+
+    /// Whether the car runs on electricity for at least some of its miles
+    pub fn uses_electricity(&self) -> bool {
+        *self != FuelType::Gasoline
+    }
+}
+
+/// A required input on the car form, for pointing at the one that's wrong
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CarField {
+    PurchasePrice,
+    CurrentMileage,
+    Mpg,
+    MpgCity,
+    MpgHighway,
+    MilesPerKwh,
+    ElectricMilesPercent,
+    /// The premium or per-mile rate, whichever the insurance mode uses
+    Insurance,
+}
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct Car {
@@ -14,6 +206,123 @@ pub struct Car {
     pub vin: String,
     pub listing_url: String,
     pub notes: String,
+    /// Vehicle age (in years, from new) at which the owner plans to sell; empty means no
+    /// limit. Unlike [`SharedSettings::ownership_years`](crate::models::SharedSettings::ownership_years), which counts from today for
+    /// every car. Saved as `ownership_years` before it was renamed.
+    #[serde(default, alias = "ownership_years")]
+    pub keep_until_age: String,
+    /// Downscaled JPEG thumbnail stored as a data URL. Anything other than a `data:image/`
+    /// URL read from storage or a backup is dropped.
+    #[serde(default, deserialize_with = "image_data_only")]
+    pub image_data_url: Option<String>,
+    #[serde(default)]
+    pub insurance_mode: InsuranceMode,
+    /// How long `insurance_cost` covers, used in `InsuranceMode::Premium`
+    #[serde(default)]
+    pub insurance_period: InsurancePeriod,
+    /// Insurance cost per mile driven, used in `InsuranceMode::PerMile`
+    #[serde(default)]
+    pub insurance_per_mile: String,
+    /// Miles left to drive in the current year; prorates the first cash-flow period.
+    /// Empty means a full first year.
+    #[serde(default)]
+    pub first_year_miles: String,
+    #[serde(default)]
+    pub depreciation_method: DepreciationMethod,
+    #[serde(default)]
+    pub resale_channel: ResaleChannel,
+    /// Whether the car counts in the comparison and aggregates; parked ideas are left out
+    #[serde(default = "default_in_comparison")]
+    pub in_comparison: bool,
+    /// Upfront price of an extended or certified pre-owned warranty; empty means none
+    /// was bought (a warranty that came with the car can still have a coverage window)
+    #[serde(default)]
+    pub extended_warranty_cost: String,
+    /// Years of warranty coverage from purchase; empty means no time limit
+    #[serde(default)]
+    pub extended_warranty_years: String,
+    /// Miles of warranty coverage from purchase; empty means no mileage limit
+    #[serde(default)]
+    pub extended_warranty_miles: String,
+    /// Cash paid upfront on a financed purchase; empty means none
+    #[serde(default)]
+    pub down_payment: String,
+    /// Loan APR in percent
+    #[serde(default)]
+    pub loan_apr: String,
+    /// Loan length in months; empty means the car is bought outright
+    #[serde(default)]
+    pub loan_term_months: String,
+    /// Seats, for cost per passenger-mile; empty hides that metric
+    #[serde(default)]
+    pub seats: String,
+    /// Expected private-sale value at the end of ownership, e.g. from a dealer quote;
+    /// replaces the depreciation projection when set
+    #[serde(default)]
+    pub expected_resale_value: String,
+    #[serde(default)]
+    pub fuel_type: FuelType,
+    /// Electric efficiency, used by electric cars and plug-in hybrids
+    #[serde(default)]
+    pub miles_per_kwh: String,
+    /// Share of a plug-in hybrid's miles driven on electricity, in percent
+    #[serde(default)]
+    pub electric_miles_percent: String,
+    /// Odometer reading this car is kept until, replacing the shared lifetime mileage;
+    /// empty means use the setting
+    #[serde(default)]
+    pub lifetime_miles_override: String,
+    /// Price of a full set of tires, installed; empty leaves tires out
+    #[serde(default)]
+    pub tire_set_cost: String,
+    /// Miles a set of tires lasts, e.g. its treadwear warranty
+    #[serde(default)]
+    pub tire_life_miles: String,
+    /// EPA city rating; with `mpg_highway`, replaces `mpg` by a blend at the shared
+    /// highway share
+    #[serde(default)]
+    pub mpg_city: String,
+    /// EPA highway rating, used together with `mpg_city`
+    #[serde(default)]
+    pub mpg_highway: String,
+    /// Tag color (a CSS hex color from `CAR_COLORS`) marking the car's card and column.
+    /// Anything else read from storage, a backup or a link is dropped.
+    #[serde(default, deserialize_with = "known_color")]
+    pub color: Option<String>,
+}
+
+/// Tag colors offered on the car form, as (name, CSS hex color)
+pub const CAR_COLORS: [(&str, &str); 8] = [
+    ("Red", "#ef4444"),
+    ("Orange", "#f97316"),
+    ("Yellow", "#eab308"),
+    ("Green", "#22c55e"),
+    ("Teal", "#14b8a6"),
+    ("Blue", "#3b82f6"),
+    ("Purple", "#a855f7"),
+    ("Pink", "#ec4899"),
+];
+
+/// Keep a stored tag color only if it's one of `CAR_COLORS`, since it ends up in a style
+/// attribute
+fn known_color<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    let color = Option::<String>::deserialize(deserializer)?;
+    Ok(color.filter(|color| CAR_COLORS.iter().any(|(_, hex)| hex == color)))
+}
+
+/// Keep a stored photo only if it's an inline image, so it can't point the page at a
+/// remote URL
+fn image_data_only<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    let url = Option::<String>::deserialize(deserializer)?;
+    Ok(url.filter(|url| is_image_data_url(url)))
+}
+
+fn is_image_data_url(url: &str) -> bool {
+    url.starts_with("data:image/")
+}
+
+fn default_in_comparison() -> bool {
+    true
 }
 
 impl Car {
@@ -31,6 +340,209 @@ impl Car {
             vin: String::new(),
             listing_url: String::new(),
             notes: String::new(),
+            keep_until_age: String::new(),
+            image_data_url: None,
+            insurance_mode: InsuranceMode::Premium,
+            insurance_period: InsurancePeriod::SixMonth,
+            insurance_per_mile: String::new(),
+            first_year_miles: String::new(),
+            depreciation_method: DepreciationMethod::ExponentialDecay,
+            resale_channel: ResaleChannel::PrivateSale,
+            in_comparison: true,
+            extended_warranty_cost: String::new(),
+            extended_warranty_years: String::new(),
+            extended_warranty_miles: String::new(),
+            down_payment: String::new(),
+            loan_apr: String::new(),
+            loan_term_months: String::new(),
+            seats: String::new(),
+            expected_resale_value: String::new(),
+            fuel_type: FuelType::Gasoline,
+            miles_per_kwh: String::new(),
+            electric_miles_percent: String::new(),
+            lifetime_miles_override: String::new(),
+            tire_set_cost: String::new(),
+            tire_life_miles: String::new(),
+            mpg_city: String::new(),
+            mpg_highway: String::new(),
+            color: None,
+        }
+    }
+
+    /// Whether the required inputs (price, mileage, efficiency for the fuel type and
+    /// insurance for the chosen mode) are filled in and valid, the minimum needed to
+    /// compute costs. Settings can still make the computation fail, e.g. when the car is
+    /// past its lifetime miles.
+    pub fn is_computable(&self) -> bool {
+        let number = |value: &str| value.trim().parse::<f64>().ok().filter(|v| v.is_finite());
+        let insurance = match self.insurance_mode {
+            InsuranceMode::Premium => &self.insurance_cost,
+            InsuranceMode::PerMile => &self.insurance_per_mile,
+        };
+        let gas = !self.fuel_type.uses_gas()
+            || number(&self.mpg).is_some_and(|mpg| mpg > 0.0)
+            || self.split_mpg().is_some();
+        let electric = !self.fuel_type.uses_electricity()
+            || number(&self.miles_per_kwh).is_some_and(|mi| mi > 0.0);
+        let share = self.fuel_type != FuelType::Hybrid
+            || number(&self.electric_miles_percent).is_some_and(|p| (0.0..=100.0).contains(&p));
+        number(&self.purchase_price).is_some()
+            && number(&self.current_mileage).is_some()
+            && gas
+            && electric
+            && share
+            && number(insurance).is_some()
+    }
+
+    /// The photo to draw, if it's an inline `data:image/` URL
+    pub fn photo_src(&self) -> Option<&str> {
+        self.image_data_url
+            .as_deref()
+            .filter(|url| is_image_data_url(url))
+    }
+
+    /// The tag color to draw, if it's one of `CAR_COLORS`
+    pub fn tag_color(&self) -> Option<&'static str> {
+        let color = self.color.as_deref()?;
+        CAR_COLORS
+            .iter()
+            .find(|(_, hex)| *hex == color)
+            .map(|(_, hex)| *hex)
+    }
+
+    /// City and highway MPG, when both are filled in with positive numbers
+    pub fn split_mpg(&self) -> Option<(f64, f64)> {
+        let rating = |value: &str| {
+            value
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .filter(|v| v.is_finite() && *v > 0.0)
+        };
+        Some((rating(&self.mpg_city)?, rating(&self.mpg_highway)?))
+    }
+
+    /// The lifetime mileage this car is costed to: its own override when that's a positive
+    /// number, otherwise the shared setting
+    pub fn lifetime_miles(&self, shared_lifetime_miles: f64) -> f64 {
+        self.own_lifetime_miles().unwrap_or(shared_lifetime_miles)
+    }
+
+    /// The lifetime mileage override, when it's a usable positive number
+    fn own_lifetime_miles(&self) -> Option<f64> {
+        self.lifetime_miles_override
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|miles| miles.is_finite() && *miles > 0.0)
+    }
+
+    /// Why a required input can't be used: it isn't a number or is out of range. Empty
+    /// fields and inputs the fuel type doesn't use aren't flagged. `lifetime_miles` is the
+    /// mileage the current mileage must stay under (see [`Car::lifetime_miles`]).
+    pub fn field_error(&self, field: CarField, lifetime_miles: f64) -> Option<&'static str> {
+        let raw = match field {
+            CarField::PurchasePrice => &self.purchase_price,
+            CarField::CurrentMileage => &self.current_mileage,
+            CarField::Mpg if self.fuel_type.uses_gas() => &self.mpg,
+            CarField::MpgCity if self.fuel_type.uses_gas() => &self.mpg_city,
+            CarField::MpgHighway if self.fuel_type.uses_gas() => &self.mpg_highway,
+            CarField::MilesPerKwh if self.fuel_type.uses_electricity() => &self.miles_per_kwh,
+            CarField::ElectricMilesPercent if self.fuel_type == FuelType::Hybrid => {
+                &self.electric_miles_percent
+            }
+            CarField::Insurance => match self.insurance_mode {
+                InsuranceMode::Premium => &self.insurance_cost,
+                InsuranceMode::PerMile => &self.insurance_per_mile,
+            },
+            _ => return None,
+        };
+        let raw = raw.trim();
+        if raw.is_empty() {
+            return None;
+        }
+        let Some(value) = raw.parse::<f64>().ok().filter(|v| v.is_finite()) else {
+            return Some("Enter a plain number, without $ or commas.");
+        };
+        match field {
+            CarField::PurchasePrice if value < 0.0 => Some("The price can't be negative."),
+            CarField::CurrentMileage if value < 0.0 => Some("Mileage can't be negative."),
+            CarField::CurrentMileage if value >= lifetime_miles => {
+                Some(if self.own_lifetime_miles().is_some() {
+                    "This is at or past this car's Lifetime Miles, so no miles are left to cost."
+                } else {
+                    "This is at or past the lifetime mileage in settings, so no miles are left to cost."
+                })
+            }
+            CarField::Mpg | CarField::MpgCity | CarField::MpgHighway if value <= 0.0 => {
+                Some("MPG must be more than 0.")
+            }
+            CarField::MilesPerKwh if value <= 0.0 => Some("Miles per kWh must be more than 0."),
+            CarField::ElectricMilesPercent if !(0.0..=100.0).contains(&value) => {
+                Some("Enter a share from 0 to 100%.")
+            }
+            CarField::Insurance if value < 0.0 => Some("Insurance can't be negative."),
+            _ => None,
+        }
+    }
+
+    /// Whether anything beyond the required inputs has been entered or changed from its default
+    pub fn has_details(&self) -> bool {
+        let required_only = Car {
+            purchase_price: self.purchase_price.clone(),
+            current_mileage: self.current_mileage.clone(),
+            mpg: self.mpg.clone(),
+            mpg_city: self.mpg_city.clone(),
+            mpg_highway: self.mpg_highway.clone(),
+            fuel_type: self.fuel_type,
+            miles_per_kwh: self.miles_per_kwh.clone(),
+            electric_miles_percent: self.electric_miles_percent.clone(),
+            insurance_cost: self.insurance_cost.clone(),
+            insurance_per_mile: self.insurance_per_mile.clone(),
+            insurance_mode: self.insurance_mode,
+            insurance_period: self.insurance_period,
+            ..Car::new(self.id)
+        };
+        *self != required_only
+    }
+
+    /// Case-insensitive substring match across the identifying and free-text fields.
+    /// An empty query matches every car.
+    pub fn matches_search(&self, query: &str) -> bool {
+        let query = query.trim().to_lowercase();
+        if query.is_empty() {
+            return true;
+        }
+        [
+            &self.make,
+            &self.model,
+            &self.trim,
+            &self.year,
+            &self.vin,
+            &self.notes,
+        ]
+        .iter()
+        .any(|field| field.to_lowercase().contains(&query))
+    }
+
+    /// Age in years from the model year, when both it and today's date are known. A model
+    /// year later than today counts as brand new.
+    pub fn age_from_model_year(&self, current_year: Option<f64>) -> Option<f64> {
+        let model_year = self.year.trim().parse::<f64>().ok()?;
+        current_year.map(|today| (today - model_year).max(0.0))
+    }
+
+    /// Human-readable name, e.g. "Toyota Camry (2020)" or "Car #3" when make and model are blank
+    pub fn display_name(&self) -> String {
+        let name = if !self.make.is_empty() || !self.model.is_empty() {
+            format!("{} {}", self.make, self.model).trim().to_string()
+        } else {
+            format!("Car #{}", self.id)
+        };
+        if self.year.is_empty() {
+            name
+        } else {
+            format!("{} ({})", name, self.year)
         }
     }
 }
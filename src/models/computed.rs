@@ -1,13 +1,289 @@
-#[derive(Clone, Debug)]
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::models::{
+    DepreciationMethod, FuelType, InsuranceMode, InsurancePeriod, MaintenanceBasis, ResaleChannel,
+    SettingsIssue,
+};
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct ComputedCarData {
     pub remaining_miles: f64,
     pub years_remaining: f64,
     pub fuel_cost_total: f64,
     pub fuel_cost_annual: f64,
+    /// Gas burned over the remaining miles (0 for an electric car)
+    pub total_gallons: f64,
+    /// Average MPG over the remaining gas miles, below the car's MPG when fuel economy
+    /// falls with age (0 for an electric car)
+    pub effective_mpg: f64,
+    /// Electricity drawn over the remaining miles (0 for a gasoline car)
+    pub total_kwh: f64,
+    /// Tailpipe CO2 from `total_gallons`; charging emissions depend on the grid and are
+    /// left out
+    pub total_co2_kg: f64,
     pub insurance_cost_annual: f64,
     pub maintenance_cost_total: f64,
     pub maintenance_cost_annual: f64,
     pub opportunity_cost: f64,
+    /// Which maintenance tables the maintenance figures are based on
+    pub maintenance_basis: MaintenanceBasis,
+    /// Name of the maintenance profile used when it only loosely matches the car's make
+    /// and model (e.g. "Ford F-150" for "ford f150"); `None` for an exact match or no profile
+    pub maintenance_profile_match: Option<String>,
+    /// Projected sale price at the end of ownership, credited against the TCO
+    pub resale_value: f64,
+    /// Value lost over the ownership: the purchase price less `resale_value`
+    pub depreciation_cost: f64,
+    /// Method actually used for `resale_value` (a missing resale curve falls back to decay)
+    pub depreciation_method: DepreciationMethod,
+    pub resale_channel: ResaleChannel,
+    /// Sales tax saved on the next purchase by trading in, credited against the TCO
+    pub trade_in_tax_credit: f64,
+    /// Upfront price of the extended warranty, added to the TCO
+    pub extended_warranty_cost: f64,
+    /// Maintenance the warranty covers, already left out of `maintenance_cost_total`
+    pub warranty_covered_maintenance: f64,
+    /// For a financed car, the down payment plus the loan payments discounted at the
+    /// opportunity cost rate: the cash price that would cost the same
+    pub effective_cash_price: Option<f64>,
+    /// Sales tax paid once on the purchase price, added to the TCO (0 when untaxed)
+    pub sales_tax: f64,
+    /// Registration fees over the ownership period, added to the TCO
+    pub registration_cost_total: f64,
+    /// Tire sets worn through over the remaining miles, added to the TCO (0 without tire inputs)
+    pub tire_cost_total: f64,
+    /// Loan interest paid while the car is owned, added to the TCO (0 for a cash purchase)
+    pub total_interest_paid: f64,
+    /// Level monthly loan payment (0 for a cash purchase)
+    pub monthly_payment: f64,
+    /// Cost per mile split across every seat, when the car's seat count is given
+    pub cost_per_passenger_mile: Option<f64>,
+    /// Fuel and maintenance already spent getting the car to its current mileage
+    pub cost_incurred_to_date: f64,
+    /// Fuel, maintenance, tires, insurance and registration still to come over the
+    /// ownership period
+    pub cost_projected_remaining: f64,
     pub total_cost_of_ownership: f64,
+    /// `total_cost_of_ownership` in future dollars: each cost as it will be priced when
+    /// it's paid, rather than in today's dollars
+    pub total_cost_nominal: f64,
+    /// Net present value of owning the car at the opportunity cost rate, when the setting
+    /// is on: every outlay and the resale discounted to today
+    pub discounted_total_cost: Option<f64>,
+    /// All-in annual cost: the total cost of ownership, purchase price included,
+    /// spread over the ownership period
     pub annual_cost: f64,
+    /// Fuel, maintenance, tires, insurance and registration per year (plus opportunity cost
+    /// if the setting is on), leaving out the purchase price and resale
+    pub annual_operating_cost: f64,
+    /// Total cost of ownership spread over the remaining miles, for comparing cars driven
+    /// different distances
+    pub cost_per_mile: f64,
+    /// Inputs and intermediate values behind the fields above
+    pub operands: ComputeOperands,
+}
+
+/// The values each computed field was derived from, kept so the numbers can be explained
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct ComputeOperands {
+    pub purchase_price: f64,
+    pub current_mileage: f64,
+    pub lifetime_miles: f64,
+    /// Whether `lifetime_miles` is the car's own override rather than the shared setting
+    pub lifetime_miles_overridden: bool,
+    /// Miles left before the planned sale (by age or years of ownership), when one is set
+    pub ownership_cap_miles: Option<f64>,
+    pub annual_mileage: f64,
+    /// Efficiency inputs and prices behind the fuel cost; inputs the fuel type doesn't
+    /// use are zero
+    pub fuel_type: FuelType,
+    pub mpg: f64,
+    /// Yearly loss of fuel economy with age, in percent
+    pub mpg_loss_percent: f64,
+    /// City and highway ratings `mpg` was blended from, if given
+    pub mpg_city_highway: Option<(f64, f64)>,
+    /// Share of miles on the highway for the blend, in percent
+    pub highway_percent: f64,
+    pub gas_price: f64,
+    pub miles_per_kwh: f64,
+    pub electricity_price: f64,
+    /// Share of miles on electricity, from 0 to 1
+    pub electric_share: f64,
+    pub insurance_mode: InsuranceMode,
+    pub insurance_period: InsurancePeriod,
+    /// The 6-month premium or per-mile rate as entered
+    pub insurance_input: f64,
+    /// Regional multiplier applied to insurance (1.0 when it doesn't apply)
+    pub insurance_multiplier: f64,
+    /// Maintenance from each table over the ownership period, before blending and scaling
+    pub maintenance_by_mileage: f64,
+    pub maintenance_by_time: f64,
+    pub region_multiplier: f64,
+    pub opportunity_cost_rate: f64,
+    /// Whether the opportunity cost was compounded rather than simple interest
+    pub opportunity_compounded: bool,
+    /// Whether `annual_operating_cost` includes opportunity cost
+    pub operating_includes_opportunity: bool,
+    /// Projected private-sale value before any trade-in discount
+    pub private_sale_value: f64,
+    /// Whether `private_sale_value` is the car's expected resale value rather than a
+    /// projection
+    pub resale_overridden: bool,
+    /// Yearly value loss for exponential decay, in percent
+    pub depreciation_rate: f64,
+    /// Trade-in discount and sales tax rate applied (0 for a private sale)
+    pub trade_in_discount_percent: f64,
+    pub sales_tax_rate: f64,
+    /// Sales tax rate charged on the purchase, in percent (0 when untaxed)
+    pub purchase_tax_rate: f64,
+    pub annual_registration_fee: f64,
+    /// Tire set price and the miles a set lasts (both 0 without tire inputs)
+    pub tire_set_cost: f64,
+    pub tire_life_miles: f64,
+    /// Years from purchase the warranty covers, its years or miles limit whichever comes
+    /// first (0 with no coverage window)
+    pub warranty_years: f64,
+    /// Loan terms behind `effective_cash_price` (all 0 for a cash purchase)
+    pub down_payment: f64,
+    pub loan_apr: f64,
+    pub loan_term_months: u32,
+    pub monthly_payment: f64,
+    /// Seat count behind `cost_per_passenger_mile` (0 when not given)
+    pub seats: f64,
+    /// Split of `cost_incurred_to_date`, with the gas price assumed for past miles
+    pub historical_gas_price: f64,
+    pub incurred_fuel: f64,
+    pub incurred_maintenance: f64,
+    /// Inflation rate in percent, and what it adds to the running costs and the resale
+    /// and credits behind `total_cost_nominal`
+    pub inflation_rate: f64,
+    pub inflation_on_running: f64,
+    pub inflation_on_resale: f64,
+    /// Parts of `discounted_total_cost`, each discounted to today (all 0 when it's off)
+    pub discounted_purchase: f64,
+    pub discounted_running: f64,
+    pub discounted_resale: f64,
+    /// Years `annual_cost` averages running costs over, and those running costs
+    pub annual_cost_window_years: f64,
+    pub annual_cost_window_running: f64,
+}
+
+impl ComputedCarData {
+    /// Resale and credits outweigh every cost, so owning the car comes out as a profit.
+    /// That's almost always an inflated resale value or tax credit, not a real deal.
+    pub fn has_net_profit(&self) -> bool {
+        self.total_cost_of_ownership < 0.0
+    }
+
+    /// Where the resale value came from: the car's expected value or the method projecting it
+    pub fn resale_source_label(&self) -> &'static str {
+        if self.operands.resale_overridden {
+            "Your expected value"
+        } else {
+            self.depreciation_method.label()
+        }
+    }
+
+    /// Covered maintenance less the warranty's price: positive when the warranty pays off
+    pub fn warranty_net_savings(&self) -> f64 {
+        self.warranty_covered_maintenance - self.extended_warranty_cost
+    }
+
+    /// Whether there's a warranty to report, bought or with a coverage window
+    pub fn has_extended_warranty(&self) -> bool {
+        self.extended_warranty_cost != 0.0 || self.warranty_covered_maintenance != 0.0
+    }
+}
+
+/// Outlays during one year of the ownership period
+#[derive(Clone, Debug, PartialEq)]
+pub struct PeriodCashFlow {
+    /// Zero-based year index from today
+    pub year: usize,
+    /// Length of the period in years (the final period may be partial)
+    pub years: f64,
+    pub fuel: f64,
+    pub maintenance: f64,
+    pub insurance: f64,
+    pub registration: f64,
+    pub tires: f64,
+    pub financing: f64,
+}
+
+impl PeriodCashFlow {
+    pub fn total(&self) -> f64 {
+        self.fuel
+            + self.maintenance
+            + self.tires
+            + self.insurance
+            + self.registration
+            + self.financing
+    }
+}
+
+/// Reasons a car's costs can't be computed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ComputeError {
+    /// A required field is empty or isn't a number
+    MissingInput,
+    /// A value parsed but is out of range (e.g. MPG of zero)
+    InvalidInput,
+    /// The car's mileage already meets or exceeds the lifetime mileage
+    PastLifetimeMiles,
+    /// The car is already older than the planned ownership period
+    PastOwnershipYears,
+    /// A shared setting is out of range, so no car can be computed
+    InvalidSettings(SettingsIssue),
+    /// An efficiency input the car's fuel type needs (e.g. an electric car's miles per
+    /// kWh) is empty
+    MissingFuelInput(FuelType, &'static str),
+    /// An efficiency input the car's fuel type needs is out of range or not a number
+    InvalidFuelInput(FuelType, &'static str),
+}
+
+impl ComputeError {
+    /// Whether the problem lies in the shared settings rather than this car
+    pub fn is_settings_error(&self) -> bool {
+        matches!(self, ComputeError::InvalidSettings(_))
+    }
+}
+
+impl fmt::Display for ComputeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            ComputeError::MissingInput => "Please fill in all required fields to calculate costs.",
+            ComputeError::InvalidInput => "Some values are out of range, so costs can't be calculated.",
+            ComputeError::PastLifetimeMiles => {
+                "This car has already exceeded your lifetime mileage setting, so there are no remaining costs to project."
+            }
+            ComputeError::PastOwnershipYears => {
+                "This car is already past your planned ownership period, so there are no remaining costs to project."
+            }
+            ComputeError::MissingFuelInput(fuel_type, input) => {
+                return write!(
+                    f,
+                    "Enter the {} for this {} car to calculate its fuel costs.",
+                    input,
+                    fuel_type.label().to_lowercase()
+                );
+            }
+            ComputeError::InvalidFuelInput(fuel_type, input) => {
+                return write!(
+                    f,
+                    "The {} for this {} car is out of range, so its fuel costs can't be calculated.",
+                    input,
+                    fuel_type.label().to_lowercase()
+                );
+            }
+            ComputeError::InvalidSettings(issue) => {
+                return write!(
+                    f,
+                    "{} in Shared Settings must be greater than zero to calculate costs.",
+                    issue.label()
+                );
+            }
+        };
+        write!(f, "{}", message)
+    }
 }
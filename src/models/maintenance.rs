@@ -8,16 +8,76 @@ pub struct MaintenanceDataPoint {
     pub y: f64, // cumulative cost in dollars
 }
 
-/// Maintenance cost data for a specific make+model
+/// How to resolve points that share the same x when merging two profiles
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MergeStrategy {
+    /// Keep the base profile's value
+    PreferBase,
+    /// Average the two values
+    Average,
+}
+
+/// Which maintenance tables an estimate was based on
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum MaintenanceBasis {
+    /// No profile (or empty tables), so maintenance is $0
+    #[default]
+    NoData,
+    /// Mileage and age tables averaged 50/50
+    Blended,
+    /// Only the mileage table has data, so it's used at full weight
+    MileageOnly,
+    /// Only the age table has data, so it's used at full weight
+    TimeOnly,
+}
+
+impl MaintenanceBasis {
+    /// Combine the cost from each table according to the basis
+    pub fn combine(&self, mileage_cost: f64, time_cost: f64) -> f64 {
+        match self {
+            MaintenanceBasis::NoData => 0.0,
+            MaintenanceBasis::Blended => (mileage_cost + time_cost) / 2.0,
+            MaintenanceBasis::MileageOnly => mileage_cost,
+            MaintenanceBasis::TimeOnly => time_cost,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            MaintenanceBasis::NoData => "No maintenance data",
+            MaintenanceBasis::Blended => "Mileage and age tables, 50/50",
+            MaintenanceBasis::MileageOnly => "Mileage table only",
+            MaintenanceBasis::TimeOnly => "Age table only",
+        }
+    }
+}
+
+/// Miles in one unit of a `by_mileage` x value. Points are stored in 10,000-mile units
+/// (x = 5 is 50,000 miles); everything shown to the user converts to plain miles.
+pub const MILES_PER_MILEAGE_X: f64 = 10000.0;
+
+/// A stored x multiplied out to display units, rounded so that 0.07 × 10,000 reads as 700
+/// rather than 700.0000000000001
+pub fn scale_x(x: f64, scale: f64) -> f64 {
+    (x * scale * 1e6).round() / 1e6
+}
+
+/// Maintenance cost data for a specific make+model, optionally narrowed to one trim
 /// Contains two tables: one based on mileage, one based on time
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct MaintenanceCostData {
     pub make: String,
     pub model: String,
+    /// Trim this profile applies to; empty covers every trim without a profile of its own
+    #[serde(default)]
+    pub trim: String,
     /// Data points where x = 10k miles, y = cumulative cost over those miles
     pub by_mileage: Vec<MaintenanceDataPoint>,
     /// Data points where x = years, y = cumulative cost over those years
     pub by_time: Vec<MaintenanceDataPoint>,
+    /// Resale curve where x = vehicle age in years, y = percent of original value retained
+    #[serde(default)]
+    pub value_by_age: Vec<MaintenanceDataPoint>,
 }
 
 impl MaintenanceCostData {
@@ -25,14 +85,26 @@ impl MaintenanceCostData {
         Self {
             make,
             model,
+            trim: String::new(),
             by_mileage: Vec::new(),
             by_time: Vec::new(),
+            value_by_age: Vec::new(),
         }
     }
 
-    /// Get a unique key for this make+model combination
+    /// Which tables have data to base an estimate on
+    pub fn basis(&self) -> MaintenanceBasis {
+        match (self.by_mileage.is_empty(), self.by_time.is_empty()) {
+            (false, false) => MaintenanceBasis::Blended,
+            (false, true) => MaintenanceBasis::MileageOnly,
+            (true, false) => MaintenanceBasis::TimeOnly,
+            (true, true) => MaintenanceBasis::NoData,
+        }
+    }
+
+    /// Get a unique key for this make+model (and trim, if set) combination
     pub fn key(&self) -> String {
-        format!("{}_{}", self.make.to_lowercase(), self.model.to_lowercase())
+        Self::make_trim_key(&self.make, &self.model, &self.trim)
     }
 
     /// Create a key from make and model strings
@@ -40,6 +112,23 @@ impl MaintenanceCostData {
         format!("{}_{}", make.to_lowercase(), model.to_lowercase())
     }
 
+    /// Create a key from make, model and trim strings; an empty trim gives the make+model key
+    pub fn make_trim_key(make: &str, model: &str, trim: &str) -> String {
+        let trim = trim.trim();
+        if trim.is_empty() {
+            Self::make_key(make, model)
+        } else {
+            format!("{}_{}", Self::make_key(make, model), trim.to_lowercase())
+        }
+    }
+
+    /// Display name, e.g. "Ford F-150" or "Ford F-150 Raptor"
+    pub fn name(&self) -> String {
+        format!("{} {} {}", self.make, self.model, self.trim)
+            .trim()
+            .to_string()
+    }
+
     /// Calculate maintenance cost for a given mileage range
     /// Uses linear interpolation between data points
     pub fn cost_for_mileage_range(&self, start_miles: f64, end_miles: f64) -> f64 {
@@ -47,11 +136,11 @@ impl MaintenanceCostData {
             return 0.0;
         }
 
-        let start_10k = start_miles / 10000.0;
-        let end_10k = end_miles / 10000.0;
+        let start_x = start_miles / MILES_PER_MILEAGE_X;
+        let end_x = end_miles / MILES_PER_MILEAGE_X;
 
-        let start_cost = self.interpolate_cost(&self.by_mileage, start_10k);
-        let end_cost = self.interpolate_cost(&self.by_mileage, end_10k);
+        let start_cost = self.interpolate_cost(&self.by_mileage, start_x);
+        let end_cost = self.interpolate_cost(&self.by_mileage, end_x);
 
         (end_cost - start_cost).max(0.0)
     }
@@ -69,6 +158,49 @@ impl MaintenanceCostData {
         (end_cost - start_cost).max(0.0)
     }
 
+    /// Return a copy with both tables smoothed by a centered moving average of `window` points.
+    /// The first and last points keep their values and the result never decreases.
+    pub fn smooth(&self, window: usize) -> MaintenanceCostData {
+        Self {
+            make: self.make.clone(),
+            model: self.model.clone(),
+            trim: self.trim.clone(),
+            by_mileage: smooth_points(&self.by_mileage, window),
+            by_time: smooth_points(&self.by_time, window),
+            value_by_age: self.value_by_age.clone(),
+        }
+    }
+
+    /// Combine another profile's points into this one, keeping this profile's make, model
+    /// and trim
+    pub fn merged_with(&self, other: &MaintenanceCostData, strategy: MergeStrategy) -> Self {
+        Self {
+            make: self.make.clone(),
+            model: self.model.clone(),
+            trim: self.trim.clone(),
+            by_mileage: merge_points(&self.by_mileage, &other.by_mileage, strategy),
+            by_time: merge_points(&self.by_time, &other.by_time, strategy),
+            // Resale curves decline, so they can't go through the monotonic merge;
+            // keep this profile's curve unless it has none
+            value_by_age: if self.value_by_age.is_empty() {
+                other.value_by_age.clone()
+            } else {
+                self.value_by_age.clone()
+            },
+        }
+    }
+
+    /// Fraction of the value at `from_age` still retained at `to_age`, from the resale curve.
+    /// Returns `None` when the profile has no usable resale curve.
+    pub fn retained_value_ratio(&self, from_age: f64, to_age: f64) -> Option<f64> {
+        if self.value_by_age.is_empty() {
+            return None;
+        }
+        let from = self.interpolate_cost(&self.value_by_age, from_age).max(0.0);
+        let to = self.interpolate_cost(&self.value_by_age, to_age).max(0.0);
+        (from > 0.0).then(|| (to / from).min(1.0))
+    }
+
     /// Interpolate cost at a given x value from a series of data points
     fn interpolate_cost(&self, data: &[MaintenanceDataPoint], x: f64) -> f64 {
         if data.is_empty() {
@@ -83,35 +215,161 @@ impl MaintenanceCostData {
             return (data[0].y / data[0].x) * x;
         }
 
-        // If after last point, extrapolate using last two points
-        if x >= data[data.len() - 1].x {
-            if data.len() == 1 {
-                // Only one point, extrapolate from origin
-                return (data[0].y / data[0].x) * x;
-            }
-            let p1 = &data[data.len() - 2];
-            let p2 = &data[data.len() - 1];
-            let slope = (p2.y - p1.y) / (p2.x - p1.x);
-            return p2.y + slope * (x - p2.x);
+        // If after last point, extrapolate using the last segment of nonzero width
+        let last = &data[data.len() - 1];
+        if x >= last.x {
+            let Some(p1) = data.iter().rev().find(|p| p.x < last.x) else {
+                // Only one distinct x, extrapolate from origin (flat if it's the origin)
+                if last.x == 0.0 {
+                    return last.y;
+                }
+                return (last.y / last.x) * x;
+            };
+            let slope = (last.y - p1.y) / (last.x - p1.x);
+            return last.y + slope * (x - last.x);
         }
 
-        // Find the two points to interpolate between
-        for i in 0..data.len() - 1 {
-            if x >= data[i].x && x <= data[i + 1].x {
-                let p1 = &data[i];
-                let p2 = &data[i + 1];
+        // Find the two points to interpolate between, skipping zero-width segments
+        // left by repeated x values
+        data.windows(2)
+            .find(|w| w[0].x < w[1].x && x >= w[0].x && x <= w[1].x)
+            .map(|w| {
+                let (p1, p2) = (&w[0], &w[1]);
+                let ratio = (x - p1.x) / (p2.x - p1.x);
+                p1.y + ratio * (p2.y - p1.y)
+            })
+            .unwrap_or(0.0)
+    }
 
-                if p2.x == p1.x {
-                    return p1.y;
+    /// Problems that would throw off interpolation, in plain language: x values that don't
+    /// strictly increase in any table, and cumulative costs that fall. The resale curve is
+    /// expected to fall, so only its x values are checked.
+    pub fn validate(&self) -> Vec<String> {
+        let tables: [(&str, &[MaintenanceDataPoint], f64, &str, bool); 3] = [
+            (
+                "By mileage",
+                &self.by_mileage,
+                MILES_PER_MILEAGE_X,
+                " miles",
+                true,
+            ),
+            ("By time", &self.by_time, 1.0, " years", true),
+            ("Resale curve", &self.value_by_age, 1.0, " years", false),
+        ];
+        let mut warnings = Vec::new();
+        for (table, points, scale, unit, cumulative) in tables {
+            let at = |p: &MaintenanceDataPoint| format!("{}{}", scale_x(p.x, scale), unit);
+            for pair in points.windows(2) {
+                let (before, after) = (&pair[0], &pair[1]);
+                if after.x == before.x {
+                    warnings.push(format!("{}: two points at {}", table, at(before)));
+                } else if after.x < before.x {
+                    warnings.push(format!(
+                        "{}: {} comes after {}, out of order",
+                        table,
+                        at(after),
+                        at(before)
+                    ));
+                } else if cumulative && after.y < before.y {
+                    warnings.push(format!(
+                        "{}: cumulative cost falls from ${:.2} at {} to ${:.2} at {}",
+                        table,
+                        before.y,
+                        at(before),
+                        after.y,
+                        at(after)
+                    ));
                 }
+            }
+        }
+        warnings
+    }
 
-                let ratio = (x - p1.x) / (p2.x - p1.x);
-                return p1.y + ratio * (p2.y - p1.y);
+    /// Collapse points sharing an x value into one, keeping the highest cost, in every
+    /// table. Returns whether anything changed.
+    pub fn coalesce_duplicate_points(&mut self) -> bool {
+        let mut changed = false;
+        for points in [
+            &mut self.by_mileage,
+            &mut self.by_time,
+            &mut self.value_by_age,
+        ] {
+            changed |= coalesce_points(points);
+        }
+        changed
+    }
+}
+
+/// Sort points by x and merge any with the same x, keeping the largest y. Returns
+/// whether anything changed. Imports use the same rule, so a point repeated in a pasted
+/// table or a backup ends up the same either way.
+pub(crate) fn coalesce_points(points: &mut Vec<MaintenanceDataPoint>) -> bool {
+    let original = points.clone();
+    points.sort_by(|a, b| a.x.total_cmp(&b.x));
+    points.dedup_by(|later, earlier| {
+        let duplicate = later.x == earlier.x;
+        if duplicate {
+            earlier.y = earlier.y.max(later.y);
+        }
+        duplicate
+    });
+    *points != original
+}
+
+/// Moving-average smoothing of cumulative cost points, preserving endpoints and monotonicity
+fn smooth_points(points: &[MaintenanceDataPoint], window: usize) -> Vec<MaintenanceDataPoint> {
+    if points.len() < 3 || window < 2 {
+        return points.to_vec();
+    }
+
+    let half = window / 2;
+    let last = points.len() - 1;
+    let last_y = points[last].y;
+
+    let mut smoothed = Vec::with_capacity(points.len());
+    smoothed.push(points[0].clone());
+
+    for i in 1..last {
+        let lo = i.saturating_sub(half);
+        let hi = (i + half).min(last);
+        let avg = points[lo..=hi].iter().map(|p| p.y).sum::<f64>() / (hi - lo + 1) as f64;
+
+        // Never dip below the previous point or overshoot the final cumulative cost
+        let prev_y = smoothed[i - 1].y;
+        let y = avg.max(prev_y).min(last_y.max(prev_y));
+        smoothed.push(MaintenanceDataPoint { x: points[i].x, y });
+    }
+
+    smoothed.push(points[last].clone());
+    smoothed
+}
+
+/// Union two point series sorted by x, resolving shared x values with `strategy`
+/// and raising any dips so the cumulative cost never decreases
+fn merge_points(
+    base: &[MaintenanceDataPoint],
+    other: &[MaintenanceDataPoint],
+    strategy: MergeStrategy,
+) -> Vec<MaintenanceDataPoint> {
+    let mut merged: Vec<MaintenanceDataPoint> = base.to_vec();
+    for point in other {
+        match merged.iter_mut().find(|p| p.x == point.x) {
+            Some(existing) => {
+                if strategy == MergeStrategy::Average {
+                    existing.y = (existing.y + point.y) / 2.0;
+                }
             }
+            None => merged.push(point.clone()),
         }
+    }
 
-        0.0
+    merged.sort_by(|a, b| a.x.total_cmp(&b.x));
+    for i in 1..merged.len() {
+        if merged[i].y < merged[i - 1].y {
+            merged[i].y = merged[i - 1].y;
+        }
     }
+    merged
 }
 
 /// Storage for all maintenance cost data, keyed by make_model
@@ -127,23 +385,109 @@ impl MaintenanceCostDatabase {
         self.data.get(&key)
     }
 
+    /// Get maintenance data for a make+model and trim as typed on a car, tolerating typos
+    /// in spacing, case and punctuation: "ford f150 " finds the "Ford F-150" profile. A
+    /// profile for the car's trim wins over the make+model profile, and an exact match over
+    /// one comparing only letters and digits (the first key, when several normalize the same).
+    pub fn get_fuzzy(&self, make: &str, model: &str, trim: &str) -> Option<&MaintenanceCostData> {
+        let normalize = |text: &str| {
+            text.chars()
+                .filter(|c| c.is_alphanumeric())
+                .flat_map(char::to_lowercase)
+                .collect::<String>()
+        };
+        let find = |trim: &str| {
+            if let Some(data) = self
+                .data
+                .get(&MaintenanceCostData::make_trim_key(make, model, trim))
+            {
+                return Some(data);
+            }
+            let wanted = (normalize(make), normalize(model), normalize(trim));
+            if wanted.0.is_empty() || wanted.1.is_empty() {
+                return None;
+            }
+            self.data
+                .iter()
+                .filter(|(_, data)| {
+                    (
+                        normalize(&data.make),
+                        normalize(&data.model),
+                        normalize(&data.trim),
+                    ) == wanted
+                })
+                .min_by(|a, b| a.0.cmp(b.0))
+                .map(|(_, data)| data)
+        };
+        if trim.trim().is_empty() {
+            find("")
+        } else {
+            find(trim).or_else(|| find(""))
+        }
+    }
+
     /// Set maintenance data for a specific make+model
     pub fn set(&mut self, data: MaintenanceCostData) {
         let key = data.key();
         self.data.insert(key, data);
     }
 
-    /// Remove maintenance data for a specific make+model
+    /// Remove maintenance data for a specific make+model (the profile covering all trims)
     pub fn remove(&mut self, make: &str, model: &str) {
         let key = MaintenanceCostData::make_key(make, model);
         self.data.remove(&key);
     }
 
-    /// Get all make+model combinations that have maintenance data
+    /// Merge the `remove` profile into the `keep` profile and delete the `remove` entry.
+    /// Returns false if either profile is missing or they are the same entry.
+    pub fn merge(
+        &mut self,
+        keep: (&str, &str),
+        remove: (&str, &str),
+        strategy: MergeStrategy,
+    ) -> bool {
+        let keep_key = MaintenanceCostData::make_key(keep.0, keep.1);
+        let remove_key = MaintenanceCostData::make_key(remove.0, remove.1);
+        self.merge_keys(&keep_key, &remove_key, strategy)
+    }
+
+    /// Like [`merge`](Self::merge), but addressing both profiles by key so trim-specific
+    /// profiles can be merged too
+    pub fn merge_keys(
+        &mut self,
+        keep_key: &str,
+        remove_key: &str,
+        strategy: MergeStrategy,
+    ) -> bool {
+        if keep_key == remove_key {
+            return false;
+        }
+        let (Some(base), Some(other)) = (self.data.get(keep_key), self.data.get(remove_key)) else {
+            return false;
+        };
+
+        let merged = base.merged_with(other, strategy);
+        self.data.remove(remove_key);
+        self.set(merged);
+        true
+    }
+
+    /// Coalesce duplicate-x points in every profile (see
+    /// [`MaintenanceCostData::coalesce_duplicate_points`]). Returns whether anything changed.
+    pub fn coalesce_duplicate_points(&mut self) -> bool {
+        self.data.values_mut().fold(false, |changed, data| {
+            data.coalesce_duplicate_points() | changed
+        })
+    }
+
+    /// Get the key and display name of every profile, trim-specific ones included
     pub fn get_all_keys(&self) -> Vec<(String, String)> {
-        self.data
-            .values()
-            .map(|d| (d.make.clone(), d.model.clone()))
-            .collect()
+        let mut keys = self
+            .data
+            .iter()
+            .map(|(key, d)| (key.clone(), d.name()))
+            .collect::<Vec<_>>();
+        keys.sort_by_key(|(_, name)| name.to_lowercase());
+        keys
     }
 }
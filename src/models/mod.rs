@@ -3,7 +3,17 @@ mod computed;
 mod maintenance;
 mod settings;
 
-pub use car::Car;
-pub use computed::ComputedCarData;
-pub use maintenance::{MaintenanceCostData, MaintenanceCostDatabase, MaintenanceDataPoint};
-pub use settings::SharedSettings;
+pub use car::{
+    Car, CarField, DepreciationMethod, FuelType, InsuranceMode, InsurancePeriod, ResaleChannel,
+    CAR_COLORS,
+};
+pub use computed::{ComputeError, ComputeOperands, ComputedCarData, PeriodCashFlow};
+pub(crate) use maintenance::coalesce_points;
+pub use maintenance::{
+    scale_x, MaintenanceBasis, MaintenanceCostData, MaintenanceCostDatabase, MaintenanceDataPoint,
+    MergeStrategy, MILES_PER_MILEAGE_X,
+};
+pub use settings::{
+    AnnualCostBasis, PrimaryMetric, SettingsIssue, SettingsPreset, SharedSettings, REGION_PRESETS,
+    SETTINGS_PRESETS,
+};
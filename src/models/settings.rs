@@ -1,11 +1,213 @@
 use serde::{Deserialize, Serialize};
 
+use crate::calculations::DEFAULT_DEPRECIATION_RATE;
+use crate::data::format_dollars;
+use crate::models::ComputedCarData;
+
+/// Regional cost-of-living presets for maintenance labor and insurance
+pub const REGION_PRESETS: &[(&str, f64)] = &[
+    ("National average", 1.0),
+    ("Rural / small town", 0.85),
+    ("Atlanta", 0.95),
+    ("Dallas", 1.0),
+    ("Chicago", 1.1),
+    ("Boston", 1.2),
+    ("Los Angeles", 1.25),
+    ("New York City", 1.3),
+    ("San Francisco Bay Area", 1.35),
+];
+
+/// A named starting point for the settings new users are least sure of
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SettingsPreset {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub opportunity_cost_rate: f64,
+    pub annual_mileage: f64,
+    pub lifetime_miles: f64,
+    pub average_gas_price: f64,
+}
+
+pub const SETTINGS_PRESETS: &[SettingsPreset] = &[
+    SettingsPreset {
+        name: "Conservative investor",
+        description: "Money would otherwise sit in bonds or savings; average driving",
+        opportunity_cost_rate: 4.0,
+        annual_mileage: 12000.0,
+        lifetime_miles: 200000.0,
+        average_gas_price: 3.50,
+    },
+    SettingsPreset {
+        name: "Aggressive investor",
+        description: "Money would otherwise be in stocks; average driving",
+        opportunity_cost_rate: 10.0,
+        annual_mileage: 12000.0,
+        lifetime_miles: 200000.0,
+        average_gas_price: 3.50,
+    },
+    SettingsPreset {
+        name: "High-mileage commuter",
+        description: "Long daily commute, keeping the car well past 200k miles",
+        opportunity_cost_rate: 7.0,
+        annual_mileage: 20000.0,
+        lifetime_miles: 250000.0,
+        average_gas_price: 3.50,
+    },
+];
+
+impl SettingsPreset {
+    /// The settings with this preset's values filled in, leaving the rest as they were
+    pub fn apply(&self, settings: &SharedSettings) -> SharedSettings {
+        SharedSettings {
+            opportunity_cost_rate: self.opportunity_cost_rate,
+            annual_mileage: self.annual_mileage,
+            lifetime_miles: self.lifetime_miles,
+            average_gas_price: self.average_gas_price,
+            ..settings.clone()
+        }
+    }
+
+    /// Whether the settings currently hold exactly this preset's values
+    pub fn matches(&self, settings: &SharedSettings) -> bool {
+        let same = |a: f64, b: f64| (a - b).abs() < 1e-9;
+        same(settings.opportunity_cost_rate, self.opportunity_cost_rate)
+            && same(settings.annual_mileage, self.annual_mileage)
+            && same(settings.lifetime_miles, self.lifetime_miles)
+            && same(settings.average_gas_price, self.average_gas_price)
+    }
+
+    /// The preset the settings currently match, if any
+    pub fn matching(settings: &SharedSettings) -> Option<&'static SettingsPreset> {
+        SETTINGS_PRESETS
+            .iter()
+            .find(|preset| preset.matches(settings))
+    }
+}
+
+/// Which cost figure is headlined on each car, with the others shown as secondary text
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum PrimaryMetric {
+    Total,
+    #[default]
+    Annual,
+    /// Annual cost excluding the purchase price
+    Operating,
+    PerMile,
+}
+
+impl PrimaryMetric {
+    pub const ALL: [PrimaryMetric; 4] = [
+        PrimaryMetric::Total,
+        PrimaryMetric::Annual,
+        PrimaryMetric::Operating,
+        PrimaryMetric::PerMile,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PrimaryMetric::Total => "Total Cost of Ownership",
+            PrimaryMetric::Annual => "Annual Cost",
+            PrimaryMetric::Operating => "Annual Operating Cost",
+            PrimaryMetric::PerMile => "Cost per Mile",
+        }
+    }
+
+    /// Short name for toggle buttons
+    pub fn short_label(&self) -> &'static str {
+        match self {
+            PrimaryMetric::Total => "Total",
+            PrimaryMetric::Annual => "Annual",
+            PrimaryMetric::Operating => "Operating",
+            PrimaryMetric::PerMile => "Per mile",
+        }
+    }
+
+    /// Format this metric's value for a computed car, e.g. "$4500" or "$0.42/mi"
+    pub fn format(&self, computed: &ComputedCarData) -> String {
+        match self {
+            PrimaryMetric::Total => format_dollars(computed.total_cost_of_ownership, 0),
+            PrimaryMetric::Annual => format_dollars(computed.annual_cost, 0),
+            PrimaryMetric::Operating => format_dollars(computed.annual_operating_cost, 0),
+            PrimaryMetric::PerMile => format!("{}/mi", format_dollars(computed.cost_per_mile, 2)),
+        }
+    }
+}
+
+/// The period `annual_cost` averages over
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum AnnualCostBasis {
+    /// Total cost of ownership spread over every remaining year
+    #[default]
+    FullRemainingLife,
+    /// The purchase spread over the remaining life, plus the running costs of only the
+    /// next `n` years
+    FixedYears(u32),
+}
+
+impl AnnualCostBasis {
+    /// Years averaged over, capped at the years the car will actually be owned
+    pub fn window_years(&self, years_remaining: f64) -> f64 {
+        match self {
+            AnnualCostBasis::FixedYears(n) if *n > 0 => (*n as f64).min(years_remaining),
+            _ => years_remaining,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
 pub struct SharedSettings {
     pub opportunity_cost_rate: f64,
     pub annual_mileage: f64,
     pub lifetime_miles: f64,
     pub average_gas_price: f64,
+    /// Scales maintenance costs (and optionally insurance) for local labor rates
+    pub region_cost_multiplier: f64,
+    pub region_multiplier_applies_to_insurance: bool,
+    pub primary_metric: PrimaryMetric,
+    /// How far below private-sale value a dealer trade-in pays, in percent
+    pub trade_in_discount_percent: f64,
+    /// Whether a trade-in's value is exempt from sales tax on the next purchase
+    pub trade_in_tax_credit: bool,
+    /// Local sales tax rate in percent, used for the trade-in credit and a taxed purchase
+    pub sales_tax_rate: f64,
+    /// Whether sales tax is paid on the purchase price, once, on top of the price
+    pub sales_tax_on_purchase: bool,
+    /// Registration and other yearly fees to keep the car on the road
+    pub annual_registration_fee: f64,
+    /// Compound the opportunity cost yearly instead of using simple interest
+    pub compound_opportunity_cost: bool,
+    /// Also report the total as a net present value, discounting each year's costs at the
+    /// opportunity cost rate instead of adding a separate opportunity cost
+    pub discounted_total: bool,
+    /// Whether opportunity cost counts toward the annual operating cost
+    pub operating_cost_includes_opportunity: bool,
+    /// Household mode: the currently owned car being sold to make room for a candidate
+    pub selling_car_id: Option<usize>,
+    /// Gas price assumed for the miles already on a car; `None` uses `average_gas_price`
+    pub historical_gas_price: Option<f64>,
+    /// What `annual_cost` averages over
+    pub annual_cost_basis: AnnualCostBasis,
+    /// Share of value lost each year under exponential decay, in percent
+    pub depreciation_rate: f64,
+    /// Home charging cost in $/kWh, for electric cars and plug-in hybrids
+    pub electricity_price: f64,
+    /// Yearly price growth in percent, used for the total in future (nominal) dollars
+    pub inflation_rate: f64,
+    /// Years each car is kept from today before it's replaced, whatever its mileage;
+    /// `None` keeps it to the lifetime mileage. Counted from today, unlike a car's
+    /// [`Car::keep_until_age`](crate::models::Car::keep_until_age), which is its age from new.
+    pub ownership_years: Option<f64>,
+    /// Share of fuel economy lost each year as a car ages, in percent. A car's MPG is
+    /// taken as what it gets today and falls from there; 0 keeps it flat.
+    pub mpg_loss_percent: f64,
+    /// Share of miles driven on the highway, in percent, for blending a car's city and
+    /// highway MPG. The EPA's combined rating assumes 45%.
+    pub highway_percent: f64,
+    /// Today as a fractional year (e.g. 2026.5), supplied by the browser rather than saved.
+    /// `None` estimates every car's age from its mileage.
+    #[serde(skip)]
+    pub current_year: Option<f64>,
 }
 
 impl Default for SharedSettings {
@@ -15,6 +217,83 @@ impl Default for SharedSettings {
             annual_mileage: 12000.0,
             lifetime_miles: 200000.0,
             average_gas_price: 3.50,
+            region_cost_multiplier: 1.0,
+            region_multiplier_applies_to_insurance: false,
+            primary_metric: PrimaryMetric::Annual,
+            trade_in_discount_percent: 15.0,
+            trade_in_tax_credit: false,
+            sales_tax_rate: 6.0,
+            sales_tax_on_purchase: false,
+            annual_registration_fee: 0.0,
+            compound_opportunity_cost: false,
+            discounted_total: false,
+            operating_cost_includes_opportunity: false,
+            selling_car_id: None,
+            historical_gas_price: None,
+            annual_cost_basis: AnnualCostBasis::FullRemainingLife,
+            depreciation_rate: DEFAULT_DEPRECIATION_RATE * 100.0,
+            electricity_price: 0.16,
+            inflation_rate: 3.0,
+            ownership_years: None,
+            mpg_loss_percent: 0.0,
+            highway_percent: 45.0,
+            current_year: None,
+        }
+    }
+}
+
+/// A shared setting whose value stops every car from being computed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SettingsIssue {
+    /// Annual mileage is zero, negative or not a number
+    AnnualMileage,
+    /// Lifetime mileage is zero, negative or not a number
+    LifetimeMiles,
+}
+
+impl SettingsIssue {
+    /// Label of the offending setting, as shown in the settings form
+    pub fn label(&self) -> &'static str {
+        match self {
+            SettingsIssue::AnnualMileage => "Annual Mileage",
+            SettingsIssue::LifetimeMiles => "Default Lifetime Miles",
+        }
+    }
+
+    /// Id of the offending setting's input in the settings form
+    pub fn input_id(&self) -> &'static str {
+        match self {
+            SettingsIssue::AnnualMileage => "annual-mileage",
+            SettingsIssue::LifetimeMiles => "lifetime-miles",
+        }
+    }
+}
+
+impl SharedSettings {
+    /// Gas price for fuel already bought, defaulting to the current average
+    pub fn historical_gas_price(&self) -> f64 {
+        self.historical_gas_price.unwrap_or(self.average_gas_price)
+    }
+
+    /// Parse a settings input that has to be a positive number, like annual mileage or the
+    /// gas price. Blank, zero, negative and non-numeric text gives `None`, which the
+    /// settings form flags rather than saving.
+    pub fn parse_positive(text: &str) -> Option<f64> {
+        text.trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|value| value.is_finite() && *value > 0.0)
+    }
+
+    /// Check the settings every car's calculation depends on
+    pub fn validate(&self) -> Result<(), SettingsIssue> {
+        let positive = |value: f64| value.is_finite() && value > 0.0;
+        if !positive(self.annual_mileage) {
+            return Err(SettingsIssue::AnnualMileage);
+        }
+        if !positive(self.lifetime_miles) {
+            return Err(SettingsIssue::LifetimeMiles);
         }
+        Ok(())
     }
 }
@@ -0,0 +1,178 @@
+use crate::data::{get_sample_maintenance_data, AppExport, ImportMode, ImportStatus};
+use crate::models::{Car, MaintenanceCostData, MaintenanceDataPoint, SharedSettings};
+
+#[test]
+fn test_app_export_round_trips_through_json() {
+    let mut car = Car::new(3);
+    car.make = "Toyota".to_string();
+    car.model = "Prius".to_string();
+    car.image_data_url = Some("data:image/jpeg;base64,AAAA".to_string());
+    let export = AppExport {
+        cars: vec![car],
+        settings: SharedSettings {
+            sales_tax_rate: 6.5,
+            ..Default::default()
+        },
+        maintenance_db: get_sample_maintenance_data(),
+    };
+
+    let json = export.to_json();
+    for key in ["\"cars\"", "\"settings\"", "\"maintenance_db\""] {
+        assert!(json.contains(key), "missing {}", key);
+    }
+    let restored: AppExport = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored, export);
+}
+
+#[test]
+fn test_app_export_rejects_invalid_json() {
+    assert!(AppExport::from_json("not json").is_err());
+    assert!(AppExport::from_json("[1, 2, 3]").is_err());
+    // Nor is an empty object or a downloaded session, which would otherwise restore nothing
+    assert!(AppExport::from_json("{}").is_err());
+    assert!(AppExport::from_json(r#"{"settings": {}, "cars": []}"#).is_err());
+    assert!(AppExport::from_json(&AppExport::default().to_json()).is_ok());
+}
+
+#[test]
+fn test_app_export_import_keeps_only_inline_photos() {
+    let mut photo = Car::new(1);
+    photo.image_data_url = Some("data:image/jpeg;base64,AAAA".to_string());
+    let mut remote = Car::new(2);
+    remote.image_data_url = Some("https://example.com/pixel.gif".to_string());
+    let backup = AppExport {
+        cars: vec![photo.clone(), remote.clone()],
+        ..Default::default()
+    };
+
+    let imported = AppExport::from_json(&backup.to_json()).unwrap();
+    assert_eq!(
+        imported.cars[0].photo_src(),
+        Some("data:image/jpeg;base64,AAAA")
+    );
+    assert_eq!(imported.cars[1].image_data_url, None);
+    // Never drawn even if one gets past loading
+    assert_eq!(remote.photo_src(), None);
+}
+
+#[test]
+fn test_app_export_replace_and_merge() {
+    let mut current_car = Car::new(1);
+    current_car.make = "Honda".to_string();
+    let mut current = AppExport {
+        cars: vec![current_car.clone()],
+        settings: SharedSettings {
+            sales_tax_rate: 6.5,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut own = MaintenanceCostData::new("Toyota".to_string(), "Prius".to_string());
+    own.by_mileage = vec![MaintenanceDataPoint { x: 1.0, y: 100.0 }];
+    current.maintenance_db.set(own);
+
+    // The backup repeats the Honda (under another id) and adds a Ford
+    let mut new_car = Car::new(1);
+    new_car.make = "Ford".to_string();
+    let backup = AppExport {
+        cars: vec![
+            Car {
+                id: 7,
+                ..current_car
+            },
+            new_car,
+        ],
+        settings: SharedSettings::default(),
+        maintenance_db: get_sample_maintenance_data(),
+    };
+
+    assert_eq!(
+        backup.clone().apply_to(&current, ImportMode::Replace),
+        backup
+    );
+
+    let merged = backup.apply_to(&current, ImportMode::Merge);
+    assert_eq!(merged.settings, current.settings);
+    let ids: Vec<usize> = merged.cars.iter().map(|c| c.id).collect();
+    assert_eq!(ids, vec![1, 2]);
+    assert_eq!(merged.cars[1].make, "Ford");
+    // Profiles only in the backup are added; shared ones keep the current points
+    assert!(merged.maintenance_db.get("Ford", "F-150").is_some());
+    let prius = merged.maintenance_db.get("Toyota", "Prius").unwrap();
+    assert_eq!(
+        prius.by_mileage[0],
+        MaintenanceDataPoint { x: 1.0, y: 100.0 }
+    );
+    assert!(prius.by_mileage.len() > 1);
+}
+
+#[test]
+fn test_app_export_import_fixes_duplicate_ids_and_points() {
+    let mut profile = MaintenanceCostData::new("Toyota".to_string(), "Prius".to_string());
+    profile.by_mileage = vec![
+        MaintenanceDataPoint { x: 2.0, y: 300.0 },
+        MaintenanceDataPoint { x: 1.0, y: 100.0 },
+        MaintenanceDataPoint { x: 2.0, y: 500.0 },
+    ];
+    let mut backup = AppExport {
+        cars: vec![Car::new(4), Car::new(2), Car::new(4)],
+        ..Default::default()
+    };
+    backup.maintenance_db.set(profile);
+
+    let preview = AppExport::from_json(&backup.to_json())
+        .unwrap()
+        .preview_import(&AppExport::default(), ImportMode::Replace);
+    let imported = &preview.result;
+    let ids: Vec<usize> = imported.cars.iter().map(|c| c.id).collect();
+    assert_eq!(ids, vec![4, 2, 5]);
+    let points = &imported
+        .maintenance_db
+        .data
+        .values()
+        .next()
+        .unwrap()
+        .by_mileage;
+    assert_eq!(
+        points,
+        &vec![
+            MaintenanceDataPoint { x: 1.0, y: 100.0 },
+            MaintenanceDataPoint { x: 2.0, y: 500.0 },
+        ]
+    );
+    // The renumbered car and the coalesced profile are called out in the report
+    let reasons: Vec<&str> = preview
+        .report
+        .records
+        .iter()
+        .filter(|r| r.status == ImportStatus::Migrated)
+        .map(|r| r.reason.as_str())
+        .collect();
+    assert_eq!(reasons.len(), 2);
+    assert!(reasons[0].contains("renumbered to 5"));
+    assert!(reasons[1].contains("merged 1 point"));
+}
+
+#[test]
+fn test_app_export_preview_merge_reports_without_applying() {
+    let mut honda = Car::new(1);
+    honda.make = "Honda".to_string();
+    let current = AppExport {
+        cars: vec![honda.clone()],
+        ..Default::default()
+    };
+    let mut ford = Car::new(2);
+    ford.make = "Ford".to_string();
+    let backup = AppExport {
+        cars: vec![Car { id: 9, ..honda }, ford],
+        ..Default::default()
+    };
+
+    let preview = backup.clone().preview_import(&current, ImportMode::Merge);
+    assert_eq!(preview.result, backup.apply_to(&current, ImportMode::Merge));
+    assert_eq!(preview.report.count(ImportStatus::Imported), 1);
+    // The repeated Honda and the kept settings
+    assert_eq!(preview.report.count(ImportStatus::Skipped), 2);
+    assert!(preview.summary.starts_with("Add 1 of the backup's 2 cars"));
+    assert!(preview.summary.contains("Your settings are kept"));
+}
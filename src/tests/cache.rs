@@ -0,0 +1,162 @@
+use crate::calculations::{cache_key, compute_car_data, ComputedCache};
+use crate::data::get_sample_maintenance_data;
+use crate::models::{Car, ComputedCarData, MaintenanceDataPoint, PrimaryMetric, SharedSettings};
+
+fn sample_car() -> Car {
+    let mut car = Car::new(1);
+    car.make = "Toyota".to_string();
+    car.model = "Prius".to_string();
+    car.purchase_price = "25000".to_string();
+    car.current_mileage = "50000".to_string();
+    car.mpg = "50".to_string();
+    car.insurance_cost = "500".to_string();
+    car
+}
+
+#[test]
+fn test_computed_data_round_trips_through_json() {
+    let db = get_sample_maintenance_data();
+    let computed = compute_car_data(&sample_car(), &SharedSettings::default(), &db).unwrap();
+
+    let json = serde_json::to_string(&computed).unwrap();
+    let restored: ComputedCarData = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored, computed);
+}
+
+#[test]
+fn test_cache_skips_non_finite_results() {
+    let db = get_sample_maintenance_data();
+    let settings = SharedSettings::default();
+    let car = sample_car();
+    let computed = compute_car_data(&car, &settings, &db).unwrap();
+
+    let mut cache = ComputedCache::default();
+    assert!(cache.insert(&car, &settings, &db, computed.clone()));
+    for broken in [
+        ComputedCarData {
+            cost_per_mile: f64::NAN,
+            ..computed.clone()
+        },
+        ComputedCarData {
+            cost_per_passenger_mile: Some(f64::INFINITY),
+            ..computed.clone()
+        },
+    ] {
+        // The stale entry goes, and nothing replaces it
+        let mut cache = cache.clone();
+        assert!(cache.insert(&car, &settings, &db, broken.clone()));
+        assert!(!cache.insert(&car, &settings, &db, broken));
+        assert!(cache.get(&car, &settings, &db).is_none());
+
+        let json = serde_json::to_string(&cache).unwrap();
+        let restored: ComputedCache = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, cache);
+    }
+}
+
+#[test]
+fn test_cache_hit_for_unchanged_inputs() {
+    let db = get_sample_maintenance_data();
+    let settings = SharedSettings::default();
+    let car = sample_car();
+    let computed = compute_car_data(&car, &settings, &db).unwrap();
+
+    let mut cache = ComputedCache::default();
+    assert!(cache.get(&car, &settings, &db).is_none());
+    assert!(cache.insert(&car, &settings, &db, computed.clone()));
+    assert!(!cache.insert(&car, &settings, &db, computed.clone()));
+    assert_eq!(cache.get(&car, &settings, &db), Some(&computed));
+
+    // Survives a trip through local storage
+    let restored: ComputedCache =
+        serde_json::from_str(&serde_json::to_string(&cache).unwrap()).unwrap();
+    assert_eq!(restored.get(&car, &settings, &db), Some(&computed));
+}
+
+#[test]
+fn test_cache_invalidated_by_relevant_inputs() {
+    let db = get_sample_maintenance_data();
+    let settings = SharedSettings::default();
+    let car = sample_car();
+    let mut cache = ComputedCache::default();
+    cache.insert(
+        &car,
+        &settings,
+        &db,
+        compute_car_data(&car, &settings, &db).unwrap(),
+    );
+
+    let mut pricier = car.clone();
+    pricier.purchase_price = "26000".to_string();
+    assert!(cache.get(&pricier, &settings, &db).is_none());
+
+    let mut gas = settings.clone();
+    gas.average_gas_price = 4.0;
+    assert!(cache.get(&car, &gas, &db).is_none());
+
+    // The model year and today's date set the car's age
+    let mut dated = car.clone();
+    dated.year = "2015".to_string();
+    assert!(cache.get(&dated, &settings, &db).is_none());
+    let mut today = settings.clone();
+    today.current_year = Some(2026.5);
+    assert!(cache.get(&car, &today, &db).is_none());
+
+    let mut edited_db = db.clone();
+    edited_db
+        .data
+        .get_mut("toyota_prius")
+        .unwrap()
+        .by_mileage
+        .push(MaintenanceDataPoint {
+            x: 25.0,
+            y: 12000.0,
+        });
+    assert!(cache.get(&car, &settings, &edited_db).is_none());
+}
+
+#[test]
+fn test_cache_ignores_display_only_inputs() {
+    let db = get_sample_maintenance_data();
+    let settings = SharedSettings::default();
+    let car = sample_car();
+    let key = cache_key(&car, &settings, &db);
+
+    let mut noted = car.clone();
+    noted.notes = "new tires".to_string();
+    noted.vin = "JTDKN3DU5A0123456".to_string();
+    noted.color = Some("#3b82f6".to_string());
+    assert_eq!(cache_key(&noted, &settings, &db), key);
+
+    let mut headline = settings.clone();
+    headline.primary_metric = PrimaryMetric::Total;
+    assert_eq!(cache_key(&car, &headline, &db), key);
+
+    // Other vehicles' maintenance profiles don't affect this car
+    let mut other_db = db.clone();
+    other_db.data.get_mut("ford_f-150").unwrap().by_time.clear();
+    assert_eq!(cache_key(&car, &settings, &other_db), key);
+}
+
+#[test]
+fn test_cache_drops_deleted_cars() {
+    let db = get_sample_maintenance_data();
+    let settings = SharedSettings::default();
+    let car = sample_car();
+    let mut other = sample_car();
+    other.id = 2;
+
+    let mut cache = ComputedCache::default();
+    for car in [&car, &other] {
+        cache.insert(
+            car,
+            &settings,
+            &db,
+            compute_car_data(car, &settings, &db).unwrap(),
+        );
+    }
+    cache.retain_cars(std::slice::from_ref(&car));
+
+    assert!(cache.get(&car, &settings, &db).is_some());
+    assert!(cache.get(&other, &settings, &db).is_none());
+}
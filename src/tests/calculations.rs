@@ -1,7 +1,15 @@
-use crate::calculations::compute_car_data;
-use crate::data::get_sample_maintenance_data;
+use crate::calculations::{
+    amortized_payment, apply_resale_channel, break_even_mileage, category_winners,
+    collect_assumptions, compute_car_data, compute_cash_flows, compute_marginal_costs,
+    cost_crossover, explain, fleet_total, household_replacement, present_value_of_payments,
+    sorted_car_ids, try_compute_car_data, BreakEvenOutcome, CarSort, CostField, CrossoverOutcome,
+};
+use crate::data::{format_dollars, format_thousands, get_sample_maintenance_data};
 use crate::models::{
-    Car, MaintenanceCostData, MaintenanceCostDatabase, MaintenanceDataPoint, SharedSettings,
+    AnnualCostBasis, Car, CarField, ComputeError, DepreciationMethod, FuelType, InsuranceMode,
+    InsurancePeriod, MaintenanceBasis, MaintenanceCostData, MaintenanceCostDatabase,
+    MaintenanceDataPoint, PrimaryMetric, ResaleChannel, SettingsIssue, SettingsPreset,
+    SharedSettings, SETTINGS_PRESETS,
 };
 
 #[test]
@@ -77,6 +85,29 @@ fn test_sample_data_exists() {
     );
 }
 
+#[test]
+fn test_sample_profiles_each_have_their_own_tables() {
+    let db = get_sample_maintenance_data();
+    let profiles: Vec<_> = db.data.values().collect();
+    for (i, a) in profiles.iter().enumerate() {
+        for b in &profiles[i + 1..] {
+            for (table, same) in [
+                ("mileage", a.by_mileage == b.by_mileage),
+                ("age", a.by_time == b.by_time),
+                ("resale", a.value_by_age == b.value_by_age),
+            ] {
+                assert!(
+                    !same,
+                    "{} and {} share a {} table",
+                    a.name(),
+                    b.name(),
+                    table
+                );
+            }
+        }
+    }
+}
+
 #[test]
 fn test_maintenance_cost_calculation_with_car() {
     let db = get_sample_maintenance_data();
@@ -154,3 +185,1921 @@ fn test_maintenance_cost_50_50_split() {
         computed.maintenance_cost_total
     );
 }
+
+#[test]
+fn test_single_point_tables_at_origin_stay_finite() {
+    // One point at x = 0 can't be scaled through the origin; it must not divide by zero
+    let mut data = MaintenanceCostData::new("Test".to_string(), "Car".to_string());
+    data.by_mileage = vec![MaintenanceDataPoint { x: 0.0, y: 500.0 }];
+    data.by_time = vec![MaintenanceDataPoint { x: 0.0, y: 500.0 }];
+    data.value_by_age = vec![MaintenanceDataPoint { x: 0.0, y: 100.0 }];
+    let mut db = MaintenanceCostDatabase::default();
+    db.set(data);
+
+    let mut car = sample_car();
+    car.make = "Test".to_string();
+    car.model = "Car".to_string();
+    for mileage in ["0", "50000"] {
+        car.current_mileage = mileage.to_string();
+        let computed = compute_car_data(&car, &SharedSettings::default(), &db).unwrap();
+        assert_eq!(computed.maintenance_cost_total, 0.0);
+        assert!(computed.resale_value.is_finite());
+        assert!(computed.total_cost_of_ownership.is_finite());
+        assert!(computed.cost_per_mile.is_finite());
+    }
+}
+
+fn sample_car() -> Car {
+    let mut car = Car::new(1);
+    car.make = "Toyota".to_string();
+    car.model = "Prius".to_string();
+    car.purchase_price = "25000".to_string();
+    car.current_mileage = "50000".to_string();
+    car.mpg = "50".to_string();
+    car.insurance_cost = "500".to_string();
+    car
+}
+
+#[test]
+fn test_past_lifetime_miles_horizon() {
+    let db = get_sample_maintenance_data();
+    let settings = SharedSettings::default(); // 200k lifetime
+
+    let mut car = sample_car();
+    car.current_mileage = "210000".to_string();
+
+    assert_eq!(
+        try_compute_car_data(&car, &settings, &db).unwrap_err(),
+        ComputeError::PastLifetimeMiles
+    );
+}
+
+#[test]
+fn test_past_keep_until_age_horizon() {
+    let db = get_sample_maintenance_data();
+    let settings = SharedSettings::default(); // 12k miles/year
+
+    // 50k miles at 12k/year is roughly 4.2 years old, already past a 3-year plan
+    let mut car = sample_car();
+    car.keep_until_age = "3".to_string();
+
+    assert_eq!(
+        try_compute_car_data(&car, &settings, &db).unwrap_err(),
+        ComputeError::PastOwnershipYears
+    );
+}
+
+#[test]
+fn test_lifetime_miles_override() {
+    let db = get_sample_maintenance_data();
+    let settings = SharedSettings::default();
+    let shared = compute_car_data(&sample_car(), &settings, &db).unwrap();
+    assert!(!shared.operands.lifetime_miles_overridden);
+
+    // The car's own lifetime replaces the shared setting
+    let mut car = sample_car();
+    car.lifetime_miles_override = "300000".to_string();
+    let computed = compute_car_data(&car, &settings, &db).unwrap();
+    assert!(computed.operands.lifetime_miles_overridden);
+    assert_eq!(computed.operands.lifetime_miles, 300000.0);
+    assert!((computed.remaining_miles - 250000.0).abs() < 0.01);
+    assert!(collect_assumptions(&car, &settings, &db)
+        .iter()
+        .any(|a| a.contains("this car's own lifetime of 300000 miles")));
+
+    // A car already past its own lifetime is reported as such
+    car.lifetime_miles_override = "40000".to_string();
+    assert_eq!(
+        try_compute_car_data(&car, &settings, &db),
+        Err(ComputeError::PastLifetimeMiles)
+    );
+    let message = car
+        .field_error(
+            CarField::CurrentMileage,
+            car.lifetime_miles(settings.lifetime_miles),
+        )
+        .unwrap();
+    assert!(message.contains("this car's Lifetime Miles"));
+
+    car.lifetime_miles_override = "lots".to_string();
+    assert_eq!(
+        car.lifetime_miles(settings.lifetime_miles),
+        settings.lifetime_miles
+    );
+    assert_eq!(
+        try_compute_car_data(&car, &settings, &db),
+        Err(ComputeError::InvalidInput)
+    );
+}
+
+#[test]
+fn test_keep_until_age_caps_horizon() {
+    let db = get_sample_maintenance_data();
+    let settings = SharedSettings::default();
+
+    let mut car = sample_car();
+    car.current_mileage = "24000".to_string(); // ~2 years old
+    car.keep_until_age = "5".to_string();
+
+    let computed = try_compute_car_data(&car, &settings, &db).unwrap();
+    assert!(
+        (computed.years_remaining - 3.0).abs() < 0.01,
+        "Expected ~3 years remaining, got {}",
+        computed.years_remaining
+    );
+    assert!((computed.remaining_miles - 36000.0).abs() < 0.01);
+
+    // Cars saved before the rename still load their planned age
+    let mut json = serde_json::to_value(&car).unwrap();
+    let age = json
+        .as_object_mut()
+        .unwrap()
+        .remove("keep_until_age")
+        .unwrap();
+    json["ownership_years"] = age;
+    let restored: Car = serde_json::from_value(json).unwrap();
+    assert_eq!(restored.keep_until_age, "5");
+}
+
+#[test]
+fn test_marginal_costs_sum_to_totals() {
+    let db = get_sample_maintenance_data();
+    let settings = SharedSettings::default();
+    let car = sample_car();
+
+    let computed = compute_car_data(&car, &settings, &db).unwrap();
+    let buckets = compute_marginal_costs(&car, &settings, &db);
+
+    // 50k to 200k miles in 10k buckets
+    assert_eq!(buckets.len(), 15);
+    assert_eq!(buckets[0].start_miles, 50000.0);
+    assert_eq!(buckets.last().unwrap().end_miles, 200000.0);
+
+    let fuel: f64 = buckets.iter().map(|b| b.fuel_cost).sum();
+    assert!((fuel - computed.fuel_cost_total).abs() < 0.01);
+
+    // Maintenance blends the mileage and age tables like the total does
+    let maintenance: f64 = buckets.iter().map(|b| b.maintenance_cost).sum();
+    assert!((maintenance - computed.maintenance_cost_total).abs() < 0.01);
+
+    // Sample data ends at 200k miles, so nothing is extrapolated
+    assert!(buckets.iter().all(|b| !b.extrapolated));
+
+    // Still adds up with regional rates and an extended warranty ending partway through
+    let settings = SharedSettings {
+        region_cost_multiplier: 1.3,
+        ..SharedSettings::default()
+    };
+    let mut car = sample_car();
+    car.extended_warranty_cost = "1500".to_string();
+    car.extended_warranty_years = "2.5".to_string();
+    let computed = compute_car_data(&car, &settings, &db).unwrap();
+    assert!(computed.warranty_covered_maintenance > 0.0);
+    let maintenance: f64 = compute_marginal_costs(&car, &settings, &db)
+        .iter()
+        .map(|b| b.maintenance_cost)
+        .sum();
+    assert!((maintenance - computed.maintenance_cost_total).abs() < 0.01);
+}
+
+#[test]
+fn test_region_cost_multiplier_scales_maintenance() {
+    let db = get_sample_maintenance_data();
+    let car = sample_car();
+
+    let base = compute_car_data(&car, &SharedSettings::default(), &db).unwrap();
+
+    let settings = SharedSettings {
+        region_cost_multiplier: 1.3,
+        ..SharedSettings::default()
+    };
+    let scaled = compute_car_data(&car, &settings, &db).unwrap();
+
+    assert!((scaled.maintenance_cost_total - base.maintenance_cost_total * 1.3).abs() < 0.01);
+    assert_eq!(scaled.insurance_cost_annual, base.insurance_cost_annual);
+    assert_eq!(scaled.fuel_cost_total, base.fuel_cost_total);
+
+    let settings = SharedSettings {
+        region_multiplier_applies_to_insurance: true,
+        ..settings
+    };
+    let scaled = compute_car_data(&car, &settings, &db).unwrap();
+    assert!((scaled.insurance_cost_annual - base.insurance_cost_annual * 1.3).abs() < 0.01);
+}
+
+#[test]
+fn test_assumptions_report_flags_defaults_and_missing_data() {
+    let db = get_sample_maintenance_data();
+    let mut car = sample_car();
+    car.make = "Honda".to_string();
+    car.model = "Fit".to_string();
+
+    let assumptions = collect_assumptions(&car, &SharedSettings::default(), &db);
+    assert!(assumptions
+        .iter()
+        .any(|a| a == "Used default gas price $3.50/gallon"));
+    assert!(assumptions
+        .iter()
+        .any(|a| a == "No maintenance data for \"Honda Fit\" (assumed $0)"));
+
+    let settings = SharedSettings {
+        average_gas_price: 4.25,
+        ..SharedSettings::default()
+    };
+    let assumptions = collect_assumptions(&sample_car(), &settings, &db);
+    assert!(assumptions
+        .iter()
+        .any(|a| a == "Used your gas price $4.25/gallon"));
+    assert!(assumptions
+        .iter()
+        .any(|a| a.starts_with("Maintenance uses the Toyota Prius profile")));
+}
+
+#[test]
+fn test_format_thousands() {
+    assert_eq!(format_thousands(0.0), "0");
+    assert_eq!(format_thousands(950.0), "950");
+    assert_eq!(format_thousands(1500.0), "1,500");
+    assert_eq!(format_thousands(1234567.4), "1,234,567");
+    assert_eq!(format_thousands(-2500.0), "-2,500");
+}
+
+#[test]
+fn test_cash_flows_sum_to_totals() {
+    let db = get_sample_maintenance_data();
+    let settings = SharedSettings::default();
+    let car = sample_car();
+
+    let computed = compute_car_data(&car, &settings, &db).unwrap();
+    let flows = compute_cash_flows(&car, &settings, &db);
+
+    // 150k remaining miles at 12k/year is 12.5 years: 12 full years plus a half
+    assert_eq!(flows.len(), 13);
+    assert!(flows[..12].iter().all(|f| f.years == 1.0));
+    assert!((flows[12].years - 0.5).abs() < 1e-9);
+    assert_eq!(
+        flows.iter().map(|f| f.year).collect::<Vec<_>>(),
+        (0..13).collect::<Vec<_>>()
+    );
+
+    let fuel: f64 = flows.iter().map(|f| f.fuel).sum();
+    let maintenance: f64 = flows.iter().map(|f| f.maintenance).sum();
+    let insurance: f64 = flows.iter().map(|f| f.insurance).sum();
+    assert!((fuel - computed.fuel_cost_total).abs() < 0.01);
+    assert!((maintenance - computed.maintenance_cost_total).abs() < 0.01);
+    assert!((insurance - computed.insurance_cost_annual * computed.years_remaining).abs() < 0.01);
+
+    // The series matches the direct whole-range calculation
+    let prius = db.get("Toyota", "Prius").unwrap();
+    let current_age = 50000.0 / settings.annual_mileage;
+    let direct = (prius.cost_for_mileage_range(50000.0, 200000.0)
+        + prius.cost_for_time_range(current_age, current_age + 12.5))
+        / 2.0;
+    assert!((maintenance - direct).abs() < 0.01);
+
+    let purchase_price = 25000.0;
+    let total: f64 = flows.iter().map(|f| f.total()).sum();
+    assert!(
+        (purchase_price + total + computed.opportunity_cost
+            - computed.resale_value
+            - computed.total_cost_of_ownership)
+            .abs()
+            < 0.01
+    );
+}
+
+#[test]
+fn test_cash_flows_empty_when_not_computable() {
+    let db = get_sample_maintenance_data();
+    let mut car = sample_car();
+    car.mpg = String::new();
+
+    assert!(compute_cash_flows(&car, &SharedSettings::default(), &db).is_empty());
+}
+
+#[test]
+fn test_per_mile_insurance() {
+    let db = get_sample_maintenance_data();
+    let settings = SharedSettings::default();
+    let mut car = sample_car();
+    car.insurance_mode = InsuranceMode::PerMile;
+    car.insurance_per_mile = "0.05".to_string();
+
+    let computed = compute_car_data(&car, &settings, &db).unwrap();
+    let insurance_total = computed.insurance_cost_annual * computed.years_remaining;
+
+    // 150,000 remaining miles at $0.05/mile
+    assert!((insurance_total - 7500.0).abs() < 0.01);
+    assert!((computed.insurance_cost_annual - 600.0).abs() < 0.01);
+
+    // The premium field is ignored in per-mile mode, but the rate is required
+    car.insurance_cost = String::new();
+    assert!(compute_car_data(&car, &settings, &db).is_some());
+    car.insurance_per_mile = String::new();
+    assert_eq!(
+        try_compute_car_data(&car, &settings, &db),
+        Err(ComputeError::MissingInput)
+    );
+}
+
+#[test]
+fn test_nominal_total_applies_inflation() {
+    let db = get_sample_maintenance_data();
+    let car = sample_car();
+    let flat = SharedSettings {
+        inflation_rate: 0.0,
+        ..Default::default()
+    };
+    let today = compute_car_data(&car, &flat, &db).unwrap();
+    assert_eq!(today.total_cost_nominal, today.total_cost_of_ownership);
+
+    // Inflation only reframes the total; the today's-dollars figures don't move
+    let computed = compute_car_data(&car, &SharedSettings::default(), &db).unwrap();
+    assert_eq!(
+        computed.total_cost_of_ownership,
+        today.total_cost_of_ownership
+    );
+    let ops = &computed.operands;
+    assert!(ops.inflation_on_running > 0.0);
+    assert!(ops.inflation_on_resale > 0.0);
+    assert!(
+        (computed.total_cost_nominal
+            - (computed.total_cost_of_ownership + ops.inflation_on_running
+                - ops.inflation_on_resale))
+            .abs()
+            < 1e-6
+    );
+
+    // The resale comes in the final year at the full growth
+    let growth = 1.03_f64.powf(computed.years_remaining) - 1.0;
+    assert!((ops.inflation_on_resale - computed.resale_value * growth).abs() < 1e-6);
+}
+
+#[test]
+fn test_discounted_total_cost() {
+    let db = get_sample_maintenance_data();
+    let car = sample_car();
+    let plain = compute_car_data(&car, &SharedSettings::default(), &db).unwrap();
+    assert_eq!(plain.discounted_total_cost, None);
+
+    let settings = SharedSettings {
+        discounted_total: true,
+        ..Default::default()
+    };
+    let computed = compute_car_data(&car, &settings, &db).unwrap();
+    assert_eq!(
+        computed.total_cost_of_ownership,
+        plain.total_cost_of_ownership
+    );
+    let ops = &computed.operands;
+    let discounted = computed.discounted_total_cost.unwrap();
+    assert!(
+        (discounted - (ops.discounted_purchase + ops.discounted_running - ops.discounted_resale))
+            .abs()
+            < 1e-6
+    );
+    assert_eq!(ops.discounted_purchase, 25000.0);
+
+    // Later costs and the resale are worth less today
+    let running = computed.fuel_cost_total
+        + computed.maintenance_cost_total
+        + computed.insurance_cost_annual * computed.years_remaining;
+    assert!(ops.discounted_running < running);
+    let resale = computed.resale_value * 1.08_f64.powf(-computed.years_remaining);
+    assert!((ops.discounted_resale - resale).abs() < 1e-6);
+
+    // With no return on the money, discounting changes nothing and there's no
+    // opportunity cost to replace
+    let free = SharedSettings {
+        opportunity_cost_rate: 0.0,
+        ..settings
+    };
+    let undiscounted = compute_car_data(&car, &free, &db).unwrap();
+    assert!(
+        (undiscounted.discounted_total_cost.unwrap() - undiscounted.total_cost_of_ownership).abs()
+            < 1e-6
+    );
+}
+
+#[test]
+fn test_current_age_from_model_year() {
+    let db = get_sample_maintenance_data();
+    let mut car = sample_car();
+    car.year = "2016".to_string();
+    car.keep_until_age = "12".to_string();
+
+    // Without today's date the 50,000 miles put the car at ~4.2 years old
+    let by_mileage = compute_car_data(&car, &SharedSettings::default(), &db).unwrap();
+    assert!((by_mileage.years_remaining - (12.0 - 50000.0 / 12000.0)).abs() < 1e-9);
+
+    // A 2016 model in 2026 is 10 years old, leaving 2 years before the planned sale
+    let settings = SharedSettings {
+        current_year: Some(2026.0),
+        ..Default::default()
+    };
+    let by_year = compute_car_data(&car, &settings, &db).unwrap();
+    assert!((by_year.years_remaining - 2.0).abs() < 1e-9);
+    assert!(collect_assumptions(&car, &settings, &db)
+        .iter()
+        .any(|a| a == "Vehicle age (10.0 years) comes from the 2016 model year"));
+
+    // No or unparseable model year falls back to the mileage estimate
+    car.year = "unknown".to_string();
+    assert_eq!(car.age_from_model_year(settings.current_year), None);
+    let fallback = compute_car_data(&car, &settings, &db).unwrap();
+    assert_eq!(fallback.years_remaining, by_mileage.years_remaining);
+}
+
+#[test]
+fn test_ownership_years_setting_caps_remaining_miles() {
+    let db = get_sample_maintenance_data();
+    let car = sample_car();
+    let uncapped = compute_car_data(&car, &SharedSettings::default(), &db).unwrap();
+    assert_eq!(uncapped.remaining_miles, 150000.0);
+
+    // Replaced after 5 years: 60,000 of the 150,000 miles left
+    let settings = SharedSettings {
+        ownership_years: Some(5.0),
+        ..Default::default()
+    };
+    let capped = compute_car_data(&car, &settings, &db).unwrap();
+    assert_eq!(capped.remaining_miles, 60000.0);
+    assert!((capped.years_remaining - 5.0).abs() < 1e-9);
+    assert_eq!(capped.operands.ownership_cap_miles, Some(60000.0));
+    assert!(capped.fuel_cost_total < uncapped.fuel_cost_total);
+    assert!(capped.maintenance_cost_total < uncapped.maintenance_cost_total);
+    assert!(capped.opportunity_cost < uncapped.opportunity_cost);
+    assert!(collect_assumptions(&car, &settings, &db)
+        .iter()
+        .any(|a| a == "Car is replaced after 5 more years, whatever its mileage"));
+
+    // The car's own planned sale age still applies when it comes sooner
+    let mut aging = car.clone();
+    aging.keep_until_age = "6".to_string();
+    let sooner = compute_car_data(&aging, &settings, &db).unwrap();
+    assert!((sooner.remaining_miles - (6.0 * 12000.0 - 50000.0)).abs() < 1e-6);
+
+    // A schedule longer than the lifetime mileage allows changes nothing
+    let long = SharedSettings {
+        ownership_years: Some(20.0),
+        ..Default::default()
+    };
+    let unchanged = compute_car_data(&car, &long, &db).unwrap();
+    assert_eq!(unchanged.remaining_miles, uncapped.remaining_miles);
+}
+
+#[test]
+fn test_loose_make_model_match_is_reported() {
+    let db = get_sample_maintenance_data();
+    let settings = SharedSettings::default();
+    let exact = compute_car_data(&sample_car(), &settings, &db).unwrap();
+    assert_eq!(exact.maintenance_profile_match, None);
+
+    let mut car = sample_car();
+    car.make = "toyota ".to_string();
+    car.model = "PRIUS".to_string();
+    let loose = compute_car_data(&car, &settings, &db).unwrap();
+    assert_eq!(loose.maintenance_cost_total, exact.maintenance_cost_total);
+    assert_eq!(
+        loose.maintenance_profile_match.as_deref(),
+        Some("Toyota Prius")
+    );
+}
+
+#[test]
+fn test_insurance_premium_period() {
+    let db = get_sample_maintenance_data();
+    let settings = SharedSettings::default();
+    let mut car = sample_car();
+
+    // The $500 premium is paid 12, 2 or 1 times a year
+    for (period, annual) in [
+        (InsurancePeriod::Monthly, 6000.0),
+        (InsurancePeriod::SixMonth, 1000.0),
+        (InsurancePeriod::Annual, 500.0),
+    ] {
+        car.insurance_period = period;
+        let computed = compute_car_data(&car, &settings, &db).unwrap();
+        assert!((computed.insurance_cost_annual - annual).abs() < 0.01);
+    }
+
+    // Cars saved before the field existed keep their 6-month premium
+    let mut json = serde_json::to_value(sample_car()).unwrap();
+    json.as_object_mut().unwrap().remove("insurance_period");
+    let restored: Car = serde_json::from_value(json).unwrap();
+    assert_eq!(restored.insurance_period, InsurancePeriod::SixMonth);
+}
+
+#[test]
+fn test_primary_metric_formatting() {
+    let db = get_sample_maintenance_data();
+    let computed = compute_car_data(&sample_car(), &SharedSettings::default(), &db).unwrap();
+
+    assert_eq!(
+        PrimaryMetric::Annual.format(&computed),
+        format!("${:.0}", computed.annual_cost)
+    );
+    assert_eq!(
+        PrimaryMetric::Total.format(&computed),
+        format!("${:.0}", computed.total_cost_of_ownership)
+    );
+    assert!(
+        (computed.cost_per_mile * computed.remaining_miles - computed.total_cost_of_ownership)
+            .abs()
+            < 0.01
+    );
+    assert!(PrimaryMetric::PerMile.format(&computed).ends_with("/mi"));
+    assert_eq!(
+        SharedSettings::default().primary_metric,
+        PrimaryMetric::Annual
+    );
+}
+
+#[test]
+fn test_first_period_prorated() {
+    let db = get_sample_maintenance_data();
+    let settings = SharedSettings::default();
+    let mut car = sample_car();
+    let full_year = compute_car_data(&car, &settings, &db).unwrap();
+
+    // 4,800 of 12,000 annual miles left this year is a 0.4-year first period
+    car.first_year_miles = "4800".to_string();
+    let flows = compute_cash_flows(&car, &settings, &db);
+
+    assert!((flows[0].years - 0.4).abs() < 1e-9);
+    assert!((flows[0].fuel - 4800.0 / 50.0 * 3.50).abs() < 0.01);
+    assert!((flows[0].insurance - 1000.0 * 0.4).abs() < 0.01);
+    assert!(flows[1..flows.len() - 1]
+        .iter()
+        .all(|f| (f.years - 1.0).abs() < 1e-9));
+
+    // 12.5 years is 0.4 + 12 full years + a 0.1-year tail
+    assert_eq!(flows.len(), 14);
+    assert!((flows[13].years - 0.1).abs() < 1e-9);
+
+    // Proration shifts period boundaries but not the totals
+    let computed = compute_car_data(&car, &settings, &db).unwrap();
+    assert!((computed.fuel_cost_total - full_year.fuel_cost_total).abs() < 0.01);
+    assert!((computed.maintenance_cost_total - full_year.maintenance_cost_total).abs() < 0.01);
+    assert!((computed.total_cost_of_ownership - full_year.total_cost_of_ownership).abs() < 0.01);
+
+    car.first_year_miles = "0".to_string();
+    assert_eq!(
+        try_compute_car_data(&car, &settings, &db),
+        Err(ComputeError::InvalidInput)
+    );
+}
+
+#[test]
+fn test_car_matches_search() {
+    let mut car = sample_car();
+    car.vin = "JTDKN3DU5A0123456".to_string();
+    car.notes = "Has a Sunroof and new tires".to_string();
+
+    assert!(car.matches_search(""));
+    assert!(car.matches_search("  "));
+    assert!(car.matches_search("sunroof"));
+    assert!(car.matches_search("jtdkn3"));
+    assert!(car.matches_search("PRIUS"));
+    assert!(!car.matches_search("leather"));
+}
+
+#[test]
+fn test_depreciation_methods_at_same_holding_period() {
+    let db = get_sample_maintenance_data();
+    let settings = SharedSettings::default();
+    let mut car = sample_car();
+    // 50k miles at 12k/year is ~4.17 years old; selling at 9.17 holds it for 5 years
+    car.keep_until_age = format!("{}", 50000.0 / 12000.0 + 5.0);
+
+    let compute_with = |method: DepreciationMethod| {
+        let mut car = car.clone();
+        car.depreciation_method = method;
+        compute_car_data(&car, &settings, &db).unwrap()
+    };
+
+    let exponential = compute_with(DepreciationMethod::ExponentialDecay);
+    let straight = compute_with(DepreciationMethod::StraightLine);
+    let curve = compute_with(DepreciationMethod::CurveFromDatabase);
+
+    assert!((exponential.years_remaining - 5.0).abs() < 1e-9);
+    // 25,000 × 0.85^5
+    assert!((exponential.resale_value - 25000.0 * 0.85_f64.powi(5)).abs() < 0.01);
+    // 60k of the 150k miles left before the lifetime mileage
+    assert!((straight.resale_value - 25000.0 * (1.0 - 60000.0 / 150000.0)).abs() < 0.01);
+    assert_eq!(
+        curve.depreciation_method,
+        DepreciationMethod::CurveFromDatabase
+    );
+    let prius = db.get("Toyota", "Prius").unwrap();
+    let ratio = prius
+        .retained_value_ratio(50000.0 / 12000.0, 50000.0 / 12000.0 + 5.0)
+        .unwrap();
+    assert!((curve.resale_value - 25000.0 * ratio).abs() < 0.01);
+
+    // Only resale differs between methods, so TCO differences mirror it exactly
+    assert!(
+        ((exponential.total_cost_of_ownership - straight.total_cost_of_ownership)
+            - (straight.resale_value - exponential.resale_value))
+            .abs()
+            < 0.01
+    );
+    assert_eq!(exponential.fuel_cost_total, curve.fuel_cost_total);
+}
+
+#[test]
+fn test_curve_depreciation_falls_back_without_curve() {
+    let db = MaintenanceCostDatabase::default();
+    let mut car = sample_car();
+    car.depreciation_method = DepreciationMethod::CurveFromDatabase;
+
+    let computed = compute_car_data(&car, &SharedSettings::default(), &db).unwrap();
+    assert_eq!(
+        computed.depreciation_method,
+        DepreciationMethod::ExponentialDecay
+    );
+    assert!(
+        (computed.resale_value - 25000.0 * 0.85_f64.powf(computed.years_remaining)).abs() < 0.01
+    );
+}
+
+#[test]
+fn test_default_depreciation_reduces_tco() {
+    let db = get_sample_maintenance_data();
+    let computed = compute_car_data(&sample_car(), &SharedSettings::default(), &db).unwrap();
+
+    assert_eq!(
+        computed.depreciation_method,
+        DepreciationMethod::ExponentialDecay
+    );
+    assert!(computed.resale_value > 0.0 && computed.resale_value < 25000.0);
+}
+
+#[test]
+fn test_category_winners_handle_ties_and_missing_cars() {
+    let db = get_sample_maintenance_data();
+    let settings = SharedSettings::default();
+
+    let prius = sample_car();
+    let mut twin = sample_car();
+    twin.id = 2;
+    let mut truck = sample_car();
+    truck.id = 3;
+    truck.make = "Ford".to_string();
+    truck.model = "F-150".to_string();
+    truck.mpg = "20".to_string();
+    truck.insurance_cost = "400".to_string();
+
+    let columns = vec![
+        (
+            "Prius".to_string(),
+            compute_car_data(&prius, &settings, &db),
+        ),
+        (
+            "Prius twin".to_string(),
+            compute_car_data(&twin, &settings, &db),
+        ),
+        (
+            "F-150".to_string(),
+            compute_car_data(&truck, &settings, &db),
+        ),
+        ("Incomplete".to_string(), None),
+    ];
+    let winners = category_winners(&columns);
+    let find = |category: &str| winners.iter().find(|w| w.category == category).unwrap();
+
+    // Identical Priuses tie on fuel and maintenance
+    assert_eq!(find("Cheapest fuel").winners, vec!["Prius", "Prius twin"]);
+    assert_eq!(
+        find("Cheapest maintenance").winners,
+        vec!["Prius", "Prius twin"]
+    );
+    // The truck's lower premium wins insurance outright
+    assert_eq!(find("Cheapest insurance").winners, vec!["F-150"]);
+    assert!((find("Cheapest insurance").value - 800.0).abs() < 0.01);
+
+    assert!(category_winners(&[("Incomplete".to_string(), None)]).is_empty());
+}
+
+#[test]
+fn test_explanations_substitute_operands() {
+    let db = get_sample_maintenance_data();
+    let settings = SharedSettings {
+        region_cost_multiplier: 1.2,
+        region_multiplier_applies_to_insurance: true,
+        ..SharedSettings::default()
+    };
+    let computed = compute_car_data(&sample_car(), &settings, &db).unwrap();
+
+    assert_eq!(
+        explain(&computed, CostField::OpportunityCost),
+        format!(
+            "$25,000 × 8% × 12.5 years = ${}",
+            format_thousands(computed.opportunity_cost)
+        )
+    );
+    assert_eq!(
+        explain(&computed, CostField::InsuranceAnnual),
+        "$500.00 6-month premium × 2 × 1.20 regional = $1,200"
+    );
+
+    // The recorded per-table operands reproduce the blended maintenance total
+    let ops = &computed.operands;
+    assert!(
+        ((ops.maintenance_by_mileage + ops.maintenance_by_time) / 2.0 * 1.2
+            - computed.maintenance_cost_total)
+            .abs()
+            < 0.01
+    );
+    assert!(
+        explain(&computed, CostField::TotalCostOfOwnership).ends_with(&format!(
+            "= ${}",
+            format_thousands(computed.total_cost_of_ownership)
+        ))
+    );
+}
+
+/// A car from 0 to 200k miles (20.0 x units) over ~16.67 years, with maintenance
+/// from a profile holding only the given tables
+fn single_table_computed(
+    by_mileage: Vec<MaintenanceDataPoint>,
+    by_time: Vec<MaintenanceDataPoint>,
+) -> crate::models::ComputedCarData {
+    let mut data = MaintenanceCostData::new("Test".to_string(), "Car".to_string());
+    data.by_mileage = by_mileage;
+    data.by_time = by_time;
+    let mut db = MaintenanceCostDatabase::default();
+    db.set(data);
+
+    let mut car = Car::new(1);
+    car.make = "Test".to_string();
+    car.model = "Car".to_string();
+    car.purchase_price = "10000".to_string();
+    car.current_mileage = "0".to_string();
+    car.mpg = "30".to_string();
+    car.insurance_cost = "500".to_string();
+    compute_car_data(&car, &SharedSettings::default(), &db).unwrap()
+}
+
+#[test]
+fn test_mileage_only_maintenance_uses_full_weight() {
+    let computed = single_table_computed(
+        vec![
+            MaintenanceDataPoint { x: 10.0, y: 1000.0 },
+            MaintenanceDataPoint { x: 20.0, y: 2000.0 },
+        ],
+        vec![],
+    );
+
+    // Not halved by blending with an empty age table
+    assert_eq!(computed.maintenance_basis, MaintenanceBasis::MileageOnly);
+    assert!(
+        (computed.maintenance_cost_total - 2000.0).abs() < 1.0,
+        "Got {}",
+        computed.maintenance_cost_total
+    );
+    assert!(explain(&computed, CostField::MaintenanceTotal).contains("no age table"));
+}
+
+#[test]
+fn test_time_only_maintenance_uses_full_weight() {
+    let computed = single_table_computed(
+        vec![],
+        vec![
+            MaintenanceDataPoint { x: 10.0, y: 1200.0 },
+            MaintenanceDataPoint { x: 20.0, y: 2400.0 },
+        ],
+    );
+
+    // 200k miles at 12k/yr is 16.67 years at $120/yr
+    assert_eq!(computed.maintenance_basis, MaintenanceBasis::TimeOnly);
+    assert!(
+        (computed.maintenance_cost_total - 2000.0).abs() < 1.0,
+        "Got {}",
+        computed.maintenance_cost_total
+    );
+    assert!(explain(&computed, CostField::MaintenanceTotal).contains("no mileage table"));
+}
+
+#[test]
+fn test_maintenance_basis_reported() {
+    let db = get_sample_maintenance_data();
+    let computed = compute_car_data(&sample_car(), &SharedSettings::default(), &db).unwrap();
+    assert_eq!(computed.maintenance_basis, MaintenanceBasis::Blended);
+
+    let computed = single_table_computed(vec![], vec![]);
+    assert_eq!(computed.maintenance_basis, MaintenanceBasis::NoData);
+    assert_eq!(computed.maintenance_cost_total, 0.0);
+}
+
+#[test]
+fn test_trade_in_tax_credit() {
+    let settings = SharedSettings {
+        trade_in_discount_percent: 20.0,
+        trade_in_tax_credit: true,
+        sales_tax_rate: 7.0,
+        ..SharedSettings::default()
+    };
+
+    // $10,000 private sale → $8,000 trade-in, saving 7% of $8,000 in sales tax
+    let (value, credit) = apply_resale_channel(ResaleChannel::TradeIn, 10_000.0, &settings);
+    assert!((value - 8_000.0).abs() < 1e-9);
+    assert!((credit - 560.0).abs() < 1e-9);
+
+    // No credit without the tax advantage, and none for a private sale
+    let no_credit = SharedSettings {
+        trade_in_tax_credit: false,
+        ..settings.clone()
+    };
+    assert_eq!(
+        apply_resale_channel(ResaleChannel::TradeIn, 10_000.0, &no_credit),
+        (8_000.0, 0.0)
+    );
+    assert_eq!(
+        apply_resale_channel(ResaleChannel::PrivateSale, 10_000.0, &settings),
+        (10_000.0, 0.0)
+    );
+
+    // The TCO credits both the trade-in value and the tax saved
+    let db = get_sample_maintenance_data();
+    let private = compute_car_data(&sample_car(), &settings, &db).unwrap();
+    let trade_in_car = Car {
+        resale_channel: ResaleChannel::TradeIn,
+        ..sample_car()
+    };
+    let trade_in = compute_car_data(&trade_in_car, &settings, &db).unwrap();
+    assert_eq!(sample_car().resale_channel, ResaleChannel::PrivateSale);
+    assert!((trade_in.resale_value - private.resale_value * 0.8).abs() < 0.01);
+    assert!((trade_in.trade_in_tax_credit - trade_in.resale_value * 0.07).abs() < 0.01);
+    let expected_tco = private.total_cost_of_ownership + private.resale_value
+        - trade_in.resale_value
+        - trade_in.trade_in_tax_credit;
+    assert!((trade_in.total_cost_of_ownership - expected_tco).abs() < 0.01);
+}
+
+#[test]
+fn test_is_computable_requires_only_core_fields() {
+    let mut car = Car::new(1);
+    assert!(!car.is_computable());
+    assert!(!car.has_details());
+
+    car.purchase_price = "25000".to_string();
+    car.current_mileage = "50000".to_string();
+    car.mpg = "50".to_string();
+    assert!(!car.is_computable());
+    car.insurance_cost = "500".to_string();
+    assert!(car.is_computable());
+    assert!(!car.has_details());
+    assert!(compute_car_data(
+        &car,
+        &SharedSettings::default(),
+        &MaintenanceCostDatabase::default()
+    )
+    .is_some());
+
+    // Insurance is read from whichever field the mode uses
+    car.insurance_mode = InsuranceMode::PerMile;
+    assert!(!car.is_computable());
+    car.insurance_per_mile = "0.08".to_string();
+    assert!(car.is_computable());
+
+    car.mpg = "0".to_string();
+    assert!(!car.is_computable());
+
+    assert!(sample_car().has_details());
+}
+
+#[test]
+fn test_field_errors_point_at_the_bad_input() {
+    let db = get_sample_maintenance_data();
+    let settings = SharedSettings::default();
+    let lifetime = settings.lifetime_miles;
+    let car = sample_car();
+    for field in [
+        CarField::PurchasePrice,
+        CarField::CurrentMileage,
+        CarField::Mpg,
+        CarField::Insurance,
+    ] {
+        assert_eq!(car.field_error(field, lifetime), None);
+    }
+    // Empty is missing rather than wrong, and unused fuel inputs aren't checked
+    assert_eq!(
+        Car::new(2).field_error(CarField::PurchasePrice, lifetime),
+        None
+    );
+    assert_eq!(car.field_error(CarField::MilesPerKwh, lifetime), None);
+
+    let mut typo = sample_car();
+    typo.purchase_price = "$25,000".to_string();
+    assert!(typo
+        .field_error(CarField::PurchasePrice, lifetime)
+        .is_some());
+
+    let mut negative = sample_car();
+    negative.purchase_price = "-100".to_string();
+    assert!(negative
+        .field_error(CarField::PurchasePrice, lifetime)
+        .is_some());
+    assert_eq!(
+        try_compute_car_data(&negative, &settings, &db),
+        Err(ComputeError::InvalidInput)
+    );
+
+    let mut no_mpg = sample_car();
+    no_mpg.mpg = "0".to_string();
+    assert!(no_mpg.field_error(CarField::Mpg, lifetime).is_some());
+
+    let mut worn_out = sample_car();
+    worn_out.current_mileage = lifetime.to_string();
+    assert!(worn_out
+        .field_error(CarField::CurrentMileage, lifetime)
+        .unwrap()
+        .contains("lifetime mileage in settings"));
+
+    let mut hybrid = sample_car();
+    hybrid.fuel_type = FuelType::Hybrid;
+    hybrid.miles_per_kwh = "3".to_string();
+    hybrid.electric_miles_percent = "120".to_string();
+    assert!(hybrid
+        .field_error(CarField::ElectricMilesPercent, lifetime)
+        .is_some());
+
+    let mut per_mile = sample_car();
+    per_mile.insurance_mode = InsuranceMode::PerMile;
+    per_mile.insurance_per_mile = "-0.05".to_string();
+    assert!(per_mile
+        .field_error(CarField::Insurance, lifetime)
+        .is_some());
+    assert_eq!(
+        try_compute_car_data(&per_mile, &settings, &db),
+        Err(ComputeError::InvalidInput)
+    );
+}
+
+#[test]
+fn test_sorted_car_ids_puts_cheapest_first_and_broken_cars_last() {
+    let db = get_sample_maintenance_data();
+    let settings = SharedSettings::default();
+    let mut pricey = sample_car();
+    pricey.id = 1;
+    pricey.make = "Zeta".to_string();
+    pricey.purchase_price = "40000".to_string();
+    let mut broken = sample_car();
+    broken.id = 2;
+    broken.mpg = String::new();
+    let mut cheap = sample_car();
+    cheap.id = 3;
+    cheap.make = "Alpha".to_string();
+    cheap.purchase_price = "15000".to_string();
+    let cars = vec![pricey, broken, cheap];
+
+    assert_eq!(
+        sorted_car_ids(&cars, &settings, &db, CarSort::Stored),
+        vec![1, 2, 3]
+    );
+    for sort in [
+        CarSort::AnnualCost,
+        CarSort::TotalCost,
+        CarSort::CostPerMile,
+    ] {
+        assert_eq!(sorted_car_ids(&cars, &settings, &db, sort), vec![3, 1, 2]);
+    }
+    assert_eq!(
+        sorted_car_ids(&cars, &settings, &db, CarSort::Name),
+        vec![3, 2, 1]
+    );
+}
+
+#[test]
+fn test_fleet_total_ties_out_with_displayed_parts() {
+    let db = get_sample_maintenance_data();
+    let settings = SharedSettings::default();
+    let car_with_price = |price: &str| Car {
+        purchase_price: price.to_string(),
+        ..sample_car()
+    };
+    let fleet: Vec<_> = ["25000.004", "18000.333", "31999.996"]
+        .into_iter()
+        .map(|price| compute_car_data(&car_with_price(price), &settings, &db).unwrap())
+        .collect();
+
+    let total = fleet_total(&fleet, 0.01);
+    let displayed_cars: Vec<String> = fleet
+        .iter()
+        .map(|c| format!("{:.2}", c.total_cost_of_ownership))
+        .collect();
+    let sum_of_displayed: f64 = displayed_cars
+        .iter()
+        .map(|s| s.parse::<f64>().unwrap())
+        .sum();
+
+    assert_eq!(total.displayed_parts.len(), 3);
+    assert!((total.displayed - sum_of_displayed).abs() < 0.01);
+    assert_eq!(
+        format!("{:.2}", total.displayed),
+        format!("{:.2}", sum_of_displayed)
+    );
+    assert!(total.rounding_difference().abs() <= 0.015);
+
+    let exact: f64 = fleet.iter().map(|c| c.total_cost_of_ownership).sum();
+    assert!((total.exact - exact).abs() < 1e-6);
+}
+
+#[test]
+fn test_household_replacement_credits_selling_car() {
+    let db = get_sample_maintenance_data();
+    let owned = Car {
+        id: 1,
+        purchase_price: "12000".to_string(),
+        current_mileage: "90000".to_string(),
+        resale_channel: ResaleChannel::TradeIn,
+        ..sample_car()
+    };
+    let candidate = Car {
+        id: 2,
+        ..sample_car()
+    };
+    let unfinished = Car::new(3);
+    let cars = vec![owned.clone(), candidate.clone(), unfinished];
+
+    // Household mode is off until a car is marked as selling
+    let settings = SharedSettings {
+        trade_in_discount_percent: 10.0,
+        trade_in_tax_credit: true,
+        sales_tax_rate: 5.0,
+        ..SharedSettings::default()
+    };
+    assert!(household_replacement(&cars, &settings, &db).is_none());
+
+    let settings = SharedSettings {
+        selling_car_id: Some(1),
+        ..settings
+    };
+    let household = household_replacement(&cars, &settings, &db).unwrap();
+
+    // $12,000 traded in at 10% off is $10,800, plus 5% of that in sales tax saved
+    assert!((household.sale_value - 10_800.0).abs() < 1e-9);
+    assert!((household.sale_tax_credit - 540.0).abs() < 1e-9);
+    assert!((household.sale_credit() - 11_340.0).abs() < 1e-9);
+
+    // Only the computable candidate is listed, netted against the sale
+    assert_eq!(household.options.len(), 1);
+    let option = &household.options[0];
+    let candidate_computed = compute_car_data(&candidate, &settings, &db).unwrap();
+    assert!(
+        (option.net_cost - (candidate_computed.total_cost_of_ownership - 11_340.0)).abs() < 0.01
+    );
+    assert!(
+        (option.net_annual - option.net_cost / candidate_computed.years_remaining).abs() < 0.01
+    );
+
+    let keep = compute_car_data(&owned, &settings, &db)
+        .unwrap()
+        .annual_cost;
+    assert_eq!(household.keep_annual, Some(keep));
+    assert!((option.change_vs_keeping_annual.unwrap() - (option.net_annual - keep)).abs() < 0.01);
+}
+
+#[test]
+fn test_in_comparison_defaults_to_included() {
+    // Cars saved before the field existed stay in the comparison
+    let mut json = serde_json::to_value(sample_car()).unwrap();
+    json.as_object_mut().unwrap().remove("in_comparison");
+    let restored: Car = serde_json::from_value(json).unwrap();
+    assert!(restored.in_comparison);
+    assert!(Car::new(1).in_comparison);
+
+    // Parked cars aren't offered as household replacements
+    let db = get_sample_maintenance_data();
+    let settings = SharedSettings {
+        selling_car_id: Some(1),
+        ..SharedSettings::default()
+    };
+    let cars = vec![
+        Car {
+            id: 1,
+            ..sample_car()
+        },
+        Car {
+            id: 2,
+            in_comparison: false,
+            ..sample_car()
+        },
+        Car {
+            id: 3,
+            ..sample_car()
+        },
+    ];
+    let household = household_replacement(&cars, &settings, &db).unwrap();
+    assert_eq!(household.options.len(), 1);
+}
+
+#[test]
+fn test_annual_operating_cost_excludes_purchase_price() {
+    let db = get_sample_maintenance_data();
+    let settings = SharedSettings::default();
+    let computed = compute_car_data(&sample_car(), &settings, &db).unwrap();
+
+    let running = computed.fuel_cost_annual
+        + computed.maintenance_cost_annual
+        + computed.insurance_cost_annual;
+    assert!((computed.annual_operating_cost - running).abs() < 0.01);
+    // The all-in figure also amortizes the purchase price (net of resale) and opportunity cost
+    let amortized =
+        (25000.0 - computed.resale_value + computed.opportunity_cost) / computed.years_remaining;
+    assert!((computed.annual_cost - (running + amortized)).abs() < 0.01);
+
+    // A pricier but otherwise identical car costs more all-in, not more to operate
+    let pricier = compute_car_data(
+        &Car {
+            purchase_price: "50000".to_string(),
+            ..sample_car()
+        },
+        &settings,
+        &db,
+    )
+    .unwrap();
+    assert!(pricier.annual_cost > computed.annual_cost);
+    assert!((pricier.annual_operating_cost - computed.annual_operating_cost).abs() < 0.01);
+
+    // Opportunity cost is optionally counted as operating
+    let with_opportunity = compute_car_data(
+        &sample_car(),
+        &SharedSettings {
+            operating_cost_includes_opportunity: true,
+            ..settings
+        },
+        &db,
+    )
+    .unwrap();
+    assert!(
+        (with_opportunity.annual_operating_cost
+            - (running + computed.opportunity_cost / computed.years_remaining))
+            .abs()
+            < 0.01
+    );
+    assert_eq!(
+        PrimaryMetric::Operating.format(&computed),
+        format!("${:.0}", computed.annual_operating_cost)
+    );
+}
+
+#[test]
+fn test_compound_opportunity_cost_exceeds_simple() {
+    let db = MaintenanceCostDatabase::default();
+    // 120k miles left at 12k/yr is a 10-year horizon
+    let car = Car {
+        current_mileage: "80000".to_string(),
+        ..sample_car()
+    };
+    let simple = compute_car_data(&car, &SharedSettings::default(), &db).unwrap();
+    let compound = compute_car_data(
+        &car,
+        &SharedSettings {
+            compound_opportunity_cost: true,
+            ..SharedSettings::default()
+        },
+        &db,
+    )
+    .unwrap();
+
+    assert!((simple.years_remaining - 10.0).abs() < 1e-9);
+    assert!((simple.opportunity_cost - 25000.0 * 0.08 * 10.0).abs() < 0.01);
+    assert!((compound.opportunity_cost - 25000.0 * (1.08_f64.powi(10) - 1.0)).abs() < 0.01);
+    assert!(compound.opportunity_cost > simple.opportunity_cost);
+    assert!(compound.total_cost_of_ownership > simple.total_cost_of_ownership);
+    assert!(explain(&compound, CostField::OpportunityCost).contains("(1 + 8%)^10.0"));
+}
+
+#[test]
+fn test_zero_annual_mileage_is_a_settings_error() {
+    let db = get_sample_maintenance_data();
+    let settings = SharedSettings {
+        annual_mileage: 0.0,
+        ..Default::default()
+    };
+
+    // Every car fails the same way, even one that's also missing its own inputs
+    let complete = try_compute_car_data(&sample_car(), &settings, &db);
+    let incomplete = try_compute_car_data(&Car::new(2), &settings, &db);
+    let expected = Err(ComputeError::InvalidSettings(SettingsIssue::AnnualMileage));
+    assert_eq!(complete, expected);
+    assert_eq!(incomplete, expected);
+    assert!(complete.unwrap_err().is_settings_error());
+    assert!(!ComputeError::MissingInput.is_settings_error());
+    assert_eq!(settings.validate(), Err(SettingsIssue::AnnualMileage));
+    assert!(SharedSettings::default().validate().is_ok());
+
+    let negative = SharedSettings {
+        annual_mileage: -500.0,
+        ..Default::default()
+    };
+    assert_eq!(
+        try_compute_car_data(&sample_car(), &negative, &db),
+        expected
+    );
+}
+
+#[test]
+fn test_extended_warranty_costing_more_than_it_covers() {
+    let db = get_sample_maintenance_data();
+    let settings = SharedSettings::default();
+    let base = compute_car_data(&sample_car(), &settings, &db).unwrap();
+
+    // 2.5 years (30,000 of 36,000 miles at 12k/yr), so coverage ends mid-period
+    let mut car = sample_car();
+    car.extended_warranty_cost = "20000".to_string();
+    car.extended_warranty_years = "2.5".to_string();
+    car.extended_warranty_miles = "36000".to_string();
+    let computed = compute_car_data(&car, &settings, &db).unwrap();
+
+    assert!((computed.operands.warranty_years - 2.5).abs() < 1e-9);
+    assert!(computed.warranty_covered_maintenance > 0.0);
+    assert!(computed.warranty_covered_maintenance < base.maintenance_cost_total);
+    assert!(
+        (computed.maintenance_cost_total
+            - (base.maintenance_cost_total - computed.warranty_covered_maintenance))
+            .abs()
+            < 0.01
+    );
+    // Paid upfront and netted against the covered maintenance
+    assert!(
+        (computed.total_cost_of_ownership
+            - (base.total_cost_of_ownership + 20000.0 - computed.warranty_covered_maintenance))
+            .abs()
+            < 0.01
+    );
+    assert!(computed.warranty_net_savings() < 0.0);
+    assert!(computed.total_cost_of_ownership > base.total_cost_of_ownership);
+    assert!(explain(&computed, CostField::ExtendedWarranty).contains("more than it covers"));
+
+    // The mileage limit ends coverage first when it's reached sooner
+    car.extended_warranty_miles = "12000".to_string();
+    let computed = compute_car_data(&car, &settings, &db).unwrap();
+    assert!((computed.operands.warranty_years - 1.0).abs() < 1e-9);
+
+    // No warranty leaves the result unchanged
+    assert_eq!(base.extended_warranty_cost, 0.0);
+    assert_eq!(base.warranty_covered_maintenance, 0.0);
+    assert!(!base.has_extended_warranty());
+
+    car.extended_warranty_cost = "-1".to_string();
+    assert_eq!(
+        try_compute_car_data(&car, &settings, &db),
+        Err(ComputeError::InvalidInput)
+    );
+}
+
+#[test]
+fn test_effective_cash_price_for_financed_car() {
+    // Textbook example: $20,000 at 6% APR over 60 months is $386.66/month
+    let payment = amortized_payment(20000.0, 6.0, 60);
+    assert!((payment - 386.66).abs() < 0.01);
+    // Discounting the payments at the loan's own rate recovers the principal
+    assert!((present_value_of_payments(payment, 6.0, 60) - 20000.0).abs() < 0.01);
+    assert!((amortized_payment(12000.0, 0.0, 48) - 250.0).abs() < 1e-9);
+
+    let db = get_sample_maintenance_data();
+    let settings = SharedSettings::default(); // 8% opportunity rate
+    let cash = compute_car_data(&sample_car(), &settings, &db).unwrap();
+    assert_eq!(cash.effective_cash_price, None);
+
+    // 0% financing on $25,000 with $5,000 down is worth less than $25,000 cash today
+    let mut car = sample_car();
+    car.down_payment = "5000".to_string();
+    car.loan_apr = "0".to_string();
+    car.loan_term_months = "60".to_string();
+    let financed = compute_car_data(&car, &settings, &db).unwrap();
+    let expected = 5000.0 + present_value_of_payments(20000.0 / 60.0, 8.0, 60);
+    assert!((financed.effective_cash_price.unwrap() - expected).abs() < 0.01);
+    assert!(financed.effective_cash_price.unwrap() < 25000.0);
+    assert!((financed.operands.monthly_payment - 20000.0 / 60.0).abs() < 1e-9);
+
+    // A loan at the opportunity rate is equivalent to paying cash
+    car.loan_apr = "8".to_string();
+    let at_rate = compute_car_data(&car, &settings, &db).unwrap();
+    assert!((at_rate.effective_cash_price.unwrap() - 25000.0).abs() < 0.01);
+
+    // A down payment above the price can't be financed
+    car.down_payment = "30000".to_string();
+    assert_eq!(
+        try_compute_car_data(&car, &settings, &db),
+        Err(ComputeError::InvalidInput)
+    );
+}
+
+#[test]
+fn test_loan_interest_adds_to_tco() {
+    let db = get_sample_maintenance_data();
+    let settings = SharedSettings::default();
+    let cash = compute_car_data(&sample_car(), &settings, &db).unwrap();
+    assert_eq!(cash.total_interest_paid, 0.0);
+    assert_eq!(cash.monthly_payment, 0.0);
+
+    // $20,000 at 6% over 60 months: 60 × $386.66 − $20,000 ≈ $3,199.36 of interest,
+    // all paid while the car is owned
+    let mut car = sample_car();
+    car.down_payment = "5000".to_string();
+    car.loan_apr = "6".to_string();
+    car.loan_term_months = "60".to_string();
+    let financed = compute_car_data(&car, &settings, &db).unwrap();
+    assert!(financed.years_remaining > 5.0);
+    assert!((financed.monthly_payment - 386.66).abs() < 0.01);
+    let interest = financed.monthly_payment * 60.0 - 20000.0;
+    assert!((financed.total_interest_paid - interest).abs() < 0.01);
+    assert!(
+        (financed.total_cost_of_ownership - cash.total_cost_of_ownership - interest).abs() < 0.01
+    );
+    let financing: f64 = compute_cash_flows(&car, &settings, &db)
+        .iter()
+        .map(|f| f.financing)
+        .sum();
+    assert!((financing - interest).abs() < 0.01);
+
+    // Selling before the loan ends pays off the balance, so less interest accrues
+    car.keep_until_age = "7".to_string(); // ~2.8 more years
+    let early = compute_car_data(&car, &settings, &db).unwrap();
+    assert!(early.total_interest_paid > 0.0);
+    assert!(early.total_interest_paid < interest);
+}
+
+#[test]
+fn test_sales_tax_and_registration_add_to_tco() {
+    let db = get_sample_maintenance_data();
+    let base = compute_car_data(&sample_car(), &SharedSettings::default(), &db).unwrap();
+    assert_eq!(base.sales_tax, 0.0);
+    assert_eq!(base.registration_cost_total, 0.0);
+
+    // 6% of $25,000 once, and $150 for each remaining year
+    let settings = SharedSettings {
+        sales_tax_on_purchase: true,
+        annual_registration_fee: 150.0,
+        ..Default::default()
+    };
+    let taxed = compute_car_data(&sample_car(), &settings, &db).unwrap();
+    assert!((taxed.sales_tax - 1500.0).abs() < 0.01);
+    let registration = 150.0 * taxed.years_remaining;
+    assert!((taxed.registration_cost_total - registration).abs() < 0.01);
+    assert!(
+        (taxed.total_cost_of_ownership - base.total_cost_of_ownership - 1500.0 - registration)
+            .abs()
+            < 0.01
+    );
+    assert!((taxed.annual_operating_cost - base.annual_operating_cost - 150.0).abs() < 0.01);
+    let flows: f64 = compute_cash_flows(&sample_car(), &settings, &db)
+        .iter()
+        .map(|f| f.registration)
+        .sum();
+    assert!((flows - registration).abs() < 0.01);
+}
+
+#[test]
+fn test_tire_replacement_adds_to_tco() {
+    let db = get_sample_maintenance_data();
+    let settings = SharedSettings::default();
+    let base = compute_car_data(&sample_car(), &settings, &db).unwrap();
+    assert_eq!(base.tire_cost_total, 0.0);
+
+    // 150,000 remaining miles at one $800 set per 50,000 miles is three sets
+    let mut car = sample_car();
+    car.tire_set_cost = "800".to_string();
+    car.tire_life_miles = "50000".to_string();
+    let with_tires = compute_car_data(&car, &settings, &db).unwrap();
+    assert!((with_tires.tire_cost_total - 2400.0).abs() < 0.01);
+    assert!(
+        (with_tires.total_cost_of_ownership - base.total_cost_of_ownership - 2400.0).abs() < 0.01
+    );
+    assert!(
+        (with_tires.cost_projected_remaining - base.cost_projected_remaining - 2400.0).abs() < 0.01
+    );
+    let flows: f64 = compute_cash_flows(&car, &settings, &db)
+        .iter()
+        .map(|f| f.tires)
+        .sum();
+    assert!((flows - 2400.0).abs() < 0.01);
+    assert_eq!(
+        explain(&with_tires, CostField::TireTotal),
+        "150,000 mi ÷ 50,000 mi per set × $800 per set = $2,400"
+    );
+
+    // Only a price, or a zero life, can't be costed
+    car.tire_life_miles = String::new();
+    let price_only = compute_car_data(&car, &settings, &db).unwrap();
+    assert_eq!(price_only.tire_cost_total, 0.0);
+    car.tire_life_miles = "0".to_string();
+    assert_eq!(
+        try_compute_car_data(&car, &settings, &db),
+        Err(ComputeError::InvalidInput)
+    );
+}
+
+#[test]
+fn test_cost_crossover_between_cheap_and_efficient_car() {
+    let db = get_sample_maintenance_data();
+    let settings = SharedSettings::default();
+    let mut guzzler = sample_car();
+    guzzler.id = 1;
+    guzzler.purchase_price = "15000".to_string();
+    guzzler.mpg = "15".to_string();
+    let mut efficient = sample_car();
+    efficient.id = 2;
+
+    // $10,000 more upfront, made back by $3.50 × (1/15 − 1/50) a mile in fuel, less the
+    // $800 a year the extra $10,000 would have earned
+    let per_mile = 3.5 / 15.0 - 3.5 / 50.0 - 10000.0 * 0.08 / 12000.0;
+    let crossover = cost_crossover(&guzzler, &efficient, &settings, &db).unwrap();
+    assert_eq!(crossover.horizon_miles, 150000.0);
+    let CrossoverOutcome::Crossover {
+        miles,
+        cheaper_after,
+    } = crossover.outcome
+    else {
+        panic!("expected a crossover, got {:?}", crossover.outcome);
+    };
+    assert!((miles - 10000.0 / per_mile).abs() < 0.01, "{}", miles);
+    assert_eq!(cheaper_after, efficient.id);
+
+    // The order the cars are given in doesn't matter
+    let swapped = cost_crossover(&efficient, &guzzler, &settings, &db).unwrap();
+    assert_eq!(swapped, crossover);
+
+    // Cheaper upfront and to run never gets caught
+    guzzler.mpg = "60".to_string();
+    let never = cost_crossover(&guzzler, &efficient, &settings, &db).unwrap();
+    assert_eq!(never.outcome, CrossoverOutcome::AlwaysCheaper(guzzler.id));
+
+    efficient.purchase_price = String::new();
+    assert_eq!(cost_crossover(&guzzler, &efficient, &settings, &db), None);
+}
+
+#[test]
+fn test_settings_presets_are_valid() {
+    let db = get_sample_maintenance_data();
+    let base = SharedSettings {
+        trade_in_tax_credit: true,
+        ..Default::default()
+    };
+    for preset in SETTINGS_PRESETS {
+        let settings = preset.apply(&base);
+        assert_eq!(settings.validate(), Ok(()), "{}", preset.name);
+        assert!(settings.opportunity_cost_rate >= 0.0, "{}", preset.name);
+        assert!(settings.average_gas_price > 0.0, "{}", preset.name);
+        assert!(
+            settings.lifetime_miles > settings.annual_mileage,
+            "{}",
+            preset.name
+        );
+        // Only the preset's own values change
+        assert!(settings.trade_in_tax_credit);
+        assert_eq!(SettingsPreset::matching(&settings), Some(preset));
+        assert!(compute_car_data(&sample_car(), &settings, &db).is_some());
+    }
+    // Presets are distinct, so at most one matches
+    assert_eq!(SettingsPreset::matching(&SharedSettings::default()), None);
+}
+
+#[test]
+fn test_cost_per_passenger_mile() {
+    let db = get_sample_maintenance_data();
+    let settings = SharedSettings::default();
+    let mut car = sample_car();
+
+    // Hidden until seats are given, and when they're zero
+    assert_eq!(
+        compute_car_data(&car, &settings, &db)
+            .unwrap()
+            .cost_per_passenger_mile,
+        None
+    );
+    car.seats = "0".to_string();
+    assert_eq!(
+        compute_car_data(&car, &settings, &db)
+            .unwrap()
+            .cost_per_passenger_mile,
+        None
+    );
+
+    car.seats = "7".to_string();
+    let computed = compute_car_data(&car, &settings, &db).unwrap();
+    let per_seat = computed.cost_per_passenger_mile.unwrap();
+    assert!((per_seat * 7.0 - computed.cost_per_mile).abs() < 1e-9);
+}
+
+#[test]
+fn test_cost_incurred_to_date_vs_projected() {
+    let db = get_sample_maintenance_data();
+    let settings = SharedSettings::default(); // $3.50/gal, 200k lifetime
+    let car = sample_car(); // 50k miles at 50 MPG
+    let computed = compute_car_data(&car, &settings, &db).unwrap();
+
+    // 50,000 mi ÷ 50 MPG × $3.50, plus maintenance from new to 50k / ~4.2 years
+    let data = db.get("Toyota", "Prius").unwrap();
+    let incurred_maintenance = data.basis().combine(
+        data.cost_for_mileage_range(0.0, 50000.0),
+        data.cost_for_time_range(0.0, 50000.0 / 12000.0),
+    );
+    assert!((computed.operands.incurred_fuel - 3500.0).abs() < 0.01);
+    assert!((computed.operands.incurred_maintenance - incurred_maintenance).abs() < 0.01);
+    assert!((computed.cost_incurred_to_date - (3500.0 + incurred_maintenance)).abs() < 0.01);
+
+    // The projected side covers the remaining 150k miles
+    let insurance_total = computed.insurance_cost_annual * computed.years_remaining;
+    assert!(
+        (computed.cost_projected_remaining
+            - (computed.fuel_cost_total + computed.maintenance_cost_total + insurance_total))
+            .abs()
+            < 0.01
+    );
+
+    // A historical gas price only changes what's already been spent
+    let cheaper_past = SharedSettings {
+        historical_gas_price: Some(2.50),
+        ..Default::default()
+    };
+    let recomputed = compute_car_data(&car, &cheaper_past, &db).unwrap();
+    assert!((recomputed.operands.incurred_fuel - 2500.0).abs() < 0.01);
+    assert_eq!(
+        recomputed.cost_projected_remaining,
+        computed.cost_projected_remaining
+    );
+    assert_eq!(
+        recomputed.total_cost_of_ownership,
+        computed.total_cost_of_ownership
+    );
+}
+
+#[test]
+fn test_annual_cost_basis_fixed_window() {
+    let db = get_sample_maintenance_data();
+    let car = sample_car(); // 150k miles left at 12k/yr = 12.5 years
+    let full = compute_car_data(&car, &SharedSettings::default(), &db).unwrap();
+    assert!((full.annual_cost - full.total_cost_of_ownership / full.years_remaining).abs() < 1e-9);
+
+    // A window at least as long as the ownership is the same as the full life
+    let long_window = SharedSettings {
+        annual_cost_basis: AnnualCostBasis::FixedYears(20),
+        ..Default::default()
+    };
+    let capped = compute_car_data(&car, &long_window, &db).unwrap();
+    assert_eq!(capped.annual_cost, full.annual_cost);
+    assert_eq!(capped.total_cost_of_ownership, full.total_cost_of_ownership);
+
+    // The next 5 years: purchase net of resale over the whole life, plus 5 years of flows
+    let five_years = SharedSettings {
+        annual_cost_basis: AnnualCostBasis::FixedYears(5),
+        ..Default::default()
+    };
+    let windowed = compute_car_data(&car, &five_years, &db).unwrap();
+    let flows = compute_cash_flows(&car, &five_years, &db);
+    let running_total: f64 = flows.iter().map(|f| f.total()).sum();
+    let first_five: f64 = flows.iter().take(5).map(|f| f.total()).sum();
+    let expected = (windowed.total_cost_of_ownership - running_total) / windowed.years_remaining
+        + first_five / 5.0;
+    assert!((windowed.annual_cost - expected).abs() < 1e-6);
+    assert_eq!(windowed.operands.annual_cost_window_years, 5.0);
+    // Maintenance rises with mileage, so the early years cost less than the average
+    assert!(windowed.annual_cost < full.annual_cost);
+    assert_eq!(
+        windowed.total_cost_of_ownership,
+        full.total_cost_of_ownership
+    );
+}
+
+#[test]
+fn test_inflated_resale_gives_flagged_net_profit() {
+    let mut db = get_sample_maintenance_data();
+    let mut data = db.get("Toyota", "Prius").unwrap().clone();
+    // A resale curve that never falls, so the car sells for its full purchase price
+    data.value_by_age = vec![
+        MaintenanceDataPoint { x: 0.0, y: 30000.0 },
+        MaintenanceDataPoint {
+            x: 30.0,
+            y: 30000.0,
+        },
+    ];
+    db.set(data);
+
+    let settings = SharedSettings {
+        opportunity_cost_rate: 0.0,
+        trade_in_discount_percent: 0.0,
+        trade_in_tax_credit: true,
+        sales_tax_rate: 10.0,
+        ..Default::default()
+    };
+    let mut car = sample_car();
+    car.current_mileage = "198000".to_string();
+    car.depreciation_method = DepreciationMethod::CurveFromDatabase;
+    car.resale_channel = ResaleChannel::TradeIn;
+    let computed = compute_car_data(&car, &settings, &db).unwrap();
+
+    assert_eq!(computed.resale_value, 25000.0);
+    assert!((computed.trade_in_tax_credit - 2500.0).abs() < 1e-9);
+    assert!(computed.total_cost_of_ownership < 0.0);
+    assert!(computed.has_net_profit());
+    assert!(PrimaryMetric::Total.format(&computed).starts_with("−$"));
+
+    // The profitable car can't win a category, even the resale value it inflates
+    let realistic = compute_car_data(&sample_car(), &SharedSettings::default(), &db).unwrap();
+    assert!(!realistic.has_net_profit());
+    let winners = category_winners(&[
+        ("Inflated".to_string(), Some(computed.clone())),
+        ("Realistic".to_string(), Some(realistic)),
+    ]);
+    assert!(winners
+        .iter()
+        .all(|w| w.winners == vec!["Realistic".to_string()]));
+
+    // The fleet total still adds the negative figure in
+    let fleet = fleet_total([&computed], 1.0);
+    assert_eq!(fleet.displayed, computed.total_cost_of_ownership.round());
+}
+
+#[test]
+fn test_format_dollars_signs() {
+    assert_eq!(format_dollars(1200.4, 0), "$1200");
+    assert_eq!(format_dollars(-300.0, 2), "−$300.00");
+    assert_eq!(format_dollars(-0.2, 0), "$0");
+}
+
+fn break_even_car(id: usize, price: &str, mpg: &str) -> Car {
+    let mut car = Car::new(id);
+    car.make = "Test".to_string();
+    car.model = format!("Car {}", id);
+    car.purchase_price = price.to_string();
+    car.current_mileage = "0".to_string();
+    car.mpg = mpg.to_string();
+    car.insurance_cost = "600".to_string();
+    car.depreciation_method = DepreciationMethod::StraightLine;
+    car
+}
+
+#[test]
+fn test_break_even_mileage_crossover() {
+    let db = MaintenanceCostDatabase::default();
+    let settings = SharedSettings::default();
+    // $10k more up front, a quarter of the fuel bill
+    let ev = break_even_car(1, "35000", "120");
+    let gas = break_even_car(2, "25000", "30");
+
+    let result = break_even_mileage(&ev, &gas, &settings, &db).unwrap();
+    let miles = result.crossover_mileage().unwrap();
+    assert!(matches!(
+        result.outcome,
+        BreakEvenOutcome::Crossover {
+            ev_cheaper_above: true,
+            ..
+        }
+    ));
+    // $800/yr more opportunity cost against $17,500 less fuel over 200k miles
+    assert!((miles - 800.0 * 200000.0 / 7500.0).abs() < 1.0);
+
+    let at = SharedSettings {
+        annual_mileage: miles,
+        ..Default::default()
+    };
+    let ev_cost = compute_car_data(&ev, &at, &db).unwrap().annual_cost;
+    let gas_cost = compute_car_data(&gas, &at, &db).unwrap().annual_cost;
+    assert!((ev_cost - gas_cost).abs() < 1.0);
+}
+
+#[test]
+fn test_break_even_mileage_none_when_one_side_always_wins() {
+    let db = MaintenanceCostDatabase::default();
+    let settings = SharedSettings::default();
+
+    // Cheaper and more efficient: never a break-even
+    let cheap_ev = break_even_car(1, "24000", "120");
+    let gas = break_even_car(2, "25000", "30");
+    let result = break_even_mileage(&cheap_ev, &gas, &settings, &db).unwrap();
+    assert_eq!(result.crossover_mileage(), None);
+    assert_eq!(result.outcome, BreakEvenOutcome::EvAlwaysCheaper);
+
+    // Far pricier for a small fuel saving
+    let pricey_ev = break_even_car(1, "80000", "35");
+    let result = break_even_mileage(&pricey_ev, &gas, &settings, &db).unwrap();
+    assert_eq!(result.crossover_mileage(), None);
+    assert_eq!(result.outcome, BreakEvenOutcome::EvAlwaysMoreExpensive);
+    assert!(result.points.iter().all(|p| p.ev_premium() > 0.0));
+
+    // A car that can't be computed has no break-even
+    let incomplete = break_even_car(3, "", "30");
+    assert!(break_even_mileage(&incomplete, &gas, &settings, &db).is_none());
+}
+
+#[test]
+fn test_depreciation_rate_setting_and_expected_resale() {
+    let db = get_sample_maintenance_data();
+    let mut car = sample_car();
+    // Held for exactly 5 years, as in the depreciation methods test
+    car.keep_until_age = format!("{}", 50000.0 / 12000.0 + 5.0);
+    let default = compute_car_data(&car, &SharedSettings::default(), &db).unwrap();
+    assert!((default.depreciation_cost - (25000.0 - default.resale_value)).abs() < 1e-9);
+
+    // A steeper rate lowers the resale value and raises the TCO by the same amount
+    let steep = SharedSettings {
+        depreciation_rate: 25.0,
+        ..Default::default()
+    };
+    let computed = compute_car_data(&car, &steep, &db).unwrap();
+    assert!((computed.resale_value - 25000.0 * 0.75_f64.powi(5)).abs() < 0.01);
+    assert!((computed.depreciation_cost - (25000.0 - computed.resale_value)).abs() < 1e-9);
+    assert!(
+        (computed.total_cost_of_ownership
+            - default.total_cost_of_ownership
+            - (default.resale_value - computed.resale_value))
+            .abs()
+            < 0.01
+    );
+    assert!(explain(&computed, CostField::ResaleValue).contains("(1 − 25%)^5.0"));
+
+    // An expected resale value replaces the projection
+    car.expected_resale_value = "14000".to_string();
+    let quoted = compute_car_data(&car, &steep, &db).unwrap();
+    assert_eq!(quoted.resale_value, 14000.0);
+    assert_eq!(quoted.depreciation_cost, 11000.0);
+    assert!(quoted.operands.resale_overridden);
+    assert_eq!(quoted.resale_source_label(), "Your expected value");
+
+    car.expected_resale_value = "lots".to_string();
+    assert_eq!(
+        try_compute_car_data(&car, &steep, &db).unwrap_err(),
+        ComputeError::InvalidInput
+    );
+}
+
+#[test]
+fn test_electric_and_hybrid_fuel_costs() {
+    let db = get_sample_maintenance_data();
+    let settings = SharedSettings::default(); // $3.50/gal, $0.16/kWh, 150k miles left
+    let gas = compute_car_data(&sample_car(), &settings, &db).unwrap();
+
+    let mut ev = sample_car();
+    ev.fuel_type = FuelType::Electric;
+    ev.mpg = String::new();
+    ev.miles_per_kwh = "4".to_string();
+    let electric = compute_car_data(&ev, &settings, &db).unwrap();
+    // 150,000 mi ÷ 4 mi/kWh × $0.16
+    assert!((electric.fuel_cost_total - 6000.0).abs() < 0.01);
+    assert_eq!(electric.operands.fuel_type, FuelType::Electric);
+    assert!(explain(&electric, CostField::FuelTotal).contains("mi/kWh"));
+    // Only fuel differs from the gas version of the same car
+    assert!(
+        ((gas.total_cost_of_ownership - electric.total_cost_of_ownership)
+            - (gas.fuel_cost_total - electric.fuel_cost_total))
+            .abs()
+            < 0.01
+    );
+
+    let mut hybrid = ev.clone();
+    hybrid.fuel_type = FuelType::Hybrid;
+    hybrid.mpg = "50".to_string();
+    hybrid.electric_miles_percent = "60".to_string();
+    let blended = compute_car_data(&hybrid, &settings, &db).unwrap();
+    // 40% of the miles on gas, 60% on electricity
+    let expected = 0.4 * gas.fuel_cost_total + 0.6 * electric.fuel_cost_total;
+    assert!((blended.fuel_cost_total - expected).abs() < 0.01);
+
+    // A gas car ignores electric inputs entirely
+    let mut gas_with_kwh = sample_car();
+    gas_with_kwh.miles_per_kwh = "not a number".to_string();
+    assert_eq!(
+        compute_car_data(&gas_with_kwh, &settings, &db)
+            .unwrap()
+            .fuel_cost_total,
+        gas.fuel_cost_total
+    );
+}
+
+#[test]
+fn test_partial_fuel_inputs_give_targeted_errors() {
+    let db = get_sample_maintenance_data();
+    let settings = SharedSettings::default();
+
+    // An electric car with only an MPG figure doesn't fall back to gasoline
+    let mut ev = sample_car();
+    ev.fuel_type = FuelType::Electric;
+    let err = try_compute_car_data(&ev, &settings, &db).unwrap_err();
+    assert_eq!(
+        err,
+        ComputeError::MissingFuelInput(FuelType::Electric, "miles per kWh")
+    );
+    assert!(err.to_string().contains("miles per kWh"));
+    assert!(!ev.is_computable());
+
+    // A hybrid with MPG and kWh but no electric share
+    let mut hybrid = sample_car();
+    hybrid.fuel_type = FuelType::Hybrid;
+    hybrid.miles_per_kwh = "3.5".to_string();
+    assert_eq!(
+        try_compute_car_data(&hybrid, &settings, &db).unwrap_err(),
+        ComputeError::MissingFuelInput(FuelType::Hybrid, "electric miles share")
+    );
+    assert!(!hybrid.is_computable());
+
+    // Filled in but unusable: named too, rather than a generic out-of-range error
+    hybrid.electric_miles_percent = "140".to_string();
+    let err = try_compute_car_data(&hybrid, &settings, &db).unwrap_err();
+    assert_eq!(
+        err,
+        ComputeError::InvalidFuelInput(FuelType::Hybrid, "electric miles share")
+    );
+    assert!(err.to_string().contains("electric miles share"));
+    ev.miles_per_kwh = "0".to_string();
+    assert_eq!(
+        try_compute_car_data(&ev, &settings, &db).unwrap_err(),
+        ComputeError::InvalidFuelInput(FuelType::Electric, "miles per kWh")
+    );
+    ev.miles_per_kwh = "abc".to_string();
+    assert_eq!(
+        try_compute_car_data(&ev, &settings, &db).unwrap_err(),
+        ComputeError::InvalidFuelInput(FuelType::Electric, "miles per kWh")
+    );
+    hybrid.electric_miles_percent = "50".to_string();
+    assert!(hybrid.is_computable());
+    assert!(try_compute_car_data(&hybrid, &settings, &db).is_ok());
+
+    // A gas car with no MPG is still a plain missing field
+    let mut gas = sample_car();
+    gas.mpg = String::new();
+    assert_eq!(
+        try_compute_car_data(&gas, &settings, &db).unwrap_err(),
+        ComputeError::MissingInput
+    );
+}
+
+#[test]
+fn test_fuel_consumed_and_tailpipe_co2() {
+    let settings = SharedSettings::default();
+    let db = MaintenanceCostDatabase::default();
+    let computed = compute_car_data(&sample_car(), &settings, &db).unwrap();
+    // 150k remaining miles at 50 MPG
+    assert!((computed.total_gallons - 3000.0).abs() < 1e-6);
+    assert!((computed.total_co2_kg - 3000.0 * 8.89).abs() < 1e-6);
+    assert_eq!(computed.total_kwh, 0.0);
+
+    let mut hybrid = sample_car();
+    hybrid.fuel_type = FuelType::Hybrid;
+    hybrid.miles_per_kwh = "4".to_string();
+    hybrid.electric_miles_percent = "40".to_string();
+    let computed = compute_car_data(&hybrid, &settings, &db).unwrap();
+    assert!((computed.total_gallons - 90_000.0 / 50.0).abs() < 1e-6);
+    assert!((computed.total_kwh - 60_000.0 / 4.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_mpg_loss_raises_fuel_cost_and_lowers_effective_mpg() {
+    let db = MaintenanceCostDatabase::default();
+    let flat = compute_car_data(&sample_car(), &SharedSettings::default(), &db).unwrap();
+    assert!((flat.effective_mpg - 50.0).abs() < 1e-9);
+
+    let settings = SharedSettings {
+        mpg_loss_percent: 1.0,
+        ..SharedSettings::default()
+    };
+    let aging = compute_car_data(&sample_car(), &settings, &db).unwrap();
+    // Gas per mile grows by 1/0.99 a year; averaged exactly over the 12.5 years left
+    let k = -(0.99_f64).ln();
+    let factor = ((k * 12.5).exp() - 1.0) / (k * 12.5);
+    assert!((aging.total_gallons - 3000.0 * factor).abs() < 1e-6);
+    assert!((aging.effective_mpg - 50.0 / factor).abs() < 1e-9);
+    assert!((aging.fuel_cost_total - flat.fuel_cost_total * factor).abs() < 1e-6);
+    assert!(explain(&aging, CostField::FuelTotal).contains("falls 1%/yr"));
+}
+
+#[test]
+fn test_city_highway_mpg_blends_by_highway_share() {
+    let db = MaintenanceCostDatabase::default();
+    let single = compute_car_data(&sample_car(), &SharedSettings::default(), &db).unwrap();
+
+    // Only one rating filled in: the combined MPG is still used
+    let mut car = sample_car();
+    car.mpg_city = "40".to_string();
+    let partial = compute_car_data(&car, &SharedSettings::default(), &db).unwrap();
+    assert_eq!(partial.total_gallons, single.total_gallons);
+
+    // 45% highway: 1 / (0.45/60 + 0.55/40) = 47.06 MPG, over 150k miles
+    car.mpg_highway = "60".to_string();
+    let blended = compute_car_data(&car, &SharedSettings::default(), &db).unwrap();
+    assert!((blended.total_gallons - 150_000.0 * 0.02125).abs() < 1e-6);
+    assert!((blended.effective_mpg - 1.0 / 0.02125).abs() < 1e-9);
+    assert!(explain(&blended, CostField::FuelTotal).contains("45% highway at 60, city at 40"));
+
+    // All-highway driving uses the highway rating alone, and MPG isn't required
+    car.mpg = String::new();
+    let settings = SharedSettings {
+        highway_percent: 100.0,
+        ..SharedSettings::default()
+    };
+    let highway = compute_car_data(&car, &settings, &db).unwrap();
+    assert!((highway.total_gallons - 2500.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_settings_inputs_must_parse_positive() {
+    assert_eq!(SharedSettings::parse_positive(" 12000 "), Some(12000.0));
+    assert_eq!(SharedSettings::parse_positive("3.49"), Some(3.49));
+    for text in ["", "0", "-500", "abc", "NaN", "inf"] {
+        assert_eq!(SharedSettings::parse_positive(text), None, "{:?}", text);
+    }
+}
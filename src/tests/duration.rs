@@ -0,0 +1,41 @@
+use crate::data::{format_duration, format_ownership_horizon, projected_end};
+
+#[test]
+fn test_format_duration() {
+    assert_eq!(format_duration(9.2), "about 9 years 2 months");
+    assert_eq!(format_duration(1.0), "about 1 year");
+    assert_eq!(format_duration(0.25), "about 3 months");
+    assert_eq!(format_duration(1.0 / 12.0), "about 1 month");
+    assert_eq!(format_duration(0.01), "less than a month");
+    // 11.6 months rounds up to a whole year
+    assert_eq!(format_duration(0.97), "about 1 year");
+}
+
+#[test]
+fn test_projected_end_rolls_over_years() {
+    // October 2026 (month 9) plus 9.2 years (110 months) is December 2035
+    assert_eq!(projected_end(2026, 9, 9.2), (2035, 11));
+    assert_eq!(projected_end(2026, 11, 1.0 / 12.0), (2027, 0));
+    assert_eq!(projected_end(2026, 0, 0.0), (2026, 0));
+}
+
+#[test]
+fn test_ownership_horizon_handles_unknown_model_year() {
+    assert_eq!(
+        format_ownership_horizon(9.2, 2026, 9, "2019"),
+        "until ~Dec 2035, about 9 years 2 months, when it's ~16 years old"
+    );
+    assert_eq!(
+        format_ownership_horizon(9.2, 2026, 9, ""),
+        "until ~Dec 2035, about 9 years 2 months"
+    );
+    assert_eq!(
+        format_ownership_horizon(2.0, 2026, 0, "next year-ish"),
+        "until ~Jan 2028, about 2 years"
+    );
+    // Implausible years are treated as unknown
+    assert_eq!(
+        format_ownership_horizon(2.0, 2026, 0, "19"),
+        "until ~Jan 2028, about 2 years"
+    );
+}
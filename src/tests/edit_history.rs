@@ -0,0 +1,60 @@
+use leptos::prelude::*;
+
+use crate::components::car_in_list;
+use crate::data::EditHistory;
+use crate::models::Car;
+
+#[test]
+fn test_undo_and_redo_step_through_recorded_states() {
+    let mut history = EditHistory::new(1, 10);
+    assert!(!history.can_undo());
+    assert!(history.record(2));
+    assert!(history.record(3));
+    // Recording the present again isn't a change
+    assert!(!history.record(3));
+
+    assert_eq!(history.undo(), Some(2));
+    assert_eq!(history.undo(), Some(1));
+    assert_eq!(history.undo(), None);
+    assert_eq!(history.redo(), Some(2));
+    assert!(history.can_redo());
+
+    // A new edit after undoing abandons the redo branch
+    assert!(history.record(5));
+    assert!(!history.can_redo());
+    assert_eq!(history.undo(), Some(2));
+}
+
+#[test]
+fn test_history_depth_drops_oldest_snapshots() {
+    let mut history = EditHistory::new(0, 3);
+    for value in 1..=5 {
+        history.record(value);
+    }
+    assert_eq!(history.undo(), Some(4));
+    assert_eq!(history.undo(), Some(3));
+    assert_eq!(history.undo(), Some(2));
+    assert_eq!(history.undo(), None);
+}
+
+#[test]
+fn test_undoing_an_edit_shows_the_restored_car_on_its_card() {
+    let mut car = Car::new(1);
+    car.make = "Toyota".to_string();
+    let cars = RwSignal::new(vec![car.clone(), Car::new(2)]);
+    let mut history = EditHistory::new(cars.get_untracked(), 10);
+    let shown = car_in_list(cars.into(), 1, car);
+
+    // An edit made on the card is written to the list and read back
+    cars.update(|cars| cars[0].make = "Honda".to_string());
+    assert_eq!(shown.get_untracked().make, "Honda");
+    history.record(cars.get_untracked());
+
+    // Undo replaces the whole list; the card shows the car as restored, not its old copy
+    cars.set(history.undo().unwrap());
+    assert_eq!(shown.get_untracked().make, "Toyota");
+
+    // Once the car is deleted the card keeps its last value while it's removed
+    cars.update(|cars| cars.retain(|c| c.id != 1));
+    assert_eq!(shown.get_untracked().make, "Toyota");
+}
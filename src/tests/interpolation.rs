@@ -0,0 +1,157 @@
+use crate::models::{MaintenanceCostData, MaintenanceDataPoint};
+
+/// Profile with the same points in both tables
+fn profile(values: &[(f64, f64)]) -> MaintenanceCostData {
+    let points: Vec<MaintenanceDataPoint> = values
+        .iter()
+        .map(|&(x, y)| MaintenanceDataPoint { x, y })
+        .collect();
+    let mut data = MaintenanceCostData::new("Test".to_string(), "Car".to_string());
+    data.by_mileage = points.clone();
+    data.by_time = points;
+    data
+}
+
+/// Interpolated cumulative cost at `x` years, read through the public range API
+fn cost_at(data: &MaintenanceCostData, x: f64) -> f64 {
+    data.cost_for_time_range(0.0, x)
+}
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() < 1e-9,
+        "expected {}, got {}",
+        expected,
+        actual
+    );
+}
+
+#[test]
+fn test_single_point_below_at_and_above() {
+    let data = profile(&[(2.0, 1000.0)]);
+
+    // Scaled linearly through the origin on both sides of the point
+    assert_close(cost_at(&data, 1.0), 500.0);
+    assert_close(cost_at(&data, 2.0), 1000.0);
+    assert_close(cost_at(&data, 4.0), 2000.0);
+    assert_close(data.cost_for_time_range(1.0, 4.0), 1500.0);
+}
+
+#[test]
+fn test_single_point_at_origin_is_flat() {
+    let data = profile(&[(0.0, 500.0)]);
+
+    assert_close(data.cost_for_time_range(0.0, 5.0), 0.0);
+    assert_close(data.cost_for_mileage_range(0.0, 50000.0), 0.0);
+}
+
+#[test]
+fn test_below_first_point_scales_from_origin() {
+    let data = profile(&[(2.0, 400.0), (4.0, 1200.0)]);
+
+    // Below the first point the line runs through the origin (200/unit), not along the
+    // first segment's steeper 400/unit slope
+    assert_close(cost_at(&data, 1.0), 200.0);
+    assert_close(cost_at(&data, 0.5), 100.0);
+    assert_close(data.cost_for_time_range(1.0, 3.0), 800.0 - 200.0);
+}
+
+#[test]
+fn test_zero_first_x_interpolates_normally() {
+    let data = profile(&[(0.0, 0.0), (2.0, 400.0), (4.0, 1200.0)]);
+
+    assert_close(cost_at(&data, 0.0), 0.0);
+    assert_close(cost_at(&data, 1.0), 200.0);
+    assert_close(cost_at(&data, 3.0), 800.0);
+
+    // A starting cost at x = 0 is a baseline: ranges beginning at 0 don't include it
+    let baseline = profile(&[(0.0, 300.0), (2.0, 700.0)]);
+    assert_close(cost_at(&baseline, 1.0), 200.0);
+    assert_close(baseline.cost_for_time_range(0.0, 2.0), 400.0);
+    assert!(cost_at(&baseline, 5.0).is_finite());
+}
+
+#[test]
+fn test_two_points_extrapolated_far_beyond() {
+    let data = profile(&[(1.0, 100.0), (2.0, 300.0)]);
+
+    // Slope of the last two points (200/unit) continues indefinitely
+    assert_close(cost_at(&data, 10.0), 1900.0);
+    assert_close(data.cost_for_time_range(2.0, 100.0), 200.0 * 98.0);
+    assert_close(
+        data.cost_for_mileage_range(20000.0, 1_000_000.0),
+        200.0 * 98.0,
+    );
+}
+
+#[test]
+fn test_query_exactly_at_first_and_last_x() {
+    let data = profile(&[(1.0, 100.0), (2.0, 300.0), (3.0, 600.0)]);
+
+    assert_close(cost_at(&data, 1.0), 100.0);
+    assert_close(cost_at(&data, 3.0), 600.0);
+    assert_close(data.cost_for_time_range(1.0, 3.0), 500.0);
+    assert_close(data.cost_for_mileage_range(10000.0, 30000.0), 500.0);
+}
+
+#[test]
+fn test_descending_tail_is_clamped_to_zero() {
+    let data = profile(&[(1.0, 100.0), (2.0, 500.0), (3.0, 400.0)]);
+
+    // The tail slope is -100/unit, so extrapolated cost falls below the last point
+    assert_close(data.cost_for_time_range(3.0, 5.0), 0.0);
+    assert_close(data.cost_for_time_range(2.0, 5.0), 0.0);
+    assert_close(data.cost_for_time_range(1.0, 5.0), 100.0);
+    assert_close(data.cost_for_mileage_range(30000.0, 50000.0), 0.0);
+}
+
+#[test]
+fn test_empty_or_inverted_ranges_cost_nothing() {
+    let data = profile(&[(1.0, 100.0), (2.0, 300.0)]);
+
+    assert_close(data.cost_for_time_range(2.0, 2.0), 0.0);
+    assert_close(data.cost_for_time_range(3.0, 1.0), 0.0);
+    assert_close(data.cost_for_mileage_range(30000.0, 10000.0), 0.0);
+    assert_close(profile(&[]).cost_for_time_range(0.0, 10.0), 0.0);
+}
+
+#[test]
+fn test_repeated_x_inside_the_table() {
+    // A zero-width segment at x = 2 must not be picked for points just after it
+    let data = profile(&[(1.0, 100.0), (2.0, 200.0), (2.0, 400.0), (4.0, 800.0)]);
+
+    assert_close(cost_at(&data, 1.5), 150.0);
+    assert_close(cost_at(&data, 3.0), 600.0);
+    assert_close(cost_at(&data, 4.0), 800.0);
+}
+
+#[test]
+fn test_repeated_x_at_the_end_extrapolates_from_a_real_segment() {
+    let data = profile(&[(1.0, 100.0), (2.0, 300.0), (2.0, 300.0)]);
+
+    // Slope of the last nonzero-width segment, not a division by zero
+    assert_close(cost_at(&data, 3.0), 500.0);
+    assert!(cost_at(&data, 5.0).is_finite());
+
+    // Only one distinct x behaves like a single point
+    let single = profile(&[(2.0, 1000.0), (2.0, 1000.0)]);
+    assert_close(cost_at(&single, 4.0), 2000.0);
+}
+
+#[test]
+fn test_coalesce_duplicate_points_keeps_max_y() {
+    let mut data = profile(&[(2.0, 400.0), (1.0, 100.0), (2.0, 200.0), (4.0, 800.0)]);
+
+    assert!(data.coalesce_duplicate_points());
+    let expected = vec![
+        MaintenanceDataPoint { x: 1.0, y: 100.0 },
+        MaintenanceDataPoint { x: 2.0, y: 400.0 },
+        MaintenanceDataPoint { x: 4.0, y: 800.0 },
+    ];
+    assert_eq!(data.by_mileage, expected);
+    assert_eq!(data.by_time, expected);
+    assert_close(cost_at(&data, 3.0), 600.0);
+
+    // Already clean tables are left alone
+    assert!(!data.coalesce_duplicate_points());
+}
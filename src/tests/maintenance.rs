@@ -0,0 +1,239 @@
+use crate::data::get_sample_maintenance_data;
+use crate::models::{
+    scale_x, MaintenanceCostData, MaintenanceCostDatabase, MaintenanceDataPoint, MergeStrategy,
+    MILES_PER_MILEAGE_X,
+};
+
+fn points(values: &[(f64, f64)]) -> Vec<MaintenanceDataPoint> {
+    values
+        .iter()
+        .map(|&(x, y)| MaintenanceDataPoint { x, y })
+        .collect()
+}
+
+fn is_non_decreasing(points: &[MaintenanceDataPoint]) -> bool {
+    points.windows(2).all(|w| w[1].y >= w[0].y)
+}
+
+#[test]
+fn test_smooth_preserves_endpoints() {
+    let mut data = MaintenanceCostData::new("Test".to_string(), "Car".to_string());
+    data.by_mileage = points(&[
+        (1.0, 300.0),
+        (2.0, 900.0),
+        (3.0, 1000.0),
+        (4.0, 1900.0),
+        (5.0, 2000.0),
+    ]);
+    data.by_time = points(&[(1.0, 400.0), (2.0, 500.0), (3.0, 1500.0), (4.0, 1600.0)]);
+
+    let smoothed = data.smooth(3);
+
+    assert_eq!(smoothed.by_mileage.first(), data.by_mileage.first());
+    assert_eq!(smoothed.by_mileage.last(), data.by_mileage.last());
+    assert_eq!(smoothed.by_time.first(), data.by_time.first());
+    assert_eq!(smoothed.by_time.last(), data.by_time.last());
+    assert_eq!(smoothed.by_mileage.len(), data.by_mileage.len());
+
+    // Interior points should actually move toward their neighbours
+    assert!(smoothed.by_mileage[1].y < 900.0);
+    assert!(smoothed.by_mileage[3].y < 1900.0);
+}
+
+#[test]
+fn test_smooth_never_decreases() {
+    let mut data = MaintenanceCostData::new("Test".to_string(), "Car".to_string());
+    // A dip and a spike that a plain moving average would carry through
+    data.by_mileage = points(&[
+        (1.0, 100.0),
+        (2.0, 2500.0),
+        (3.0, 200.0),
+        (4.0, 300.0),
+        (5.0, 400.0),
+        (6.0, 3000.0),
+    ]);
+
+    for window in [3, 5, 7] {
+        let smoothed = data.smooth(window);
+        assert!(
+            is_non_decreasing(&smoothed.by_mileage),
+            "Window {} produced a decreasing segment: {:?}",
+            window,
+            smoothed.by_mileage
+        );
+        assert!(smoothed.by_mileage.iter().all(|p| p.y <= 3000.0));
+    }
+}
+
+#[test]
+fn test_smooth_short_series_unchanged() {
+    let mut data = MaintenanceCostData::new("Test".to_string(), "Car".to_string());
+    data.by_mileage = points(&[(1.0, 100.0), (2.0, 250.0)]);
+
+    assert_eq!(data.smooth(3).by_mileage, data.by_mileage);
+}
+
+#[test]
+fn test_merge_overlapping_points_averages() {
+    let mut db = MaintenanceCostDatabase::default();
+    let mut base = MaintenanceCostData::new("Chevrolet".to_string(), "Malibu".to_string());
+    base.by_mileage = points(&[(1.0, 400.0), (2.0, 800.0), (3.0, 1200.0)]);
+    let mut dup = MaintenanceCostData::new("Chevy".to_string(), "Malibu".to_string());
+    dup.by_mileage = points(&[(1.0, 600.0), (2.0, 1000.0), (3.0, 1400.0)]);
+    db.set(base);
+    db.set(dup);
+
+    assert!(db.merge(
+        ("Chevrolet", "Malibu"),
+        ("Chevy", "Malibu"),
+        MergeStrategy::Average
+    ));
+
+    assert!(db.get("Chevy", "Malibu").is_none());
+    let merged = db.get("Chevrolet", "Malibu").unwrap();
+    assert_eq!(
+        merged.by_mileage,
+        points(&[(1.0, 500.0), (2.0, 900.0), (3.0, 1300.0)])
+    );
+}
+
+#[test]
+fn test_merge_prefer_base_keeps_base_values() {
+    let mut base = MaintenanceCostData::new("Chevrolet".to_string(), "Malibu".to_string());
+    base.by_time = points(&[(1.0, 400.0), (2.0, 800.0)]);
+    let mut other = MaintenanceCostData::new("Chevy".to_string(), "Malibu".to_string());
+    other.by_time = points(&[(2.0, 1000.0), (3.0, 1500.0)]);
+
+    let merged = base.merged_with(&other, MergeStrategy::PreferBase);
+    assert_eq!(
+        merged.by_time,
+        points(&[(1.0, 400.0), (2.0, 800.0), (3.0, 1500.0)])
+    );
+    assert_eq!(merged.make, "Chevrolet");
+}
+
+#[test]
+fn test_merge_disjoint_points_stays_monotonic() {
+    let mut base = MaintenanceCostData::new("Chevrolet".to_string(), "Malibu".to_string());
+    base.by_mileage = points(&[(1.0, 500.0), (3.0, 1500.0)]);
+    let mut other = MaintenanceCostData::new("Chevy".to_string(), "Malibu".to_string());
+    // Interleaved points from a cheaper source would dip below the base curve
+    other.by_mileage = points(&[(2.0, 400.0), (4.0, 1800.0)]);
+
+    let merged = base.merged_with(&other, MergeStrategy::Average);
+    let xs: Vec<f64> = merged.by_mileage.iter().map(|p| p.x).collect();
+    assert_eq!(xs, vec![1.0, 2.0, 3.0, 4.0]);
+    assert!(is_non_decreasing(&merged.by_mileage));
+    assert_eq!(merged.by_mileage[1].y, 500.0);
+}
+
+#[test]
+fn test_merge_rejects_same_or_missing_profile() {
+    let mut db = MaintenanceCostDatabase::default();
+    db.set(MaintenanceCostData::new(
+        "Chevrolet".to_string(),
+        "Malibu".to_string(),
+    ));
+
+    assert!(!db.merge(
+        ("Chevrolet", "Malibu"),
+        ("chevrolet", "malibu"),
+        MergeStrategy::Average
+    ));
+    assert!(!db.merge(
+        ("Chevrolet", "Malibu"),
+        ("Chevy", "Malibu"),
+        MergeStrategy::Average
+    ));
+    assert!(db.get("Chevrolet", "Malibu").is_some());
+}
+
+#[test]
+fn test_validate_reports_dips_and_bad_ordering() {
+    let mut data = MaintenanceCostData::new("Test".to_string(), "Car".to_string());
+    data.by_mileage = points(&[(1.0, 300.0), (2.0, 900.0), (3.0, 1000.0)]);
+    data.by_time = points(&[(1.0, 400.0), (2.0, 500.0)]);
+    // Resale curves are supposed to fall
+    data.value_by_age = points(&[(0.0, 100.0), (5.0, 60.0)]);
+    assert!(data.validate().is_empty());
+
+    data.by_mileage = points(&[(1.0, 300.0), (2.0, 250.0), (2.0, 900.0), (1.5, 950.0)]);
+    data.value_by_age = points(&[(5.0, 60.0), (0.0, 100.0)]);
+    let warnings = data.validate();
+    assert_eq!(warnings.len(), 4, "{:?}", warnings);
+    assert!(warnings[0].contains("falls from $300.00 at 10000 miles to $250.00 at 20000 miles"));
+    assert!(warnings[1].contains("two points at 20000 miles"));
+    assert!(warnings[2].contains("15000 miles comes after 20000 miles"));
+    assert!(warnings[3].starts_with("Resale curve"));
+}
+
+#[test]
+fn test_get_fuzzy_tolerates_spacing_case_and_punctuation() {
+    let db = get_sample_maintenance_data();
+    let exact = db.get("Ford", "F-150").unwrap();
+    assert_eq!(db.get("ford", "F150"), None);
+    assert_eq!(db.get_fuzzy("ford", "F150", ""), Some(exact));
+    assert_eq!(db.get_fuzzy(" Ford ", "f 150 ", ""), Some(exact));
+    assert_eq!(db.get_fuzzy("Ford", "F-250", ""), None);
+    assert_eq!(db.get_fuzzy("", "", ""), None);
+}
+
+#[test]
+fn test_trim_profile_overrides_make_model_profile() {
+    let mut db = get_sample_maintenance_data();
+    let mut raptor = MaintenanceCostData::new("Ford".to_string(), "F-150".to_string());
+    raptor.trim = "Raptor".to_string();
+    raptor.by_mileage = points(&[(1.0, 2000.0), (5.0, 12000.0)]);
+    assert_eq!(raptor.key(), "ford_f-150_raptor");
+    assert_eq!(raptor.name(), "Ford F-150 Raptor");
+    db.set(raptor.clone());
+
+    // The base profile keeps its key alongside the trim one
+    let base = db.get("Ford", "F-150").unwrap().clone();
+    assert_eq!(db.get_fuzzy("Ford", "F-150", "Raptor"), Some(&raptor));
+    assert_eq!(db.get_fuzzy("ford", "F150", " raptor"), Some(&raptor));
+    assert_eq!(db.get_fuzzy("Ford", "F-150", "XLT"), Some(&base));
+    assert_eq!(db.get_fuzzy("Ford", "F-150", ""), Some(&base));
+
+    assert!(db.merge_keys(&base.key(), &raptor.key(), MergeStrategy::PreferBase));
+    assert_eq!(db.get_fuzzy("Ford", "F-150", "Raptor"), Some(&base));
+}
+
+#[test]
+fn test_sample_profiles_are_complete_and_valid() {
+    let db = get_sample_maintenance_data();
+    assert!(db.data.len() >= 10);
+    for data in db.data.values() {
+        let name = format!("{} {}", data.make, data.model);
+        assert!(!data.by_mileage.is_empty(), "{} has no mileage table", name);
+        assert!(!data.by_time.is_empty(), "{} has no time table", name);
+        assert!(
+            data.validate().is_empty(),
+            "{}: {:?}",
+            name,
+            data.validate()
+        );
+        // Resale starts at the full price and only falls from there
+        assert_eq!(
+            data.value_by_age.first().map(|p| p.y),
+            Some(100.0),
+            "{}",
+            name
+        );
+        assert!(
+            data.value_by_age.windows(2).all(|w| w[1].y <= w[0].y),
+            "{}",
+            name
+        );
+    }
+    assert!(db.get("Tesla", "Model 3").is_some());
+    assert!(db.get("Chevrolet", "Silverado 1500").is_some());
+}
+
+#[test]
+fn test_mileage_points_round_trip_through_plain_miles() {
+    for miles in (0..300_000).step_by(100) {
+        let stored = miles as f64 / MILES_PER_MILEAGE_X;
+        assert_eq!(scale_x(stored, MILES_PER_MILEAGE_X), miles as f64);
+    }
+}
@@ -0,0 +1,158 @@
+use crate::data::{
+    get_sample_maintenance_data, parse_maintenance_import, parse_points_csv, ImportStatus,
+};
+use crate::models::{MaintenanceCostDatabase, MaintenanceDataPoint, MILES_PER_MILEAGE_X};
+
+#[test]
+fn test_csv_import_reports_each_record() {
+    let csv = "make,model,table,x,y\n\
+               Honda,Civic,mileage,100000,2000\n\
+               Honda,Civic,mileage,\"50,000\",800\n\
+               Honda,Civic,time,5,900\n\
+               Honda,Civic,decade,1,1\n\
+               Mazda,3,mileage,ten,100\n\
+               Kia,Rio,time,3,400\n";
+    let import = parse_maintenance_import(csv, &MaintenanceCostDatabase::default());
+    let report = &import.report;
+
+    assert_eq!(report.count(ImportStatus::Imported), 1);
+    assert_eq!(report.count(ImportStatus::Migrated), 1);
+    assert_eq!(report.count(ImportStatus::Skipped), 2);
+
+    // Out-of-order mileage points are sorted rather than rejected
+    let civic = report
+        .records
+        .iter()
+        .find(|r| r.label == "Honda Civic")
+        .unwrap();
+    assert_eq!(civic.status, ImportStatus::Migrated);
+    assert!(civic.reason.contains("sorted"));
+    let skipped: Vec<&str> = report
+        .records
+        .iter()
+        .filter(|r| r.status == ImportStatus::Skipped)
+        .map(|r| r.label.as_str())
+        .collect();
+    assert_eq!(skipped, ["Row 5", "Row 6"]);
+
+    assert_eq!(import.profiles.len(), 2);
+    let mut db = MaintenanceCostDatabase::default();
+    import.apply(&mut db);
+    let civic = db.get("Honda", "Civic").unwrap();
+    assert_eq!(civic.by_mileage[0].x, 5.0);
+}
+
+#[test]
+fn test_json_import_round_trips_exported_database() {
+    let sample = get_sample_maintenance_data();
+    let json = serde_json::to_string(&sample).unwrap();
+    let import = parse_maintenance_import(&json, &sample);
+
+    assert_eq!(import.profiles.len(), sample.data.len());
+    assert_eq!(import.report.count(ImportStatus::Skipped), 0);
+    assert!(import
+        .report
+        .records
+        .iter()
+        .all(|r| r.reason.contains("replaces the existing profile")));
+}
+
+#[test]
+fn test_json_import_skips_invalid_records() {
+    let json = r#"[
+        {"make": "Ford", "model": "Focus", "by_mileage": [{"x": 5, "y": 900}, {"x": 10, "y": -1}], "by_time": []},
+        {"make": "", "model": "Nameless", "by_mileage": [{"x": 1, "y": 1}], "by_time": []},
+        {"make": "Ford", "model": "Empty", "by_mileage": [], "by_time": []},
+        {"not": "a profile"}
+    ]"#;
+    let import = parse_maintenance_import(json, &MaintenanceCostDatabase::default());
+
+    assert_eq!(import.profiles.len(), 1);
+    assert_eq!(import.report.count(ImportStatus::Migrated), 1);
+    assert_eq!(import.report.count(ImportStatus::Skipped), 3);
+    assert!(import.report.records[0]
+        .reason
+        .contains("dropped 1 invalid point"));
+
+    let broken = parse_maintenance_import("{not json", &MaintenanceCostDatabase::default());
+    assert!(broken.profiles.is_empty());
+    assert_eq!(broken.report.count(ImportStatus::Skipped), 1);
+}
+
+#[test]
+fn test_points_csv_reports_malformed_rows() {
+    let csv = "miles,cumulative_cost\n\
+               50000,1200\n\
+               \n\
+               \"100,000\",3000\n\
+               75000\n\
+               eighty,900\n\
+               90000,-5\n\
+               30000\t600\n";
+    let paste = parse_points_csv(csv, MILES_PER_MILEAGE_X);
+    let xs: Vec<f64> = paste.points.iter().map(|p| p.x).collect();
+    assert_eq!(xs, vec![3.0, 5.0, 10.0]);
+
+    let skipped: Vec<&str> = paste
+        .report
+        .records
+        .iter()
+        .filter(|r| r.status == ImportStatus::Skipped)
+        .map(|r| r.label.as_str())
+        .collect();
+    assert_eq!(skipped, vec!["Line 5", "Line 6", "Line 7"]);
+}
+
+#[test]
+fn test_json_import_reads_large_mileage_as_plain_miles() {
+    let json = r#"[
+        {"make": "Ford", "model": "Focus", "by_mileage": [{"x": 50000, "y": 900}, {"x": 100000, "y": 2000}], "by_time": []},
+        {"make": "Ford", "model": "Fiesta", "by_mileage": [{"x": 5, "y": 900}], "by_time": []}
+    ]"#;
+    let import = parse_maintenance_import(json, &MaintenanceCostDatabase::default());
+
+    let xs: Vec<Vec<f64>> = import
+        .profiles
+        .iter()
+        .map(|p| p.by_mileage.iter().map(|pt| pt.x).collect())
+        .collect();
+    assert_eq!(xs, vec![vec![5.0, 10.0], vec![5.0]]);
+    assert_eq!(import.report.records[0].status, ImportStatus::Migrated);
+    assert!(import.report.records[0].reason.contains("plain miles"));
+    assert_eq!(import.report.records[1].status, ImportStatus::Imported);
+}
+
+#[test]
+fn test_import_duplicate_x_keeps_highest_cost() {
+    let csv = "Kia,Rio,mileage,50000,900\n\
+               Kia,Rio,mileage,50000,700\n";
+    let import = parse_maintenance_import(csv, &MaintenanceCostDatabase::default());
+    assert_eq!(
+        import.profiles[0].by_mileage,
+        vec![MaintenanceDataPoint { x: 5.0, y: 900.0 }]
+    );
+    assert!(import.report.records[0]
+        .reason
+        .contains("merged 1 duplicate"));
+
+    let paste = parse_points_csv("1,300\n1,500\n1,400", 1.0);
+    assert_eq!(
+        paste.points,
+        vec![MaintenanceDataPoint { x: 1.0, y: 500.0 }]
+    );
+}
+
+#[test]
+fn test_points_paste_appends_or_replaces() {
+    let existing = vec![
+        MaintenanceDataPoint { x: 1.0, y: 100.0 },
+        MaintenanceDataPoint { x: 2.0, y: 300.0 },
+    ];
+    let paste = parse_points_csv("2,350\n3,600", 1.0);
+
+    let appended = paste.apply_to(&existing, true);
+    let pairs: Vec<(f64, f64)> = appended.iter().map(|p| (p.x, p.y)).collect();
+    assert_eq!(pairs, vec![(1.0, 100.0), (2.0, 350.0), (3.0, 600.0)]);
+
+    assert_eq!(paste.apply_to(&existing, false), paste.points);
+}
@@ -1,2 +1,26 @@
 #[cfg(test)]
+mod app_export;
+#[cfg(test)]
+mod cache;
+#[cfg(test)]
 mod calculations;
+#[cfg(test)]
+mod duration;
+#[cfg(test)]
+mod edit_history;
+#[cfg(test)]
+mod interpolation;
+#[cfg(test)]
+mod maintenance;
+#[cfg(test)]
+mod maintenance_import;
+#[cfg(test)]
+mod row_import;
+#[cfg(test)]
+mod service_schedule;
+#[cfg(test)]
+mod session_link;
+#[cfg(test)]
+mod summary_csv;
+#[cfg(test)]
+mod vin;
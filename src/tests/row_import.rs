@@ -0,0 +1,64 @@
+use crate::calculations::compute_car_data;
+use crate::data::{get_sample_maintenance_data, parse_car_row, parse_money};
+use crate::models::SharedSettings;
+
+#[test]
+fn test_parse_money() {
+    assert_eq!(parse_money("$25,000"), Some(25000.0));
+    assert_eq!(parse_money(" 1,234.5 "), Some(1234.5));
+    assert_eq!(parse_money("12k"), None);
+    assert_eq!(parse_money(""), None);
+}
+
+#[test]
+fn test_parse_tab_separated_row() {
+    let import = parse_car_row(
+        "Toyota\tPrius\tLE\t2019\t$25,000\t50,000\t50\t$500\tVIN123\thttps://example.com\tclean title",
+        7,
+    );
+
+    assert!(!import.has_errors());
+    assert!(import.ignored.is_empty());
+    let car = &import.car;
+    assert_eq!(car.id, 7);
+    assert_eq!((car.make.as_str(), car.model.as_str()), ("Toyota", "Prius"));
+    assert_eq!(car.year, "2019");
+    assert_eq!(car.purchase_price, "25000");
+    assert_eq!(car.current_mileage, "50000");
+    assert_eq!(car.notes, "clean title");
+
+    // Normalized values feed straight into the calculator
+    let db = get_sample_maintenance_data();
+    assert!(compute_car_data(car, &SharedSettings::default(), &db).is_some());
+}
+
+#[test]
+fn test_parse_comma_separated_row_with_quotes() {
+    let import = parse_car_row(
+        "Ford,F-150,XLT,2020,\"$32,500\",40000,20,700,,,\"says \"\"runs great\"\"\"",
+        1,
+    );
+
+    assert!(!import.has_errors());
+    assert_eq!(import.car.purchase_price, "32500");
+    assert_eq!(import.car.notes, "says \"runs great\"");
+}
+
+#[test]
+fn test_unparseable_and_extra_columns_are_reported() {
+    let import = parse_car_row(
+        "Honda\tCivic\t\t2018\tcall for price\t30000\t\t\t\t\t\textra",
+        1,
+    );
+
+    let errors: Vec<_> = import
+        .fields
+        .iter()
+        .filter(|f| f.error.is_some())
+        .map(|f| f.column)
+        .collect();
+    assert_eq!(errors, vec!["Purchase Price"]);
+    assert_eq!(import.car.purchase_price, "");
+    assert_eq!(import.car.current_mileage, "30000");
+    assert_eq!(import.ignored, vec!["extra".to_string()]);
+}
@@ -0,0 +1,58 @@
+use crate::data::{schedule_to_cumulative, ServiceEntry};
+use crate::models::MaintenanceDataPoint;
+
+fn entry(miles: f64, cost: f64) -> ServiceEntry {
+    ServiceEntry { miles, cost }
+}
+
+#[test]
+fn test_schedule_prefix_sum() {
+    let points = schedule_to_cumulative(&[
+        entry(10000.0, 100.0),
+        entry(20000.0, 250.0),
+        entry(30000.0, 100.0),
+        entry(60000.0, 900.0),
+    ]);
+    assert_eq!(
+        points,
+        vec![
+            MaintenanceDataPoint { x: 1.0, y: 100.0 },
+            MaintenanceDataPoint { x: 2.0, y: 350.0 },
+            MaintenanceDataPoint { x: 3.0, y: 450.0 },
+            MaintenanceDataPoint { x: 6.0, y: 1350.0 },
+        ]
+    );
+}
+
+#[test]
+fn test_schedule_sorts_merges_and_skips_invalid_entries() {
+    let points = schedule_to_cumulative(&[
+        entry(30000.0, 100.0),
+        entry(10000.0, 100.0),
+        // Two services due at the same mileage are one visit's worth
+        entry(30000.0, 50.0),
+        entry(20000.0, -40.0),
+        entry(f64::NAN, 500.0),
+    ]);
+    assert_eq!(
+        points,
+        vec![
+            MaintenanceDataPoint { x: 1.0, y: 100.0 },
+            MaintenanceDataPoint { x: 3.0, y: 250.0 },
+        ]
+    );
+    assert!(schedule_to_cumulative(&[]).is_empty());
+}
+
+#[test]
+fn test_schedule_result_is_monotonic() {
+    let entries: Vec<ServiceEntry> = (1..=15)
+        .rev()
+        .map(|i| entry(i as f64 * 7500.0, (i % 4) as f64 * 120.0))
+        .collect();
+    let points = schedule_to_cumulative(&entries);
+    assert_eq!(points.len(), 15);
+    assert!(points
+        .windows(2)
+        .all(|w| w[0].x < w[1].x && w[0].y <= w[1].y));
+}
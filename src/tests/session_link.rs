@@ -0,0 +1,123 @@
+use crate::data::{
+    decode_session_fragment, encode_session_fragment, SessionState, MAX_SESSION_FRAGMENT_LEN,
+};
+use crate::models::{Car, SharedSettings};
+
+fn sample_state() -> SessionState {
+    let settings = SharedSettings {
+        sales_tax_rate: 7.25,
+        ..Default::default()
+    };
+    let mut car = Car::new(1);
+    car.make = "Honda".to_string();
+    car.model = "Civic — Touring".to_string();
+    car.purchase_price = "24500".to_string();
+    car.image_data_url = Some("data:image/png;base64,AAAA".to_string());
+    SessionState::for_link(&settings, &[car])
+}
+
+#[test]
+fn test_session_fragment_round_trips() {
+    let state = sample_state();
+    let fragment = encode_session_fragment(&state);
+    assert!(fragment.starts_with("#state="));
+    // URL-safe without escaping
+    assert!(fragment["#state=".len()..]
+        .bytes()
+        .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_'));
+    assert_eq!(decode_session_fragment(&fragment), Some(Ok(state)));
+}
+
+#[test]
+fn test_session_link_drops_photos() {
+    assert!(sample_state()
+        .cars
+        .iter()
+        .all(|c| c.image_data_url.is_none()));
+
+    // A crafted link carrying one anyway has it dropped when opened
+    let mut crafted = sample_state();
+    crafted.cars[0].image_data_url = Some("https://example.com/pixel.gif".to_string());
+    let decoded = decode_session_fragment(&encode_session_fragment(&crafted))
+        .unwrap()
+        .unwrap();
+    assert_eq!(decoded, sample_state());
+}
+
+#[test]
+fn test_decode_ignores_other_fragments_and_reports_corrupt_links() {
+    assert_eq!(decode_session_fragment(""), None);
+    assert_eq!(decode_session_fragment("#settings"), None);
+    assert!(matches!(
+        decode_session_fragment("#state=not*base64"),
+        Some(Err(_))
+    ));
+    // Valid base64url, but not a session
+    assert!(matches!(
+        decode_session_fragment("#state=aGVsbG8"),
+        Some(Err(_))
+    ));
+}
+
+#[test]
+fn test_large_sessions_exceed_the_link_limit() {
+    let cars: Vec<Car> = (0..200)
+        .map(|id| Car {
+            notes: "A long note about this car's history and condition".to_string(),
+            ..Car::new(id)
+        })
+        .collect();
+    let state = SessionState::for_link(&SharedSettings::default(), &cars);
+    assert!(encode_session_fragment(&state).len() > MAX_SESSION_FRAGMENT_LEN);
+    assert!(encode_session_fragment(&sample_state()).len() <= MAX_SESSION_FRAGMENT_LEN);
+}
+
+#[test]
+fn test_decode_drops_colors_outside_the_palette() {
+    let mut state = sample_state();
+    state.cars[0].color = Some("red; background-image: url(https://example.com/t)".to_string());
+    let mut tagged = state.cars[0].clone();
+    tagged.color = Some("#3b82f6".to_string());
+    state.cars.push(tagged);
+
+    let decoded = decode_session_fragment(&encode_session_fragment(&state))
+        .unwrap()
+        .unwrap();
+    assert_eq!(decoded.cars[0].color, None);
+    assert_eq!(decoded.cars[0].tag_color(), None);
+    assert_eq!(decoded.cars[1].tag_color(), Some("#3b82f6"));
+
+    // A color set some other way still isn't drawn unless it's in the palette
+    assert_eq!(state.cars[0].tag_color(), None);
+}
+
+#[test]
+fn test_saving_a_shared_session_merges_into_the_saved_one() {
+    let mut saved_car = Car::new(1);
+    saved_car.make = "Honda".to_string();
+    let saved = SessionState {
+        settings: SharedSettings {
+            sales_tax_rate: 6.5,
+            ..Default::default()
+        },
+        cars: vec![saved_car.clone()],
+    };
+    let mut shared_car = Car::new(1);
+    shared_car.make = "Ford".to_string();
+    let shared = SessionState {
+        settings: SharedSettings {
+            sales_tax_rate: 9.0,
+            ..Default::default()
+        },
+        cars: vec![Car { id: 4, ..saved_car }, shared_car],
+    };
+
+    let merged = shared.merged_into(&saved);
+    assert_eq!(merged.settings, saved.settings);
+    let cars: Vec<(usize, &str)> = merged
+        .cars
+        .iter()
+        .map(|c| (c.id, c.make.as_str()))
+        .collect();
+    assert_eq!(cars, vec![(1, "Honda"), (2, "Ford")]);
+}
@@ -0,0 +1,52 @@
+use crate::calculations::compute_car_data;
+use crate::data::{get_sample_maintenance_data, summary_csv, summary_csv_filename};
+use crate::models::{Car, SharedSettings};
+
+fn sample_car() -> Car {
+    let mut car = Car::new(1);
+    car.make = "Toyota".to_string();
+    car.model = "Prius".to_string();
+    car.year = "2010".to_string();
+    car.purchase_price = "25000".to_string();
+    car.current_mileage = "50000".to_string();
+    car.mpg = "50".to_string();
+    car.insurance_cost = "500".to_string();
+    car
+}
+
+#[test]
+fn test_summary_csv_lists_inputs_and_results() {
+    let mut car = sample_car();
+    car.notes = "Seller says \"new brakes\", clean title".to_string();
+    car.image_data_url = Some("data:image/jpeg;base64,AAAA".to_string());
+    let computed = compute_car_data(
+        &car,
+        &SharedSettings::default(),
+        &get_sample_maintenance_data(),
+    )
+    .unwrap();
+    let csv = summary_csv(&car, &computed);
+    let lines: Vec<&str> = csv.lines().collect();
+
+    assert_eq!(lines[0], "field,value");
+    assert!(lines.contains(&"car.purchase_price,25000"));
+    assert!(lines.contains(&"car.notes,\"Seller says \"\"new brakes\"\", clean title\""));
+    assert!(lines.contains(&"remaining_miles,150000.0"));
+    assert!(lines
+        .iter()
+        .any(|l| l.starts_with("total_cost_of_ownership,")));
+    // Nested operands are flattened with dotted keys
+    assert!(lines
+        .iter()
+        .any(|l| l.starts_with("operands.purchase_price,")));
+    // The photo would swamp the file
+    assert!(!csv.contains("base64"));
+}
+
+#[test]
+fn test_summary_csv_filename() {
+    assert_eq!(
+        summary_csv_filename(&sample_car()),
+        "carcalc-toyota-prius-2010.csv"
+    );
+}
@@ -0,0 +1,48 @@
+use crate::data::{normalize_vin, parse_vpic_response, DecodedVin};
+
+#[test]
+fn test_normalize_vin_accepts_and_rejects() {
+    assert_eq!(
+        normalize_vin(" jtdkn3du5a0123456 "),
+        Ok("JTDKN3DU5A0123456".to_string())
+    );
+    assert!(normalize_vin("").is_err());
+    assert!(normalize_vin("JTDKN3DU5A012345")
+        .unwrap_err()
+        .contains("has 16"));
+    assert!(normalize_vin("JTDKN3DU5AO123456")
+        .unwrap_err()
+        .contains("letter O"));
+    assert!(normalize_vin("JTDKN3DU5A-123456")
+        .unwrap_err()
+        .contains("'-'"));
+}
+
+#[test]
+fn test_parse_vpic_response() {
+    let json = r#"{"Count":1,"Message":"Results returned successfully","Results":[
+        {"Make":"TOYOTA","Model":"Prius","ModelYear":"2010","ErrorCode":"0","ErrorText":"0 - VIN decoded clean."}
+    ]}"#;
+    let decoded = parse_vpic_response(json).unwrap();
+    assert_eq!(
+        decoded,
+        DecodedVin {
+            make: "Toyota".to_string(),
+            model: "Prius".to_string(),
+            year: "2010".to_string(),
+        }
+    );
+    assert_eq!(decoded.name(), "2010 Toyota Prius");
+
+    // Short makes are initialisms and keep their capitals
+    let json = r#"{"Results":[{"Make":"BMW","Model":"330i","ModelYear":""}]}"#;
+    assert_eq!(parse_vpic_response(json).unwrap().name(), "BMW 330i");
+
+    let json = r#"{"Results":[{"Make":"","Model":"","ModelYear":"","ErrorText":"11 - Incorrect Model Year"}]}"#;
+    assert_eq!(
+        parse_vpic_response(json),
+        Err("The VIN couldn't be decoded: 11 - Incorrect Model Year".to_string())
+    );
+    assert!(parse_vpic_response(r#"{"Results":[]}"#).is_err());
+    assert!(parse_vpic_response("not json").is_err());
+}